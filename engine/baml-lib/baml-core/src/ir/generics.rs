@@ -0,0 +1,246 @@
+//! Monomorphization of generic classes.
+//!
+//! Generic classes like `class Paginated<T>` are turned into one concrete
+//! class for every set of type arguments they're used with, so that
+//! `Paginated<Resume>` becomes the class `Paginated__Resume`. Everything that
+//! consumes the IR can keep working with concrete types, and code generators
+//! that support generics can map the specialized classes back to the generic
+//! one with [`super::IntermediateRepr::find_specialization`].
+//!
+//! Generic type aliases are simpler, they're expanded in place wherever
+//! they're applied to type arguments.
+
+use anyhow::{bail, Result};
+use baml_types::{BamlMediaType, FieldType, LiteralValue, TypeValue};
+use indexmap::IndexMap;
+use internal_baml_parser_database::{walkers::ClassWalker, ParserDatabase, TypeWalker};
+use internal_baml_schema_ast::ast::{self, WithName};
+use std::collections::VecDeque;
+
+use super::repr::WithRepr;
+
+/// A generic class applied to type arguments.
+#[derive(Debug)]
+pub struct Specialization {
+    /// Name of the generic class.
+    pub generic: String,
+    /// Type arguments, one for each type parameter of the generic class.
+    pub args: Vec<FieldType>,
+}
+
+/// A generic class applied to type arguments found in the AST.
+pub(super) struct Instantiation<'db> {
+    pub class: ClassWalker<'db>,
+    pub args: Vec<ast::FieldType>,
+    pub ir_args: Vec<FieldType>,
+    /// Whether the type arguments are concrete types. Otherwise they refer to
+    /// the type parameters of a generic class, like `Paginated<T>` inside
+    /// `class Page<T>`, and there's no class to generate.
+    pub is_concrete: bool,
+}
+
+/// Name of the class that a generic class turns into when it's applied to the
+/// given type arguments, like `Paginated__Resume` for `Paginated<Resume>`.
+pub fn specialization_name(generic: &str, args: &[FieldType]) -> String {
+    let mut name = generic.to_string();
+    for arg in args {
+        name.push_str("__");
+        name.push_str(&type_arg_name(arg));
+    }
+    name
+}
+
+/// A name for the type argument that can be part of a class name.
+fn type_arg_name(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::Class(name) | FieldType::Enum(name) | FieldType::RecursiveTypeAlias(name) => {
+            name.to_string()
+        }
+        FieldType::Primitive(type_value) => match type_value {
+            TypeValue::String => "String",
+            TypeValue::Int => "Int",
+            TypeValue::Float => "Float",
+            TypeValue::Bool => "Bool",
+            TypeValue::Null => "Null",
+            TypeValue::Media(BamlMediaType::Image) => "Image",
+            TypeValue::Media(BamlMediaType::Audio) => "Audio",
+        }
+        .to_string(),
+        FieldType::Literal(value) => {
+            let value = match value {
+                LiteralValue::String(s) => s.to_string(),
+                LiteralValue::Int(i) if *i < 0 => format!("Minus{}", i.unsigned_abs()),
+                LiteralValue::Int(i) => i.to_string(),
+                LiteralValue::Bool(true) => "True".to_string(),
+                LiteralValue::Bool(false) => "False".to_string(),
+            };
+            let value = value
+                .chars()
+                .filter(char::is_ascii_alphanumeric)
+                .collect::<String>();
+            format!("Literal{value}")
+        }
+        FieldType::List(item) => format!("{}List", type_arg_name(item)),
+        FieldType::Optional(item) => format!("Optional{}", type_arg_name(item)),
+        FieldType::Map(key, value) => {
+            format!("Map{}{}", type_arg_name(key), type_arg_name(value))
+        }
+        FieldType::Union(items) => items
+            .iter()
+            .map(type_arg_name)
+            .collect::<Vec<_>>()
+            .join("Or"),
+        FieldType::Tuple(items) => format!(
+            "Tuple{}",
+            items.iter().map(type_arg_name).collect::<String>()
+        ),
+        FieldType::WithMetadata { base, .. } => type_arg_name(base),
+    }
+}
+
+/// Finds every application of a generic class in the schema, by name of the
+/// specialized class.
+///
+/// Applications with concrete type arguments are expanded, since the fields of
+/// `Paginated<Resume>` can use more generic classes. Validation guarantees
+/// that the expansion terminates.
+pub(super) fn collect_instantiations(
+    db: &ParserDatabase,
+) -> Result<IndexMap<String, Instantiation<'_>>> {
+    let mut queue = VecDeque::new();
+
+    for class in db.walk_classes() {
+        for field in class.static_fields() {
+            if let Some(field_type) = field.r#type() {
+                find_applications(db, field_type, class.type_params(), &mut queue);
+            }
+        }
+    }
+
+    for function in db.walk_functions() {
+        for arg in function
+            .walk_input_args()
+            .chain(function.walk_output_args())
+        {
+            find_applications(db, &arg.ast_arg().1.field_type, &[], &mut queue);
+        }
+    }
+
+    for template in db.walk_templates() {
+        for arg in template.walk_input_args() {
+            find_applications(db, &arg.ast_arg().1.field_type, &[], &mut queue);
+        }
+    }
+
+    for alias in db.walk_type_aliases() {
+        if alias.type_params().is_empty() {
+            find_applications(db, alias.target(), &[], &mut queue);
+        }
+    }
+
+    let mut instantiations: IndexMap<String, Instantiation<'_>> = IndexMap::new();
+
+    while let Some((class, args, is_concrete)) = queue.pop_front() {
+        let ir_args = args
+            .iter()
+            .map(|arg| arg.repr(db))
+            .collect::<Result<Vec<_>>>()?;
+        let name = specialization_name(class.name(), &ir_args);
+
+        if let Some(existing) = instantiations.get(&name) {
+            if existing.class.id != class.id || existing.ir_args != ir_args {
+                bail!(
+                    "`{}<{}>` and `{}<{}>` would both become the class `{name}`",
+                    existing.class.name(),
+                    display_args(&existing.args),
+                    class.name(),
+                    display_args(&args),
+                );
+            }
+            continue;
+        }
+
+        if db.find_type_by_str(&name).is_some() {
+            bail!(
+                "`{}<{}>` would become the class `{name}`, which is already defined",
+                class.name(),
+                display_args(&args),
+            );
+        }
+
+        if is_concrete {
+            for field in class.fields() {
+                if let Some(field_type) = field.r#type() {
+                    let field_type = field_type.substitute_type_params(class.type_params(), &args);
+                    find_applications(db, &field_type, &[], &mut queue);
+                }
+            }
+        }
+
+        instantiations.insert(
+            name,
+            Instantiation {
+                class,
+                args,
+                ir_args,
+                is_concrete,
+            },
+        );
+    }
+
+    Ok(instantiations)
+}
+
+/// Pushes the generic classes applied to type arguments in `field_type` to the
+/// queue, along with whether the arguments are free of `type_params`.
+fn find_applications<'db>(
+    db: &'db ParserDatabase,
+    field_type: &ast::FieldType,
+    type_params: &[ast::Identifier],
+    queue: &mut VecDeque<(ClassWalker<'db>, Vec<ast::FieldType>, bool)>,
+) {
+    match field_type {
+        ast::FieldType::Symbol(_, ident, args, _) if !args.is_empty() => {
+            match db.find_type(ident) {
+                Some(TypeWalker::Class(class)) => {
+                    let is_concrete = args
+                        .iter()
+                        .flat_map(|arg| arg.flat_idns())
+                        .all(|idn| !type_params.iter().any(|param| param.name() == idn.name()));
+                    queue.push_back((class, args.to_owned(), is_concrete));
+
+                    for arg in args {
+                        find_applications(db, arg, type_params, queue);
+                    }
+                }
+                Some(TypeWalker::TypeAlias(alias)) => {
+                    let target = alias
+                        .target()
+                        .substitute_type_params(alias.type_params(), args);
+                    find_applications(db, &target, type_params, queue);
+                }
+                _ => {}
+            }
+        }
+        ast::FieldType::Symbol(..)
+        | ast::FieldType::Primitive(..)
+        | ast::FieldType::Literal(..) => {}
+        ast::FieldType::List(_, item, ..) => find_applications(db, item, type_params, queue),
+        ast::FieldType::Map(_, kv, ..) => {
+            find_applications(db, &kv.0, type_params, queue);
+            find_applications(db, &kv.1, type_params, queue);
+        }
+        ast::FieldType::Union(_, items, ..) | ast::FieldType::Tuple(_, items, ..) => {
+            for item in items {
+                find_applications(db, item, type_params, queue);
+            }
+        }
+    }
+}
+
+fn display_args(args: &[ast::FieldType]) -> String {
+    args.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
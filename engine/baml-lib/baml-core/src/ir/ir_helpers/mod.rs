@@ -1265,6 +1265,7 @@ mod tests {
                 level: ConstraintLevel::Assert,
                 expression: JinjaExpression(s.to_string()),
                 label: Some(s.to_string()),
+                validator: None,
             }
         }
 
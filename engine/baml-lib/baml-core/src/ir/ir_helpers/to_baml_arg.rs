@@ -8,7 +8,7 @@ use std::path::PathBuf;
 use crate::ir::IntermediateRepr;
 
 use super::{scope_diagnostics::ScopeStack, IRHelper};
-use crate::ir::jinja_helpers::evaluate_predicate;
+use crate::ir::jinja_helpers::evaluate_constraint;
 
 #[derive(Default)]
 pub struct ParameterError {
@@ -366,6 +366,13 @@ impl ArgCoercer {
                 scope.push_error(format!("Failed to evaluate assert: {:?}", e));
             })?;
         match search_for_failures_result {
+            Some(Constraint {
+                validator: Some(validator),
+                ..
+            }) => {
+                scope.push_error(format!("Failed validator: {validator}"));
+                Err(())
+            }
             Some(Constraint {
                 label, expression, ..
             }) => {
@@ -395,7 +402,7 @@ fn first_failing_assert_nested<'a>(
                 .filter_map(|c| {
                     let constraint = c.clone();
                     let baml_value: BamlValue = value_node.into();
-                    let result = evaluate_predicate(&baml_value, &c.expression).map_err(|e| {
+                    let result = evaluate_constraint(&baml_value, &c).map_err(|e| {
                         anyhow::anyhow!(format!("Error evaluating constraint: {:?}", e))
                     });
                     match result {
@@ -450,6 +457,7 @@ mod tests {
                 level: ConstraintLevel::Assert,
                 expression: JinjaExpression("this.length() > 0".to_string()),
                 label: Some("foo".to_string()),
+                validator: None,
             }],
             streaming_behavior: StreamingBehavior::default(),
         };
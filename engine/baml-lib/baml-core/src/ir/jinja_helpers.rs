@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-use baml_types::{BamlValue, JinjaExpression};
+use baml_types::{BamlValue, Constraint, JinjaExpression};
 use minijinja::value::Value;
 use regex::Regex;
 
@@ -72,6 +72,15 @@ pub fn evaluate_predicate(
     }
 }
 
+/// Evaluate a constraint against `this`. Built-in validators are checked
+/// natively, other constraints through their jinja expression.
+pub fn evaluate_constraint(this: &BamlValue, constraint: &Constraint) -> anyhow::Result<bool> {
+    match &constraint.validator {
+        Some(validator) => Ok(super::validators::evaluate_validator(this, validator)),
+        None => evaluate_predicate(this, &constraint.expression),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
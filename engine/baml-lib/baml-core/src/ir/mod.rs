@@ -1,7 +1,9 @@
+mod generics;
 mod ir_helpers;
 pub mod jinja_helpers;
 mod json_schema;
 pub mod repr;
+pub(crate) mod validators;
 mod walker;
 
 pub use ir_helpers::{
@@ -9,6 +11,7 @@ pub use ir_helpers::{
     EnumWalker, FunctionWalker, IRHelper, RetryPolicyWalker, TemplateStringWalker, TestCaseWalker,
 };
 
+pub use generics::{specialization_name, Specialization};
 pub(super) use repr::IntermediateRepr;
 
 // Add aliases for the IR types
@@ -1,4 +1,4 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use anyhow::{anyhow, Result};
 use baml_types::{
@@ -12,15 +12,19 @@ use internal_baml_parser_database::{
         ClassWalker, ClientWalker, ConfigurationWalker, EnumValueWalker, EnumWalker, FieldWalker,
         FunctionWalker, TemplateStringWalker, TypeAliasWalker, Walker as AstWalker,
     },
-    Attributes, ParserDatabase, PromptAst, RetryPolicyStrategy, TypeWalker,
+    Attributes, ParserDatabase, PromptAst, RetryPolicyStrategy, Tarjan, TypeWalker,
 };
 
 use internal_baml_schema_ast::ast::{
     self, Attribute, FieldArity, SubType, ValExpId, WithName, WithSpan,
 };
-use internal_llm_client::{ClientProvider, ClientSpec, UnresolvedClientProperty};
+use internal_llm_client::{
+    ClientProvider, ClientSpec, Coercion, RetryOn, UnresolvedClientProperty,
+};
 use serde::Serialize;
 
+use super::generics::{self, Instantiation, Specialization};
+use super::validators::attribute_as_validator;
 use crate::Configuration;
 
 /// This class represents the intermediate representation of the BAML AST.
@@ -31,6 +35,20 @@ use crate::Configuration;
 pub struct IntermediateRepr {
     enums: Vec<Node<Enum>>,
     classes: Vec<Node<Class>>,
+
+    /// Generic classes, before they're applied to type arguments.
+    ///
+    /// These are templates for code generators that support generics. The
+    /// classes in [`Self::classes`] are the concrete specializations, and
+    /// type parameters show up as [`FieldType::Class`] with the name of the
+    /// parameter in the fields of these templates.
+    generic_classes: Vec<Node<Class>>,
+
+    /// Generic classes applied to type arguments, by name of the specialized
+    /// class. Includes the applications inside generic classes, like
+    /// `Paginated<T>` in `class Page<T>`, which have no concrete class.
+    specializations: IndexMap<String, Specialization>,
+
     type_aliases: Vec<Node<TypeAlias>>,
     functions: Vec<Node<Function>>,
     clients: Vec<Node<Client>>,
@@ -63,6 +81,8 @@ impl IntermediateRepr {
         IntermediateRepr {
             enums: vec![],
             classes: vec![],
+            generic_classes: vec![],
+            specializations: IndexMap::new(),
             type_aliases: vec![],
             finite_recursive_cycles: vec![],
             structural_recursive_alias_cycles: vec![],
@@ -123,6 +143,26 @@ impl IntermediateRepr {
         self.classes.iter().map(|e| Walker { db: self, item: e })
     }
 
+    pub fn walk_generic_classes(&self) -> impl ExactSizeIterator<Item = Walker<'_, &Node<Class>>> {
+        self.generic_classes
+            .iter()
+            .map(|e| Walker { db: self, item: e })
+    }
+
+    /// Returns the generic class and type arguments that the class `name` was
+    /// specialized from, if any.
+    pub fn find_specialization(&self, name: &str) -> Option<&Specialization> {
+        self.specializations.get(name)
+    }
+
+    /// Whether the class `name` is a type parameter of a generic class, as
+    /// found in the fields of [`Self::walk_generic_classes`].
+    pub fn is_type_param(&self, name: &str) -> bool {
+        self.generic_classes
+            .iter()
+            .any(|class| class.elem.type_params.iter().any(|param| param == name))
+    }
+
     pub fn walk_type_aliases(&self) -> impl ExactSizeIterator<Item = Walker<'_, &Node<TypeAlias>>> {
         self.type_aliases
             .iter()
@@ -181,6 +221,24 @@ impl IntermediateRepr {
         db: &ParserDatabase,
         configuration: Configuration,
     ) -> Result<IntermediateRepr> {
+        let instantiations = generics::collect_instantiations(db)?;
+
+        let mut classes = db
+            .walk_classes()
+            .filter(|c| c.type_params().is_empty())
+            .map(|e| e.node(db))
+            .collect::<Result<Vec<_>>>()?;
+        for (name, instantiation) in &instantiations {
+            if instantiation.is_concrete {
+                classes.push(specialized_class(
+                    instantiation.class,
+                    name,
+                    &instantiation.args,
+                    db,
+                )?);
+            }
+        }
+
         // TODO: We're iterating over the AST tops once for every property in
         // the IR. Easy performance optimization here by iterating only one time
         // and distributing the tops to the appropriate IR properties.
@@ -189,23 +247,29 @@ impl IntermediateRepr {
                 .walk_enums()
                 .map(|e| e.node(db))
                 .collect::<Result<Vec<_>>>()?,
-            classes: db
+            finite_recursive_cycles: finite_recursive_cycles(db, &classes, &instantiations),
+            classes,
+            generic_classes: db
                 .walk_classes()
+                .filter(|c| !c.type_params().is_empty())
                 .map(|e| e.node(db))
                 .collect::<Result<Vec<_>>>()?,
+            specializations: instantiations
+                .into_iter()
+                .map(|(name, instantiation)| {
+                    let specialization = Specialization {
+                        generic: instantiation.class.name().to_string(),
+                        args: instantiation.ir_args,
+                    };
+                    (name, specialization)
+                })
+                .collect(),
+            // Generic aliases are expanded wherever they're used.
             type_aliases: db
                 .walk_type_aliases()
+                .filter(|alias| alias.type_params().is_empty())
                 .map(|e| e.node(db))
                 .collect::<Result<Vec<_>>>()?,
-            finite_recursive_cycles: db
-                .finite_recursive_cycles()
-                .iter()
-                .map(|ids| {
-                    ids.iter()
-                        .map(|id| db.ast()[*id].name().to_string())
-                        .collect()
-                })
-                .collect(),
             structural_recursive_alias_cycles: {
                 let mut recursive_aliases = vec![];
                 for cycle in db.recursive_alias_cycles() {
@@ -240,6 +304,8 @@ impl IntermediateRepr {
         // Sort each item by name.
         repr.enums.sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
         repr.classes.sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
+        repr.generic_classes
+            .sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
         repr.functions
             .sort_by(|a, b| a.elem.name().cmp(b.elem.name()));
         repr.clients.sort_by(|a, b| a.elem.name.cmp(&b.elem.name));
@@ -250,6 +316,69 @@ impl IntermediateRepr {
     }
 }
 
+/// Strongly connected components of the class dependency graph.
+///
+/// The parser database only knows about the classes written in the source, so
+/// when there are specialized generic classes the cycles are computed from the
+/// classes in the IR instead.
+fn finite_recursive_cycles(
+    db: &ParserDatabase,
+    classes: &[Node<Class>],
+    instantiations: &IndexMap<String, Instantiation<'_>>,
+) -> Vec<IndexSet<String>> {
+    if instantiations.is_empty() {
+        return db
+            .finite_recursive_cycles()
+            .iter()
+            .map(|ids| {
+                ids.iter()
+                    .map(|id| db.ast()[*id].name().to_string())
+                    .collect()
+            })
+            .collect();
+    }
+
+    let index = classes
+        .iter()
+        .map(|class| class.elem.name.as_str())
+        .collect::<IndexSet<_>>();
+
+    let graph = classes
+        .iter()
+        .enumerate()
+        .map(|(i, class)| {
+            let mut deps = HashSet::new();
+            for field in &class.elem.static_fields {
+                let mut stack = vec![&field.elem.r#type.elem];
+                while let Some(field_type) = stack.pop() {
+                    match field_type {
+                        FieldType::Class(name) => deps.extend(index.get_index_of(name.as_str())),
+                        FieldType::List(item) | FieldType::Optional(item) => stack.push(item),
+                        FieldType::Map(key, value) => stack.extend([key.as_ref(), value.as_ref()]),
+                        FieldType::Union(items) | FieldType::Tuple(items) => stack.extend(items),
+                        FieldType::WithMetadata { base, .. } => stack.push(base),
+                        FieldType::Primitive(_)
+                        | FieldType::Enum(_)
+                        | FieldType::Literal(_)
+                        | FieldType::RecursiveTypeAlias(_) => {}
+                    }
+                }
+            }
+            (i, deps)
+        })
+        .collect::<HashMap<_, _>>();
+
+    Tarjan::components(&graph)
+        .into_iter()
+        .map(|component| {
+            component
+                .into_iter()
+                .map(|i| index[i].to_string())
+                .collect()
+        })
+        .collect()
+}
+
 // TODO:
 //
 //   [x] clients - need to finish expressions
@@ -274,6 +403,7 @@ pub struct NodeAttributes {
     ///
     ///   - @skip becomes ("skip", bool)
     ///   - @alias(...) becomes ("alias", ...)
+    ///   - @default(...) becomes ("default", ...)
     meta: IndexMap<String, UnresolvedValue<()>>,
 
     pub constraints: Vec<Constraint>,
@@ -326,6 +456,7 @@ fn to_ir_attributes(
             streaming_done,
             streaming_needed,
             streaming_state,
+            default_value,
         } = attributes;
 
         let description = description
@@ -378,6 +509,10 @@ fn to_ir_attributes(
             }
         });
 
+        let default_value = default_value
+            .as_ref()
+            .map(|v| ("default".to_string(), v.without_meta()));
+
         let meta = vec![
             description,
             alias,
@@ -386,6 +521,7 @@ fn to_ir_attributes(
             streaming_done,
             streaming_needed,
             streaming_state,
+            default_value,
         ]
         .into_iter()
         .filter_map(|s| s)
@@ -437,6 +573,9 @@ impl WithRepr<FieldType> for ast::FieldType {
             .attributes()
             .iter()
             .filter_map(|attr| {
+                if let Some(validator) = attribute_as_validator(attr) {
+                    return validator.ok().map(Constraint::from_validator);
+                }
                 let level = match attr.name.to_string().as_str() {
                     "assert" => Some(ConstraintLevel::Assert),
                     "check" => Some(ConstraintLevel::Check),
@@ -462,6 +601,7 @@ impl WithRepr<FieldType> for ast::FieldType {
                     level,
                     expression,
                     label,
+                    validator: None,
                 })
             })
             .collect::<Vec<Constraint>>();
@@ -515,10 +655,19 @@ impl WithRepr<FieldType> for ast::FieldType {
                     repr
                 }
             }
-            ast::FieldType::Symbol(arity, idn, ..) => type_with_arity(
+            ast::FieldType::Symbol(arity, idn, args, ..) => type_with_arity(
                 match db.find_type(idn) {
                     Some(TypeWalker::Class(class_walker)) => {
-                        let base_class = FieldType::Class(class_walker.name().to_string());
+                        let name = if args.is_empty() {
+                            class_walker.name().to_string()
+                        } else {
+                            let args = args
+                                .iter()
+                                .map(|arg| arg.repr(db))
+                                .collect::<Result<Vec<_>>>()?;
+                            generics::specialization_name(class_walker.name(), &args)
+                        };
+                        let base_class = FieldType::Class(name);
                         match class_walker.get_constraints(SubType::Class) {
                             Some(constraints) if !constraints.is_empty() => {
                                 FieldType::WithMetadata {
@@ -543,6 +692,11 @@ impl WithRepr<FieldType> for ast::FieldType {
                             _ => base_type,
                         }
                     }
+                    // Generic aliases are expanded in place.
+                    Some(TypeWalker::TypeAlias(alias_walker)) if !args.is_empty() => alias_walker
+                        .target()
+                        .substitute_type_params(alias_walker.type_params(), args)
+                        .repr(db)?,
                     Some(TypeWalker::TypeAlias(alias_walker)) => {
                         if db.is_recursive_type_alias(&alias_walker.id) {
                             FieldType::RecursiveTypeAlias(alias_walker.name().to_string())
@@ -551,6 +705,9 @@ impl WithRepr<FieldType> for ast::FieldType {
                         }
                     }
 
+                    // Type parameters, which can only be found in the fields of
+                    // generic classes.
+                    None if args.is_empty() => FieldType::Class(idn.name().to_string()),
                     None => return Err(anyhow!("Field type uses unresolvable local identifier")),
                 },
                 arity,
@@ -641,6 +798,7 @@ impl WithRepr<TemplateString> for TemplateStringWalker<'_> {
                                 name: id.name().to_string(),
                                 r#type: f,
                                 docstring: None,
+                                inherited_from: None,
                             })
                             .ok()
                     })
@@ -723,6 +881,9 @@ pub struct Field {
     pub name: String,
     pub r#type: Node<FieldType>,
     pub docstring: Option<Docstring>,
+    /// The class that defines the field, when it's inherited from a base
+    /// class.
+    pub inherited_from: Option<ClassId>,
 }
 
 impl WithRepr<Field> for FieldWalker<'_> {
@@ -738,23 +899,36 @@ impl WithRepr<Field> for FieldWalker<'_> {
     }
 
     fn repr(&self, db: &ParserDatabase) -> Result<Field> {
-        let ast_field_type = self.ast_field().expr.as_ref().ok_or(anyhow!(
-            "Internal error occurred while resolving repr of field {:?}",
-            self.name(),
-        ))?;
-        let field_type_attributes = WithRepr::attributes(ast_field_type, db);
-        let field_type = ast_field_type.repr(db)?;
-        Ok(Field {
-            name: self.name().to_string(),
-            r#type: Node {
-                elem: field_type,
-                attributes: field_type_attributes,
-            },
-            docstring: self.get_documentation().map(Docstring),
-        })
+        specialized_field(*self, &[], &[], db)
     }
 }
 
+/// Represents a field with the type parameters of its class replaced by the
+/// given type arguments.
+fn specialized_field(
+    field: FieldWalker<'_>,
+    type_params: &[ast::Identifier],
+    args: &[ast::FieldType],
+    db: &ParserDatabase,
+) -> Result<Field> {
+    let ast_field_type = field.ast_field().expr.as_ref().ok_or(anyhow!(
+        "Internal error occurred while resolving repr of field {:?}",
+        field.name(),
+    ))?;
+    let ast_field_type = ast_field_type.substitute_type_params(type_params, args);
+    let field_type_attributes = WithRepr::attributes(&ast_field_type, db);
+    let field_type = ast_field_type.repr(db)?;
+    Ok(Field {
+        name: field.name().to_string(),
+        r#type: Node {
+            elem: field_type,
+            attributes: field_type_attributes,
+        },
+        docstring: field.get_documentation().map(Docstring),
+        inherited_from: None,
+    })
+}
+
 type ClassId = String;
 
 /// A BAML Class.
@@ -763,9 +937,16 @@ pub struct Class {
     /// User defined class name.
     pub name: ClassId,
 
-    /// Fields of the class.
+    /// Fields of the class, including the inherited ones.
     pub static_fields: Vec<Node<Field>>,
 
+    /// Classes this class extends, in the order they are listed.
+    pub bases: Vec<ClassId>,
+
+    /// Type parameters of a generic class, see
+    /// [`IntermediateRepr::walk_generic_classes`].
+    pub type_params: Vec<String>,
+
     /// Parameters to the class definition.
     pub inputs: Vec<(String, FieldType)>,
 
@@ -798,9 +979,21 @@ impl WithRepr<Class> for ClassWalker<'_> {
                 self.name().to_string()
             },
             static_fields: self
-                .static_fields()
-                .map(|e| e.node(db))
+                .fields()
+                .map(|field| {
+                    let mut node = field.node(db)?;
+                    if field.model().id != self.id {
+                        node.elem.inherited_from = Some(field.model().name().to_string());
+                    }
+                    Ok(node)
+                })
                 .collect::<Result<Vec<_>>>()?,
+            bases: self.bases().map(|base| base.name().to_string()).collect(),
+            type_params: self
+                .type_params()
+                .iter()
+                .map(|param| param.name().to_string())
+                .collect(),
             inputs: match self.ast_type_block().input() {
                 Some(input) => input
                     .args
@@ -817,6 +1010,39 @@ impl WithRepr<Class> for ClassWalker<'_> {
     }
 }
 
+/// Builds the class that a generic class turns into when it's applied to the
+/// given type arguments.
+fn specialized_class(
+    class: ClassWalker<'_>,
+    name: &str,
+    args: &[ast::FieldType],
+    db: &ParserDatabase,
+) -> Result<Node<Class>> {
+    Ok(Node {
+        elem: Class {
+            name: name.to_string(),
+            static_fields: class
+                .fields()
+                .map(|field| {
+                    let mut field_repr = specialized_field(field, class.type_params(), args, db)?;
+                    if field.model().id != class.id {
+                        field_repr.inherited_from = Some(field.model().name().to_string());
+                    }
+                    Ok(Node {
+                        elem: field_repr,
+                        attributes: WithRepr::attributes(&field, db),
+                    })
+                })
+                .collect::<Result<Vec<_>>>()?,
+            bases: class.bases().map(|base| base.name().to_string()).collect(),
+            type_params: Vec::new(),
+            inputs: Vec::new(),
+            docstring: class.get_documentation().map(Docstring),
+        },
+        attributes: WithRepr::<Class>::attributes(&class, db),
+    })
+}
+
 impl Class {
     pub fn inputs(&self) -> &Vec<(String, FieldType)> {
         &self.inputs
@@ -938,6 +1164,8 @@ pub struct FunctionConfig {
     pub prompt_template: String,
     pub prompt_span: ast::Span,
     pub client: ClientSpec,
+    /// Overrides the `forbid_coercions` of the client when set.
+    pub forbid_coercions: Option<Vec<Coercion>>,
 }
 
 // impl std::fmt::Display for ClientSpec {
@@ -1030,6 +1258,7 @@ impl WithRepr<Function> for FunctionWalker<'_> {
                     Ok(spec) => spec,
                     Err(e) => anyhow::bail!("{}", e.message()),
                 },
+                forbid_coercions: self.metadata().forbid_coercions.clone(),
             }],
             default_config: "default_config".to_string(),
             tests: self
@@ -1081,6 +1310,7 @@ pub struct RetryPolicy {
     pub name: RetryPolicyId,
     pub max_retries: u32,
     pub strategy: RetryPolicyStrategy,
+    pub retry_on: RetryOn,
     // NB: the parser DB has a notion of "empty options" vs "no options"; we collapse
     // those here into an empty vec
     options: Vec<(String, UnresolvedValue<()>)>,
@@ -1100,6 +1330,7 @@ impl WithRepr<RetryPolicy> for ConfigurationWalker<'_> {
             name: RetryPolicyId(self.name().to_string()),
             max_retries: self.retry_policy().max_retries,
             strategy: self.retry_policy().strategy,
+            retry_on: self.retry_policy().retry_on.clone(),
             options: match &self.retry_policy().options {
                 Some(o) => o
                     .iter()
@@ -1558,4 +1789,81 @@ mod tests {
         assert_eq!(constraints[2].level, ConstraintLevel::Check);
         assert_eq!(constraints[2].label, Some("gt_ten".to_string()));
     }
+
+    #[test]
+    fn test_specialize_generic_classes() {
+        let ir = make_test_ir(
+            r##"
+            class Paginated<T> {
+                items T[]
+                next Paginated<T>?
+            }
+
+            class Result<T, E> {
+                value T?
+                error E?
+            }
+
+            type Page<T> = Paginated<T>
+
+            class Resume {
+                name string
+            }
+
+            class Feed {
+                resumes Page<Resume>
+                results Result<Paginated<int>, string>
+            }
+        "##,
+        )
+        .unwrap();
+
+        let feed = ir.find_class("Feed").unwrap();
+        assert_eq!(
+            *feed.find_field("resumes").unwrap().r#type(),
+            FieldType::Class("Paginated__Resume".to_string())
+        );
+        assert_eq!(
+            *feed.find_field("results").unwrap().r#type(),
+            FieldType::Class("Result__Paginated__Int__String".to_string())
+        );
+
+        let page = ir.find_class("Paginated__Resume").unwrap();
+        assert_eq!(
+            *page.find_field("items").unwrap().r#type(),
+            FieldType::List(Box::new(FieldType::Class("Resume".to_string())))
+        );
+        assert_eq!(
+            *page.find_field("next").unwrap().r#type(),
+            FieldType::Optional(Box::new(FieldType::Class("Paginated__Resume".to_string())))
+        );
+
+        // The type argument of `Result` is specialized as well.
+        ir.find_class("Paginated__Int").unwrap();
+
+        let specialization = ir.find_specialization("Paginated__Resume").unwrap();
+        assert_eq!(specialization.generic, "Paginated");
+        assert_eq!(
+            specialization.args,
+            vec![FieldType::Class("Resume".to_string())]
+        );
+
+        // Generic classes are templates, not classes.
+        assert!(ir.find_class("Paginated").is_err());
+        let generic = ir
+            .walk_generic_classes()
+            .find(|c| c.name() == "Paginated")
+            .unwrap();
+        assert_eq!(generic.elem().type_params, vec!["T".to_string()]);
+        assert_eq!(
+            *generic.find_field("next").unwrap().r#type(),
+            FieldType::Optional(Box::new(FieldType::Class("Paginated__T".to_string())))
+        );
+        assert!(ir.find_specialization("Paginated__T").is_some());
+
+        assert!(ir
+            .finite_recursive_cycles()
+            .iter()
+            .any(|cycle| cycle.contains("Paginated__Resume")));
+    }
 }
@@ -0,0 +1,135 @@
+use std::{
+    collections::HashMap,
+    str::FromStr,
+    sync::{OnceLock, RwLock},
+};
+
+use baml_types::{BamlValue, Format, Validator};
+use internal_baml_schema_ast::ast::{Attribute, Expression, WithName};
+use regex::Regex;
+use strum::VariantNames;
+
+/// Interpret a type attribute as a built-in validator.
+///
+/// Returns `None` for attributes that are not validators, and the error
+/// message if the arguments of the validator are malformed.
+pub(crate) fn attribute_as_validator(attribute: &Attribute) -> Option<Result<Validator, String>> {
+    let arguments = attribute
+        .arguments
+        .arguments
+        .iter()
+        .map(|arg| &arg.value)
+        .collect::<Vec<_>>();
+
+    let validator = match (attribute.name(), arguments.as_slice()) {
+        ("regex", [pattern]) => match pattern.as_string_value() {
+            Some((pattern, _)) => match compile(pattern) {
+                Ok(_) => Ok(Validator::Regex(pattern.to_string())),
+                Err(e) => Err(format!("Invalid regex: {e}")),
+            },
+            None => Err("@regex takes the pattern as a string.".to_string()),
+        },
+        ("regex", _) => Err("@regex takes the pattern as a string.".to_string()),
+
+        ("min", [min]) => as_number(min)
+            .map(Validator::Min)
+            .ok_or_else(|| "@min takes a single number.".to_string()),
+        ("max", [max]) => as_number(max)
+            .map(Validator::Max)
+            .ok_or_else(|| "@max takes a single number.".to_string()),
+        ("min" | "max", _) => Err(format!("@{} takes a single number.", attribute.name())),
+
+        ("length", [min, max]) => match (as_length(min), as_length(max)) {
+            (Some(min), Some(max)) if min <= max => Ok(Validator::Length { min, max }),
+            (Some(_), Some(_)) => {
+                Err("The minimum length of @length must not exceed the maximum.".to_string())
+            }
+            _ => Err(LENGTH_USAGE.to_string()),
+        },
+        ("length", _) => Err(LENGTH_USAGE.to_string()),
+
+        ("format", [format]) => format
+            .as_string_value()
+            .and_then(|(format, _)| Format::from_str(format).ok())
+            .map(Validator::Format)
+            .ok_or_else(|| {
+                format!(
+                    "@format must be one of: {}.",
+                    Format::VARIANTS
+                        .iter()
+                        .map(|f| format!("\"{f}\""))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )
+            }),
+        ("format", _) => Err("@format takes the name of a format.".to_string()),
+
+        _ => return None,
+    };
+    Some(validator)
+}
+
+const LENGTH_USAGE: &str =
+    "@length takes the minimum and maximum length as integers, e.g. @length(1, 10).";
+
+fn as_number(expr: &Expression) -> Option<f64> {
+    expr.as_numeric_value()
+        .and_then(|(n, _)| n.parse::<f64>().ok())
+}
+
+fn as_length(expr: &Expression) -> Option<usize> {
+    expr.as_numeric_value()
+        .and_then(|(n, _)| n.parse::<usize>().ok())
+}
+
+/// Compiled regexes by pattern. Patterns of `@regex` are compiled when the
+/// schema is validated and the IR is built, so checking a value only looks
+/// up the compiled regex.
+static REGEXES: OnceLock<RwLock<HashMap<String, Regex>>> = OnceLock::new();
+
+fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+    let regexes = REGEXES.get_or_init(Default::default);
+    if let Some(regex) = regexes
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .get(pattern)
+    {
+        return Ok(regex.clone());
+    }
+    let regex = Regex::new(pattern)?;
+    regexes
+        .write()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(pattern.to_string(), regex.clone());
+    Ok(regex)
+}
+
+/// Whether `this` passes the validator. Null always passes, values of types
+/// the validator doesn't apply to never do.
+pub(crate) fn evaluate_validator(this: &BamlValue, validator: &Validator) -> bool {
+    let matches = |pattern: &str| match this {
+        BamlValue::String(s) => compile(pattern).is_ok_and(|re| re.is_match(s)),
+        _ => false,
+    };
+    let number = || match this {
+        BamlValue::Int(i) => Some(*i as f64),
+        BamlValue::Float(f) => Some(*f),
+        _ => None,
+    };
+
+    match (this, validator) {
+        (BamlValue::Null, _) => true,
+        (_, Validator::Regex(pattern)) => matches(pattern),
+        (_, Validator::Format(format)) => matches(format.pattern()),
+        (_, Validator::Min(min)) => number().is_some_and(|n| n >= *min),
+        (_, Validator::Max(max)) => number().is_some_and(|n| n <= *max),
+        (_, Validator::Length { min, max }) => {
+            let length = match this {
+                BamlValue::String(s) => s.chars().count(),
+                BamlValue::List(items) => items.len(),
+                _ => return false,
+            };
+            (*min..=*max).contains(&length)
+        }
+    }
+}
@@ -4,7 +4,7 @@ use indexmap::IndexMap;
 
 use internal_baml_diagnostics::Span;
 use internal_baml_parser_database::RetryPolicyStrategy;
-use internal_llm_client::ClientSpec;
+use internal_llm_client::{ClientSpec, RetryOn};
 
 use std::collections::{HashMap, HashSet};
 
@@ -287,6 +287,20 @@ impl<'a> Walker<'a, &'a Class> {
             })
     }
 
+    /// The `@default` values of the fields, by field name.
+    pub fn default_values(&'a self) -> Result<IndexMap<String, serde_json::Value>> {
+        self.walk_fields()
+            .filter_map(|field| {
+                let value = field.default_value()?;
+                Some(
+                    value
+                        .resolve_serde(&EvaluationContext::default())
+                        .map(|value| (field.name().to_string(), value)),
+                )
+            })
+            .collect()
+    }
+
     pub fn elem(&self) -> &'a repr::Class {
         &self.item.elem
     }
@@ -357,6 +371,10 @@ impl<'a> Walker<'a, &'a RetryPolicy> {
         &self.elem().strategy
     }
 
+    pub fn retry_on(&self) -> RetryOn {
+        self.elem().retry_on.clone()
+    }
+
     pub fn span(&self) -> Option<&crate::Span> {
         self.item.attributes.span.as_ref()
     }
@@ -421,6 +439,11 @@ impl<'a> Walker<'a, &'a Field> {
         self.item.attributes.get("stream.not_null").is_some()
     }
 
+    /// The value of the field when the LLM leaves it out, from `@default`.
+    pub fn default_value(&self) -> Option<&'a UnresolvedValue<()>> {
+        self.item.attributes.get("default")
+    }
+
     pub fn streaming_state(&self) -> bool {
         self.item.attributes.get("stream.with_state").is_some()
     }
@@ -19,6 +19,11 @@ use std::collections::HashSet;
 pub(super) fn validate(ctx: &mut Context<'_>) {
     enums::validate(ctx);
     classes::validate(ctx);
+    for alias in ctx.db.walk_type_aliases() {
+        types::validate_type_params(ctx, alias.type_params());
+        types::validate_type_arguments(ctx, alias.target(), alias.type_params());
+        types::validate_type_validators(ctx, alias.target());
+    }
     functions::validate(ctx);
     clients::validate(ctx);
     template_strings::validate(ctx);
@@ -1,7 +1,13 @@
 use baml_types::GeneratorOutputType;
-use internal_baml_schema_ast::ast::{Field, FieldType, WithName, WithSpan};
+use internal_baml_parser_database::{
+    walkers::{ClassWalker, FieldWalker},
+    TypeWalker,
+};
+use internal_baml_schema_ast::ast::{
+    Field, FieldType, SubType, WithIdentifier, WithName, WithSpan,
+};
 
-use super::types::validate_type;
+use super::types::{validate_generic_type, validate_type, validate_type_params};
 use crate::validate::validation_pipeline::context::Context;
 use internal_baml_diagnostics::DatamodelError;
 
@@ -14,13 +20,27 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
     );
 
     for cls in ctx.db.walk_classes() {
+        validate_type_params(ctx, cls.type_params());
+
         for c in cls.static_fields() {
             let field = c.ast_field();
             if let Some(ft) = &field.expr {
-                validate_type(ctx, ft);
+                validate_generic_type(ctx, ft, cls.type_params());
             }
         }
 
+        let is_dynamic = cls
+            .get_default_attributes(SubType::Class)
+            .is_some_and(|attrs| attrs.dynamic_type().is_some());
+        if !cls.type_params().is_empty() && is_dynamic {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!("Generic class `{}` can't be @@dynamic.", cls.name()),
+                cls.identifier().span().clone(),
+            ));
+        }
+
+        validate_inheritance(ctx, cls);
+
         for args in cls.walk_input_args() {
             let arg = args.ast_arg();
             validate_type(ctx, &arg.1.field_type)
@@ -50,6 +70,81 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
     }
 }
 
+/// Checks that a class only extends classes, and that its fields don't clash
+/// with the fields it inherits.
+fn validate_inheritance(ctx: &mut Context<'_>, cls: ClassWalker<'_>) {
+    for base in &cls.ast_type_block().extends {
+        match ctx.db.find_type(base) {
+            Some(TypeWalker::Class(base_cls)) if !base_cls.type_params().is_empty() => ctx
+                .push_error(DatamodelError::new_validation_error(
+                    &format!(
+                        "Class `{}` can't extend the generic class `{}`.",
+                        cls.name(),
+                        base.name()
+                    ),
+                    base.span().clone(),
+                )),
+            Some(TypeWalker::Class(_)) => {}
+            Some(_) => ctx.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "Class `{}` can only extend classes, but `{}` is not a class.",
+                    cls.name(),
+                    base.name()
+                ),
+                base.span().clone(),
+            )),
+            None => ctx.push_error(DatamodelError::new_type_not_found_error(
+                base.name(),
+                ctx.db.valid_type_names(),
+                base.span().clone(),
+            )),
+        }
+    }
+
+    let mut seen: HashMap<String, FieldWalker<'_>> = HashMap::new();
+    for field in cls.fields() {
+        let Some(previous) = seen.get(field.name()).copied() else {
+            seen.insert(field.name().to_string(), field);
+            continue;
+        };
+
+        if field.model().id == cls.id {
+            // Duplicate fields defined in the class itself are reported when
+            // names are resolved.
+            if previous.model().id != cls.id {
+                ctx.push_error(DatamodelError::new_field_validation_error(
+                    format!(
+                        "The field is already inherited from `{}`. Inherited fields can't be redefined.",
+                        previous.model().name()
+                    ),
+                    "class",
+                    cls.name(),
+                    field.name(),
+                    field.ast_field().span.clone(),
+                ));
+            }
+            continue;
+        }
+
+        // A clash that a base class inherits as well is reported on that base.
+        let reported_on_base = cls
+            .bases()
+            .any(|base| base.fields().any(|f| f == field) && base.fields().any(|f| f == previous));
+        if !reported_on_base {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "Class `{}` inherits the field `{}` from both `{}` and `{}`.",
+                    cls.name(),
+                    field.name(),
+                    previous.model().name(),
+                    field.model().name()
+                ),
+                cls.identifier().span().clone(),
+            ));
+        }
+    }
+}
+
 /// Enforce that keywords in the user's requested target languages
 /// do not appear as field names in BAML classes, and that field
 /// names are not equal to type names when using Pydantic.
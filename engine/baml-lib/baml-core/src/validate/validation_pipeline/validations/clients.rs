@@ -35,6 +35,9 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
             internal_llm_client::UnresolvedClientProperty::Fallback(options) => {
                 validate_strategy(options, ctx);
             }
+            internal_llm_client::UnresolvedClientProperty::Weighted(options) => {
+                validate_strategy(options, ctx);
+            }
         }
     }
 }
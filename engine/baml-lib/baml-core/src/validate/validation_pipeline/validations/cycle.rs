@@ -7,13 +7,21 @@ use std::{
 use internal_baml_diagnostics::DatamodelError;
 use internal_baml_parser_database::{Tarjan, TypeWalker};
 use internal_baml_schema_ast::ast::{
-    self, FieldType, SchemaAst, TypeAliasId, TypeExpId, WithName, WithSpan,
+    self, FieldType, SchemaAst, TopId, TypeAliasId, TypeExpId, WithIdentifier, WithName, WithSpan,
 };
 
 use crate::validate::validation_pipeline::context::Context;
 
 /// Validates if the dependency graph contains one or more infinite cycles.
 pub(super) fn validate(ctx: &mut Context<'_>) {
+    // Generic classes and type aliases are expanded into concrete types when
+    // building the IR, the checks below assume that the expansion terminates.
+    report_recursive_generic_aliases(ctx);
+    report_infinite_expansions(ctx);
+    if ctx.diagnostics.has_errors() {
+        return;
+    }
+
     // We'll check type alias cycles first. Just like Typescript, cycles are
     // allowed only for maps and lists. We'll call such cycles "structural
     // recursion". Anything else like nulls or unions won't terminate a cycle.
@@ -43,6 +51,19 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
         .copied()
         .collect();
 
+    // A class can't extend itself, directly or through the classes it extends.
+    let inheritance_graph = HashMap::from_iter(
+        ctx.db
+            .walk_classes()
+            .map(|class| (class.id, class.bases().map(|base| base.id).collect())),
+    );
+
+    report_infinite_cycles(
+        &inheritance_graph,
+        ctx,
+        "These classes form an inheritance cycle",
+    );
+
     // Now build a graph of all the "required" dependencies represented as an
     // adjacency list. We're only going to consider type dependencies that can
     // actually cause infinite recursion. Unions and optionals can stop the
@@ -50,8 +71,6 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
     // graph because technically an optional field doesn't "depend" on anything,
     // it can just be null.
     let class_dependency_graph = HashMap::from_iter(ctx.db.walk_classes().map(|class| {
-        let expr_block = &ctx.db.ast()[class.id];
-
         // TODO: There's already a hash set that returns "dependencies" in
         // the DB, it shoudn't be necessary to traverse all the fields here
         // again and build yet another graph, we need to refactor
@@ -62,15 +81,18 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
         // baml-lib/parser-database/src/types/mod.rs
         // fn visit_class()
         let mut dependencies = HashSet::new();
+        let mut expanded = HashSet::new();
 
-        for field in &expr_block.fields {
-            if let Some(field_type) = &field.expr {
+        // Inherited fields are part of the class too.
+        for field in class.fields() {
+            if let Some(field_type) = field.r#type() {
                 insert_required_class_deps(
                     class.id,
                     field_type,
                     ctx,
                     &mut dependencies,
                     &complete_alias_cycles,
+                    &mut expanded,
                 );
             }
         }
@@ -165,19 +187,41 @@ where
 /// it reaches stack overflows with large inputs.
 ///
 /// TODO: Use a struct to keep all this state. Too many parameters already.
+///
+/// Generic types applied to type arguments are expanded, since a generic class
+/// depends on whatever its fields are instantiated with. `expanded` keeps
+/// track of the applications that were already expanded.
 fn insert_required_class_deps(
     id: TypeExpId,
     field: &FieldType,
     ctx: &Context<'_>,
     deps: &mut HashSet<TypeExpId>,
     alias_cycles: &HashSet<TypeAliasId>,
+    expanded: &mut HashSet<String>,
 ) {
     match field {
-        FieldType::Symbol(arity, ident, _) if arity.is_required() => {
+        FieldType::Symbol(arity, ident, args, _) if arity.is_required() => {
             match ctx.db.find_type_by_str(ident.name()) {
                 Some(TypeWalker::Class(class)) => {
                     deps.insert(class.id);
 
+                    if !args.is_empty() && expanded.insert(field.to_string()) {
+                        for class_field in class.fields() {
+                            if let Some(field_type) = class_field.r#type() {
+                                let field_type =
+                                    field_type.substitute_type_params(class.type_params(), args);
+                                insert_required_class_deps(
+                                    id,
+                                    &field_type,
+                                    ctx,
+                                    deps,
+                                    alias_cycles,
+                                    expanded,
+                                );
+                            }
+                        }
+                    }
+
                     // TODO: #1343 Temporary solution until we implement scoping in the AST.
                     if !class.name().starts_with(ast::DYNAMIC_TYPE_NAME_PREFIX) {
                         let dyn_def_name =
@@ -189,10 +233,25 @@ fn insert_required_class_deps(
                         }
                     }
                 }
+                Some(TypeWalker::TypeAlias(alias)) if !args.is_empty() => {
+                    if expanded.insert(field.to_string()) {
+                        let target = alias
+                            .target()
+                            .substitute_type_params(alias.type_params(), args);
+                        insert_required_class_deps(id, &target, ctx, deps, alias_cycles, expanded)
+                    }
+                }
                 Some(TypeWalker::TypeAlias(alias)) => {
                     // This code runs after aliases are already resolved.
                     if !alias_cycles.contains(&alias.id) {
-                        insert_required_class_deps(id, alias.resolved(), ctx, deps, alias_cycles)
+                        insert_required_class_deps(
+                            id,
+                            alias.resolved(),
+                            ctx,
+                            deps,
+                            alias_cycles,
+                            expanded,
+                        )
                     }
                 }
                 _ => {}
@@ -209,7 +268,7 @@ fn insert_required_class_deps(
             let mut nested_deps = HashSet::new();
 
             for f in field_types {
-                insert_required_class_deps(id, f, ctx, &mut nested_deps, alias_cycles);
+                insert_required_class_deps(id, f, ctx, &mut nested_deps, alias_cycles, expanded);
 
                 // No nested deps found on this component, this makes the
                 // union finite, so no need to go deeper.
@@ -247,7 +306,7 @@ fn insert_required_alias_deps(
     required: &mut HashSet<TypeAliasId>,
 ) {
     match field_type {
-        FieldType::Symbol(_, ident, _) => {
+        FieldType::Symbol(_, ident, ..) => {
             if let Some(TypeWalker::TypeAlias(alias)) = ctx.db.find_type_by_str(ident.name()) {
                 required.insert(alias.id);
             }
@@ -262,3 +321,165 @@ fn insert_required_alias_deps(
         _ => {}
     }
 }
+
+/// Generic type aliases are expanded when they're applied to type arguments,
+/// so unlike other aliases they can't refer to themselves.
+fn report_recursive_generic_aliases(ctx: &mut Context<'_>) {
+    let recursive_generics = ctx
+        .db
+        .recursive_alias_cycles()
+        .iter()
+        .flatten()
+        .map(|id| ctx.db.walk(*id))
+        .filter(|alias| !alias.type_params().is_empty())
+        .collect::<Vec<_>>();
+
+    for alias in recursive_generics {
+        ctx.push_error(DatamodelError::new_validation_error(
+            &format!("Generic type alias `{}` can't be recursive.", alias.name()),
+            alias.span().clone(),
+        ));
+    }
+}
+
+/// A type parameter, as the generic type that declares it and its position.
+type TypeParam = (TopId, usize);
+
+/// Reports generic types that would expand into infinitely many types, like
+/// `class Nested<T> { inner Nested<T[]>? }`, where every level of nesting
+/// needs a new specialization.
+///
+/// The nodes of the graph are type parameters, identified by the generic type
+/// that declares them and their position. There's an edge from a parameter to
+/// every parameter it's passed to as a type argument, and the edge "grows" if
+/// the parameter is wrapped in another type instead of passed as is. The
+/// expansion is infinite when a growing edge is part of a cycle.
+fn report_infinite_expansions(ctx: &mut Context<'_>) {
+    let mut graph = HashMap::new();
+    let mut growing = Vec::new();
+
+    let generics = ctx
+        .db
+        .walk_classes()
+        .filter(|class| !class.type_params().is_empty())
+        .map(|class| {
+            let field_types = class
+                .static_fields()
+                .filter_map(|field| field.r#type().as_ref());
+            (
+                TopId::Class(class.id),
+                class.type_params(),
+                field_types.collect(),
+            )
+        })
+        .chain(
+            ctx.db
+                .walk_type_aliases()
+                .filter(|alias| !alias.type_params().is_empty())
+                .map(|alias| {
+                    (
+                        TopId::TypeAlias(alias.id),
+                        alias.type_params(),
+                        vec![alias.target()],
+                    )
+                }),
+        )
+        .collect::<Vec<(TopId, &[ast::Identifier], Vec<&FieldType>)>>();
+
+    for (top_id, type_params, _) in &generics {
+        for position in 0..type_params.len() {
+            graph.insert((*top_id, position), HashSet::new());
+        }
+    }
+
+    for (top_id, type_params, field_types) in &generics {
+        for field_type in field_types {
+            insert_type_arg_edges(
+                ctx,
+                (*top_id, type_params),
+                field_type,
+                &mut graph,
+                &mut growing,
+            );
+        }
+    }
+
+    let mut reported = HashSet::new();
+    for component in Tarjan::components(&graph) {
+        let is_infinite = growing
+            .iter()
+            .any(|(from, to)| component.contains(from) && component.contains(to));
+
+        if is_infinite && reported.insert(component[0].0) {
+            let top = &ctx.db.ast()[component[0].0];
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "Generic type `{}` expands infinitely: it refers to itself with type arguments that keep growing.",
+                    top.name()
+                ),
+                top.identifier().span().clone(),
+            ));
+        }
+    }
+}
+
+/// Adds an edge for every type parameter of `generic` that's passed as a type
+/// argument to a generic type in `field_type`.
+fn insert_type_arg_edges(
+    ctx: &Context<'_>,
+    generic: (TopId, &[ast::Identifier]),
+    field_type: &FieldType,
+    graph: &mut HashMap<TypeParam, HashSet<TypeParam>>,
+    growing: &mut Vec<(TypeParam, TypeParam)>,
+) {
+    let (top_id, type_params) = generic;
+
+    match field_type {
+        FieldType::Symbol(_, ident, args, _) => {
+            let target = match ctx.db.find_type_by_str(ident.name()) {
+                Some(TypeWalker::Class(class)) => Some(TopId::Class(class.id)),
+                Some(TypeWalker::TypeAlias(alias)) => Some(TopId::TypeAlias(alias.id)),
+                _ => None,
+            };
+
+            if let Some(target) = target.filter(|_| !args.is_empty()) {
+                for (arg_position, arg) in args.iter().enumerate() {
+                    for (position, param) in type_params.iter().enumerate() {
+                        let edge = ((top_id, position), (target, arg_position));
+                        match arg {
+                            FieldType::Symbol(_, arg_ident, arg_args, _)
+                                if arg_ident.name() == param.name() && arg_args.is_empty() =>
+                            {
+                                graph.entry(edge.0).or_default().insert(edge.1);
+                            }
+                            _ if arg.flat_idns().iter().any(|i| i.name() == param.name()) => {
+                                graph.entry(edge.0).or_default().insert(edge.1);
+                                growing.push(edge);
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+
+            for arg in args {
+                insert_type_arg_edges(ctx, generic, arg, graph, growing);
+            }
+        }
+
+        FieldType::List(_, item, ..) => insert_type_arg_edges(ctx, generic, item, graph, growing),
+
+        FieldType::Map(_, kv, ..) => {
+            insert_type_arg_edges(ctx, generic, &kv.0, graph, growing);
+            insert_type_arg_edges(ctx, generic, &kv.1, graph, growing);
+        }
+
+        FieldType::Union(_, items, ..) | FieldType::Tuple(_, items, ..) => {
+            for item in items {
+                insert_type_arg_edges(ctx, generic, item, graph, growing);
+            }
+        }
+
+        FieldType::Primitive(..) | FieldType::Literal(..) => {}
+    }
+}
@@ -253,7 +253,7 @@ impl<'c> NestedChecks<'c> {
                         return false;
                     }
 
-                    let mut fields = class_walker.static_fields();
+                    let mut fields = class_walker.fields();
                     fields.any(|field| {
                         field
                             .ast_field()
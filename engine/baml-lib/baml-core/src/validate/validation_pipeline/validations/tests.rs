@@ -15,6 +15,7 @@ pub(super) fn validate(ctx: &mut Context<'_>) {
                 label,
                 level,
                 expression,
+                ..
             },
             constraint_span,
             expr_span,
@@ -1,6 +1,6 @@
 use std::collections::VecDeque;
 
-use baml_types::{LiteralValue, TypeValue};
+use baml_types::{LiteralValue, TypeValue, Validator};
 use either::Either;
 use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Span};
 use internal_baml_parser_database::TypeWalker;
@@ -8,7 +8,9 @@ use internal_baml_schema_ast::ast::{
     Argument, Attribute, Expression, FieldArity, FieldType, Identifier, WithName, WithSpan,
 };
 
-use crate::validate::validation_pipeline::context::Context;
+use crate::{
+    ir::validators::attribute_as_validator, validate::validation_pipeline::context::Context,
+};
 
 fn errors_with_names(ctx: &mut Context<'_>, idn: &Identifier) {
     // Push the error with the appropriate message
@@ -25,18 +27,36 @@ fn errors_with_names(ctx: &mut Context<'_>, idn: &Identifier) {
 ///
 ///   1. Verify that the type is resolveable (for REF types).
 ///   2. Verify that the type is well-formed/allowed in the language.
-///   3. Verify that constraints on the type are well-formed.
+///   3. Verify that constraints and validators on the type are well-formed.
 pub(crate) fn validate_type(ctx: &mut Context<'_>, field_type: &FieldType) {
-    validate_type_exists(ctx, field_type);
+    validate_generic_type(ctx, field_type, &[]);
+}
+
+/// Same as [`validate_type`], but the type can also refer to the given type
+/// parameters, like the fields of a generic class do.
+pub(crate) fn validate_generic_type(
+    ctx: &mut Context<'_>,
+    field_type: &FieldType,
+    type_params: &[Identifier],
+) {
+    if !validate_type_exists(ctx, field_type, type_params) {
+        validate_type_arguments(ctx, field_type, type_params);
+    }
     validate_type_allowed(ctx, field_type);
     validate_type_constraints(ctx, field_type);
+    validate_type_validators(ctx, field_type);
 }
 
-fn validate_type_exists(ctx: &mut Context<'_>, field_type: &FieldType) -> bool {
+fn validate_type_exists(
+    ctx: &mut Context<'_>,
+    field_type: &FieldType,
+    type_params: &[Identifier],
+) -> bool {
     let mut errors = false;
     field_type
         .flat_idns()
         .iter()
+        .filter(|f| !type_params.iter().any(|param| param.name() == f.name()))
         .for_each(|f| match ctx.db.find_type(f) {
             Some(_) => {}
 
@@ -51,6 +71,92 @@ fn validate_type_exists(ctx: &mut Context<'_>, field_type: &FieldType) -> bool {
     errors
 }
 
+/// Checks that generic types get as many type arguments as they have type
+/// parameters, and that nothing else gets type arguments.
+pub(crate) fn validate_type_arguments(
+    ctx: &mut Context<'_>,
+    field_type: &FieldType,
+    type_params: &[Identifier],
+) {
+    match field_type {
+        FieldType::Symbol(_, identifier, args, _) => {
+            let expected = if type_params
+                .iter()
+                .any(|param| param.name() == identifier.name())
+            {
+                Some(0)
+            } else {
+                match ctx.db.find_type(identifier) {
+                    Some(TypeWalker::Class(class)) => Some(class.type_params().len()),
+                    Some(TypeWalker::TypeAlias(alias)) => Some(alias.type_params().len()),
+                    Some(TypeWalker::Enum(_)) => Some(0),
+                    // Already reported.
+                    None => None,
+                }
+            };
+
+            match expected {
+                Some(0) if !args.is_empty() => {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        &format!(
+                            "`{}` is not generic, it doesn't take type arguments.",
+                            identifier.name()
+                        ),
+                        field_type.span().clone(),
+                    ))
+                }
+                Some(expected) if expected != args.len() => {
+                    ctx.push_error(DatamodelError::new_validation_error(
+                        &format!(
+                            "`{}` expects {expected} type argument{}, but got {}.",
+                            identifier.name(),
+                            if expected == 1 { "" } else { "s" },
+                            args.len()
+                        ),
+                        field_type.span().clone(),
+                    ))
+                }
+                _ => {}
+            }
+
+            for arg in args {
+                validate_type_arguments(ctx, arg, type_params);
+            }
+        }
+        FieldType::Primitive(..) | FieldType::Literal(..) => {}
+        FieldType::List(_, item, ..) => validate_type_arguments(ctx, item, type_params),
+        FieldType::Map(_, kv, ..) => {
+            validate_type_arguments(ctx, &kv.0, type_params);
+            validate_type_arguments(ctx, &kv.1, type_params);
+        }
+        FieldType::Tuple(_, items, ..) | FieldType::Union(_, items, ..) => {
+            for item in items {
+                validate_type_arguments(ctx, item, type_params);
+            }
+        }
+    }
+}
+
+/// Checks the type parameters declared by a generic class or type alias.
+pub(crate) fn validate_type_params(ctx: &mut Context<'_>, type_params: &[Identifier]) {
+    for (i, param) in type_params.iter().enumerate() {
+        if type_params[..i].iter().any(|p| p.name() == param.name()) {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!("Duplicate type parameter `{}`.", param.name()),
+                param.span().clone(),
+            ));
+        } else if ctx.db.find_type(param).is_some() || param.name().parse::<TypeValue>().is_ok() {
+            ctx.push_error(DatamodelError::new_validation_error(
+                &format!(
+                    "Type parameter `{}` has the same name as a declared type.",
+                    param.name()
+                ),
+                param.span().clone(),
+            ));
+        }
+    }
+}
+
 fn validate_type_allowed(ctx: &mut Context<'_>, field_type: &FieldType) {
     match field_type {
         FieldType::Map(arity, kv_types, ..) => {
@@ -59,11 +165,12 @@ fn validate_type_allowed(ctx: &mut Context<'_>, field_type: &FieldType) {
                 FieldType::Primitive(FieldArity::Required, TypeValue::String, ..) => {}
 
                 // Enum key.
-                FieldType::Symbol(FieldArity::Required, identifier, _)
-                    if ctx
-                        .db
-                        .find_type(identifier)
-                        .is_some_and(|t| matches!(t, TypeWalker::Enum(_))) => {}
+                FieldType::Symbol(FieldArity::Required, identifier, args, _)
+                    if args.is_empty()
+                        && ctx
+                            .db
+                            .find_type(identifier)
+                            .is_some_and(|t| matches!(t, TypeWalker::Enum(_))) => {}
 
                 // Literal string key.
                 FieldType::Literal(FieldArity::Required, LiteralValue::String(_), ..) => {}
@@ -109,7 +216,11 @@ fn validate_type_allowed(ctx: &mut Context<'_>, field_type: &FieldType) {
 
         FieldType::Primitive(..) => {}
         FieldType::Literal(..) => {}
-        FieldType::Symbol(..) => {}
+        FieldType::Symbol(_, _, args, _) => {
+            for arg in args {
+                validate_type_allowed(ctx, arg);
+            }
+        }
 
         FieldType::List(arity, field_type, ..) => validate_type_allowed(ctx, field_type),
         FieldType::Tuple(_, field_types, ..) | FieldType::Union(_, field_types, ..) => {
@@ -120,6 +231,86 @@ fn validate_type_allowed(ctx: &mut Context<'_>, field_type: &FieldType) {
     }
 }
 
+/// Checks the arguments of `@regex`, `@min`, `@max`, `@length` and `@format`,
+/// and that they are put on types they apply to. Recurses into nested types.
+pub(crate) fn validate_type_validators(ctx: &mut Context<'_>, field_type: &FieldType) {
+    for attribute in field_type.attributes() {
+        let validator = match attribute_as_validator(attribute) {
+            None => continue,
+            Some(Ok(validator)) => validator,
+            Some(Err(message)) => {
+                ctx.push_error(DatamodelError::new_attribute_validation_error(
+                    &message,
+                    attribute.name(),
+                    attribute.span.clone(),
+                ));
+                continue;
+            }
+        };
+        let kind = ValidatedKind::of(ctx, field_type);
+        let applies = match validator {
+            Validator::Regex(_) | Validator::Format(_) => kind == Some(ValidatedKind::String),
+            Validator::Min(_) | Validator::Max(_) => kind == Some(ValidatedKind::Number),
+            Validator::Length { .. } => {
+                matches!(kind, Some(ValidatedKind::String | ValidatedKind::List))
+            }
+        };
+        if !applies {
+            let applies_to = match validator {
+                Validator::Regex(_) | Validator::Format(_) => "strings",
+                Validator::Min(_) | Validator::Max(_) => "ints and floats",
+                Validator::Length { .. } => "strings and lists",
+            };
+            ctx.push_error(DatamodelError::new_attribute_validation_error(
+                &format!("@{} can only be used on {applies_to}.", attribute.name()),
+                attribute.name(),
+                attribute.span.clone(),
+            ));
+        }
+    }
+
+    match field_type {
+        FieldType::Primitive(..) | FieldType::Literal(..) => {}
+        FieldType::Symbol(_, _, args, _) => {
+            for arg in args {
+                validate_type_validators(ctx, arg);
+            }
+        }
+        FieldType::List(_, item, ..) => validate_type_validators(ctx, item),
+        FieldType::Map(_, kv, ..) => validate_type_validators(ctx, &kv.1),
+        FieldType::Tuple(_, items, ..) | FieldType::Union(_, items, ..) => {
+            for item in items {
+                validate_type_validators(ctx, item);
+            }
+        }
+    }
+}
+
+/// The kinds of values validators apply to.
+#[derive(PartialEq)]
+enum ValidatedKind {
+    String,
+    Number,
+    List,
+}
+
+impl ValidatedKind {
+    fn of(ctx: &Context<'_>, field_type: &FieldType) -> Option<Self> {
+        match field_type {
+            FieldType::Primitive(_, TypeValue::String, ..)
+            | FieldType::Literal(_, LiteralValue::String(_), ..) => Some(Self::String),
+            FieldType::Primitive(_, TypeValue::Int | TypeValue::Float, ..)
+            | FieldType::Literal(_, LiteralValue::Int(_), ..) => Some(Self::Number),
+            FieldType::List(..) => Some(Self::List),
+            FieldType::Symbol(_, identifier, ..) => match ctx.db.find_type(identifier) {
+                Some(TypeWalker::TypeAlias(alias)) => Self::of(ctx, alias.resolved()),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
 fn validate_type_constraints(ctx: &mut Context<'_>, field_type: &FieldType) {
     let constraint_attrs = field_type
         .attributes()
@@ -5,9 +5,22 @@ pub struct Constraint {
     pub level: ConstraintLevel,
     pub expression: JinjaExpression,
     pub label: Option<String>,
+    /// Set when the constraint comes from a built-in validator such as
+    /// `@regex`. `expression` is then the jinja equivalent of the validator.
+    pub validator: Option<Validator>,
 }
 
 impl Constraint {
+    /// The assert a built-in validator stands for.
+    pub fn from_validator(validator: Validator) -> Self {
+        Constraint {
+            level: ConstraintLevel::Assert,
+            expression: validator.to_expression(),
+            label: None,
+            validator: Some(validator),
+        }
+    }
+
     pub fn as_check(self) -> Option<(String, JinjaExpression)> {
         match self.level {
             ConstraintLevel::Check => Some((
@@ -26,6 +39,121 @@ pub enum ConstraintLevel {
     Assert,
 }
 
+/// A built-in validator, written as a type attribute like `@regex("...")` or
+/// `@min(0)`.
+///
+/// Unlike a jinja expression, the meaning of a validator is known ahead of
+/// time, so the coercer checks it natively and generators emit it as a native
+/// validator of the target language. Null values always pass, so validators
+/// can be put on optional types.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub enum Validator {
+    /// `@regex(pattern)`: the string contains a match of the pattern.
+    Regex(String),
+    /// `@min(n)`: the number is at least `n`.
+    Min(f64),
+    /// `@max(n)`: the number is at most `n`.
+    Max(f64),
+    /// `@length(min, max)`: the string or list has between `min` and `max`
+    /// items, inclusive.
+    Length { min: usize, max: usize },
+    /// `@format(name)`: the string is in a well-known format.
+    Format(Format),
+}
+
+/// The formats accepted by `@format`, named as in JSON schema.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    PartialEq,
+    Eq,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    strum::VariantNames,
+)]
+pub enum Format {
+    #[strum(serialize = "email")]
+    Email,
+    #[strum(serialize = "uri")]
+    Uri,
+    #[strum(serialize = "date")]
+    Date,
+    #[strum(serialize = "date-time")]
+    DateTime,
+    #[strum(serialize = "uuid")]
+    Uuid,
+}
+
+impl Format {
+    /// Regex that values of the format match. Intentionally loose for email
+    /// and uri, the aim is to catch values that are clearly something else.
+    pub fn pattern(&self) -> &'static str {
+        match self {
+            Format::Email => r"^[^@\s]+@[^@\s]+\.[^@\s]+$",
+            Format::Uri => r"^[a-zA-Z][a-zA-Z0-9+.-]*:[^\s]+$",
+            Format::Date => r"^\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])$",
+            Format::DateTime => {
+                r"^\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])[Tt ]([01]\d|2[0-3]):[0-5]\d:[0-5]\d(\.\d+)?([Zz]|[+-]([01]\d|2[0-3]):[0-5]\d)$"
+            }
+            Format::Uuid => {
+                r"^[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}$"
+            }
+        }
+    }
+}
+
+impl Validator {
+    /// Names of the attributes that declare validators.
+    pub const ATTRIBUTE_NAMES: [&'static str; 5] = ["regex", "min", "max", "length", "format"];
+
+    /// A jinja expression that is true exactly when the validator passes, for
+    /// the places that only know how to evaluate expressions.
+    pub fn to_expression(&self) -> JinjaExpression {
+        let check = match self {
+            Validator::Regex(pattern) => format!("this|regex_match({})", quote(pattern)),
+            Validator::Min(min) => format!("this >= {min}"),
+            Validator::Max(max) => format!("this <= {max}"),
+            Validator::Length { min, max } => {
+                format!("this|length >= {min} and this|length <= {max}")
+            }
+            Validator::Format(format) => {
+                format!("this|regex_match({})", quote(format.pattern()))
+            }
+        };
+        JinjaExpression(format!("this is none or ({check})"))
+    }
+
+    /// Short description of the validator, e.g. `min: 0`, for prompts and
+    /// error messages.
+    pub fn describe(&self) -> String {
+        match self {
+            Validator::Regex(pattern) => format!("pattern: {pattern}"),
+            Validator::Min(min) => format!("min: {min}"),
+            Validator::Max(max) => format!("max: {max}"),
+            Validator::Length { min, max } => format!("length: {min} to {max}"),
+            Validator::Format(format) => format!("format: {format}"),
+        }
+    }
+}
+
+impl std::fmt::Display for Validator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Validator::Regex(pattern) => write!(f, "@regex({})", quote(pattern)),
+            Validator::Min(min) => write!(f, "@min({min})"),
+            Validator::Max(max) => write!(f, "@max({max})"),
+            Validator::Length { min, max } => write!(f, "@length({min}, {max})"),
+            Validator::Format(format) => write!(f, "@format(\"{format}\")"),
+        }
+    }
+}
+
+fn quote(s: &str) -> String {
+    serde_json::to_string(s).expect("Strings always serialize")
+}
+
 /// The user-visible schema for a failed check.
 #[derive(Clone, Debug, serde::Serialize, PartialEq, Eq)]
 pub struct ResponseCheck {
@@ -45,6 +173,7 @@ impl ResponseCheck {
                 level,
                 expression,
                 label,
+                ..
             },
             succeeded,
         ): (Constraint, bool),
@@ -22,6 +22,9 @@ pub enum GeneratorOutputType {
 
     #[strum(serialize = "ruby/sorbet")]
     RubySorbet,
+
+    #[strum(serialize = "go")]
+    Go,
 }
 
 impl std::hash::Hash for GeneratorOutputType {
@@ -39,6 +42,7 @@ impl GeneratorOutputType {
             Self::PythonPydantic => GeneratorDefaultClientMode::Async,
             Self::Typescript => GeneratorDefaultClientMode::Async,
             Self::RubySorbet => GeneratorDefaultClientMode::Sync,
+            Self::Go => GeneratorDefaultClientMode::Sync,
         }
     }
 
@@ -49,6 +53,7 @@ impl GeneratorOutputType {
             Self::PythonPydantic => GeneratorDefaultClientMode::Sync,
             Self::Typescript => GeneratorDefaultClientMode::Async,
             Self::RubySorbet => GeneratorDefaultClientMode::Sync,
+            Self::Go => GeneratorDefaultClientMode::Sync,
         }
     }
 }
@@ -6,6 +6,7 @@ mod output_format;
 use internal_baml_core::ir::jinja_helpers::get_env;
 use internal_baml_core::ir::repr::IntermediateRepr;
 pub use output_format::types;
+pub use output_format::ToolSchema;
 mod baml_value_to_jinja_value;
 
 use minijinja::{self, value::Kwargs};
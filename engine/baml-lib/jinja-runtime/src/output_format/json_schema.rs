@@ -0,0 +1,291 @@
+use anyhow::Result;
+use baml_types::{FieldType, LiteralValue, TypeValue};
+use serde_json::json;
+
+use super::types::OutputFormatContent;
+
+/// A native tool definition generated from one class of the output type.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ToolSchema {
+    pub name: String,
+    /// JSON schema of the tool arguments. The root is always an object.
+    pub parameters: serde_json::Value,
+}
+
+impl OutputFormatContent {
+    /// Self-contained JSON schema for the target type.
+    pub fn target_json_schema(&self) -> Result<serde_json::Value> {
+        self.json_schema_for(&self.target)
+    }
+
+    /// Self-contained JSON schema for `field_type`.
+    ///
+    /// Field names and enum values use their rendered names (aliases) so that
+    /// the model produces the same keys it would see in the prompt. Classes,
+    /// enums and recursive aliases referenced from the root are emitted under
+    /// `$defs`.
+    pub fn json_schema_for(&self, field_type: &FieldType) -> Result<serde_json::Value> {
+        let mut defs = serde_json::Map::new();
+        let mut schema = match field_type {
+            // Inline the root class, tool parameters must be an object.
+            FieldType::Class(name) => self.class_schema(name, &mut defs)?,
+            other => self.type_schema(other, &mut defs)?,
+        };
+        if !defs.is_empty() {
+            schema["$defs"] = serde_json::Value::Object(defs);
+        }
+        Ok(schema)
+    }
+
+    /// One tool per class that the target type may resolve to.
+    ///
+    /// The target must be a class, a union of classes, or an optional or list
+    /// of those.
+    pub fn tool_schemas(&self) -> Result<Vec<ToolSchema>> {
+        fn tool_classes<'a>(ft: &'a FieldType, out: &mut Vec<&'a str>) -> bool {
+            match ft {
+                FieldType::Class(name) => {
+                    if !out.contains(&name.as_str()) {
+                        out.push(name);
+                    }
+                    true
+                }
+                FieldType::Union(options) => options.iter().all(|o| tool_classes(o, out)),
+                FieldType::Optional(inner) | FieldType::List(inner) => tool_classes(inner, out),
+                FieldType::WithMetadata { base, .. } => tool_classes(base, out),
+                _ => false,
+            }
+        }
+
+        let mut classes = vec![];
+        if !tool_classes(&self.target, &mut classes) {
+            anyhow::bail!(
+                "Tool calling requires the function to return a class or a union of classes, got {}",
+                self.target
+            );
+        }
+
+        classes
+            .into_iter()
+            .map(|name| {
+                Ok(ToolSchema {
+                    name: name.to_string(),
+                    parameters: self.json_schema_for(&FieldType::class(name))?,
+                })
+            })
+            .collect()
+    }
+
+    fn class_schema(
+        &self,
+        name: &str,
+        defs: &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let class = self.find_class(name)?;
+        let mut properties = serde_json::Map::new();
+        let mut required = vec![];
+        for (field_name, field_type, description, _) in &class.fields {
+            let mut schema = self.type_schema(field_type, defs)?;
+            if let Some(description) = description {
+                schema["description"] = json!(description.trim());
+            }
+            properties.insert(field_name.rendered_name().to_string(), schema);
+            if !field_type.is_optional() {
+                required.push(field_name.rendered_name().to_string());
+            }
+        }
+        Ok(json!({
+            "type": "object",
+            "properties": properties,
+            "required": required,
+        }))
+    }
+
+    fn type_schema(
+        &self,
+        field_type: &FieldType,
+        defs: &mut serde_json::Map<String, serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        Ok(match field_type {
+            FieldType::Primitive(t) => match t {
+                TypeValue::String => json!({ "type": "string" }),
+                TypeValue::Int => json!({ "type": "integer" }),
+                TypeValue::Float => json!({ "type": "number" }),
+                TypeValue::Bool => json!({ "type": "boolean" }),
+                TypeValue::Null => json!({ "type": "null" }),
+                TypeValue::Media(media_type) => {
+                    anyhow::bail!("type '{media_type}' is not supported in outputs")
+                }
+            },
+            FieldType::Literal(v) => match v {
+                LiteralValue::String(s) => json!({ "type": "string", "const": s }),
+                LiteralValue::Int(i) => json!({ "type": "integer", "const": i }),
+                LiteralValue::Bool(b) => json!({ "type": "boolean", "const": b }),
+            },
+            FieldType::Enum(name) => {
+                if !defs.contains_key(name) {
+                    let enm = self.find_enum(name)?;
+                    let mut schema = json!({
+                        "type": "string",
+                        "enum": enm
+                            .values
+                            .iter()
+                            .map(|(v, _)| v.rendered_name())
+                            .collect::<Vec<_>>(),
+                    });
+                    let described = enm
+                        .values
+                        .iter()
+                        .filter_map(|(v, d)| {
+                            d.as_ref()
+                                .map(|d| format!("{}: {}", v.rendered_name(), d.trim()))
+                        })
+                        .collect::<Vec<_>>();
+                    if !described.is_empty() {
+                        schema["description"] = json!(described.join("\n"));
+                    }
+                    defs.insert(name.clone(), schema);
+                }
+                json!({ "$ref": format!("#/$defs/{name}") })
+            }
+            FieldType::Class(name) => {
+                if !defs.contains_key(name) {
+                    // Reserve the slot first so recursive classes terminate.
+                    defs.insert(name.clone(), serde_json::Value::Null);
+                    let schema = self.class_schema(name, defs)?;
+                    defs.insert(name.clone(), schema);
+                }
+                json!({ "$ref": format!("#/$defs/{name}") })
+            }
+            FieldType::RecursiveTypeAlias(name) => {
+                if !defs.contains_key(name) {
+                    defs.insert(name.clone(), serde_json::Value::Null);
+                    let target = self.find_recursive_alias_target(name)?;
+                    let schema = self.type_schema(target, defs)?;
+                    defs.insert(name.clone(), schema);
+                }
+                json!({ "$ref": format!("#/$defs/{name}") })
+            }
+            FieldType::List(item) => json!({
+                "type": "array",
+                "items": self.type_schema(item, defs)?,
+            }),
+            FieldType::Map(_, value) => json!({
+                "type": "object",
+                "additionalProperties": self.type_schema(value, defs)?,
+            }),
+            FieldType::Tuple(items) => json!({
+                "type": "array",
+                "prefixItems": items
+                    .iter()
+                    .map(|t| self.type_schema(t, defs))
+                    .collect::<Result<Vec<_>>>()?,
+            }),
+            FieldType::Union(options) => json!({
+                "anyOf": options
+                    .iter()
+                    .map(|t| self.type_schema(t, defs))
+                    .collect::<Result<Vec<_>>>()?,
+            }),
+            FieldType::Optional(inner) => json!({
+                "anyOf": [self.type_schema(inner, defs)?, { "type": "null" }],
+            }),
+            FieldType::WithMetadata { base, .. } => self.type_schema(base, defs)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use baml_types::StreamingBehavior;
+    use indexmap::IndexMap;
+
+    use super::*;
+    use crate::types::{Class, Enum, Name};
+
+    fn person() -> Class {
+        Class {
+            name: Name::new("Person".to_string()),
+            fields: vec![
+                (
+                    Name::new_with_alias("name".to_string(), Some("full_name".to_string())),
+                    FieldType::string(),
+                    Some("The person's name".to_string()),
+                    false,
+                ),
+                (
+                    Name::new("role".to_string()),
+                    FieldType::optional(FieldType::Enum("Role".to_string())),
+                    None,
+                    false,
+                ),
+            ],
+            constraints: Vec::new(),
+            streaming_behavior: StreamingBehavior::default(),
+            defaults: IndexMap::new(),
+        }
+    }
+
+    fn role() -> Enum {
+        Enum {
+            name: Name::new("Role".to_string()),
+            values: vec![
+                (Name::new("Admin".to_string()), None),
+                (
+                    Name::new_with_alias("Guest".to_string(), Some("visitor".to_string())),
+                    None,
+                ),
+            ],
+            constraints: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn class_schema_uses_rendered_names() {
+        let content = OutputFormatContent::target(FieldType::class("Person"))
+            .classes(vec![person()])
+            .enums(vec![role()])
+            .build();
+
+        assert_eq!(
+            content.target_json_schema().unwrap(),
+            json!({
+                "type": "object",
+                "properties": {
+                    "full_name": { "type": "string", "description": "The person's name" },
+                    "role": { "anyOf": [{ "$ref": "#/$defs/Role" }, { "type": "null" }] },
+                },
+                "required": ["full_name"],
+                "$defs": {
+                    "Role": { "type": "string", "enum": ["Admin", "visitor"] },
+                },
+            })
+        );
+    }
+
+    #[test]
+    fn tool_per_union_member() {
+        let mut other = person();
+        other.name = Name::new("Robot".to_string());
+        let content = OutputFormatContent::target(FieldType::list(FieldType::union(vec![
+            FieldType::class("Person"),
+            FieldType::class("Robot"),
+        ])))
+        .classes(vec![person(), other])
+        .enums(vec![role()])
+        .build();
+
+        let tools = content.tool_schemas().unwrap();
+        assert_eq!(
+            tools.iter().map(|t| t.name.as_str()).collect::<Vec<_>>(),
+            vec!["Person", "Robot"]
+        );
+        assert_eq!(tools[0].parameters["type"], json!("object"));
+    }
+
+    #[test]
+    fn tools_require_classes() {
+        let content = OutputFormatContent::target(FieldType::string()).build();
+        assert!(content.tool_schemas().is_err());
+    }
+}
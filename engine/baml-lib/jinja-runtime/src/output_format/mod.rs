@@ -1,5 +1,9 @@
+mod json_schema;
+mod styles;
 pub mod types;
 
+pub use json_schema::ToolSchema;
+
 use std::str::FromStr;
 
 use minijinja::{value::Kwargs, ErrorKind, Value};
@@ -163,10 +167,55 @@ impl minijinja::value::Object for OutputFormat {
             None
         };
 
+        let style = if kwargs.has("style") {
+            match kwargs
+                .get::<String>("style")
+                .map(|s| types::OutputStyle::from_str(s.as_str()))
+            {
+                Ok(Ok(style)) => Some(style),
+                Ok(Err(e)) => {
+                    return Err(Error::new(
+                        ErrorKind::SyntaxError,
+                        format!(
+                            "Invalid value for style (expected one of {}): {}",
+                            types::OutputStyle::VARIANTS.join(", "),
+                            e
+                        ),
+                    ))
+                }
+                Err(e) => {
+                    return Err(Error::new(
+                        ErrorKind::SyntaxError,
+                        format!(
+                            "Invalid value for style (expected one of {}): {}",
+                            types::OutputStyle::VARIANTS.join(", "),
+                            e
+                        ),
+                    ))
+                }
+            }
+        } else {
+            None
+        };
+
+        let validators = if kwargs.has("describe_validators") {
+            match kwargs.get::<bool>("describe_validators") {
+                Ok(validators) => Some(validators),
+                Err(e) => {
+                    return Err(Error::new(
+                        ErrorKind::SyntaxError,
+                        format!("Invalid value for describe_validators (expected bool): {e}"),
+                    ))
+                }
+            }
+        } else {
+            None
+        };
+
         let Ok(_) = kwargs.assert_all_used() else {
             return Err(Error::new(
                 ErrorKind::TooManyArguments,
-                "output_format() got an unexpected keyword argument (only 'prefix', 'always_host_enums', 'enum_value_prefix', and 'or_splitter' are allowed)",
+                "output_format() got an unexpected keyword argument (only 'prefix', 'always_hoist_enums', 'enum_value_prefix', 'or_splitter', 'hoisted_class_prefix', 'map_style', 'style' and 'describe_validators' are allowed)",
             ));
         };
 
@@ -177,6 +226,8 @@ impl minijinja::value::Object for OutputFormat {
             always_hoist_enums,
             map_style,
             hoisted_class_prefix,
+            style,
+            validators,
         ))?;
 
         match content {
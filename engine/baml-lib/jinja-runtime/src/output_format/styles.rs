@@ -0,0 +1,655 @@
+//! The renderers behind `ctx.output_format(style=...)`, other than BAML's own
+//! type definitions. Different models follow different schema formats better,
+//! so these describe the same types in formats they may know from training.
+
+use baml_types::{FieldType, LiteralValue, TypeValue};
+use indexmap::IndexSet;
+use minijinja::ErrorKind;
+
+use super::types::{
+    field_comment, Class, Enum, MapStyle, OutputFormatContent, OutputStyle, RenderOptions,
+    RenderSetting,
+};
+
+type Result<T> = std::result::Result<T, minijinja::Error>;
+
+fn bad_serialization(message: impl std::fmt::Display) -> minijinja::Error {
+    minijinja::Error::new(ErrorKind::BadSerialization, message.to_string())
+}
+
+/// Indents every line of `text` but the first one.
+fn indent(text: &str, spaces: usize) -> String {
+    text.replace('\n', &format!("\n{}", " ".repeat(spaces)))
+}
+
+/// A type rendered as a value in an outline: either on the same line as its
+/// key, or as an indented block below it.
+enum Shape {
+    Inline(String),
+    Block(String),
+}
+
+/// Classes and enums that are rendered by name and defined above the answer.
+#[derive(Default)]
+struct Hoisted {
+    enums: IndexSet<String>,
+    classes: IndexSet<String>,
+}
+
+impl OutputFormatContent {
+    pub(super) fn render_style(
+        &self,
+        style: OutputStyle,
+        options: &RenderOptions,
+    ) -> Result<Option<String>> {
+        let (definitions, auto_prefix, answer) = match style {
+            OutputStyle::Default => unreachable!("rendered by OutputFormatContent::render"),
+            OutputStyle::JsonSchema => self.render_json_schema()?,
+            OutputStyle::TypeScript => self.render_typescript(options)?,
+            OutputStyle::Yaml => self.render_yaml(options)?,
+            OutputStyle::Xml => self.render_xml(options)?,
+        };
+        let prefix = match &options.prefix {
+            RenderSetting::Always(prefix) => prefix.as_str(),
+            RenderSetting::Never => "",
+            RenderSetting::Auto => auto_prefix,
+        };
+
+        let mut output = String::new();
+        for definition in definitions {
+            output.push_str(&definition);
+            output.push_str("\n\n");
+        }
+        output.push_str(prefix);
+        output.push_str(&answer);
+
+        let output = output.trim_end();
+        if output.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(output.to_string()))
+        }
+    }
+
+    fn class(&self, name: &str) -> Result<&Class> {
+        self.find_class(name).map_err(bad_serialization)
+    }
+
+    fn enm(&self, name: &str) -> Result<&Enum> {
+        self.find_enum(name).map_err(bad_serialization)
+    }
+
+    fn render_json_schema(&self) -> Result<(Vec<String>, &'static str, String)> {
+        let schema = self.target_json_schema().map_err(bad_serialization)?;
+        let schema = serde_json::to_string_pretty(&schema).map_err(bad_serialization)?;
+        Ok((
+            vec![],
+            "Answer in JSON that matches this JSON schema:\n",
+            schema,
+        ))
+    }
+
+    fn render_typescript(
+        &self,
+        options: &RenderOptions,
+    ) -> Result<(Vec<String>, &'static str, String)> {
+        let mut classes = IndexSet::new();
+        let mut enums = IndexSet::new();
+        let mut aliases = IndexSet::new();
+        self.reachable(&self.target, &mut classes, &mut enums, &mut aliases)?;
+
+        let mut definitions = vec![];
+        for name in &enums {
+            let enm = self.enm(name)?;
+            let mut definition = format!("type {} =", enm.name.rendered_name());
+            for (value, description) in &enm.values {
+                if let Some(description) = description {
+                    for line in description.trim().lines() {
+                        definition.push_str(&format!("\n  // {line}"));
+                    }
+                }
+                definition.push_str(&format!(
+                    "\n  | {}",
+                    serde_json::Value::from(value.rendered_name())
+                ));
+            }
+            definition.push(';');
+            definitions.push(definition);
+        }
+        for name in &classes {
+            let class = self.class(name)?;
+            let mut definition = format!("interface {} {{", class.name.rendered_name());
+            for (field, field_type, description, _) in &class.fields {
+                if let Some(comment) = field_comment(description.as_ref(), field_type, options) {
+                    for line in comment.trim().lines() {
+                        definition.push_str(&format!("\n  // {line}"));
+                    }
+                }
+                definition.push_str(&format!(
+                    "\n  {}{}: {};",
+                    field.rendered_name(),
+                    if field_type.is_optional() { "?" } else { "" },
+                    self.typescript_type(field_type)?
+                ));
+            }
+            definition.push_str("\n}");
+            definitions.push(definition);
+        }
+        for name in &aliases {
+            let target = self
+                .find_recursive_alias_target(name)
+                .map_err(bad_serialization)?;
+            definitions.push(format!("type {name} = {};", self.typescript_type(target)?));
+        }
+
+        Ok((
+            definitions,
+            "Answer in JSON using this TypeScript type:\n",
+            self.typescript_type(&self.target)?,
+        ))
+    }
+
+    /// Collects the classes, enums and recursive aliases `field_type` refers
+    /// to, directly or through other classes.
+    fn reachable(
+        &self,
+        field_type: &FieldType,
+        classes: &mut IndexSet<String>,
+        enums: &mut IndexSet<String>,
+        aliases: &mut IndexSet<String>,
+    ) -> Result<()> {
+        match field_type {
+            FieldType::Primitive(_) | FieldType::Literal(_) => {}
+            FieldType::Enum(name) => {
+                enums.insert(name.clone());
+            }
+            FieldType::Class(name) => {
+                if classes.insert(name.clone()) {
+                    for (_, field_type, _, _) in &self.class(name)?.fields {
+                        self.reachable(field_type, classes, enums, aliases)?;
+                    }
+                }
+            }
+            FieldType::RecursiveTypeAlias(name) => {
+                if aliases.insert(name.clone()) {
+                    let target = self
+                        .find_recursive_alias_target(name)
+                        .map_err(bad_serialization)?;
+                    self.reachable(target, classes, enums, aliases)?;
+                }
+            }
+            FieldType::List(inner) | FieldType::Optional(inner) => {
+                self.reachable(inner, classes, enums, aliases)?
+            }
+            FieldType::Map(key, value) => {
+                self.reachable(key, classes, enums, aliases)?;
+                self.reachable(value, classes, enums, aliases)?;
+            }
+            FieldType::Union(items) | FieldType::Tuple(items) => {
+                for item in items {
+                    self.reachable(item, classes, enums, aliases)?;
+                }
+            }
+            FieldType::WithMetadata { base, .. } => {
+                self.reachable(base, classes, enums, aliases)?
+            }
+        }
+        Ok(())
+    }
+
+    fn typescript_type(&self, field_type: &FieldType) -> Result<String> {
+        Ok(match field_type {
+            FieldType::Primitive(t) => match t {
+                TypeValue::String => "string".to_string(),
+                TypeValue::Int | TypeValue::Float => "number".to_string(),
+                TypeValue::Bool => "boolean".to_string(),
+                TypeValue::Null => "null".to_string(),
+                TypeValue::Media(media_type) => {
+                    return Err(bad_serialization(format!(
+                        "type '{media_type}' is not supported in outputs"
+                    )))
+                }
+            },
+            FieldType::Literal(v) => match v {
+                LiteralValue::String(s) => serde_json::Value::from(s.as_str()).to_string(),
+                LiteralValue::Int(i) => i.to_string(),
+                LiteralValue::Bool(b) => b.to_string(),
+            },
+            FieldType::Enum(name) => self.enm(name)?.name.rendered_name().to_string(),
+            FieldType::Class(name) => self.class(name)?.name.rendered_name().to_string(),
+            FieldType::RecursiveTypeAlias(name) => name.clone(),
+            FieldType::List(inner) => match inner.as_ref() {
+                FieldType::Union(_) | FieldType::Optional(_) => {
+                    format!("({})[]", self.typescript_type(inner)?)
+                }
+                _ => format!("{}[]", self.typescript_type(inner)?),
+            },
+            FieldType::Map(key, value) => format!(
+                "Record<{}, {}>",
+                self.typescript_type(key)?,
+                self.typescript_type(value)?
+            ),
+            FieldType::Tuple(items) => format!(
+                "[{}]",
+                items
+                    .iter()
+                    .map(|t| self.typescript_type(t))
+                    .collect::<Result<Vec<_>>>()?
+                    .join(", ")
+            ),
+            FieldType::Union(items) => items
+                .iter()
+                .map(|t| self.typescript_type(t))
+                .collect::<Result<Vec<_>>>()?
+                .join(" | "),
+            FieldType::Optional(inner) if inner.is_optional() => self.typescript_type(inner)?,
+            FieldType::Optional(inner) => format!("{} | null", self.typescript_type(inner)?),
+            FieldType::WithMetadata { base, .. } => self.typescript_type(base)?,
+        })
+    }
+
+    fn render_yaml(&self, options: &RenderOptions) -> Result<(Vec<String>, &'static str, String)> {
+        let mut hoisted = Hoisted {
+            classes: self.recursive_classes.iter().cloned().collect(),
+            ..Default::default()
+        };
+        let answer = match self.outline_shape(&self.target, options, &mut hoisted)? {
+            Shape::Inline(answer) | Shape::Block(answer) => answer,
+        };
+
+        let mut definitions = vec![];
+        // Hoisted classes can hoist more classes and enums.
+        let mut next = 0;
+        while let Some(name) = hoisted.classes.get_index(next).cloned() {
+            next += 1;
+            let body = self.outline_class(&name, options, &mut hoisted)?;
+            definitions.push(format!("{name}:\n  {}", indent(&body, 2)));
+        }
+        for (alias, target) in self.structural_recursive_aliases.iter() {
+            let target = self.inline_type(target, options, &mut hoisted)?;
+            definitions.push(format!("{alias}: {target}"));
+        }
+        let mut enums = vec![];
+        for name in &hoisted.enums {
+            let enm = self.enm(name)?;
+            let mut definition = format!("{}:", enm.name.rendered_name());
+            for (value, description) in &enm.values {
+                definition.push_str(&format!("\n  - {}", value.rendered_name()));
+                if let Some(description) = description {
+                    definition.push_str(&format!("  # {}", description.trim().replace('\n', " ")));
+                }
+            }
+            enums.push(definition);
+        }
+        enums.extend(definitions);
+
+        Ok((enums, "Answer in JSON using this schema:\n", answer))
+    }
+
+    /// Renders `field_type` as the value of a key in an outline. Classes are
+    /// nested under their key, unless they are recursive.
+    fn outline_shape(
+        &self,
+        field_type: &FieldType,
+        options: &RenderOptions,
+        hoisted: &mut Hoisted,
+    ) -> Result<Shape> {
+        Ok(match field_type {
+            FieldType::Class(name) if !self.recursive_classes.contains(name) => {
+                Shape::Block(self.outline_class(name, options, hoisted)?)
+            }
+            FieldType::List(inner) => match self.outline_shape(inner, options, hoisted)? {
+                Shape::Block(item) => Shape::Block(format!("- {}", indent(&item, 2))),
+                Shape::Inline(_) => Shape::Inline(self.inline_type(field_type, options, hoisted)?),
+            },
+            FieldType::Map(key, value) => match self.outline_shape(value, options, hoisted)? {
+                Shape::Block(value) => Shape::Block(format!(
+                    "<{}>:\n  {}",
+                    self.inline_type(key, options, hoisted)?,
+                    indent(&value, 2)
+                )),
+                Shape::Inline(_) => Shape::Inline(self.inline_type(field_type, options, hoisted)?),
+            },
+            FieldType::WithMetadata { base, .. } => self.outline_shape(base, options, hoisted)?,
+            _ => Shape::Inline(self.inline_type(field_type, options, hoisted)?),
+        })
+    }
+
+    fn outline_class(
+        &self,
+        name: &str,
+        options: &RenderOptions,
+        hoisted: &mut Hoisted,
+    ) -> Result<String> {
+        let mut lines = vec![];
+        for (field, field_type, description, _) in &self.class(name)?.fields {
+            if let Some(comment) = field_comment(description.as_ref(), field_type, options) {
+                for line in comment.trim().lines() {
+                    lines.push(format!("# {line}"));
+                }
+            }
+            match self.outline_shape(field_type, options, hoisted)? {
+                Shape::Inline(t) => lines.push(format!("{}: {t}", field.rendered_name())),
+                Shape::Block(block) => lines.push(format!(
+                    "{}:\n  {}",
+                    field.rendered_name(),
+                    indent(&block, 2)
+                )),
+            }
+        }
+        if lines.is_empty() {
+            return Ok("{}".to_string());
+        }
+        Ok(lines.join("\n"))
+    }
+
+    fn render_xml(&self, options: &RenderOptions) -> Result<(Vec<String>, &'static str, String)> {
+        let mut hoisted = Hoisted {
+            classes: self.recursive_classes.iter().cloned().collect(),
+            ..Default::default()
+        };
+        let answer = self.xml_element("answer", &self.target, options, &mut hoisted)?;
+
+        let mut definitions = vec![];
+        let mut next = 0;
+        while let Some(name) = hoisted.classes.get_index(next).cloned() {
+            next += 1;
+            let body = self.xml_class(&name, options, &mut hoisted)?;
+            definitions.push(format!("{name}:\n  {}", indent(&body, 2)));
+        }
+        for (alias, target) in self.structural_recursive_aliases.iter() {
+            let target = self.inline_type(target, options, &mut hoisted)?;
+            definitions.push(format!("{alias} = {target}"));
+        }
+        let mut enums = hoisted
+            .enums
+            .iter()
+            .map(|name| Ok(self.enum_to_string(self.enm(name)?, options)))
+            .collect::<Result<Vec<_>>>()?;
+        enums.extend(definitions);
+
+        Ok((enums, "Answer in XML using this structure:\n", answer))
+    }
+
+    /// Renders `field_type` as the element `tag`. Lists repeat an `<item>`
+    /// element and maps have one element per key.
+    fn xml_element(
+        &self,
+        tag: &str,
+        field_type: &FieldType,
+        options: &RenderOptions,
+        hoisted: &mut Hoisted,
+    ) -> Result<String> {
+        Ok(match field_type {
+            FieldType::Class(name) if !self.recursive_classes.contains(name) => {
+                let body = self.xml_class(name, options, hoisted)?;
+                format!("<{tag}>\n  {}\n</{tag}>", indent(&body, 2))
+            }
+            FieldType::List(inner) => {
+                let item = self.xml_element("item", inner, options, hoisted)?;
+                format!("<{tag}>\n  {}\n  ...\n</{tag}>", indent(&item, 2))
+            }
+            FieldType::Map(_, value) => {
+                let entry = self.xml_element("key", value, options, hoisted)?;
+                format!(
+                    "<{tag}>\n  <!-- one element per key, named after the key -->\n  {}\n  ...\n</{tag}>",
+                    indent(&entry, 2)
+                )
+            }
+            FieldType::WithMetadata { base, .. } => {
+                self.xml_element(tag, base, options, hoisted)?
+            }
+            _ => format!(
+                "<{tag}>{}</{tag}>",
+                self.inline_type(field_type, options, hoisted)?
+            ),
+        })
+    }
+
+    fn xml_class(
+        &self,
+        name: &str,
+        options: &RenderOptions,
+        hoisted: &mut Hoisted,
+    ) -> Result<String> {
+        let mut lines = vec![];
+        for (field, field_type, description, _) in &self.class(name)?.fields {
+            if let Some(comment) = field_comment(description.as_ref(), field_type, options) {
+                lines.push(format!("<!-- {} -->", comment.trim().replace('\n', " ")));
+            }
+            lines.push(self.xml_element(field.rendered_name(), field_type, options, hoisted)?);
+        }
+        Ok(lines.join("\n"))
+    }
+
+    /// Renders `field_type` on a single line, referring to classes and large
+    /// enums by name.
+    fn inline_type(
+        &self,
+        field_type: &FieldType,
+        options: &RenderOptions,
+        hoisted: &mut Hoisted,
+    ) -> Result<String> {
+        Ok(match field_type {
+            FieldType::Primitive(t) => match t {
+                TypeValue::String => "string".to_string(),
+                TypeValue::Int => "int".to_string(),
+                TypeValue::Float => "float".to_string(),
+                TypeValue::Bool => "bool".to_string(),
+                TypeValue::Null => "null".to_string(),
+                TypeValue::Media(media_type) => {
+                    return Err(bad_serialization(format!(
+                        "type '{media_type}' is not supported in outputs"
+                    )))
+                }
+            },
+            FieldType::Literal(v) => v.to_string(),
+            FieldType::Enum(name) => {
+                let enm = self.enm(name)?;
+                if enm.values.len() <= 6
+                    && enm.values.iter().all(|(_, d)| d.is_none())
+                    && !matches!(options.always_hoist_enums, RenderSetting::Always(true))
+                {
+                    enm.values
+                        .iter()
+                        .map(|(n, _)| format!("'{}'", n.rendered_name()))
+                        .collect::<Vec<_>>()
+                        .join(&options.or_splitter)
+                } else {
+                    hoisted.enums.insert(name.clone());
+                    enm.name.rendered_name().to_string()
+                }
+            }
+            FieldType::Class(name) => {
+                hoisted.classes.insert(name.clone());
+                name.clone()
+            }
+            FieldType::RecursiveTypeAlias(name) => name.clone(),
+            FieldType::List(inner) => match inner.as_ref() {
+                FieldType::Union(_) | FieldType::Optional(_) => {
+                    format!("({})[]", self.inline_type(inner, options, hoisted)?)
+                }
+                _ => format!("{}[]", self.inline_type(inner, options, hoisted)?),
+            },
+            FieldType::Map(key, value) => {
+                let key = self.inline_type(key, options, hoisted)?;
+                let value = self.inline_type(value, options, hoisted)?;
+                match options.map_style {
+                    MapStyle::TypeParameters => format!("map<{key}, {value}>"),
+                    MapStyle::ObjectLiteral => format!("{{{key}: {value}}}"),
+                }
+            }
+            FieldType::Union(items) => items
+                .iter()
+                .map(|t| self.inline_type(t, options, hoisted))
+                .collect::<Result<Vec<_>>>()?
+                .join(&options.or_splitter),
+            FieldType::Optional(inner) if inner.is_optional() => {
+                self.inline_type(inner, options, hoisted)?
+            }
+            FieldType::Optional(inner) => format!(
+                "{}{}null",
+                self.inline_type(inner, options, hoisted)?,
+                options.or_splitter
+            ),
+            FieldType::Tuple(_) => {
+                return Err(bad_serialization("Tuple type is not supported in outputs"))
+            }
+            FieldType::WithMetadata { base, .. } => self.inline_type(base, options, hoisted)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use baml_types::StreamingBehavior;
+    use indexmap::IndexMap;
+
+    use super::*;
+    use crate::types::Name;
+
+    fn content(target: FieldType) -> OutputFormatContent {
+        OutputFormatContent::target(target)
+            .classes(vec![
+                Class {
+                    name: Name::new("Person".to_string()),
+                    fields: vec![
+                        (
+                            Name::new("name".to_string()),
+                            FieldType::string(),
+                            Some("Full name".to_string()),
+                            false,
+                        ),
+                        (
+                            Name::new("role".to_string()),
+                            FieldType::optional(FieldType::Enum("Role".to_string())),
+                            None,
+                            false,
+                        ),
+                        (
+                            Name::new("address".to_string()),
+                            FieldType::class("Address"),
+                            None,
+                            false,
+                        ),
+                    ],
+                    constraints: Vec::new(),
+                    streaming_behavior: StreamingBehavior::default(),
+                    defaults: IndexMap::new(),
+                },
+                Class {
+                    name: Name::new("Address".to_string()),
+                    fields: vec![(
+                        Name::new("city".to_string()),
+                        FieldType::string(),
+                        None,
+                        false,
+                    )],
+                    constraints: Vec::new(),
+                    streaming_behavior: StreamingBehavior::default(),
+                    defaults: IndexMap::new(),
+                },
+            ])
+            .enums(vec![Enum {
+                name: Name::new("Role".to_string()),
+                values: vec![
+                    (Name::new("Admin".to_string()), None),
+                    (Name::new("Guest".to_string()), None),
+                ],
+                constraints: Vec::new(),
+            }])
+            .build()
+    }
+
+    fn render(target: FieldType, style: OutputStyle) -> Option<String> {
+        content(target)
+            .render(RenderOptions::new(
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                Some(style),
+                None,
+            ))
+            .unwrap()
+    }
+
+    #[test]
+    fn render_typescript() {
+        assert_eq!(
+            render(FieldType::class("Person"), OutputStyle::TypeScript).unwrap(),
+            r#"type Role =
+  | "Admin"
+  | "Guest";
+
+interface Person {
+  // Full name
+  name: string;
+  role?: Role | null;
+  address: Address;
+}
+
+interface Address {
+  city: string;
+}
+
+Answer in JSON using this TypeScript type:
+Person"#
+        );
+    }
+
+    #[test]
+    fn render_yaml() {
+        assert_eq!(
+            render(
+                FieldType::list(FieldType::class("Person")),
+                OutputStyle::Yaml
+            )
+            .unwrap(),
+            r#"Answer in JSON using this schema:
+- # Full name
+  name: string
+  role: 'Admin' or 'Guest' or null
+  address:
+    city: string"#
+        );
+    }
+
+    #[test]
+    fn render_xml() {
+        assert_eq!(
+            render(FieldType::class("Person"), OutputStyle::Xml).unwrap(),
+            r#"Answer in XML using this structure:
+<answer>
+  <!-- Full name -->
+  <name>string</name>
+  <role>'Admin' or 'Guest' or null</role>
+  <address>
+    <city>string</city>
+  </address>
+</answer>"#
+        );
+    }
+
+    #[test]
+    fn render_json_schema() {
+        let rendered = render(FieldType::class("Address"), OutputStyle::JsonSchema).unwrap();
+        let (prefix, schema) = rendered.split_once('\n').unwrap();
+        assert_eq!(prefix, "Answer in JSON that matches this JSON schema:");
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(schema).unwrap(),
+            serde_json::json!({
+                "type": "object",
+                "properties": { "city": { "type": "string" } },
+                "required": ["city"],
+            })
+        );
+    }
+
+    #[test]
+    fn strings_need_no_schema() {
+        assert_eq!(render(FieldType::string(), OutputStyle::Xml), None);
+    }
+}
@@ -1,7 +1,7 @@
 use std::sync::Arc;
 
 use anyhow::Result;
-use baml_types::{Constraint, FieldType, StreamingBehavior, TypeValue};
+use baml_types::{Constraint, FieldType, StreamingBehavior, TypeValue, Validator};
 use indexmap::{IndexMap, IndexSet};
 
 #[derive(Debug)]
@@ -52,14 +52,16 @@ pub struct Class {
     pub fields: Vec<(Name, FieldType, Option<String>, bool)>,
     pub constraints: Vec<Constraint>,
     pub streaming_behavior: StreamingBehavior,
+    /// The `@default` values of fields, by field name.
+    pub defaults: IndexMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone)]
 pub struct OutputFormatContent {
     pub enums: Arc<IndexMap<String, Enum>>,
     pub classes: Arc<IndexMap<String, Class>>,
-    recursive_classes: Arc<IndexSet<String>>,
-    structural_recursive_aliases: Arc<IndexMap<String, FieldType>>,
+    pub(super) recursive_classes: Arc<IndexSet<String>>,
+    pub(super) structural_recursive_aliases: Arc<IndexMap<String, FieldType>>,
     pub target: FieldType,
 }
 
@@ -136,7 +138,7 @@ impl Builder {
     }
 }
 
-enum RenderSetting<T> {
+pub(crate) enum RenderSetting<T> {
     Auto,
     Always(T),
     Never,
@@ -157,13 +159,40 @@ pub(crate) enum MapStyle {
     ObjectLiteral,
 }
 
+/// The renderer used for the schema.
+#[derive(Clone, Copy, Debug, PartialEq, strum::EnumString, strum::VariantNames)]
+pub(crate) enum OutputStyle {
+    /// BAML's own type definitions.
+    #[strum(serialize = "default")]
+    Default,
+
+    /// A JSON schema of the output type.
+    #[strum(serialize = "json_schema")]
+    JsonSchema,
+
+    /// TypeScript interfaces and type aliases.
+    #[strum(serialize = "typescript")]
+    TypeScript,
+
+    /// An indented outline of the fields, like a YAML document.
+    #[strum(serialize = "yaml")]
+    Yaml,
+
+    /// XML tags, answered in XML instead of JSON.
+    #[strum(serialize = "xml")]
+    Xml,
+}
+
 pub(crate) struct RenderOptions {
-    prefix: RenderSetting<String>,
+    pub(crate) prefix: RenderSetting<String>,
     pub(crate) or_splitter: String,
     enum_value_prefix: RenderSetting<String>,
     hoisted_class_prefix: RenderSetting<String>,
-    always_hoist_enums: RenderSetting<bool>,
-    map_style: MapStyle,
+    pub(crate) always_hoist_enums: RenderSetting<bool>,
+    pub(crate) map_style: MapStyle,
+    style: OutputStyle,
+    /// Whether to describe the validators of class fields, e.g. `min: 0`.
+    pub(crate) validators: bool,
 }
 
 impl Default for RenderOptions {
@@ -175,6 +204,8 @@ impl Default for RenderOptions {
             hoisted_class_prefix: RenderSetting::Auto,
             always_hoist_enums: RenderSetting::Auto,
             map_style: MapStyle::TypeParameters,
+            style: OutputStyle::Default,
+            validators: false,
         }
     }
 }
@@ -190,6 +221,8 @@ impl RenderOptions {
         always_hoist_enums: Option<bool>,
         map_style: Option<MapStyle>,
         hoisted_class_prefix: Option<Option<String>>,
+        style: Option<OutputStyle>,
+        validators: Option<bool>,
     ) -> Self {
         Self {
             prefix: prefix.map_or(RenderSetting::Auto, |p| {
@@ -205,6 +238,8 @@ impl RenderOptions {
             hoisted_class_prefix: hoisted_class_prefix.map_or(RenderSetting::Auto, |p| {
                 p.map_or(RenderSetting::Never, RenderSetting::Always)
             }),
+            style: style.unwrap_or(OutputStyle::Default),
+            validators: validators.unwrap_or(false),
         }
     }
 
@@ -217,6 +252,29 @@ impl RenderOptions {
     }
 }
 
+/// The comment rendered with a class field: its description, followed by its
+/// validators when [`RenderOptions::validators`] is set.
+pub(crate) fn field_comment(
+    description: Option<&String>,
+    field_type: &FieldType,
+    options: &RenderOptions,
+) -> Option<String> {
+    let hints = match field_type {
+        FieldType::WithMetadata { constraints, .. } if options.validators => constraints
+            .iter()
+            .filter_map(|c| c.validator.as_ref().map(Validator::describe))
+            .collect::<Vec<_>>(),
+        _ => vec![],
+    };
+    if hints.is_empty() {
+        return description.cloned();
+    }
+    Some(match description {
+        Some(description) => format!("{}\n{}", description.trim_end(), hints.join(", ")),
+        None => hints.join(", "),
+    })
+}
+
 struct Attribute {
     name: String,
     description: Option<String>,
@@ -379,7 +437,7 @@ impl OutputFormatContent {
         }
     }
 
-    fn enum_to_string(&self, enm: &Enum, options: &RenderOptions) -> String {
+    pub(super) fn enum_to_string(&self, enm: &Enum, options: &RenderOptions) -> String {
         EnumRender {
             name: enm.name.rendered_name().to_string(),
             delimiter: "----".into(),
@@ -495,7 +553,11 @@ impl OutputFormatContent {
                         .map(|(name, field_type, description, _streaming_needed)| {
                             Ok(ClassFieldRender {
                                 name: name.rendered_name().to_string(),
-                                description: description.clone(),
+                                description: field_comment(
+                                    description.as_ref(),
+                                    field_type,
+                                    options,
+                                ),
                                 r#type: self.render_possibly_recursive_type(
                                     options,
                                     field_type,
@@ -581,6 +643,13 @@ impl OutputFormatContent {
         &self,
         options: RenderOptions,
     ) -> Result<Option<String>, minijinja::Error> {
+        // Strings don't need a schema in any style.
+        if !matches!(options.style, OutputStyle::Default)
+            && !matches!(self.target, FieldType::Primitive(TypeValue::String))
+        {
+            return self.render_style(options.style, &options);
+        }
+
         let prefix = self.prefix(&options);
 
         let mut render_state = RenderState {
@@ -800,6 +869,7 @@ mod tests {
             ],
             constraints: Vec::new(),
             streaming_behavior: StreamingBehavior::default(),
+            defaults: IndexMap::new(),
         }];
 
         let content = OutputFormatContent::target(FieldType::class("Person"))
@@ -814,6 +884,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn render_class_with_validators() {
+        let classes = vec![Class {
+            name: Name::new("Person".to_string()),
+            fields: vec![
+                (
+                    Name::new("email".to_string()),
+                    FieldType::WithMetadata {
+                        base: Box::new(FieldType::string()),
+                        constraints: vec![Constraint::from_validator(Validator::Format(
+                            baml_types::Format::Email,
+                        ))],
+                        streaming_behavior: StreamingBehavior::default(),
+                    },
+                    None,
+                    false,
+                ),
+                (
+                    Name::new("age".to_string()),
+                    FieldType::WithMetadata {
+                        base: Box::new(FieldType::int()),
+                        constraints: vec![
+                            Constraint::from_validator(Validator::Min(0.0)),
+                            Constraint::from_validator(Validator::Max(130.0)),
+                        ],
+                        streaming_behavior: StreamingBehavior::default(),
+                    },
+                    Some("The person's age".to_string()),
+                    false,
+                ),
+            ],
+            constraints: Vec::new(),
+            streaming_behavior: StreamingBehavior::default(),
+            defaults: IndexMap::new(),
+        }];
+
+        let content = OutputFormatContent::target(FieldType::class("Person"))
+            .classes(classes)
+            .build();
+        assert_eq!(
+            content.render(RenderOptions::default()).unwrap(),
+            Some(String::from(
+                "Answer in JSON using this schema:\n{\n  email: string,\n  // The person's age\n  age: int,\n}"
+            ))
+        );
+        let options = RenderOptions {
+            validators: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            content.render(options).unwrap(),
+            Some(String::from(
+                "Answer in JSON using this schema:\n{\n  // format: email\n  email: string,\n  // The person's age\n  // min: 0, max: 130\n  age: int,\n}"
+            ))
+        );
+    }
+
     #[test]
     fn render_class_with_multiline_descriptions() {
         let classes = vec![Class {
@@ -835,6 +962,7 @@ mod tests {
             ],
             constraints: Vec::new(),
             streaming_behavior: StreamingBehavior::default(),
+            defaults: IndexMap::new(),
         }];
 
         let content = OutputFormatContent::target(FieldType::class("Education"))
@@ -865,6 +993,7 @@ mod tests {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Enhancement".to_string()),
@@ -879,6 +1008,7 @@ mod tests {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Documentation".to_string()),
@@ -888,6 +1018,7 @@ mod tests {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -938,6 +1069,7 @@ r#"Answer in JSON using any of these schemas:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Bug".to_string()),
@@ -952,6 +1084,7 @@ r#"Answer in JSON using any of these schemas:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Enhancement".to_string()),
@@ -966,6 +1099,7 @@ r#"Answer in JSON using any of these schemas:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Documentation".to_string()),
@@ -975,6 +1109,7 @@ r#"Answer in JSON using any of these schemas:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1019,6 +1154,7 @@ r#"Answer in JSON using this schema:
             ],
             constraints: Vec::new(),
             streaming_behavior: StreamingBehavior::default(),
+            defaults: IndexMap::new(),
         }];
 
         let content = OutputFormatContent::target(FieldType::class("Node"))
@@ -1056,6 +1192,7 @@ Answer in JSON using this schema: Node"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("LinkedList".to_string()),
@@ -1070,6 +1207,7 @@ Answer in JSON using this schema: Node"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1109,6 +1247,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("B".to_string()),
@@ -1120,6 +1259,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("C".to_string()),
@@ -1131,6 +1271,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1175,6 +1316,7 @@ Answer in JSON using this schema: A"#
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("B".to_string()),
@@ -1186,6 +1328,7 @@ Answer in JSON using this schema: A"#
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("C".to_string()),
@@ -1197,6 +1340,7 @@ Answer in JSON using this schema: A"#
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -1212,6 +1356,7 @@ Answer in JSON using this schema: A"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1269,6 +1414,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("B".to_string()),
@@ -1280,6 +1426,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("C".to_string()),
@@ -1291,6 +1438,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -1306,6 +1454,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Nested".to_string()),
@@ -1315,6 +1464,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1371,6 +1521,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Forest".to_string()),
@@ -1382,6 +1533,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1426,6 +1578,7 @@ Answer in JSON using this schema: Tree"#
             )],
             constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
         }];
 
         let content = OutputFormatContent::target(FieldType::class("SelfReferential"))
@@ -1464,6 +1617,7 @@ Answer in JSON using this schema: SelfReferential"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Tree".to_string()),
@@ -1478,6 +1632,7 @@ Answer in JSON using this schema: SelfReferential"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1533,6 +1688,7 @@ Node or Tree"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Node".to_string()),
@@ -1547,6 +1703,7 @@ Node or Tree"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Tree".to_string()),
@@ -1561,6 +1718,7 @@ Node or Tree"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1611,6 +1769,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Tree".to_string()),
@@ -1625,6 +1784,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -1634,6 +1794,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1697,6 +1858,7 @@ Node or Tree or {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Node".to_string()),
@@ -1711,6 +1873,7 @@ Node or Tree or {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Tree".to_string()),
@@ -1725,6 +1888,7 @@ Node or Tree or {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -1734,6 +1898,7 @@ Node or Tree or {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1784,6 +1949,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("B".to_string()),
@@ -1795,6 +1961,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("C".to_string()),
@@ -1806,6 +1973,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -1821,6 +1989,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1875,6 +2044,7 @@ Answer in JSON using this interface:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Tree".to_string()),
@@ -1889,6 +2059,7 @@ Answer in JSON using this interface:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -1938,6 +2109,7 @@ Node or int or string or Tree"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Tree".to_string()),
@@ -1952,6 +2124,7 @@ Node or int or string or Tree"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -1970,6 +2143,7 @@ Node or int or string or Tree"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -2019,6 +2193,7 @@ Answer in JSON using this schema:
             ],
             constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
         }];
 
         let content = OutputFormatContent::target(FieldType::list(FieldType::class("Node")))
@@ -2053,6 +2228,7 @@ Node[]"#
             )],
             constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
         }];
 
         let content = OutputFormatContent::target(FieldType::class("RecursiveMap"))
@@ -2086,6 +2262,7 @@ Answer in JSON using this schema: RecursiveMap"#
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -2097,6 +2274,7 @@ Answer in JSON using this schema: RecursiveMap"#
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -2136,6 +2314,7 @@ Answer in JSON using this schema:
             ],
             constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
         }];
 
         let content = OutputFormatContent::target(FieldType::map(
@@ -2174,6 +2353,7 @@ map<string, Node>"#
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Node".to_string()),
@@ -2188,6 +2368,7 @@ map<string, Node>"#
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -2229,6 +2410,7 @@ Answer in JSON using this schema:
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Node".to_string()),
@@ -2243,6 +2425,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -2284,6 +2467,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -2293,6 +2477,7 @@ Answer in JSON using this schema:
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -2346,6 +2531,7 @@ map<string, Node or int or {
                 )],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("Node".to_string()),
@@ -2360,6 +2546,7 @@ map<string, Node or int or {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
             Class {
                 name: Name::new("NonRecursive".to_string()),
@@ -2369,6 +2556,7 @@ map<string, Node or int or {
                 ],
                 constraints: Vec::new(),
                 streaming_behavior: StreamingBehavior::default(),
+                defaults: IndexMap::new(),
             },
         ];
 
@@ -303,6 +303,10 @@ impl PredefinedTypes {
                                 "hoisted_class_prefix".into(),
                                 Type::merge(vec![Type::String, Type::None]),
                             ),
+                            (
+                                "describe_validators".into(),
+                                Type::merge(vec![Type::Bool, Type::None]),
+                            ),
                         ],
                     ),
                 ),
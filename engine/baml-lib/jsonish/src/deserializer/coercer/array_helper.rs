@@ -164,7 +164,9 @@ pub(super) fn pick_best(
                     cond.conditions().flags.iter().any(|f| {
                         matches!(
                             f,
-                            Flag::OptionalDefaultFromNoValue | Flag::DefaultFromNoValue
+                            Flag::OptionalDefaultFromNoValue
+                                | Flag::DefaultFromNoValue
+                                | Flag::DefaultFromAttribute
                         )
                     })
                 });
@@ -172,7 +174,9 @@ pub(super) fn pick_best(
                     cond.conditions().flags.iter().any(|f| {
                         matches!(
                             f,
-                            Flag::OptionalDefaultFromNoValue | Flag::DefaultFromNoValue
+                            Flag::OptionalDefaultFromNoValue
+                                | Flag::DefaultFromNoValue
+                                | Flag::DefaultFromAttribute
                         )
                     })
                 });
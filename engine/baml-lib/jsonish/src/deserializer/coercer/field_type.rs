@@ -97,7 +97,10 @@ impl TypeCoercer for FieldType {
                         scope: ctx.scope.clone(),
                         causes: Vec::new(),
                     })?;
-                    validate_asserts(&constraint_results)?;
+                    validate_asserts(&constraint_results).map_err(|e| ParsingError {
+                        scope: ctx.scope.clone(),
+                        ..e
+                    })?;
                     let check_results = constraint_results
                         .into_iter()
                         .filter_map(|(maybe_check, result)| {
@@ -118,42 +121,45 @@ impl TypeCoercer for FieldType {
     }
 }
 
+pub(super) const ASSERTIONS_FAILED: &str = "Assertions failed.";
+
 pub fn validate_asserts(constraints: &[(Constraint, bool)]) -> Result<(), ParsingError> {
     let failing_asserts = constraints
         .iter()
-        .filter_map(
-            |(
-                Constraint {
-                    level,
-                    expression,
-                    label,
-                },
-                result,
-            )| {
-                if !result && ConstraintLevel::Assert == *level {
-                    Some((label, expression))
-                } else {
-                    None
-                }
-            },
-        )
+        .filter_map(|(constraint, result)| {
+            if !result && ConstraintLevel::Assert == constraint.level {
+                Some(constraint)
+            } else {
+                None
+            }
+        })
         .collect::<Vec<_>>();
     let causes = failing_asserts
         .into_iter()
-        .map(|(label, expr)| ParsingError {
-            causes: vec![],
-            reason: format!(
-                "Failed: {}{}",
-                label.as_ref().map_or("".to_string(), |l| format!("{} ", l)),
-                expr.0
-            ),
-            scope: vec![],
-        })
+        .map(
+            |Constraint {
+                 expression,
+                 label,
+                 validator,
+                 ..
+             }| ParsingError {
+                causes: vec![],
+                reason: match validator {
+                    Some(validator) => format!("Failed: {validator}"),
+                    None => format!(
+                        "Failed: {}{}",
+                        label.as_ref().map_or("".to_string(), |l| format!("{} ", l)),
+                        expression.0
+                    ),
+                },
+                scope: vec![],
+            },
+        )
         .collect::<Vec<_>>();
     if !causes.is_empty() {
         Err(ParsingError {
-            causes: vec![],
-            reason: "Assertions failed.".to_string(),
+            causes,
+            reason: ASSERTIONS_FAILED.to_string(),
             scope: vec![],
         })
     } else {
@@ -177,6 +177,21 @@ impl TypeCoercer for Class {
             }
         }
 
+        // The `@default` of a missing field, coerced like a value of the field.
+        // While streaming, the field may still be on its way, so it is left
+        // pending instead.
+        let default_value = |field_name: &Name, t: &FieldType| {
+            if ctx.allow_partials {
+                return None;
+            }
+            let value = self.defaults.get(field_name.real_name())?;
+            let value = serde_json::from_value::<crate::jsonish::Value>(value.clone()).ok()?;
+            let scope = ctx.enter_scope(field_name.real_name());
+            let mut value = t.coerce(&scope, t, Some(&value)).ok()?;
+            value.add_flag(Flag::DefaultFromAttribute);
+            Some(value)
+        };
+
         // Check what we have / what we need
         {
             self.fields.iter().for_each(|(field_name, t, ..)| {
@@ -193,10 +208,12 @@ impl TypeCoercer for Class {
                                 t.default_value(Some(e))
                             }
                             // If we're missing a field, thats ok!
-                            None => Some(BamlValueWithFlags::Null(
-                                DeserializerConditions::new()
-                                    .with_flag(Flag::OptionalDefaultFromNoValue)
-                            )),
+                            None => default_value(field_name, t).or_else(|| {
+                                Some(BamlValueWithFlags::Null(
+                                    DeserializerConditions::new()
+                                        .with_flag(Flag::OptionalDefaultFromNoValue),
+                                ))
+                            }),
                         };
 
                         if let Some(next) = next {
@@ -218,17 +235,19 @@ impl TypeCoercer for Class {
                                 None
                             }
                         }),
-                        None => t.default_value(None).or_else(|| {
-                            if ctx.allow_partials {
-                                Some(BamlValueWithFlags::Null(
-                                    DeserializerConditions::new()
-                                        .with_flag(Flag::OptionalDefaultFromNoValue)
-                                        .with_flag(Flag::Pending),
-                                ))
-                            } else {
-                                None
-                            }
-                        }),
+                        None => default_value(field_name, t)
+                            .or_else(|| t.default_value(None))
+                            .or_else(|| {
+                                if ctx.allow_partials {
+                                    Some(BamlValueWithFlags::Null(
+                                        DeserializerConditions::new()
+                                            .with_flag(Flag::OptionalDefaultFromNoValue)
+                                            .with_flag(Flag::Pending),
+                                    ))
+                                } else {
+                                    None
+                                }
+                            }),
                     };
 
                     if let Some(next) = next {
@@ -16,7 +16,7 @@ use anyhow::Result;
 use baml_types::{BamlValue, Constraint, JinjaExpression};
 use internal_baml_jinja::types::OutputFormatContent;
 
-use internal_baml_core::ir::{jinja_helpers::evaluate_predicate, FieldType};
+use internal_baml_core::ir::{jinja_helpers::evaluate_constraint, FieldType};
 
 use crate::jsonish;
 
@@ -247,6 +247,14 @@ impl std::fmt::Display for ParsingError {
 
 impl std::error::Error for ParsingError {}
 
+impl ParsingError {
+    /// Whether this error, or any of its causes, came from a failing `@assert`.
+    pub fn has_failed_asserts(&self) -> bool {
+        self.reason == field_type::ASSERTIONS_FAILED
+            || self.causes.iter().any(ParsingError::has_failed_asserts)
+    }
+}
+
 pub trait TypeCoercer {
     fn coerce(
         &self,
@@ -274,7 +282,7 @@ pub fn run_user_checks(
         FieldType::WithMetadata { constraints, .. } => constraints
             .iter()
             .map(|constraint| {
-                let result = evaluate_predicate(baml_value, &constraint.expression)?;
+                let result = evaluate_constraint(baml_value, constraint)?;
                 Ok((constraint.clone(), result))
             })
             .collect::<Result<Vec<_>>>(),
@@ -33,6 +33,8 @@ pub enum Flag {
     DefaultFromNoValue,
     DefaultButHadValue(crate::jsonish::Value),
     OptionalDefaultFromNoValue,
+    /// A missing class field took the value of its `@default`.
+    DefaultFromAttribute,
 
     // String -> X convertions.
     StringToBool(String),
@@ -93,6 +95,7 @@ impl DeserializerConditions {
                 Flag::DefaultFromNoValue => None,
                 Flag::DefaultButHadValue(_) => None,
                 Flag::OptionalDefaultFromNoValue => None,
+                Flag::DefaultFromAttribute => None,
                 Flag::StringToBool(_) => None,
                 Flag::StringToNull(_) => None,
                 Flag::StringToChar(_) => None,
@@ -162,6 +165,9 @@ impl std::fmt::Display for Flag {
             Flag::DefaultFromNoValue => {
                 write!(f, "Default value")?;
             }
+            Flag::DefaultFromAttribute => {
+                write!(f, "Default value from @default")?;
+            }
             Flag::ObjectFromFixedJson(fixes) => {
                 write!(f, "JSON (Fixed {} mistakes)", fixes.len())?;
             }
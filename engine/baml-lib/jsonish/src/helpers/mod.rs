@@ -221,11 +221,13 @@ fn relevant_data_models<'a>(
                         }
                     }
 
+                    let walker = walker?;
                     classes.push(Class {
-                        name: Name::new_with_alias(cls.to_string(), walker?.alias(env_values)?),
+                        name: Name::new_with_alias(cls.to_string(), walker.alias(env_values)?),
                         fields,
                         constraints,
                         streaming_behavior: StreamingBehavior::default(),
+                        defaults: walker.default_values()?,
                     });
                 }
             }
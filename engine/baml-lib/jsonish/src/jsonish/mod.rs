@@ -1,11 +1,9 @@
 mod iterative_parser;
 
 mod parser;
-// #[cfg(test)]
-// mod test_iterative_parser;
 mod value;
 
 pub use value::{Fixes, Value};
 
-// pub use iterative_parser::{parse_jsonish_value, JSONishOptions};
+pub use iterative_parser::IterativeParser;
 pub use parser::{parse, ParseOptions};
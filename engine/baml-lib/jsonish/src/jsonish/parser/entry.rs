@@ -4,7 +4,7 @@ use crate::jsonish::{
     parser::{
         fixing_parser,
         markdown_parser::{self, MarkdownResult},
-        multi_json_parser,
+        multi_json_parser, xml_parser, ParsingMode,
     },
     value::Fixes,
     Value,
@@ -136,6 +136,27 @@ pub fn parse(str: &str, mut options: ParseOptions) -> Result<Value> {
         }
     }
 
+    if options.allow_xml {
+        match xml_parser::parse(str, &options) {
+            Ok(items) => {
+                // The tags may also just be part of a JSON or text answer,
+                // which is preferred when both fit equally well.
+                let items = parse(str, options.next_from_mode(ParsingMode::Xml))
+                    .into_iter()
+                    .chain(
+                        items
+                            .into_iter()
+                            .map(|v| Value::FixedJson(v.into(), vec![Fixes::ParsedXml])),
+                    )
+                    .collect::<Vec<_>>();
+                return Ok(Value::AnyOf(items, str.to_string()));
+            }
+            Err(e) => {
+                log::debug!("Error parsing XML: {:?}", e);
+            }
+        }
+    }
+
     if options.all_finding_all_json_objects {
         match multi_json_parser::parse(str, &options) {
             Ok(items) => match items.len() {
@@ -1,6 +1,8 @@
 mod json_collection;
 mod json_parse_state;
 
+use std::cell::Cell;
+
 use baml_types::CompletionState;
 use crate::jsonish::{value::Fixes, Value};
 
@@ -29,22 +31,98 @@ pub fn parse(str: &str, _options: &ParseOptions) -> Result<Vec<(Value, Vec<Fixes
     // - Unterminated strings
 
     let mut state = JsonParseState::new();
+    feed(&mut state, str, &mut 0, &mut 0, str.len())?;
+    finish(state)
+}
 
-    let mut chars = str.char_indices().peekable();
-    while let Some((count, c)) = chars.next() {
-        let peekable = str[count + c.len_utf8()..].char_indices().peekable();
-        match state.process_token(c, peekable) {
-            Ok(increments) => {
-                for _ in 0..increments {
-                    chars.next();
-                }
+/// A fixing parse of a string that keeps growing, such as a streamed
+/// response. Each call only processes the characters added since the last
+/// one, plus the few before them whose handling depended on where the string
+/// ended at the time.
+///
+/// Those last steps can't be kept, and closing the collections left open
+/// consumes the state, so each call works on a copy of the state.
+#[derive(Debug, Default)]
+pub struct ResumableParse {
+    /// The parse of `..pos`, none of whose steps looked past the end of the
+    /// string.
+    state: JsonParseState,
+    pos: usize,
+    /// Characters after `pos` the parser still has to skip.
+    skip: usize,
+}
+
+impl ResumableParse {
+    /// Same as [`parse`], for `str` extending the string of the previous call.
+    pub fn parse(&mut self, str: &str) -> Result<Vec<(Value, Vec<Fixes>)>> {
+        let mut state = self.state.clone();
+        let (mut pos, mut skip) = (self.pos, self.skip);
+        match feed(&mut state, str, &mut pos, &mut skip, str.len())? {
+            // Nothing depends on the end of the string yet.
+            None => {
+                self.state = state.clone();
+                self.pos = pos;
+                self.skip = skip;
             }
-            Err(e) => {
-                return Err(e);
+            // Only keep the steps before the first one that did.
+            Some(end) => {
+                feed(&mut self.state, str, &mut self.pos, &mut self.skip, end)?;
             }
         }
+        finish(state)
+    }
+}
+
+/// Looks ahead in the string, remembering whether it ran into its end.
+struct Lookahead<'a, I> {
+    chars: I,
+    hit_end: &'a Cell<bool>,
+}
+
+impl<I: Iterator<Item = (usize, char)>> Iterator for Lookahead<'_, I> {
+    type Item = (usize, char);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = self.chars.next();
+        if next.is_none() {
+            self.hit_end.set(true);
+        }
+        next
     }
+}
+
+/// Processes the characters of `str` from `pos` up to `end`. Returns where
+/// the first character whose processing looked past the end of `str` starts.
+fn feed(
+    state: &mut JsonParseState,
+    str: &str,
+    pos: &mut usize,
+    skip: &mut usize,
+    end: usize,
+) -> Result<Option<usize>> {
+    let hit_end = Cell::new(false);
+    let mut first_hit = None;
+    while let Some(c) = str[*pos..end].chars().next() {
+        let start = *pos;
+        *pos += c.len_utf8();
+        if *skip > 0 {
+            *skip -= 1;
+            continue;
+        }
+        let next = Lookahead {
+            chars: str[*pos..].char_indices(),
+            hit_end: &hit_end,
+        };
+        *skip = state.process_token(c, next.peekable())?;
+        if hit_end.get() && first_hit.is_none() {
+            first_hit = Some(start);
+        }
+    }
+    Ok(first_hit)
+}
 
+/// Closes any collection still open and picks the values to return.
+fn finish(mut state: JsonParseState) -> Result<Vec<(Value, Vec<Fixes>)>> {
     // If we still have a collection open, close it
     while !state.collection_stack.is_empty() {
         state.complete_collection(CompletionState::Incomplete);
@@ -171,4 +249,26 @@ mod tests {
             _ => panic!("Expected object"),
         }
     }
+
+    #[test]
+    fn test_resumable_parse_matches_parse() {
+        let opts = ParseOptions::default();
+        let inputs = [
+            r#"{"a": 1, "b": [true, null, 'x'], c: unquoted value, "d": "esc\"aped\n"}"#,
+            "[1, 2, /* comment */ 3,]\n// trailing\n",
+            r#"{"code": """triple "quoted" text""", "e": `tick`, "f": "caf\u00e9 ☕"}"#,
+            "Some text {key: value with spaces, \n other: 12.5} and more {\"x\": [[1], [2",
+        ];
+        for input in inputs {
+            let mut resumable = ResumableParse::default();
+            for end in (1..=input.len()).filter(|&end| input.is_char_boundary(end)) {
+                let prefix = &input[..end];
+                assert_eq!(
+                    resumable.parse(prefix).ok(),
+                    parse(prefix, &opts).ok(),
+                    "prefix: {prefix:?}"
+                );
+            }
+        }
+    }
 }
@@ -4,7 +4,7 @@ use bstd::dedent;
 use baml_types::CompletionState;
 use crate::jsonish::Value;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub enum JsonCollection {
     // Key, Value
     Object(Vec<String>, Vec<Value>, CompletionState),
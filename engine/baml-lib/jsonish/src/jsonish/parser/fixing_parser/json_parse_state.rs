@@ -6,7 +6,7 @@ use anyhow::Result;
 
 use super::json_collection::JsonCollection;
 
-#[derive(Debug)]
+#[derive(Clone, Debug, Default)]
 pub struct JsonParseState {
     /// The stack of Json collection values being assembled.
     /// The stack-ness is used in order to parse nested values,
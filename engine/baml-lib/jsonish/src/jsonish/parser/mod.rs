@@ -2,13 +2,22 @@ mod entry;
 mod fixing_parser;
 mod markdown_parser;
 mod multi_json_parser;
+mod xml_parser;
 
 pub use entry::parse;
+pub(super) use fixing_parser::ResumableParse;
+pub(super) use xml_parser::name_len as xml_name_len;
 
-#[derive(Clone, Copy, Debug)]
+use std::{collections::HashSet, sync::Arc};
+
+#[derive(Clone, Debug)]
 pub struct ParseOptions {
     all_finding_all_json_objects: bool,
     allow_markdown_json: bool,
+    allow_xml: bool,
+    /// The tags an XML answer may use. Text is only read as XML when one of
+    /// its elements has one of these tags.
+    xml_tags: Arc<HashSet<String>>,
     allow_fixes: bool,
     allow_as_string: bool,
     depth: usize,
@@ -19,6 +28,8 @@ impl Default for ParseOptions {
         Self {
             all_finding_all_json_objects: true,
             allow_markdown_json: true,
+            allow_xml: true,
+            xml_tags: Default::default(),
             allow_fixes: true,
             allow_as_string: true,
             depth: 0,
@@ -30,11 +41,24 @@ pub(super) enum ParsingMode {
     JsonMarkdown,
     JsonMarkdownString,
     AllJsonObjects,
+    Xml,
 }
 
 impl ParseOptions {
+    /// Options that read elements with one of `tags` as XML answers.
+    pub fn with_xml_tags(self, tags: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            xml_tags: Arc::new(tags.into_iter().collect()),
+            ..self
+        }
+    }
+
+    pub(super) fn is_xml_tag(&self, tag: &str) -> bool {
+        self.xml_tags.contains(tag)
+    }
+
     pub(super) fn next_from_mode(&self, curr_mode: ParsingMode) -> Self {
-        let mut new = *self;
+        let mut new = self.clone();
         match curr_mode {
             ParsingMode::JsonMarkdownString => {
                 new.allow_markdown_json = false;
@@ -48,6 +72,9 @@ impl ParseOptions {
                 new.all_finding_all_json_objects = false;
                 new.allow_as_string = false;
             }
+            ParsingMode::Xml => {
+                new.allow_xml = false;
+            }
         }
         new
     }
@@ -0,0 +1,424 @@
+//! Reads answers written as XML tags, like `<name>Bob</name>`, which some
+//! models write more reliably than JSON.
+//!
+//! An element with child elements becomes an object, with repeated tags
+//! collected into an array. An element whose children are all `<item>` is an
+//! array. An element with only text becomes the value of the text. The XML
+//! doesn't have to be well formed: text around the elements is ignored, and
+//! elements left open at the end, like in a streaming response, are
+//! incomplete. Only text with an element named like a tag of the schema, see
+//! [`ParseOptions::with_xml_tags`], is read as XML. Other tags, like a
+//! `<thinking>` before a JSON answer, are left to the JSON parsers.
+
+use anyhow::Result;
+use baml_types::CompletionState;
+
+use crate::jsonish::{parser::entry, Value};
+
+use super::ParseOptions;
+
+#[derive(Debug, Default)]
+struct Element {
+    tag: String,
+    children: Vec<Element>,
+    text: String,
+    /// Whether the text is from a CDATA section, which is taken as-is.
+    cdata: bool,
+    /// `<tag/>`.
+    empty: bool,
+    closed: bool,
+}
+
+impl Element {
+    fn completion_state(&self) -> CompletionState {
+        if self.closed {
+            CompletionState::Complete
+        } else {
+            CompletionState::Incomplete
+        }
+    }
+}
+
+/// The values the elements in `str` may stand for. A single root element is
+/// read both as its content and as an object with one key, since the model
+/// may or may not wrap its answer in a root tag.
+pub fn parse(str: &str, options: &ParseOptions) -> Result<Vec<Value>> {
+    let (elements, _) = parse_elements(str);
+    if !has_schema_tag(&elements, options) {
+        anyhow::bail!("No XML elements with a tag of the schema found");
+    }
+    match elements.as_slice() {
+        [] => anyhow::bail!("No XML elements found"),
+        [root] => {
+            let content = to_value(root, options);
+            let wrapped = Value::Object(
+                vec![(root.tag.clone(), content.clone())],
+                root.completion_state(),
+            );
+            Ok(vec![content, wrapped])
+        }
+        many => {
+            let state = many[many.len() - 1].completion_state();
+            Ok(vec![to_object(many, state, options)])
+        }
+    }
+}
+
+/// Whether any of `elements`, or of their descendants, has a tag of the schema.
+fn has_schema_tag(elements: &[Element], options: &ParseOptions) -> bool {
+    elements
+        .iter()
+        .any(|e| options.is_xml_tag(&e.tag) || has_schema_tag(&e.children, options))
+}
+
+/// The top-level elements of `text`, and the text around them.
+fn parse_elements(text: &str) -> (Vec<Element>, String) {
+    // The open elements, innermost last. The first one stands for the whole
+    // text and can't be closed.
+    let mut stack = vec![Element::default()];
+    let mut rest = text;
+
+    while let Some(at) = rest.find('<') {
+        push_text(&mut stack, &rest[..at]);
+        rest = &rest[at..];
+
+        if let Some(after) = rest.strip_prefix("<!--") {
+            rest = after.find("-->").map_or("", |end| &after[end + 3..]);
+        } else if let Some(after) = rest.strip_prefix("<![CDATA[") {
+            let (content, next) = match after.find("]]>") {
+                Some(end) => (&after[..end], &after[end + 3..]),
+                None => (after, ""),
+            };
+            let current = stack.last_mut().expect("the root is never popped");
+            current.text.push_str(content);
+            current.cdata = true;
+            rest = next;
+        } else if rest.starts_with("<?") || rest.starts_with("<!") {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let len = name_len(after);
+            match after[len..].find('>') {
+                Some(end) if len > 0 => {
+                    close(&mut stack, &after[..len]);
+                    rest = &after[len + end + 1..];
+                }
+                Some(_) => {
+                    push_text(&mut stack, "<");
+                    rest = &rest[1..];
+                }
+                // A closing tag that is still being streamed.
+                None => rest = "",
+            }
+        } else {
+            let after = &rest[1..];
+            let len = name_len(after);
+            let is_tag = len > 0
+                && match after[len..].chars().next() {
+                    Some(c) => c.is_whitespace() || c == '>' || c == '/',
+                    None => true,
+                };
+            if !is_tag {
+                push_text(&mut stack, "<");
+                rest = after;
+                continue;
+            }
+            match after[len..].find('>') {
+                Some(end) => {
+                    let mut element = Element {
+                        tag: after[..len].to_string(),
+                        ..Default::default()
+                    };
+                    // Attributes are ignored.
+                    if after[..len + end].ends_with('/') {
+                        element.empty = true;
+                        element.closed = true;
+                        stack
+                            .last_mut()
+                            .expect("the root is never popped")
+                            .children
+                            .push(element);
+                    } else {
+                        stack.push(element);
+                    }
+                    rest = &after[len + end + 1..];
+                }
+                // A tag that is still being streamed.
+                None => rest = "",
+            }
+        }
+    }
+    push_text(&mut stack, rest);
+
+    // Elements still open at the end are incomplete.
+    while stack.len() > 1 {
+        let element = stack.pop().expect("checked the length");
+        stack
+            .last_mut()
+            .expect("the root is never popped")
+            .children
+            .push(element);
+    }
+    let root = stack.pop().expect("the root is never popped");
+    (root.children, root.text)
+}
+
+/// Closes the innermost open element named `tag`, and any element opened
+/// inside it that was left open. A closing tag without an open element is
+/// ignored.
+fn close(stack: &mut Vec<Element>, tag: &str) {
+    let Some(at) = stack.iter().rposition(|e| e.tag == tag) else {
+        return;
+    };
+    while stack.len() > at.max(1) {
+        let mut element = stack.pop().expect("checked the length");
+        element.closed = true;
+        stack
+            .last_mut()
+            .expect("the root is never popped")
+            .children
+            .push(element);
+    }
+}
+
+fn push_text(stack: &mut [Element], text: &str) {
+    if text.is_empty() {
+        return;
+    }
+    let current = stack.last_mut().expect("the root is never popped");
+    current.text.push_str(&unescape(text));
+}
+
+/// The length of the XML name at the start of `s`, or 0 if there is none.
+pub fn name_len(s: &str) -> usize {
+    let mut chars = s.char_indices();
+    match chars.next() {
+        Some((_, c)) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return 0,
+    }
+    chars
+        .find(|(_, c)| !(c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')))
+        .map_or(s.len(), |(i, _)| i)
+}
+
+/// Replaces the predefined and numeric entities, like `&lt;` and `&#38;`.
+/// Anything else that starts with `&` is kept as-is.
+fn unescape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(at) = rest.find('&') {
+        out.push_str(&rest[..at]);
+        rest = &rest[at..];
+        let decoded = rest.find(';').and_then(|end| {
+            let c = match &rest[1..end] {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                entity => {
+                    let code = match entity.strip_prefix("#x").or(entity.strip_prefix("#X")) {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn to_value(element: &Element, options: &ParseOptions) -> Value {
+    let state = element.completion_state();
+    if element.children.is_empty() {
+        return text_value(element, state, options);
+    }
+    if element.children.iter().all(|child| child.tag == "item") {
+        return Value::Array(
+            element
+                .children
+                .iter()
+                .map(|child| to_value(child, options))
+                .collect(),
+            state,
+        );
+    }
+    to_object(&element.children, state, options)
+}
+
+/// An object with a key per tag. The values of repeated tags are collected
+/// into an array, at the position of the first one.
+fn to_object(children: &[Element], state: CompletionState, options: &ParseOptions) -> Value {
+    let mut fields: Vec<(String, Vec<Value>)> = vec![];
+    for child in children {
+        let value = to_value(child, options);
+        match fields.iter_mut().find(|(tag, _)| *tag == child.tag) {
+            Some((_, values)) => values.push(value),
+            None => fields.push((child.tag.clone(), vec![value])),
+        }
+    }
+    let fields = fields
+        .into_iter()
+        .map(|(tag, mut values)| {
+            let value = match values.len() {
+                1 => values.pop().expect("checked the length"),
+                _ => Value::Array(values, state.clone()),
+            };
+            (tag, value)
+        })
+        .collect();
+    Value::Object(fields, state)
+}
+
+fn text_value(element: &Element, state: CompletionState, options: &ParseOptions) -> Value {
+    if element.empty {
+        return Value::Null;
+    }
+    let text = element.text.trim();
+    if element.cdata {
+        return Value::String(text.to_string(), state);
+    }
+    // Numbers, booleans and null are read like JSON, but remain available as
+    // text for string fields.
+    match serde_json::from_str::<Value>(text) {
+        Ok(Value::String(s, _)) => Value::String(s, state),
+        Ok(mut value) => {
+            if let Value::Number(_, number_state) = &mut value {
+                *number_state = state;
+            }
+            Value::AnyOf(vec![value], text.to_string())
+        }
+        Err(_) if text.starts_with(['{', '[']) => entry::parse(text, options.clone())
+            .unwrap_or_else(|_| Value::String(text.to_string(), state)),
+        Err(_) => Value::String(text.to_string(), state),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> ParseOptions {
+        let tags = [
+            "answer", "item", "name", "tag", "address", "city", "bio", "code",
+        ];
+        ParseOptions::default().with_xml_tags(tags.map(String::from))
+    }
+
+    fn parse_one(text: &str) -> Value {
+        parse(text, &options()).unwrap().into_iter().next().unwrap()
+    }
+
+    fn string(s: &str, state: CompletionState) -> Value {
+        Value::String(s.to_string(), state)
+    }
+
+    #[test]
+    fn nested_and_repeated_tags() {
+        let value = parse_one(
+            "Sure:\n<answer>\n  <name>Bob &amp; Alice</name>\n  <tag>a</tag>\n  <tag>b</tag>\n  <address><city>Paris</city></address>\n</answer>",
+        );
+        assert_eq!(
+            value,
+            Value::Object(
+                vec![
+                    (
+                        "name".to_string(),
+                        string("Bob & Alice", CompletionState::Complete)
+                    ),
+                    (
+                        "tag".to_string(),
+                        Value::Array(
+                            vec![
+                                string("a", CompletionState::Complete),
+                                string("b", CompletionState::Complete)
+                            ],
+                            CompletionState::Complete
+                        )
+                    ),
+                    (
+                        "address".to_string(),
+                        Value::Object(
+                            vec![(
+                                "city".to_string(),
+                                string("Paris", CompletionState::Complete)
+                            )],
+                            CompletionState::Complete
+                        )
+                    ),
+                ],
+                CompletionState::Complete
+            )
+        );
+    }
+
+    #[test]
+    fn items_are_arrays() {
+        let value = parse_one("<answer><item>1</item><item>2</item></answer>");
+        let Value::Array(items, CompletionState::Complete) = value else {
+            panic!("expected an array, got {value:?}");
+        };
+        assert_eq!(items.len(), 2);
+    }
+
+    #[test]
+    fn cdata_is_taken_as_is() {
+        let value = parse_one("<code><![CDATA[if a < b && c { <x> }]]></code>");
+        assert_eq!(
+            value,
+            string("if a < b && c { <x> }", CompletionState::Complete)
+        );
+    }
+
+    #[test]
+    fn streaming_xml_is_incomplete() {
+        let value = parse_one("<answer><name>Bob</name><bio>Likes tea an");
+        assert_eq!(
+            value,
+            Value::Object(
+                vec![
+                    ("name".to_string(), string("Bob", CompletionState::Complete)),
+                    (
+                        "bio".to_string(),
+                        string("Likes tea an", CompletionState::Incomplete)
+                    ),
+                ],
+                CompletionState::Incomplete
+            )
+        );
+
+        // A tag cut off in the middle is left out.
+        let value = parse_one("<answer><name>Bob</name><bi");
+        let Value::Object(fields, CompletionState::Incomplete) = value else {
+            panic!("expected an incomplete object, got {value:?}");
+        };
+        assert_eq!(fields.len(), 1);
+    }
+
+    #[test]
+    fn text_without_tags() {
+        assert!(parse("a < b and c > d", &options()).is_err());
+    }
+
+    #[test]
+    fn only_tags_of_the_schema_are_xml() {
+        let text = "<thinking>The user wants a list.</thinking>\n[1, 2]";
+        assert!(parse(text, &options()).is_err());
+
+        // Even when the answer has JSON in it.
+        let text = "<answer><name>Bob</name><bio>{\"likes\": \"tea\"}</bio></answer>";
+        assert!(parse(text, &options()).is_ok());
+    }
+}
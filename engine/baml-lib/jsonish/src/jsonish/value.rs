@@ -9,6 +9,8 @@ use baml_types::{BamlMap, CompletionState};
 pub enum Fixes {
     GreppedForJSON,
     InferredArray,
+    /// Read from XML tags instead of JSON.
+    ParsedXml,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
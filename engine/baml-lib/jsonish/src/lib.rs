@@ -6,6 +6,7 @@ use indexmap::IndexMap;
 pub mod deserializer;
 use std::collections::HashMap;
 pub mod jsonish;
+mod parse_report;
 
 use baml_types::{BamlValue, BamlValueWithMeta, FieldType, JinjaExpression, ResponseCheck};
 use deserializer::{
@@ -14,6 +15,7 @@ use deserializer::{
 };
 
 pub use deserializer::types::BamlValueWithFlags;
+pub use parse_report::{ParseReport, ParseReportEntry, UnionBranch};
 use internal_baml_core::ir::TypeValue;
 use internal_baml_jinja::types::OutputFormatContent;
 
@@ -151,6 +153,26 @@ fn serialize_with_meta<S: Serializer, T: Serialize>(
     }
 }
 
+/// Returned by [`from_str`] when the parsed text cannot be coerced into the
+/// target type. Callers can downcast to this to tell apart failing asserts
+/// from other parsing errors.
+#[derive(Debug)]
+pub struct CoercionError(pub ParsingError);
+
+impl CoercionError {
+    pub fn is_assert_failure(&self) -> bool {
+        self.0.has_failed_asserts()
+    }
+}
+
+impl std::fmt::Display for CoercionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Failed to coerce value: {}", self.0)
+    }
+}
+
+impl std::error::Error for CoercionError {}
+
 pub fn from_str(
     of: &OutputFormatContent,
     target: &FieldType,
@@ -162,8 +184,59 @@ pub fn from_str(
     }
 
     // When the schema is just a string, i should really just return the raw_string w/o parsing it.
-    let value = jsonish::parse(raw_string, jsonish::ParseOptions::default())?;
+    let value = jsonish::parse(raw_string, parse_options(of))?;
+
+    coerce(of, target, &value, allow_partials)
+}
+
+/// The options to parse an answer for `of`: the tags of an XML answer are the
+/// `<answer>` root, the `<item>`s of lists and the fields of its classes.
+fn parse_options(of: &OutputFormatContent) -> jsonish::ParseOptions {
+    let fields = of
+        .classes
+        .values()
+        .flat_map(|class| &class.fields)
+        .flat_map(|(name, ..)| [name.real_name(), name.rendered_name()]);
+    let tags = ["answer", "item"].into_iter().chain(fields).map(String::from);
+    jsonish::ParseOptions::default().with_xml_tags(tags)
+}
+
+/// Parses a streamed response each time a chunk is added to it, only
+/// tokenizing the text added since the previous call. Building and coercing
+/// the value still covers the whole response. Use one per stream.
+#[derive(Debug, Default)]
+pub struct IncrementalParser {
+    values: jsonish::IterativeParser,
+    /// Computed on the first call, the schema doesn't change during a stream.
+    options: Option<jsonish::ParseOptions>,
+}
 
+impl IncrementalParser {
+    /// Same as [`from_str`] with `allow_partials`, for a `raw_string` that
+    /// extends the one of the previous call.
+    pub fn parse(
+        &mut self,
+        of: &OutputFormatContent,
+        target: &FieldType,
+        raw_string: &str,
+    ) -> Result<BamlValueWithFlags> {
+        if matches!(target, FieldType::Primitive(TypeValue::String)) {
+            return Ok(BamlValueWithFlags::String(raw_string.to_string().into()));
+        }
+
+        let options = self.options.get_or_insert_with(|| parse_options(of));
+        let value = self.values.parse(raw_string, options)?;
+
+        coerce(of, target, &value, true)
+    }
+}
+
+fn coerce(
+    of: &OutputFormatContent,
+    target: &FieldType,
+    value: &Value,
+    allow_partials: bool,
+) -> Result<BamlValueWithFlags> {
     // Pick the schema that is the most specific.
     log::debug!("Parsed JSONish (step 1 of parsing): {:#?}", value);
     let ctx = ParsingContext::new(of, allow_partials);
@@ -171,7 +244,7 @@ pub fn from_str(
     // Determine the best way to get the desired schema from the parsed schema.
 
     // Lets try to now coerce the value into the expected schema.
-    let parsed_value: BamlValueWithFlags = match target.coerce(&ctx, target, Some(&value)) {
+    let parsed_value: BamlValueWithFlags = match target.coerce(&ctx, target, Some(value)) {
         Ok(v) => {
             if v.conditions()
                 .flags()
@@ -183,7 +256,7 @@ pub fn from_str(
 
             Ok::<BamlValueWithFlags, anyhow::Error>(v)
         }
-        Err(e) => anyhow::bail!("Failed to coerce value: {}", e),
+        Err(e) => Err(CoercionError(e).into()),
     }?;
 
     Ok(parsed_value)
@@ -200,6 +273,19 @@ impl ResponseBamlValue {
         expl.into_iter().map(|e| e.to_ui_json()).collect::<Vec<_>>()
     }
 
+    /// The parser flags of every node, with the path of the node, like
+    /// `items.0.name`. The path of the root is empty.
+    pub fn flags(&self) -> Vec<(String, &Flag)> {
+        let mut flags = vec![];
+        collect_flags(&self.0, String::new(), &mut flags);
+        flags
+    }
+
+    /// The score and the repairs the parser made to get this value.
+    pub fn parse_report(&self) -> ParseReport {
+        ParseReport::new(self.score(), self.flags())
+    }
+
     fn explanation_impl(&self, scope: Vec<String>, expls: &mut Vec<ParsingError>) {
         self.0.iter().for_each(|node| {
             let message = match node {
@@ -233,6 +319,31 @@ impl ResponseBamlValue {
     }
 }
 
+fn collect_flags<'a>(
+    value: &'a BamlValueWithMeta<(Vec<Flag>, Vec<ResponseCheck>, Completion)>,
+    path: String,
+    flags: &mut Vec<(String, &'a Flag)>,
+) {
+    flags.extend(value.meta().0.iter().map(|flag| (path.clone(), flag)));
+    let child = |key: &dyn std::fmt::Display| match path.as_str() {
+        "" => key.to_string(),
+        _ => format!("{path}.{key}"),
+    };
+    match value {
+        BamlValueWithMeta::List(items, _) => {
+            for (i, item) in items.iter().enumerate() {
+                collect_flags(item, child(&i), flags);
+            }
+        }
+        BamlValueWithMeta::Map(fields, _) | BamlValueWithMeta::Class(_, fields, _) => {
+            for (key, field) in fields {
+                collect_flags(field, child(key), flags);
+            }
+        }
+        _ => {}
+    }
+}
+
 impl From<ResponseBamlValue> for BamlValue {
     fn from(v: ResponseBamlValue) -> BamlValue {
         v.0.into()
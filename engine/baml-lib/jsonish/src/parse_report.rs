@@ -0,0 +1,177 @@
+//! A structured summary of the repairs the parser made to a response.
+//!
+//! The score and the flags of a parsed value are enough to tell a clean
+//! response from one that only parsed after heavy fixing. Tracking them per
+//! prompt shows when its output starts to degrade, before it fails to parse.
+
+use serde::Serialize;
+
+use crate::deserializer::deserialize_flags::Flag;
+use crate::jsonish::Fixes;
+
+/// How much a parsed response had to be repaired to fit its type.
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct ParseReport {
+    /// The score of the parser. Lower is better. Picking a value out of its
+    /// candidates is scored too, so a clean response can score above 0.
+    pub score: i32,
+    /// Whether the response parsed without any fixes or coercions.
+    pub clean: bool,
+    /// Repairs of the raw text, like taking the value out of a markdown
+    /// block or fixing malformed JSON.
+    pub fixes: Vec<ParseReportEntry>,
+    /// Conversions made to fit values to their type, like rounding a float
+    /// for an int field.
+    pub coercions: Vec<ParseReportEntry>,
+    /// The branch picked for each union.
+    pub unions: Vec<UnionBranch>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct ParseReportEntry {
+    /// The path of the value, like `items.0.name`. Empty for the root.
+    pub path: String,
+    /// A stable name for the repair, like `float_to_int`.
+    pub kind: &'static str,
+    pub description: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct UnionBranch {
+    /// The path of the value, like `items.0.name`. Empty for the root.
+    pub path: String,
+    /// The index of the picked branch in the union.
+    pub branch: usize,
+    /// How many branches the value could be parsed as.
+    pub candidates: usize,
+}
+
+impl ParseReport {
+    pub(crate) fn new<'a>(score: i32, flags: impl IntoIterator<Item = (String, &'a Flag)>) -> Self {
+        let mut report = ParseReport {
+            score,
+            ..Default::default()
+        };
+        for (path, flag) in flags {
+            let entry = |kind, description| ParseReportEntry {
+                path: path.clone(),
+                kind,
+                description,
+            };
+            match flag {
+                Flag::ObjectFromMarkdown(_) => report
+                    .fixes
+                    .push(entry("markdown", "Taken from a markdown block".into())),
+                Flag::ObjectFromFixedJson(fixes) if fixes.is_empty() => report
+                    .fixes
+                    .push(entry("fixed_json", "Fixed malformed JSON".into())),
+                Flag::ObjectFromFixedJson(fixes) => {
+                    report.fixes.extend(fixes.iter().map(|fix| match fix {
+                        Fixes::GreppedForJSON => {
+                            entry("json_in_text", "Found in between other text".into())
+                        }
+                        Fixes::InferredArray => entry(
+                            "inferred_array",
+                            "Collected several values into an array".into(),
+                        ),
+                        Fixes::ParsedXml => entry("xml", "Read from XML tags".into()),
+                    }))
+                }
+                // Every value is picked out of its candidates, which is only
+                // worth reporting when there was more than one.
+                Flag::FirstMatch(_, candidates) if candidates.len() <= 1 => {}
+                Flag::UnionMatch(branch, candidates) => report.unions.push(UnionBranch {
+                    path,
+                    branch: *branch,
+                    candidates: candidates.iter().filter(|c| c.is_ok()).count(),
+                }),
+                flag => {
+                    if let Some((kind, description)) = coercion(flag) {
+                        report.coercions.push(entry(kind, description));
+                    }
+                }
+            }
+        }
+        report.clean = report.fixes.is_empty() && report.coercions.is_empty();
+        report
+    }
+}
+
+/// The kind and description of a flag that converts a value.
+fn coercion(flag: &Flag) -> Option<(&'static str, String)> {
+    Some(match flag {
+        Flag::DefaultButHadUnparseableValue(e) => (
+            "dropped_value",
+            format!("Replaced a value that didn't parse with null: {e}"),
+        ),
+        Flag::DefaultButHadValue(v) => (
+            "dropped_value",
+            format!("Replaced a {} with null", v.r#type()),
+        ),
+        Flag::DefaultFromNoValue => ("missing_value", "Used a default for a missing value".into()),
+        Flag::OptionalDefaultFromNoValue => (
+            "missing_optional",
+            "Used null for a missing optional value".into(),
+        ),
+        Flag::DefaultFromAttribute => (
+            "missing_default",
+            "Used the @default of a missing field".into(),
+        ),
+        Flag::ObjectToString(v) | Flag::JsonToString(v) => (
+            "object_to_string",
+            format!("Turned a {} into a string", v.r#type()),
+        ),
+        Flag::ObjectToPrimitive(_) => (
+            "object_to_primitive",
+            "Turned an object with a single key into its value".into(),
+        ),
+        Flag::ObjectToMap(_) => ("object_to_map", "Turned an object into a map".into()),
+        Flag::ExtraKey(key, _) => ("extra_key", format!("Ignored the extra key {key}")),
+        Flag::ImpliedKey(key) => ("implied_key", format!("Used the value for the field {key}")),
+        Flag::InferedObject(v) => (
+            "inferred_object",
+            format!("Built an object out of a {}", v.r#type()),
+        ),
+        Flag::NoFields(_) => ("no_fields", "Built an object without any fields".into()),
+        Flag::SingleToArray => (
+            "single_to_array",
+            "Turned a single value into an array".into(),
+        ),
+        Flag::FirstMatch(i, _) => (
+            "first_match",
+            format!("Picked value {i} of several candidates"),
+        ),
+        Flag::SubstringMatch(s) => ("substring_match", format!("Matched within {s:?}")),
+        Flag::StrMatchOneFromMany(values) => (
+            "substring_match",
+            format!(
+                "Matched one of several values: {}",
+                values
+                    .iter()
+                    .map(|(value, count)| format!("{value} ({count})"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+        Flag::StrippedNonAlphaNumeric(s) => (
+            "stripped_non_alphanumeric",
+            format!("Matched {s:?} without punctuation"),
+        ),
+        Flag::StringToBool(s) => ("string_to_bool", format!("Turned {s:?} into a bool")),
+        Flag::StringToNull(s) => ("string_to_null", format!("Turned {s:?} into null")),
+        Flag::StringToChar(s) => ("string_to_char", format!("Turned {s:?} into a char")),
+        Flag::StringToFloat(s) => ("string_to_float", format!("Turned {s:?} into a number")),
+        Flag::FloatToInt(f) => ("float_to_int", format!("Rounded {f} to an int")),
+        Flag::ArrayItemParseError(i, e) => ("skipped_item", format!("Left out item {i}: {e}")),
+        Flag::MapKeyParseError(i, e) => ("skipped_item", format!("Left out key {i}: {e}")),
+        Flag::MapValueParseError(key, e) => {
+            ("skipped_item", format!("Left out the value of {key}: {e}"))
+        }
+        Flag::ObjectFromMarkdown(_)
+        | Flag::ObjectFromFixedJson(_)
+        | Flag::UnionMatch(..)
+        | Flag::ConstraintResults(_)
+        | Flag::Incomplete
+        | Flag::Pending => return None,
+    })
+}
@@ -1467,3 +1467,49 @@ test_partial_deserializer_streaming!(
       "prop2": 1
     }
 );
+
+const DEFAULT_VALUES_SCHEMA: &str = r#"
+enum Currency {
+  USD
+  EUR
+}
+
+class Price {
+  amount float
+  currency Currency @default(USD)
+  note string? @default("none")
+  tags string[] @default(["retail"])
+}
+"#;
+
+test_deserializer!(
+    test_default_values_for_missing_fields,
+    DEFAULT_VALUES_SCHEMA,
+    r#"{"amount": 9.5}"#,
+    FieldType::class("Price"),
+    {"amount": 9.5, "currency": "USD", "note": "none", "tags": ["retail"]}
+);
+
+test_deserializer!(
+    test_default_values_not_used_for_present_fields,
+    DEFAULT_VALUES_SCHEMA,
+    r#"{"amount": 9.5, "currency": "EUR", "note": null, "tags": []}"#,
+    FieldType::class("Price"),
+    {"amount": 9.5, "currency": "EUR", "note": null, "tags": []}
+);
+
+// The fields may still be streamed, so they don't take their default yet.
+test_partial_deserializer!(
+    test_default_values_not_used_while_streaming,
+    DEFAULT_VALUES_SCHEMA,
+    r#"{"amount": 9.5"#,
+    FieldType::class("Price"),
+    {"amount": 9.5, "currency": null, "note": null, "tags": []}
+);
+
+test_failing_deserializer!(
+    test_default_values_do_not_fill_required_fields,
+    DEFAULT_VALUES_SCHEMA,
+    r#"{"currency": "EUR"}"#,
+    FieldType::class("Price")
+);
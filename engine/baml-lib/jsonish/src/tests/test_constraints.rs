@@ -154,3 +154,103 @@ test_failing_deserializer!(
     r#"THREE"#,
     FieldType::Enum("MyEnum".to_string())
 );
+
+const VALIDATORS: &str = r#"
+class Signup {
+  username string @regex("^[a-z0-9_]+$") @length(3, 20)
+  email string? @format("email")
+  age int @min(13) @max(130)
+  tags string[] @length(0, 2)
+}
+
+class Ticket {
+  value string @format("uuid")
+}
+
+class Email {
+  value string @format("email")
+}
+"#;
+
+test_deserializer!(
+    test_validators_pass,
+    VALIDATORS,
+    r#"{"username": "greg_42", "email": "greg@example.com", "age": 30, "tags": ["a"]}"#,
+    FieldType::Class("Signup".to_string()),
+    {"username": "greg_42", "email": "greg@example.com", "age": 30, "tags": ["a"]}
+);
+
+test_deserializer!(
+    test_validators_allow_null,
+    VALIDATORS,
+    r#"{"username": "greg", "email": null, "age": 13, "tags": []}"#,
+    FieldType::Class("Signup".to_string()),
+    {"username": "greg", "email": null, "age": 13, "tags": []}
+);
+
+test_failing_deserializer!(
+    test_validator_regex_fails,
+    VALIDATORS,
+    r#"{"username": "Greg!", "email": null, "age": 30, "tags": []}"#,
+    FieldType::Class("Signup".to_string())
+);
+
+// Like a failing assert, an optional field that fails its validator is null.
+test_deserializer!(
+    test_validator_format_fails_optional,
+    VALIDATORS,
+    r#"{"username": "greg", "email": "not an email", "age": 30, "tags": []}"#,
+    FieldType::Class("Signup".to_string()),
+    {"username": "greg", "email": null, "age": 30, "tags": []}
+);
+
+test_failing_deserializer!(
+    test_validator_format_fails,
+    VALIDATORS,
+    r#"{"value": "not an email"}"#,
+    FieldType::Class("Email".to_string())
+);
+
+test_failing_deserializer!(
+    test_validator_min_fails,
+    VALIDATORS,
+    r#"{"username": "greg", "email": null, "age": 12, "tags": []}"#,
+    FieldType::Class("Signup".to_string())
+);
+
+test_failing_deserializer!(
+    test_validator_list_length_fails,
+    VALIDATORS,
+    r#"{"username": "greg", "email": null, "age": 30, "tags": ["a", "b", "c"]}"#,
+    FieldType::Class("Signup".to_string())
+);
+
+test_deserializer!(
+    test_union_decision_from_validator,
+    VALIDATORS,
+    r#"{"value": "greg@example.com"}"#,
+    FieldType::union(vec![
+        FieldType::Class("Ticket".to_string()),
+        FieldType::Class("Email".to_string()),
+    ]),
+    {"value": "greg@example.com"}
+);
+
+#[test]
+fn test_validator_error_names_field_and_validator() {
+    let ir = crate::helpers::load_test_ir(VALIDATORS);
+    let target_type = FieldType::Class("Signup".to_string());
+    let target =
+        crate::helpers::render_output_format(&ir, &target_type, &Default::default()).unwrap();
+
+    let result = from_str(
+        &target,
+        &target_type,
+        r#"{"username": "greg", "email": null, "age": 200, "tags": []}"#,
+        false,
+    );
+
+    let error = format!("{:?}", result.unwrap_err());
+    assert!(error.contains("age"), "{error}");
+    assert!(error.contains("Failed: @max(130)"), "{error}");
+}
@@ -254,6 +254,27 @@ const MEMORY_PAYLOAD: &str = r#"
 }
 "#;
 
+#[test_log::test]
+fn test_incremental_parser_matches_from_str() {
+    let ir = load_test_ir(MEMORY_TEST);
+    let target_type = FieldType::class("TestMemoryOutput");
+    let target =
+        crate::helpers::render_output_format(&ir, &target_type, &Default::default()).unwrap();
+
+    let mut parser = crate::IncrementalParser::default();
+    let ends = (1..MEMORY_PAYLOAD.len()).step_by(7).chain([MEMORY_PAYLOAD.len()]);
+    for end in ends {
+        let prefix = &MEMORY_PAYLOAD[..end];
+        let streamed = parser.parse(&target, &target_type, prefix);
+        let reparsed = from_str(&target, &target_type, prefix, true);
+        assert_eq!(
+            streamed.ok().map(BamlValue::from),
+            reparsed.ok().map(BamlValue::from),
+            "prefix: {prefix:?}"
+        );
+    }
+}
+
 test_partial_deserializer_streaming!(
     test_memory,
     MEMORY_TEST,
@@ -0,0 +1,85 @@
+use baml_types::{FieldType, TypeValue};
+
+use super::*;
+
+const RESUME_FILE: &str = r#"
+class Resume {
+    name string
+    years int
+    skills string[]
+}
+
+class Tags {
+    tag string[]
+}
+"#;
+
+test_deserializer!(
+    test_xml_class,
+    RESUME_FILE,
+    r#"
+Here is the resume:
+<answer>
+  <name>Bob &amp; Co</name>
+  <years>5</years>
+  <skills>
+    <item>Rust</item>
+    <item>Go</item>
+  </skills>
+</answer>
+"#,
+    FieldType::Class("Resume".to_string()),
+    {"name": "Bob & Co", "years": 5, "skills": ["Rust", "Go"]}
+);
+
+test_deserializer!(
+    test_xml_class_without_root,
+    RESUME_FILE,
+    r#"<name>Bob</name><years>5</years><skills><item><![CDATA[C & <C++>]]></item></skills>"#,
+    FieldType::Class("Resume".to_string()),
+    {"name": "Bob", "years": 5, "skills": ["C & <C++>"]}
+);
+
+test_deserializer!(
+    test_xml_repeated_tags,
+    RESUME_FILE,
+    r#"<tag>a</tag>
+<tag>b</tag>"#,
+    FieldType::Class("Tags".to_string()),
+    {"tag": ["a", "b"]}
+);
+
+test_deserializer!(
+    test_xml_number_as_string,
+    RESUME_FILE,
+    r#"<answer><item>1</item><item>two</item></answer>"#,
+    FieldType::List(FieldType::Primitive(TypeValue::String).into()),
+    ["1", "two"]
+);
+
+test_deserializer!(
+    test_xml_in_a_string,
+    RESUME_FILE,
+    r#"<b>bold</b> text"#,
+    FieldType::Primitive(TypeValue::String),
+    "<b>bold</b> text"
+);
+
+test_partial_deserializer!(
+    test_xml_partial,
+    RESUME_FILE,
+    r#"<answer><name>Bob</name><skills><item>Rust</item><item>G"#,
+    FieldType::Class("Resume".to_string()),
+    {"name": "Bob", "years": null, "skills": ["Rust", "G"]}
+);
+
+test_deserializer!(
+    test_xml_thinking_before_json,
+    RESUME_FILE,
+    r#"<thinking>
+The user wants the resume as JSON.
+</thinking>
+{"name": "Bob", "years": 5, "skills": ["Rust"]}"#,
+    FieldType::Class("Resume".to_string()),
+    {"name": "Bob", "years": 5, "skills": ["Rust"]}
+);
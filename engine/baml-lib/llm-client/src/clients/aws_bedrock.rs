@@ -1,7 +1,8 @@
 use std::collections::HashSet;
 
 use crate::{
-    AllowedRoleMetadata, FinishReasonFilter, RolesSelection, SupportedRequestModes,
+    AllowedRoleMetadata, CacheConfig, CircuitBreakerConfig, Coercion, FinishReasonFilter,
+    OutputMode, Pricing, RateLimitConfig, RolesSelection, SupportedRequestModes, TimeoutConfig,
     UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter, UnresolvedRolesSelection,
 };
 use anyhow::Result;
@@ -24,6 +25,14 @@ pub struct UnresolvedAwsBedrock {
     supported_request_modes: SupportedRequestModes,
     inference_config: Option<UnresolvedInferenceConfiguration>,
     finish_reason_filter: UnresolvedFinishReasonFilter,
+    cache: Option<CacheConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    rate_limit: Option<RateLimitConfig>,
+    timeouts: Option<TimeoutConfig>,
+    forbid_coercions: Vec<Coercion>,
+    max_context_tokens: Option<u64>,
+    pricing: Option<Pricing>,
+    output_mode: OutputMode,
 }
 
 #[derive(Debug, Clone)]
@@ -76,6 +85,14 @@ pub struct ResolvedAwsBedrock {
     pub allowed_role_metadata: AllowedRoleMetadata,
     pub supported_request_modes: SupportedRequestModes,
     pub finish_reason_filter: FinishReasonFilter,
+    pub cache: Option<CacheConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub timeouts: Option<TimeoutConfig>,
+    pub forbid_coercions: Vec<Coercion>,
+    pub max_context_tokens: Option<u64>,
+    pub pricing: Option<Pricing>,
+    pub output_mode: OutputMode,
 }
 
 impl ResolvedAwsBedrock {
@@ -272,6 +289,14 @@ impl UnresolvedAwsBedrock {
                 .map(|c| c.resolve(ctx))
                 .transpose()?,
             finish_reason_filter: self.finish_reason_filter.resolve(ctx)?,
+            cache: self.cache.clone(),
+            circuit_breaker: self.circuit_breaker.clone(),
+            rate_limit: self.rate_limit.clone(),
+            timeouts: self.timeouts.clone(),
+            forbid_coercions: self.forbid_coercions.clone(),
+            max_context_tokens: self.max_context_tokens,
+            pricing: self.pricing.clone(),
+            output_mode: self.output_mode,
         })
     }
 
@@ -380,6 +405,14 @@ impl UnresolvedAwsBedrock {
             Some(inference_config)
         };
         let finish_reason_filter = properties.ensure_finish_reason_filter();
+        let cache = properties.ensure_cache();
+        let circuit_breaker = properties.ensure_circuit_breaker();
+        let rate_limit = properties.ensure_rate_limit();
+        let timeouts = properties.ensure_timeouts();
+        let forbid_coercions = properties.ensure_forbid_coercions();
+        let max_context_tokens = properties.ensure_max_context_tokens();
+        let pricing = properties.ensure_pricing();
+        let output_mode = properties.ensure_output_mode(&[OutputMode::Prompt, OutputMode::Tools]);
 
         // TODO: Handle inference_configuration
         let errors = properties.finalize_empty();
@@ -399,6 +432,14 @@ impl UnresolvedAwsBedrock {
             supported_request_modes,
             inference_config,
             finish_reason_filter,
+            cache,
+            circuit_breaker,
+            rate_limit,
+            timeouts,
+            forbid_coercions,
+            max_context_tokens,
+            pricing,
+            output_mode,
         })
     }
 }
@@ -3,23 +3,26 @@ use std::collections::HashSet;
 use anyhow::Result;
 use baml_types::{EvaluationContext, StringOr};
 
-use crate::ClientSpec;
+use crate::{ClientSpec, RetryOn};
 
 use super::helpers::{Error, PropertyHandler};
 
 #[derive(Debug)]
 pub struct UnresolvedFallback<Meta> {
     strategy: Vec<(either::Either<StringOr, ClientSpec>, Meta)>,
+    retry_on: Option<RetryOn>,
 }
 
 pub struct ResolvedFallback {
     pub strategy: Vec<ClientSpec>,
+    pub retry_on: RetryOn,
 }
 
 impl<Meta: Clone> UnresolvedFallback<Meta> {
     pub fn without_meta(&self) -> UnresolvedFallback<()> {
         UnresolvedFallback {
             strategy: self.strategy.iter().map(|(s, _)| (s.clone(), ())).collect(),
+            retry_on: self.retry_on.clone(),
         }
     }
 
@@ -42,11 +45,15 @@ impl<Meta: Clone> UnresolvedFallback<Meta> {
                 either::Either::Right(s) => Ok(s.clone()),
             })
             .collect::<Result<Vec<_>>>()?;
-        Ok(ResolvedFallback { strategy })
+        Ok(ResolvedFallback {
+            strategy,
+            retry_on: self.retry_on.clone().unwrap_or_default(),
+        })
     }
 
     pub fn create_from(mut properties: PropertyHandler<Meta>) -> Result<Self, Vec<Error<Meta>>> {
         let strategy = properties.ensure_strategy();
+        let retry_on = properties.ensure_retry_on();
         let errors = properties.finalize_empty();
 
         if !errors.is_empty() {
@@ -55,7 +62,7 @@ impl<Meta: Clone> UnresolvedFallback<Meta> {
 
         let strategy = strategy.expect("strategy is required");
 
-        Ok(Self { strategy })
+        Ok(Self { strategy, retry_on })
     }
 }
 
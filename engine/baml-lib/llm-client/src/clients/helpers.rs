@@ -4,8 +4,9 @@ use baml_types::{GetEnvVar, StringOr, UnresolvedValue};
 use indexmap::IndexMap;
 
 use crate::{
-    SupportedRequestModes, UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter,
-    UnresolvedRolesSelection,
+    CacheConfig, CacheStore, CircuitBreakerConfig, Coercion, OutputMode, Pricing, RateLimitConfig,
+    RetryOn, SupportedRequestModes, TimeoutConfig, UnresolvedAllowedRoleMetadata,
+    UnresolvedFinishReasonFilter, UnresolvedRolesSelection,
 };
 
 #[derive(Debug, Clone)]
@@ -389,6 +390,311 @@ impl<Meta: Clone> PropertyHandler<Meta> {
             })
     }
 
+    /// One non-negative weight per strategy entry, `count` being the number of
+    /// entries if the strategy itself is valid.
+    pub fn ensure_weights(&mut self, count: Option<usize>) -> Option<Vec<f64>> {
+        let (_, value, value_span) = self.ensure_array("weights", false)?;
+        let weights = value
+            .iter()
+            .filter_map(|v| match v.as_numeric().map(|n| n.parse::<f64>()) {
+                Some(Ok(w)) if w >= 0.0 && w.is_finite() => Some(w),
+                _ => {
+                    self.push_error(
+                        "values in weights must be non-negative numbers",
+                        v.meta().clone(),
+                    );
+                    None
+                }
+            })
+            .collect::<Vec<_>>();
+        if weights.len() != value.len() {
+            return None;
+        }
+        if let Some(count) = count.filter(|c| *c != weights.len()) {
+            self.push_error(
+                format!(
+                    "weights must have one entry per client in strategy. Expected {count}, got {}",
+                    weights.len()
+                ),
+                value_span,
+            );
+            return None;
+        }
+        if !weights.iter().any(|w| *w > 0.0) {
+            self.push_error("at least one weight must be positive", value_span);
+            return None;
+        }
+        Some(weights)
+    }
+
+    pub fn ensure_retry_on(&mut self) -> Option<RetryOn> {
+        self.ensure_array("retry_on", false)
+            .map(|(_, value, value_span)| {
+                let values = value
+                    .into_iter()
+                    .filter_map(|v| match v.as_str() {
+                        Some(StringOr::Value(s)) => Some(s.clone()),
+                        _ => {
+                            self.push_error(
+                                format!(
+                                    "values in retry_on must be one of: {}",
+                                    RetryOn::allowed_values().join(", ")
+                                ),
+                                v.meta().clone(),
+                            );
+                            None
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                match RetryOn::from_values(values.iter().map(String::as_str)) {
+                    Ok(retry_on) => retry_on,
+                    Err(e) => {
+                        self.push_error(e.to_string(), value_span);
+                        RetryOn::default()
+                    }
+                }
+            })
+    }
+
+    /// Reads `output_mode`, reporting an error if the provider does not
+    /// implement the requested mode.
+    pub fn ensure_output_mode(&mut self, supported: &[OutputMode]) -> OutputMode {
+        let Some((key_span, value, value_span)) = self.ensure_string("output_mode", false) else {
+            return OutputMode::default();
+        };
+        let StringOr::Value(value) = value else {
+            self.push_error("output_mode must be a string literal", value_span);
+            return OutputMode::default();
+        };
+        match value.parse::<OutputMode>() {
+            Ok(mode) if supported.contains(&mode) => mode,
+            Ok(mode) => {
+                self.push_error(
+                    format!("output_mode \"{mode}\" is not supported by this provider"),
+                    key_span,
+                );
+                OutputMode::default()
+            }
+            Err(e) => {
+                self.push_error(e.to_string(), value_span);
+                OutputMode::default()
+            }
+        }
+    }
+
+    /// Reads the `cache { ... }` block. Returns `None` if caching is not
+    /// configured.
+    pub fn ensure_cache(&mut self) -> Option<CacheConfig> {
+        let (_, options, span) = self.ensure_map("cache", false)?;
+        let mut cache = PropertyHandler::new(options, span);
+
+        let literal = |cache: &mut Self, key: &str| match cache.ensure_string(key, false) {
+            Some((key_span, StringOr::Value(value), value_span)) => {
+                Some((key_span, value, value_span))
+            }
+            Some((_, _, value_span)) => {
+                cache.push_error(format!("cache {key} must be a string literal"), value_span);
+                None
+            }
+            None => None,
+        };
+        let store = literal(&mut cache, "store");
+        let path = literal(&mut cache, "path");
+        let max_entries = cache.ensure_int("max_entries", false);
+        let ttl_seconds = cache.ensure_int("ttl_seconds", false);
+
+        let store = match store
+            .as_ref()
+            .map(|(_, store, span)| (span, store.as_str()))
+        {
+            None | Some((_, "memory")) => {
+                if let Some((key_span, ..)) = path {
+                    cache.push_error("cache path is only supported by the disk store", key_span);
+                }
+                let max_entries = match max_entries {
+                    Some((_, n, _)) if n > 0 => n as usize,
+                    Some((_, _, value_span)) => {
+                        cache.push_error("cache max_entries must be positive", value_span);
+                        CacheStore::DEFAULT_MAX_ENTRIES
+                    }
+                    None => CacheStore::DEFAULT_MAX_ENTRIES,
+                };
+                CacheStore::Memory { max_entries }
+            }
+            Some((_, "disk")) => {
+                if let Some((key_span, ..)) = max_entries {
+                    cache.push_error(
+                        "cache max_entries is only supported by the memory store",
+                        key_span,
+                    );
+                }
+                CacheStore::Disk {
+                    path: path
+                        .map(|(_, path, _)| path)
+                        .unwrap_or_else(|| CacheStore::DEFAULT_PATH.to_string()),
+                }
+            }
+            Some((value_span, other)) => {
+                cache.push_error(
+                    format!(
+                        "Invalid cache store: {}. Allowed values are: {}",
+                        other,
+                        CacheStore::allowed_values().join(", ")
+                    ),
+                    value_span.clone(),
+                );
+                CacheStore::Memory {
+                    max_entries: CacheStore::DEFAULT_MAX_ENTRIES,
+                }
+            }
+        };
+
+        let ttl_seconds = match ttl_seconds {
+            Some((_, n, _)) if n > 0 => Some(n as u64),
+            Some((_, _, value_span)) => {
+                cache.push_error("cache ttl_seconds must be positive", value_span);
+                None
+            }
+            None => None,
+        };
+
+        self.errors.extend(cache.finalize_empty());
+        Some(CacheConfig { store, ttl_seconds })
+    }
+
+    pub fn ensure_circuit_breaker(&mut self) -> Option<CircuitBreakerConfig> {
+        let (_, options, span) = self.ensure_map("circuit_breaker", false)?;
+        let mut breaker = PropertyHandler::new(options, span);
+        let mut config = CircuitBreakerConfig::default();
+
+        let positive = |breaker: &mut Self, key: &str| match breaker.ensure_int(key, false) {
+            Some((_, n, _)) if n > 0 => Some(n as u64),
+            Some((_, _, value_span)) => {
+                breaker.push_error(
+                    format!("circuit_breaker {key} must be positive"),
+                    value_span,
+                );
+                None
+            }
+            None => None,
+        };
+        if let Some(n) = positive(&mut breaker, "failure_threshold") {
+            config.failure_threshold = n as u32;
+        }
+        if let Some(n) = positive(&mut breaker, "window_seconds") {
+            config.window_seconds = n;
+        }
+        if let Some(n) = positive(&mut breaker, "cooldown_seconds") {
+            config.cooldown_seconds = n;
+        }
+
+        self.errors.extend(breaker.finalize_empty());
+        Some(config)
+    }
+
+    pub fn ensure_rate_limit(&mut self) -> Option<RateLimitConfig> {
+        let mut positive = |key: &str| match self.ensure_int(key, false) {
+            Some((_, n, _)) if n > 0 => Some(n as u32),
+            Some((_, _, value_span)) => {
+                self.push_error(format!("{key} must be positive"), value_span);
+                None
+            }
+            None => None,
+        };
+        let config = RateLimitConfig {
+            max_concurrency: positive("max_concurrency"),
+            requests_per_minute: positive("requests_per_minute"),
+            tokens_per_minute: positive("tokens_per_minute").map(u64::from),
+        };
+        (config != RateLimitConfig::default()).then_some(config)
+    }
+
+    pub fn ensure_timeouts(&mut self) -> Option<TimeoutConfig> {
+        let mut positive = |key: &str| match self.ensure_int(key, false) {
+            Some((_, n, _)) if n > 0 => Some(n as u64),
+            Some((_, _, value_span)) => {
+                self.push_error(format!("{key} must be positive"), value_span);
+                None
+            }
+            None => None,
+        };
+        let config = TimeoutConfig {
+            connect_timeout_ms: positive("connect_timeout_ms"),
+            request_timeout_ms: positive("request_timeout_ms"),
+            time_to_first_token_timeout_ms: positive("time_to_first_token_timeout_ms"),
+            idle_timeout_ms: positive("idle_timeout_ms"),
+        };
+        (config != TimeoutConfig::default()).then_some(config)
+    }
+
+    /// Reads `forbid_coercions`, the parser coercions the responses of this
+    /// client may not need.
+    pub fn ensure_forbid_coercions(&mut self) -> Vec<Coercion> {
+        let Some((_, values, _)) = self.ensure_array("forbid_coercions", false) else {
+            return vec![];
+        };
+        let mut coercions = vec![];
+        for v in values {
+            let coercion = match v.as_str() {
+                Some(StringOr::Value(s)) => s.parse::<Coercion>().map_err(|e| e.to_string()),
+                _ => Err(format!(
+                    "values in forbid_coercions must be one of: {}",
+                    Coercion::allowed_values().join(", ")
+                )),
+            };
+            match coercion {
+                Ok(c) if !coercions.contains(&c) => coercions.push(c),
+                Ok(_) => {}
+                Err(e) => self.push_error(e, v.meta().clone()),
+            }
+        }
+        coercions
+    }
+
+    /// Reads `pricing`, what the tokens of this client cost.
+    pub fn ensure_pricing(&mut self) -> Option<Pricing> {
+        let (_, options, span) = self.ensure_map("pricing", false)?;
+        let mut pricing = PropertyHandler::new(options, span.clone());
+
+        let mut price = |key: &str| match pricing.options.shift_remove(key) {
+            Some((_, value)) => match value.as_numeric().map(|n| n.parse::<f64>()) {
+                Some(Ok(n)) if n >= 0.0 && n.is_finite() => Some(n),
+                _ => {
+                    pricing.push_error(
+                        format!("pricing {key} must be a non-negative number"),
+                        value.meta().clone(),
+                    );
+                    None
+                }
+            },
+            None => {
+                pricing.push_error(format!("pricing is missing {key}"), span.clone());
+                None
+            }
+        };
+        let input = price("input_per_million_tokens");
+        let output = price("output_per_million_tokens");
+
+        self.errors.extend(pricing.finalize_empty());
+        Some(Pricing {
+            input_per_million_tokens: input?,
+            output_per_million_tokens: output?,
+        })
+    }
+
+    /// Reads `max_context_tokens`, the largest prompt the model of this client
+    /// accepts.
+    pub fn ensure_max_context_tokens(&mut self) -> Option<u64> {
+        match self.ensure_int("max_context_tokens", false) {
+            Some((_, n, _)) if n > 0 => Some(n as u64),
+            Some((_, _, value_span)) => {
+                self.push_error("max_context_tokens must be positive", value_span);
+                None
+            }
+            None => None,
+        }
+    }
+
     pub fn finalize_empty(self) -> Vec<Error<Meta>> {
         let mut errors = self.errors;
         for (k, (key_span, _)) in self.options {
@@ -14,6 +14,7 @@ pub mod google_ai;
 pub mod openai;
 pub mod round_robin;
 pub mod vertex;
+pub mod weighted;
 
 #[derive(Debug)]
 /// `Meta` is a generic carrying span information, so that if it comes from a .baml file,
@@ -27,6 +28,7 @@ pub enum UnresolvedClientProperty<Meta> {
     GoogleAI(google_ai::UnresolvedGoogleAI<Meta>),
     RoundRobin(round_robin::UnresolvedRoundRobin<Meta>),
     Fallback(fallback::UnresolvedFallback<Meta>),
+    Weighted(weighted::UnresolvedWeighted<Meta>),
 }
 
 pub enum ResolvedClientProperty {
@@ -37,6 +39,7 @@ pub enum ResolvedClientProperty {
     GoogleAI(google_ai::ResolvedGoogleAI),
     RoundRobin(round_robin::ResolvedRoundRobin),
     Fallback(fallback::ResolvedFallback),
+    Weighted(weighted::ResolvedWeighted),
 }
 
 impl ResolvedClientProperty {
@@ -44,6 +47,7 @@ impl ResolvedClientProperty {
         match self {
             ResolvedClientProperty::RoundRobin(_) => "round-robin",
             ResolvedClientProperty::Fallback(_) => "fallback",
+            ResolvedClientProperty::Weighted(_) => "weighted",
             ResolvedClientProperty::OpenAI(_) => "openai",
             ResolvedClientProperty::Anthropic(_) => "anthropic",
             ResolvedClientProperty::AWSBedrock(_) => "aws-bedrock",
@@ -63,6 +67,7 @@ impl<Meta: Clone> UnresolvedClientProperty<Meta> {
             UnresolvedClientProperty::GoogleAI(g) => g.required_env_vars(),
             UnresolvedClientProperty::RoundRobin(r) => r.required_env_vars(),
             UnresolvedClientProperty::Fallback(f) => f.required_env_vars(),
+            UnresolvedClientProperty::Weighted(w) => w.required_env_vars(),
         }
     }
 
@@ -93,6 +98,9 @@ impl<Meta: Clone> UnresolvedClientProperty<Meta> {
             UnresolvedClientProperty::Fallback(f) => {
                 f.resolve(ctx).map(ResolvedClientProperty::Fallback)
             }
+            UnresolvedClientProperty::Weighted(w) => {
+                w.resolve(ctx).map(ResolvedClientProperty::Weighted)
+            }
         }
     }
 
@@ -119,6 +127,9 @@ impl<Meta: Clone> UnresolvedClientProperty<Meta> {
             UnresolvedClientProperty::Fallback(f) => {
                 UnresolvedClientProperty::Fallback(f.without_meta())
             }
+            UnresolvedClientProperty::Weighted(w) => {
+                UnresolvedClientProperty::Weighted(w.without_meta())
+            }
         }
     }
 }
@@ -187,6 +198,9 @@ impl crate::StrategyClientProvider {
             crate::StrategyClientProvider::RoundRobin => Ok(UnresolvedClientProperty::RoundRobin(
                 round_robin::UnresolvedRoundRobin::create_from(properties)?,
             )),
+            crate::StrategyClientProvider::Weighted => Ok(UnresolvedClientProperty::Weighted(
+                weighted::UnresolvedWeighted::create_from(properties)?,
+            )),
         }
     }
 }
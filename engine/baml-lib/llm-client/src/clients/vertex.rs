@@ -1,7 +1,8 @@
 use std::collections::HashSet;
 
 use crate::{
-    AllowedRoleMetadata, FinishReasonFilter, RolesSelection, SupportedRequestModes,
+    AllowedRoleMetadata, CacheConfig, CircuitBreakerConfig, Coercion, FinishReasonFilter,
+    OutputMode, Pricing, RateLimitConfig, RolesSelection, SupportedRequestModes, TimeoutConfig,
     UnresolvedAllowedRoleMetadata, UnresolvedFinishReasonFilter, UnresolvedRolesSelection,
 };
 use anyhow::Result;
@@ -158,6 +159,14 @@ pub struct UnresolvedVertex<Meta> {
     allowed_role_metadata: UnresolvedAllowedRoleMetadata,
     supported_request_modes: SupportedRequestModes,
     finish_reason_filter: UnresolvedFinishReasonFilter,
+    cache: Option<CacheConfig>,
+    circuit_breaker: Option<CircuitBreakerConfig>,
+    rate_limit: Option<RateLimitConfig>,
+    timeouts: Option<TimeoutConfig>,
+    forbid_coercions: Vec<Coercion>,
+    max_context_tokens: Option<u64>,
+    pricing: Option<Pricing>,
+    output_mode: OutputMode,
     properties: IndexMap<String, (Meta, UnresolvedValue<Meta>)>,
 }
 
@@ -178,6 +187,14 @@ pub struct ResolvedVertex {
     pub properties: IndexMap<String, serde_json::Value>,
     pub proxy_url: Option<String>,
     pub finish_reason_filter: FinishReasonFilter,
+    pub cache: Option<CacheConfig>,
+    pub circuit_breaker: Option<CircuitBreakerConfig>,
+    pub rate_limit: Option<RateLimitConfig>,
+    pub timeouts: Option<TimeoutConfig>,
+    pub forbid_coercions: Vec<Coercion>,
+    pub max_context_tokens: Option<u64>,
+    pub pricing: Option<Pricing>,
+    pub output_mode: OutputMode,
 }
 
 impl ResolvedVertex {
@@ -247,6 +264,14 @@ impl<Meta: Clone> UnresolvedVertex<Meta> {
                 .map(|(k, (_, v))| (k.clone(), ((), v.without_meta())))
                 .collect(),
             finish_reason_filter: self.finish_reason_filter.clone(),
+            cache: self.cache.clone(),
+            circuit_breaker: self.circuit_breaker.clone(),
+            rate_limit: self.rate_limit.clone(),
+            timeouts: self.timeouts.clone(),
+            forbid_coercions: self.forbid_coercions.clone(),
+            max_context_tokens: self.max_context_tokens,
+            pricing: self.pricing.clone(),
+            output_mode: self.output_mode,
         }
     }
 
@@ -286,6 +311,14 @@ impl<Meta: Clone> UnresolvedVertex<Meta> {
                 .collect::<Result<IndexMap<_, _>>>()?,
             proxy_url: super::helpers::get_proxy_url(ctx),
             finish_reason_filter: self.finish_reason_filter.resolve(ctx)?,
+            cache: self.cache.clone(),
+            circuit_breaker: self.circuit_breaker.clone(),
+            rate_limit: self.rate_limit.clone(),
+            timeouts: self.timeouts.clone(),
+            forbid_coercions: self.forbid_coercions.clone(),
+            max_context_tokens: self.max_context_tokens,
+            pricing: self.pricing.clone(),
+            output_mode: self.output_mode,
         })
     }
 
@@ -364,6 +397,18 @@ impl<Meta: Clone> UnresolvedVertex<Meta> {
         let supported_request_modes = properties.ensure_supported_request_modes();
         let headers = properties.ensure_headers().unwrap_or_default();
         let finish_reason_filter = properties.ensure_finish_reason_filter();
+        let cache = properties.ensure_cache();
+        let circuit_breaker = properties.ensure_circuit_breaker();
+        let rate_limit = properties.ensure_rate_limit();
+        let timeouts = properties.ensure_timeouts();
+        let forbid_coercions = properties.ensure_forbid_coercions();
+        let max_context_tokens = properties.ensure_max_context_tokens();
+        let pricing = properties.ensure_pricing();
+        let output_mode = properties.ensure_output_mode(&[
+            OutputMode::Prompt,
+            OutputMode::Tools,
+            OutputMode::JsonSchema,
+        ]);
 
         let (properties, errors) = properties.finalize();
         if !errors.is_empty() {
@@ -385,6 +430,14 @@ impl<Meta: Clone> UnresolvedVertex<Meta> {
             supported_request_modes,
             properties,
             finish_reason_filter,
+            cache,
+            circuit_breaker,
+            rate_limit,
+            timeouts,
+            forbid_coercions,
+            max_context_tokens,
+            pricing,
+            output_mode,
         })
     }
 }
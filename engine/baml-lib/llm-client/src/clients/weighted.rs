@@ -0,0 +1,92 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use baml_types::{EvaluationContext, StringOr};
+
+use crate::ClientSpec;
+
+use super::helpers::{Error, PropertyHandler};
+
+#[derive(Debug)]
+pub struct UnresolvedWeighted<Meta> {
+    pub strategy: Vec<(either::Either<StringOr, ClientSpec>, Meta)>,
+    weights: Option<Vec<f64>>,
+    adaptive: bool,
+}
+
+pub struct ResolvedWeighted {
+    pub strategy: Vec<ClientSpec>,
+    /// One weight per client in `strategy`.
+    pub weights: Vec<f64>,
+    /// Scale weights by the observed latency and error rate of each client.
+    pub adaptive: bool,
+}
+
+impl<Meta: Clone> UnresolvedWeighted<Meta> {
+    pub fn without_meta(&self) -> UnresolvedWeighted<()> {
+        UnresolvedWeighted {
+            strategy: self.strategy.iter().map(|(s, _)| (s.clone(), ())).collect(),
+            weights: self.weights.clone(),
+            adaptive: self.adaptive,
+        }
+    }
+
+    pub fn required_env_vars(&self) -> HashSet<String> {
+        self.strategy
+            .iter()
+            .flat_map(|(s, _)| match s {
+                either::Either::Left(s) => s.required_env_vars(),
+                either::Either::Right(_) => Default::default(),
+            })
+            .collect()
+    }
+
+    pub fn resolve(&self, ctx: &EvaluationContext<'_>) -> Result<ResolvedWeighted> {
+        let strategy = self
+            .strategy
+            .iter()
+            .map(|(s, _)| match s {
+                either::Either::Left(s) => ClientSpec::new_from_id(s.resolve(ctx)?.as_str()),
+                either::Either::Right(s) => Ok(s.clone()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let weights = self
+            .weights
+            .clone()
+            .unwrap_or_else(|| vec![1.0; strategy.len()]);
+
+        Ok(ResolvedWeighted {
+            strategy,
+            weights,
+            adaptive: self.adaptive,
+        })
+    }
+
+    pub fn create_from(mut properties: PropertyHandler<Meta>) -> Result<Self, Vec<Error<Meta>>> {
+        let strategy = properties.ensure_strategy();
+        let weights = properties.ensure_weights(strategy.as_ref().map(Vec::len));
+        let adaptive = properties
+            .ensure_bool("adaptive", false)
+            .map(|(_, v, _)| v)
+            .unwrap_or(false);
+        let errors = properties.finalize_empty();
+
+        if !errors.is_empty() {
+            return Err(errors);
+        }
+
+        let strategy = strategy.expect("strategy is required");
+
+        Ok(Self {
+            strategy,
+            weights,
+            adaptive,
+        })
+    }
+}
+
+impl<Meta> super::StrategyClientProperty<Meta> for UnresolvedWeighted<Meta> {
+    fn strategy(&self) -> &Vec<(either::Either<StringOr, ClientSpec>, Meta)> {
+        &self.strategy
+    }
+}
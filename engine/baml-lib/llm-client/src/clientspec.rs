@@ -65,6 +65,8 @@ pub enum StrategyClientProvider {
     RoundRobin,
     /// The fallback strategy client provider variant
     Fallback,
+    /// The weighted strategy client provider variant
+    Weighted,
 }
 
 impl std::fmt::Display for ClientProvider {
@@ -96,6 +98,7 @@ impl std::fmt::Display for StrategyClientProvider {
         match self {
             StrategyClientProvider::RoundRobin => write!(f, "round-robin"),
             StrategyClientProvider::Fallback => write!(f, "fallback"),
+            StrategyClientProvider::Weighted => write!(f, "weighted"),
         }
     }
 }
@@ -121,6 +124,7 @@ impl std::str::FromStr for ClientProvider {
             "baml-fallback" => Ok(ClientProvider::Strategy(StrategyClientProvider::Fallback)),
             "round-robin" => Ok(ClientProvider::Strategy(StrategyClientProvider::RoundRobin)),
             "baml-round-robin" => Ok(ClientProvider::Strategy(StrategyClientProvider::RoundRobin)),
+            "weighted" => Ok(ClientProvider::Strategy(StrategyClientProvider::Weighted)),
             _ => Err(anyhow::anyhow!("Invalid client provider: {}", s)),
         }
     }
@@ -150,6 +154,7 @@ impl std::str::FromStr for StrategyClientProvider {
         match s {
             "round-robin" => Ok(StrategyClientProvider::RoundRobin),
             "fallback" => Ok(StrategyClientProvider::Fallback),
+            "weighted" => Ok(StrategyClientProvider::Weighted),
             _ => Err(anyhow::anyhow!(
                 "Invalid strategy client provider variant: {}",
                 s
@@ -168,11 +173,82 @@ impl ClientProvider {
             "ollama",
             "round-robin",
             "fallback",
+            "weighted",
             "google-ai",
             "vertex-ai",
             "aws-bedrock",
         ]
     }
+
+    /// The options this provider reads, for editor completions. LLM
+    /// providers also send any other option, like `temperature`, to the model
+    /// as it is.
+    pub fn known_options(&self) -> Vec<&'static str> {
+        const LLM_OPTIONS: &[&str] = &[
+            "model",
+            "temperature",
+            "max_tokens",
+            "headers",
+            "allowed_roles",
+            "default_role",
+            "allowed_role_metadata",
+            "supports_streaming",
+            "finish_reason_allow_list",
+            "finish_reason_deny_list",
+            "cache",
+            "circuit_breaker",
+            "max_concurrency",
+            "requests_per_minute",
+            "tokens_per_minute",
+            "connect_timeout_ms",
+            "request_timeout_ms",
+            "time_to_first_token_timeout_ms",
+            "idle_timeout_ms",
+            "forbid_coercions",
+            "max_context_tokens",
+            "pricing",
+        ];
+        let specific: &[&str] = match self {
+            ClientProvider::OpenAI(OpenAIClientProviderVariant::Azure) => &[
+                "resource_name",
+                "deployment_id",
+                "api_version",
+                "api_key",
+                "base_url",
+                "output_mode",
+            ],
+            ClientProvider::OpenAI(_) | ClientProvider::Anthropic | ClientProvider::GoogleAi => {
+                &["api_key", "base_url", "output_mode"]
+            }
+            ClientProvider::Vertex => &[
+                "project_id",
+                "location",
+                "credentials",
+                "credentials_content",
+                "base_url",
+                "output_mode",
+            ],
+            ClientProvider::AwsBedrock => &[
+                "model_id",
+                "region",
+                "access_key_id",
+                "secret_access_key",
+                "session_token",
+                "profile",
+                "inference_configuration",
+            ],
+            ClientProvider::Strategy(StrategyClientProvider::Fallback) => {
+                return vec!["strategy", "retry_on"]
+            }
+            ClientProvider::Strategy(StrategyClientProvider::RoundRobin) => {
+                return vec!["strategy", "start"]
+            }
+            ClientProvider::Strategy(StrategyClientProvider::Weighted) => {
+                return vec!["strategy", "weights", "adaptive"]
+            }
+        };
+        specific.iter().chain(LLM_OPTIONS).copied().collect()
+    }
 }
 
 impl std::fmt::Display for ClientSpec {
@@ -268,6 +344,359 @@ impl FinishReasonFilter {
     }
 }
 
+/// The kinds of failures that make a retry policy or fallback strategy move on
+/// to its next attempt instead of returning the result.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize)]
+pub struct RetryOn {
+    /// The request failed, or the provider returned a non-2xx response.
+    pub http: bool,
+    /// When set, `http` only applies to responses with one of these status
+    /// codes. Requests that failed without a response are still retried.
+    pub status_codes: Option<Vec<u16>>,
+    /// The model responded, but the output could not be parsed.
+    pub parse_error: bool,
+    /// The model output parsed, but an `@assert` failed.
+    pub assert_failed: bool,
+}
+
+impl Default for RetryOn {
+    fn default() -> Self {
+        Self {
+            http: true,
+            status_codes: None,
+            parse_error: false,
+            assert_failed: false,
+        }
+    }
+}
+
+impl RetryOn {
+    pub fn allowed_values() -> &'static [&'static str] {
+        &["http", "parse_error", "assert_failed"]
+    }
+
+    /// Build a `RetryOn` from an explicit list of conditions. Conditions that are
+    /// not listed are disabled, including `http`.
+    pub fn from_values<'a>(values: impl IntoIterator<Item = &'a str>) -> Result<Self> {
+        let mut retry_on = Self {
+            http: false,
+            status_codes: None,
+            parse_error: false,
+            assert_failed: false,
+        };
+        for value in values {
+            match value {
+                "http" => retry_on.http = true,
+                "parse_error" => retry_on.parse_error = true,
+                "assert_failed" => retry_on.assert_failed = true,
+                other => anyhow::bail!(
+                    "Invalid retry_on value: {}. Allowed values are: {}",
+                    other,
+                    Self::allowed_values().join(", ")
+                ),
+            }
+        }
+        Ok(retry_on)
+    }
+
+    pub fn union(self, other: Self) -> Self {
+        let status_codes = match (self.http, other.http) {
+            (true, true) => match (self.status_codes, other.status_codes) {
+                (Some(mut a), Some(b)) => {
+                    a.extend(b);
+                    a.sort_unstable();
+                    a.dedup();
+                    Some(a)
+                }
+                _ => None,
+            },
+            (true, false) => self.status_codes,
+            (false, true) => other.status_codes,
+            (false, false) => None,
+        };
+        Self {
+            http: self.http || other.http,
+            status_codes,
+            parse_error: self.parse_error || other.parse_error,
+            assert_failed: self.assert_failed || other.assert_failed,
+        }
+    }
+
+    /// Whether a failed request should be retried. `status` is the HTTP
+    /// status code of the response, if there was one.
+    pub fn http_status(&self, status: Option<u16>) -> bool {
+        match (&self.status_codes, status) {
+            _ if !self.http => false,
+            (Some(codes), Some(status)) => codes.contains(&status),
+            _ => true,
+        }
+    }
+}
+
+/// How a function's return type is communicated to the model.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputMode {
+    /// The schema is rendered into the prompt through `ctx.output_format`.
+    #[default]
+    Prompt,
+    /// The return type is sent as native tool definitions, and the model's
+    /// tool call arguments are parsed as the response.
+    Tools,
+    /// The return type is sent as a JSON schema the provider constrains the
+    /// response to, like OpenAI's `response_format`. The response is still
+    /// parsed, to map aliases and run checks.
+    JsonSchema,
+}
+
+impl OutputMode {
+    pub fn allowed_values() -> &'static [&'static str] {
+        &["prompt", "tools", "json_schema"]
+    }
+}
+
+impl std::str::FromStr for OutputMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "prompt" => Ok(Self::Prompt),
+            "tools" => Ok(Self::Tools),
+            "json_schema" => Ok(Self::JsonSchema),
+            other => anyhow::bail!(
+                "Invalid output_mode: {}. Allowed values are: {}",
+                other,
+                Self::allowed_values().join(", ")
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for OutputMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Prompt => write!(f, "prompt"),
+            Self::Tools => write!(f, "tools"),
+            Self::JsonSchema => write!(f, "json_schema"),
+        }
+    }
+}
+
+/// A way the parser bends a response to fit the return type. Clients can
+/// forbid some of them with `forbid_coercions`, so that responses needing
+/// them fail to parse instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Coercion {
+    /// The value was taken from a markdown code block.
+    Markdown,
+    /// The value was found in between other text.
+    JsonInText,
+    /// The JSON was malformed, like with unquoted keys or missing brackets.
+    FixedJson,
+    /// Several values were collected into an array.
+    InferredArray,
+    /// An object or array was turned into a string.
+    ObjectToString,
+    /// An object with a single key was turned into its value.
+    ObjectToPrimitive,
+    /// An object had keys that aren't fields of the class.
+    ExtraKey,
+    /// A value was used for a missing field.
+    ImpliedKey,
+    /// An object was built out of a value that isn't one.
+    InferredObject,
+    /// A single value was turned into an array.
+    SingleToArray,
+    /// An enum or literal was matched from a string that only contains it.
+    SubstringMatch,
+    /// An enum or literal was matched after dropping punctuation.
+    StrippedNonAlphanumeric,
+    /// A string, like `"True"`, was turned into a bool.
+    StringToBool,
+    /// A number was pulled out of a string, like `"1,000"`.
+    StringToFloat,
+    /// A float was rounded to an int.
+    FloatToInt,
+    /// A missing value was replaced with a default, like `null` or `[]`.
+    MissingValue,
+    /// A value that didn't parse was replaced with `null`.
+    DroppedValue,
+    /// An array item or map entry that didn't parse was left out.
+    SkippedItem,
+}
+
+impl Coercion {
+    pub const ALL: [Coercion; 18] = [
+        Self::Markdown,
+        Self::JsonInText,
+        Self::FixedJson,
+        Self::InferredArray,
+        Self::ObjectToString,
+        Self::ObjectToPrimitive,
+        Self::ExtraKey,
+        Self::ImpliedKey,
+        Self::InferredObject,
+        Self::SingleToArray,
+        Self::SubstringMatch,
+        Self::StrippedNonAlphanumeric,
+        Self::StringToBool,
+        Self::StringToFloat,
+        Self::FloatToInt,
+        Self::MissingValue,
+        Self::DroppedValue,
+        Self::SkippedItem,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Markdown => "markdown",
+            Self::JsonInText => "json_in_text",
+            Self::FixedJson => "fixed_json",
+            Self::InferredArray => "inferred_array",
+            Self::ObjectToString => "object_to_string",
+            Self::ObjectToPrimitive => "object_to_primitive",
+            Self::ExtraKey => "extra_key",
+            Self::ImpliedKey => "implied_key",
+            Self::InferredObject => "inferred_object",
+            Self::SingleToArray => "single_to_array",
+            Self::SubstringMatch => "substring_match",
+            Self::StrippedNonAlphanumeric => "stripped_non_alphanumeric",
+            Self::StringToBool => "string_to_bool",
+            Self::StringToFloat => "string_to_float",
+            Self::FloatToInt => "float_to_int",
+            Self::MissingValue => "missing_value",
+            Self::DroppedValue => "dropped_value",
+            Self::SkippedItem => "skipped_item",
+        }
+    }
+
+    pub fn allowed_values() -> Vec<&'static str> {
+        Self::ALL.iter().map(Self::as_str).collect()
+    }
+}
+
+impl std::str::FromStr for Coercion {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match Self::ALL.into_iter().find(|c| c.as_str() == s) {
+            Some(coercion) => Ok(coercion),
+            None => anyhow::bail!(
+                "Invalid forbid_coercions value: {}. Allowed values are: {}",
+                s,
+                Self::allowed_values().join(", ")
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for Coercion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Response caching of a client, configured with `cache { ... }`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CacheConfig {
+    pub store: CacheStore,
+    /// Entries older than this are treated as misses. Unset means forever.
+    pub ttl_seconds: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CacheStore {
+    /// An in-process LRU holding at most `max_entries` responses.
+    Memory { max_entries: usize },
+    /// One file per response in the `path` directory, shared across runs.
+    Disk { path: String },
+}
+
+impl CacheStore {
+    pub const DEFAULT_MAX_ENTRIES: usize = 1000;
+    pub const DEFAULT_PATH: &'static str = ".baml_cache";
+
+    pub fn allowed_values() -> &'static [&'static str] {
+        &["memory", "disk"]
+    }
+}
+
+/// Process-wide circuit breaker of a client, configured with
+/// `circuit_breaker { ... }`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit.
+    pub failure_threshold: u32,
+    /// Failures further apart than this don't count as consecutive.
+    pub window_seconds: u64,
+    /// How long the circuit stays open before a probe call is let through.
+    pub cooldown_seconds: u64,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 5,
+            window_seconds: 60,
+            cooldown_seconds: 30,
+        }
+    }
+}
+
+/// Client-side limits on the calls made to a client, configured with
+/// `max_concurrency`, `requests_per_minute` and `tokens_per_minute`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RateLimitConfig {
+    /// Calls in flight at the same time.
+    pub max_concurrency: Option<u32>,
+    /// Calls started in any sliding one minute window.
+    pub requests_per_minute: Option<u32>,
+    /// Prompt and output tokens used in any sliding one minute window.
+    pub tokens_per_minute: Option<u64>,
+}
+
+/// Timeouts of a client, configured with `connect_timeout_ms`,
+/// `request_timeout_ms`, `time_to_first_token_timeout_ms` and
+/// `idle_timeout_ms`.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct TimeoutConfig {
+    /// Establishing the connection to the provider.
+    pub connect_timeout_ms: Option<u64>,
+    /// The whole call, including reading the full stream.
+    pub request_timeout_ms: Option<u64>,
+    /// From sending the request until the first chunk of a stream, or the
+    /// whole response of a non-streaming call.
+    pub time_to_first_token_timeout_ms: Option<u64>,
+    /// Between two chunks of a stream.
+    pub idle_timeout_ms: Option<u64>,
+}
+
+/// What the tokens of a model cost, configured with `pricing { ... }` on a
+/// client or per model in the client registry.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Pricing {
+    /// US dollars per million prompt tokens.
+    pub input_per_million_tokens: f64,
+    /// US dollars per million output tokens.
+    pub output_per_million_tokens: f64,
+}
+
+impl Pricing {
+    /// The cost of a call in US dollars, or `None` if the provider didn't
+    /// report how many tokens it used.
+    pub fn cost(&self, prompt_tokens: Option<u64>, output_tokens: Option<u64>) -> Option<f64> {
+        if prompt_tokens.is_none() && output_tokens.is_none() {
+            return None;
+        }
+        let tokens = |n: Option<u64>, per_million: f64| n.unwrap_or(0) as f64 * per_million / 1e6;
+        Some(
+            tokens(prompt_tokens, self.input_per_million_tokens)
+                + tokens(output_tokens, self.output_per_million_tokens),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct UnresolvedRolesSelection {
     pub allowed: Option<Vec<StringOr>>,
@@ -406,3 +835,31 @@ impl AllowedRoleMetadata {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Pricing;
+
+    const PRICING: Pricing = Pricing {
+        input_per_million_tokens: 3.0,
+        output_per_million_tokens: 15.0,
+    };
+
+    #[test]
+    fn prompt_and_output_tokens_have_their_own_rate() {
+        assert_eq!(PRICING.cost(Some(1_000_000), Some(0)), Some(3.0));
+        assert_eq!(PRICING.cost(Some(0), Some(1_000_000)), Some(15.0));
+        assert_eq!(PRICING.cost(Some(2_000_000), Some(1_000_000)), Some(21.0));
+    }
+
+    #[test]
+    fn missing_token_counts_cost_nothing() {
+        assert_eq!(PRICING.cost(Some(1_000_000), None), Some(3.0));
+        assert_eq!(PRICING.cost(None, Some(1_000_000)), Some(15.0));
+    }
+
+    #[test]
+    fn unknown_usage_has_no_cost() {
+        assert_eq!(PRICING.cost(None, None), None);
+    }
+}
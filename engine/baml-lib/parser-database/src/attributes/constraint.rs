@@ -61,6 +61,7 @@ pub fn attribute_as_constraint(
         label,
         expression,
         level,
+        validator: None,
     };
     (Some((constraint, span, expr_span)), datamodel_errors)
 }
@@ -120,5 +121,6 @@ pub(super) fn visit_constraint_attributes(
         level,
         expression,
         label,
+        validator: None,
     });
 }
@@ -0,0 +1,174 @@
+use baml_types::{LiteralValue, StringOr, TypeValue, UnresolvedValue};
+use internal_baml_diagnostics::{DatamodelError, Span};
+use internal_baml_schema_ast::ast::{Field, FieldType, TopId, WithName};
+
+use crate::{context::Context, types::Attributes};
+
+/// `@default(value)` on a class field, the value used when the LLM leaves the
+/// field out of its response.
+pub(super) fn visit_default_attribute(
+    attributes: &mut Attributes,
+    field: &Field<FieldType>,
+    ctx: &mut Context<'_>,
+) {
+    let value = match ctx.visit_default_arg_with_idx("default") {
+        Ok((_, value)) => value,
+        Err(err) => return ctx.push_error(err),
+    };
+    let Some(value) = value.to_unresolved_value(ctx.diagnostics) else {
+        return;
+    };
+    if !value.required_env_vars().is_empty() || is_expression(&value) {
+        return ctx.push_error(DatamodelError::new_attribute_validation_error(
+            "Default values must be constants, not environment variables or expressions.",
+            "@default",
+            value.meta().clone(),
+        ));
+    }
+    let Some(field_type) = &field.expr else {
+        return;
+    };
+    if let Err(span) = check_value(&value, field_type, ctx) {
+        return ctx.push_error(DatamodelError::new_attribute_validation_error(
+            "The default value does not match the type of the field.",
+            "@default",
+            span,
+        ));
+    }
+    attributes.default_value = Some(value);
+}
+
+fn is_expression(value: &UnresolvedValue<Span>) -> bool {
+    match value {
+        UnresolvedValue::String(StringOr::JinjaExpression(_), _) => true,
+        UnresolvedValue::Array(values, _) => values.iter().any(is_expression),
+        UnresolvedValue::Map(entries, _) => entries.values().any(|(_, v)| is_expression(v)),
+        _ => false,
+    }
+}
+
+/// Checks that `value` is a value of `field_type`. Returns the span of the
+/// part of the value that isn't.
+fn check_value(
+    value: &UnresolvedValue<Span>,
+    field_type: &FieldType,
+    ctx: &Context<'_>,
+) -> Result<(), Span> {
+    let mismatch = || Err(value.meta().clone());
+    if let UnresolvedValue::Null(_) = value {
+        if field_type.is_optional() {
+            return Ok(());
+        }
+    }
+
+    match (field_type, value) {
+        (FieldType::Primitive(_, TypeValue::String, ..), UnresolvedValue::String(..)) => Ok(()),
+        (FieldType::Primitive(_, TypeValue::Int, ..), UnresolvedValue::Numeric(n, _))
+            if n.parse::<i64>().is_ok() =>
+        {
+            Ok(())
+        }
+        (FieldType::Primitive(_, TypeValue::Float, ..), UnresolvedValue::Numeric(..)) => Ok(()),
+        (FieldType::Primitive(_, TypeValue::Bool, ..), UnresolvedValue::Bool(..)) => Ok(()),
+        (FieldType::Primitive(_, TypeValue::Null, ..), UnresolvedValue::Null(..)) => Ok(()),
+
+        (FieldType::Literal(_, literal, ..), value) => {
+            let matches = match (literal, value) {
+                (LiteralValue::String(s), UnresolvedValue::String(StringOr::Value(v), _)) => s == v,
+                (LiteralValue::Int(i), UnresolvedValue::Numeric(n, _)) => {
+                    n.parse::<i64>().is_ok_and(|n| n == *i)
+                }
+                (LiteralValue::Bool(b), UnresolvedValue::Bool(v, _)) => b == v,
+                _ => false,
+            };
+            if matches {
+                Ok(())
+            } else {
+                mismatch()
+            }
+        }
+
+        (FieldType::List(arity, item_type, dims, span, _), UnresolvedValue::Array(items, _)) => {
+            let item_type = match dims {
+                1 => item_type.as_ref().clone(),
+                _ => FieldType::List(*arity, item_type.clone(), dims - 1, span.clone(), None),
+            };
+            items
+                .iter()
+                .try_for_each(|item| check_value(item, &item_type, ctx))
+        }
+
+        (FieldType::Map(_, kv, ..), UnresolvedValue::Map(entries, _)) => {
+            let (key_type, value_type) = kv.as_ref();
+            entries.iter().try_for_each(|(key, (key_span, value))| {
+                let key = UnresolvedValue::String(StringOr::Value(key.clone()), key_span.clone());
+                check_value(&key, key_type, ctx)?;
+                check_value(value, value_type, ctx)
+            })
+        }
+
+        (FieldType::Union(_, options, ..), value) => {
+            if options
+                .iter()
+                .any(|option| check_value(value, option, ctx).is_ok())
+            {
+                Ok(())
+            } else {
+                mismatch()
+            }
+        }
+
+        (FieldType::Symbol(_, identifier, _, _), value) => {
+            let top = ctx
+                .interner
+                .lookup(identifier.name())
+                .and_then(|name| ctx.names.tops.get(&name));
+            match (top, value) {
+                (Some(TopId::Enum(enum_id)), UnresolvedValue::String(StringOr::Value(v), _)) => {
+                    if ctx.ast[*enum_id].fields.iter().any(|f| f.name() == v) {
+                        Ok(())
+                    } else {
+                        mismatch()
+                    }
+                }
+                (Some(TopId::Class(class_id)), UnresolvedValue::Map(entries, span)) => {
+                    let fields = ctx
+                        .types
+                        .class_fields(ctx.ast, *class_id)
+                        .into_iter()
+                        .map(|(class_id, field_id)| &ctx.ast[class_id][field_id])
+                        .collect::<Vec<_>>();
+                    for (key, (key_span, value)) in entries {
+                        match fields.iter().find(|f| f.name() == key) {
+                            Some(Field {
+                                expr: Some(field_type),
+                                ..
+                            }) => check_value(value, field_type, ctx)?,
+                            _ => return Err(key_span.clone()),
+                        }
+                    }
+                    // Fields left out must be able to get a value of their own.
+                    let complete = fields.iter().all(|f| {
+                        entries.contains_key(f.name())
+                            || f.expr.as_ref().is_some_and(FieldType::is_optional)
+                            || f.attributes.iter().any(|a| a.name() == "default")
+                    });
+                    if complete {
+                        Ok(())
+                    } else {
+                        Err(span.clone())
+                    }
+                }
+                (Some(TopId::TypeAlias(alias_id)), value) => {
+                    match ctx.types.resolved_type_aliases.get(alias_id) {
+                        Some(resolved) => check_value(value, resolved, ctx),
+                        None => mismatch(),
+                    }
+                }
+                _ => mismatch(),
+            }
+        }
+
+        _ => mismatch(),
+    }
+}
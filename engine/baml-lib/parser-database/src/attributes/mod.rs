@@ -5,11 +5,12 @@ use internal_baml_schema_ast::ast::{
 
 mod alias;
 pub mod constraint;
+mod default;
 mod description;
 mod to_string_attribute;
 use crate::interner::StringId;
 use crate::{context::Context, types::ClassAttributes, types::EnumAttributes};
-use baml_types::{Constraint, UnresolvedValue};
+use baml_types::{Constraint, UnresolvedValue, Validator};
 use internal_baml_schema_ast::ast::{Expression, SubType};
 
 /// Node attributes.
@@ -38,6 +39,9 @@ pub struct Attributes {
 
    /// Whether the node has a `@stream.with_state` attribute.
     pub streaming_state: Option<bool>,
+
+    /// Value of a class field when the LLM leaves it out, from `@default`.
+    pub default_value: Option<UnresolvedValue<Span>>,
 }
 
 impl Attributes {
@@ -129,7 +133,16 @@ fn resolve_type_exp_block_attributes<'db>(
 
             for (field_idx, field) in ast_typexpr.iter_fields() {
                 ctx.assert_all_attributes_processed((type_id, field_idx).into());
-                if let Some(attrs) = to_string_attribute::visit(ctx, &field.span, false) {
+                let mut attrs = to_string_attribute::visit(ctx, &field.span, false);
+                if ctx.visit_optional_single_attr("default") {
+                    default::visit_default_attribute(
+                        attrs.get_or_insert_with(Default::default),
+                        field,
+                        ctx,
+                    );
+                    ctx.validate_visited_arguments();
+                }
+                if let Some(attrs) = attrs {
                     class_attributes.field_serilizers.insert(field_idx, attrs);
                 }
                 ctx.validate_visited_attributes();
@@ -176,6 +189,14 @@ fn resolve_type_alias_attributes<'db>(
         // checks and asserts.
         let type_alias_attributes = to_string_attribute::visit(ctx, assignment.value.span(), false);
 
+        // Validators are checked and collected from the type itself.
+        if ctx
+            .visit_repeated_attr_from_names(&Validator::ATTRIBUTE_NAMES)
+            .is_some()
+        {
+            ctx.discard_arguments();
+        }
+
         // Some additional specific validation for type alias attributes.
         if let Some(attrs) = &type_alias_attributes {
             if attrs.dynamic_type().is_some()
@@ -44,7 +44,7 @@ use internal_baml_schema_ast::ast::{FieldType, SchemaAst, ValExpId, WithName};
 pub use tarjan::Tarjan;
 pub use types::{
     Attributes, ClientProperties, ContantDelayStrategy, ExponentialBackoffStrategy, PrinterType,
-    PromptAst, PromptVariable, RetryPolicy, RetryPolicyStrategy, StaticType,
+    PromptAst, PromptVariable, RetryJitter, RetryPolicy, RetryPolicyStrategy, StaticType,
 };
 pub use walkers::TypeWalker;
 
@@ -177,22 +177,27 @@ impl ParserDatabase {
                     Some(TypeWalker::Enum(_)) => {}
                     // Gotta resolve type aliases.
                     Some(TypeWalker::TypeAlias(alias)) => {
-                        resolved_deps.extend(alias.resolved().flat_idns().iter().filter_map(
-                            |ident| {
-                                match self.find_type_by_str(ident.name()) {
-                                    Some(TypeWalker::Class(cls)) => Some(cls.id),
-                                    // Enums are not part of the dependency
-                                    // graph because they can't depend on other
-                                    // enums.
-                                    Some(TypeWalker::Enum(_)) => None,
-                                    // Skip this one, recursive type aliases are
-                                    // not part of the finite class cycle. They
-                                    // are handled separately.
-                                    Some(TypeWalker::TypeAlias(alias)) => None,
-                                    None => panic!("Unknown class `{dep}`"),
-                                }
-                            },
-                        ))
+                        resolved_deps.extend(
+                            alias
+                                .resolved()
+                                .flat_idns()
+                                .iter()
+                                .filter(|ident| !alias.is_type_param(ident.name()))
+                                .filter_map(|ident| {
+                                    match self.find_type_by_str(ident.name()) {
+                                        Some(TypeWalker::Class(cls)) => Some(cls.id),
+                                        // Enums are not part of the dependency
+                                        // graph because they can't depend on other
+                                        // enums.
+                                        Some(TypeWalker::Enum(_)) => None,
+                                        // Skip this one, recursive type aliases are
+                                        // not part of the finite class cycle. They
+                                        // are handled separately.
+                                        Some(TypeWalker::TypeAlias(alias)) => None,
+                                        None => panic!("Unknown class `{dep}`"),
+                                    }
+                                }),
+                        )
                     }
                     None => panic!("Unknown class `{dep}`"),
                 }
@@ -252,6 +257,10 @@ impl ParserDatabase {
                 // add their dependencies as well.
                 Some(TypeWalker::TypeAlias(walker)) => {
                     stack.extend(walker.resolved().flat_idns().iter().filter_map(|ident| {
+                        // Type parameters of generic aliases are not types.
+                        if walker.is_type_param(ident.name()) {
+                            return None;
+                        }
                         // Add the resolved name itself to the deps.
                         collected_deps.insert(ident.name().to_owned());
                         // If the type is an alias then don't recurse.
@@ -696,7 +705,7 @@ mod test {
             panic!("expected a list type, got {resolved:?}");
         };
 
-        let FieldType::Symbol(_, ident, _) = &**inner else {
+        let FieldType::Symbol(_, ident, _, _) = &**inner else {
             panic!("expected a symbol type, got {inner:?}");
         };
 
@@ -4,6 +4,7 @@ use internal_baml_diagnostics::{DatamodelError, DatamodelWarning, Span};
 use internal_baml_schema_ast::ast::{
     Attribute, ValExpId, ValueExprBlock, WithIdentifier, WithName, WithSpan,
 };
+use internal_llm_client::RetryOn;
 use regex::Regex;
 use std::{collections::HashSet, ops::Deref};
 
@@ -11,7 +12,8 @@ use crate::attributes::constraint::attribute_as_constraint;
 use crate::{coerce, coerce_array, coerce_expression::coerce_map, context::Context};
 
 use super::{
-    Attributes, ContantDelayStrategy, ExponentialBackoffStrategy, RetryPolicy, RetryPolicyStrategy,
+    Attributes, ContantDelayStrategy, ExponentialBackoffStrategy, RetryJitter, RetryPolicy,
+    RetryPolicyStrategy,
 };
 
 fn dedent(s: &str) -> String {
@@ -44,9 +46,14 @@ pub(crate) fn visit_retry_policy<'db>(
     let mut max_reties = None;
 
     let mut strategy = Some(RetryPolicyStrategy::ConstantDelay(
-        super::ContantDelayStrategy { delay_ms: 200 },
+        super::ContantDelayStrategy {
+            delay_ms: 200,
+            jitter: RetryJitter::None,
+        },
     ));
     let mut options = None;
+    let mut retry_on = RetryOn::default();
+    let mut status_codes = None;
 
     config
         .iter_fields()
@@ -71,6 +78,33 @@ pub(crate) fn visit_retry_policy<'db>(
                     }
                 }
             }
+            ("retry_on", Some(val)) => {
+                if let Some(values) = coerce_array(val, &coerce::string_with_span, ctx.diagnostics)
+                {
+                    match RetryOn::from_values(values.iter().map(|(v, _)| *v)) {
+                        Ok(values) => retry_on = values,
+                        Err(e) => ctx.push_error(DatamodelError::new_validation_error(
+                            &e.to_string(),
+                            val.span().clone(),
+                        )),
+                    }
+                }
+            }
+            ("retry_on_status", Some(val)) => {
+                if let Some(values) = coerce_array(val, &coerce::integer, ctx.diagnostics) {
+                    match values
+                        .into_iter()
+                        .map(|v| u16::try_from(v).ok().filter(|v| (100..600).contains(v)))
+                        .collect::<Option<Vec<_>>>()
+                    {
+                        Some(codes) => status_codes = Some(codes),
+                        None => ctx.push_error(DatamodelError::new_validation_error(
+                            "values in retry_on_status must be HTTP status codes between 100 and 599",
+                            val.span().clone(),
+                        )),
+                    }
+                }
+            }
             ("options", Some(val)) => match val.to_unresolved_value(ctx.diagnostics) {
                 Some(UnresolvedValue::<Span>::Map(kv, _)) => options = Some(kv),
                 Some(other) => {
@@ -84,9 +118,17 @@ pub(crate) fn visit_retry_policy<'db>(
             (name, Some(_)) => ctx.push_error(DatamodelError::new_property_not_known_error(
                 name,
                 f.identifier().span().clone(),
-                ["max_retries", "strategy", "options"].to_vec(),
+                [
+                    "max_retries",
+                    "strategy",
+                    "retry_on",
+                    "retry_on_status",
+                    "options",
+                ]
+                .to_vec(),
             )),
         });
+    retry_on.status_codes = status_codes;
     match (max_reties, strategy) {
         (Some(max_retries), Some(strategy)) => {
             ctx.types.retry_policies.insert(
@@ -94,6 +136,7 @@ pub(crate) fn visit_retry_policy<'db>(
                 RetryPolicy {
                     max_retries,
                     strategy,
+                    retry_on,
                     options,
                 },
             );
@@ -121,6 +164,7 @@ fn visit_strategy(
     let mut delay_ms = None;
     let mut max_delay_ms = None;
     let mut multiplier = None;
+    let mut jitter = None;
 
     val.iter()
         .for_each(|(name_and_span, val)| match name_and_span.0 {
@@ -144,6 +188,19 @@ fn visit_strategy(
                     multiplier = Some((_val, val.span()))
                 }
             }
+            "jitter" => {
+                if let Some((value, span)) = coerce::string_with_span(val, diagnostics) {
+                    match value.parse::<RetryJitter>() {
+                        Ok(value) => jitter = Some((value, span)),
+                        Err(e) => diagnostics.push_error(
+                            internal_baml_diagnostics::DatamodelError::new_validation_error(
+                                &e.to_string(),
+                                span.clone(),
+                            ),
+                        ),
+                    }
+                }
+            }
             _ => {}
         });
 
@@ -165,8 +222,17 @@ fn visit_strategy(
                 ),
             )
             }
+            if let Some((RetryJitter::Decorrelated, span)) = jitter {
+                diagnostics.push_error(
+                internal_baml_diagnostics::DatamodelError::new_validation_error(
+                    "The `decorrelated` jitter is not supported for the `constant_delay` strategy",
+                    span.clone(),
+                ),
+            )
+            }
             Some(RetryPolicyStrategy::ConstantDelay(ContantDelayStrategy {
                 delay_ms: delay_ms.unwrap_or(200) as u32,
+                jitter: jitter.map(|(v, _)| v).unwrap_or_default(),
             }))
         }
         Some(("exponential_backoff", _)) => Some(RetryPolicyStrategy::ExponentialBackoff(
@@ -174,6 +240,7 @@ fn visit_strategy(
                 delay_ms: delay_ms.unwrap_or(200) as u32,
                 multiplier: multiplier.map(|(v, _)| v as f32).unwrap_or(1.5),
                 max_delay_ms: max_delay_ms.map(|(v, _)| v as u32).unwrap_or(10000),
+                jitter: jitter.map(|(v, _)| v).unwrap_or_default(),
             },
         )),
         Some((name, span)) => {
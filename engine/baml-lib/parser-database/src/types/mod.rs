@@ -4,7 +4,7 @@ use std::hash::Hash;
 use std::ops::Deref;
 
 use crate::types::configurations::visit_test_case;
-use crate::{coerce, ParserDatabase, Tarjan};
+use crate::{coerce, coerce_array, ParserDatabase, Tarjan};
 use crate::{context::Context, DatamodelError};
 
 use baml_types::Constraint;
@@ -16,7 +16,7 @@ use internal_baml_schema_ast::ast::{
     self, BlockArgs, Expression, FieldId, FieldType, RawString, TypeAliasId, TypeBuilderBlock,
     ValExpId, WithIdentifier, WithName, WithSpan,
 };
-use internal_llm_client::{ClientProvider, PropertyHandler, UnresolvedClientProperty};
+use internal_llm_client::{ClientProvider, Coercion, PropertyHandler, UnresolvedClientProperty};
 
 mod configurations;
 mod prompt;
@@ -84,6 +84,14 @@ pub(super) fn resolve_type_aliases(ctx: &mut Context<'_>) {
     // Resolve type aliases.
     // Cycles are already computed so this should not stack overflow.
     for alias_id in ctx.types.type_alias_dependencies.keys() {
+        // Generic aliases can only be resolved once they're applied to type
+        // arguments, which happens when building the IR.
+        if !ctx.ast[*alias_id].type_params.is_empty() {
+            let target = ctx.ast[*alias_id].value.to_owned();
+            ctx.types.resolved_type_aliases.insert(*alias_id, target);
+            continue;
+        }
+
         // We can ignore the error here because it's already reported in the
         // diagnostics at [`visit_type_alias`].
         if let Ok(resolved) = resolve_type_alias(&ctx.ast[*alias_id].value, &ctx) {
@@ -212,6 +220,8 @@ pub struct RetryPolicy {
     pub max_retries: u32,
     /// The strategy to use.
     pub strategy: RetryPolicyStrategy,
+    /// Which failures should trigger a retry.
+    pub retry_on: internal_llm_client::RetryOn,
     /// Any additional options.
     pub options: Option<IndexMap<String, (Span, UnresolvedValue<Span>)>>,
 }
@@ -230,6 +240,8 @@ pub enum RetryPolicyStrategy {
 pub struct ContantDelayStrategy {
     /// The delay in milliseconds.
     pub delay_ms: u32,
+    /// How the delay is randomized.
+    pub jitter: RetryJitter,
 }
 
 #[derive(Debug, Clone, Copy, serde::Serialize)]
@@ -241,6 +253,47 @@ pub struct ExponentialBackoffStrategy {
     pub multiplier: f32,
     /// The maximum delay in milliseconds.
     pub max_delay_ms: u32,
+    /// How the delay is randomized.
+    pub jitter: RetryJitter,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+/// How the delay between retries is randomized, so that clients that failed
+/// together don't retry together.
+pub enum RetryJitter {
+    /// Always wait the computed delay.
+    #[default]
+    None,
+    /// Wait a random duration between zero and the computed delay.
+    Full,
+    /// Wait a random duration between the initial delay and `multiplier`
+    /// times the previous delay, capped at `max_delay_ms`.
+    Decorrelated,
+}
+
+impl RetryJitter {
+    /// The values `jitter` accepts in a retry policy.
+    pub fn allowed_values() -> &'static [&'static str] {
+        &["none", "full", "decorrelated"]
+    }
+}
+
+impl std::str::FromStr for RetryJitter {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "none" => Ok(Self::None),
+            "full" => Ok(Self::Full),
+            "decorrelated" => Ok(Self::Decorrelated),
+            other => anyhow::bail!(
+                "Invalid jitter: {}. Allowed values are: {}",
+                other,
+                Self::allowed_values().join(", ")
+            ),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -248,6 +301,9 @@ pub struct FunctionType {
     pub dependencies: (HashSet<String>, HashSet<String>),
     pub prompt: Option<RawString>,
     pub client: Option<(String, Span)>,
+    /// Coercions the parser may not use for this function, overriding the
+    /// `forbid_coercions` of its client when set.
+    pub forbid_coercions: Option<Vec<Coercion>>,
 }
 
 #[derive(Debug, Clone)]
@@ -266,6 +322,12 @@ pub(super) struct Types {
     pub(super) class_dependencies: HashMap<ast::TypeExpId, HashSet<String>>,
     pub(super) enum_dependencies: HashMap<ast::TypeExpId, HashSet<String>>,
 
+    /// The classes each class extends, in the order they are listed.
+    ///
+    /// Names that don't resolve to a class are left out, the validation
+    /// pipeline reports them.
+    pub(super) class_bases: HashMap<ast::TypeExpId, Vec<ast::TypeExpId>>,
+
     /// Graph of type aliases.
     ///
     /// This graph is only used to detect infinite cycles in type aliases.
@@ -333,6 +395,41 @@ pub(super) struct Types {
         HashMap<either::Either<ast::TemplateStringId, ast::ValExpId>, TemplateStringProperties>,
 }
 
+impl Types {
+    /// All the fields of a class, including the inherited ones, as pairs of
+    /// the class that defines the field and the field.
+    ///
+    /// Inherited fields come first, in the order the base classes are listed.
+    /// A class reached through more than one path adds its fields once, and
+    /// inheritance cycles are cut here so that the cycle validation can report
+    /// them.
+    pub(super) fn class_fields(
+        &self,
+        ast: &ast::SchemaAst,
+        class_id: ast::TypeExpId,
+    ) -> Vec<(ast::TypeExpId, FieldId)> {
+        fn collect(
+            types: &Types,
+            ast: &ast::SchemaAst,
+            class_id: ast::TypeExpId,
+            visited: &mut HashSet<ast::TypeExpId>,
+            fields: &mut Vec<(ast::TypeExpId, FieldId)>,
+        ) {
+            if !visited.insert(class_id) {
+                return;
+            }
+            for base in types.class_bases.get(&class_id).into_iter().flatten() {
+                collect(types, ast, *base, visited, fields);
+            }
+            fields.extend(ast[class_id].iter_fields().map(|(id, _)| (class_id, id)));
+        }
+
+        let mut fields = Vec::new();
+        collect(self, ast, class_id, &mut HashSet::new(), &mut fields);
+        fields
+    }
+}
+
 fn visit_template_string<'db>(
     idx: ast::TemplateStringId,
     template_string: &'db ast::TemplateString,
@@ -415,8 +512,27 @@ fn visit_class<'db>(
         .flat_map(|(_, f)| f.expr.iter().flat_map(FieldType::flat_idns))
         .map(|id| id.name().to_string())
         .collect::<HashSet<_>>();
+    // Type parameters of a generic class are not types of their own.
+    for param in &class.type_params {
+        used_types.remove(param.name());
+    }
     let input_deps = class.input().map(BlockArgs::flat_idns).unwrap_or_default();
 
+    // A class depends on the classes it extends, since their fields are its
+    // own.
+    let bases = class
+        .extends
+        .iter()
+        .filter_map(|base| {
+            ctx.interner
+                .lookup(base.name())
+                .and_then(|name| ctx.names.tops.get(&name))
+                .and_then(|top_id| top_id.as_class_id())
+        })
+        .collect::<Vec<_>>();
+    used_types.extend(bases.iter().map(|base| ctx.ast[*base].name().to_string()));
+    ctx.types.class_bases.insert(class_id, bases);
+
     ctx.types.class_dependencies.insert(class_id, {
         used_types.extend(input_deps.iter().map(|id| id.name().to_string()));
         used_types
@@ -441,7 +557,7 @@ fn visit_class<'db>(
 pub fn resolve_type_alias(field_type: &FieldType, ctx: &Context<'_>) -> Result<FieldType, String> {
     Ok(match field_type {
         // For symbols we need to check if we're dealing with aliases.
-        FieldType::Symbol(arity, ident, attrs) => {
+        FieldType::Symbol(arity, ident, _, attrs) => {
             let Some(string_id) = ctx.interner.lookup(ident.name()) else {
                 return Err(format!(
                     "Attempting to resolve alias `{ident}` that does not exist in the interner"
@@ -455,6 +571,12 @@ pub fn resolve_type_alias(field_type: &FieldType, ctx: &Context<'_>) -> Result<F
             };
 
             match top_id {
+                // Generic aliases are expanded with their type arguments when
+                // building the IR.
+                ast::TopId::TypeAlias(alias_id) if !ctx.ast[*alias_id].type_params.is_empty() => {
+                    field_type.to_owned()
+                }
+
                 ast::TopId::TypeAlias(alias_id) => {
                     let mut resolved = match ctx.types.resolved_type_aliases.get(alias_id) {
                         // Check if we can avoid deeper recursion.
@@ -552,7 +674,19 @@ fn visit_type_alias<'db>(
 
     while let Some(item) = stack.pop() {
         match item {
-            FieldType::Symbol(_, ident, _) => {
+            // Type parameters of a generic alias.
+            FieldType::Symbol(_, ident, args, _)
+                if assignment
+                    .type_params
+                    .iter()
+                    .any(|param| param.name() == ident.name()) =>
+            {
+                stack.extend(args.iter());
+            }
+
+            FieldType::Symbol(_, ident, args, _) => {
+                stack.extend(args.iter());
+
                 let Some(string_id) = ctx.interner.lookup(ident.name()) else {
                     ctx.push_error(DatamodelError::new_validation_error(
                         &format!("Type alias points to unknown identifier `{ident}`"),
@@ -621,6 +755,7 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
 
     let mut prompt = None;
     let mut client = None;
+    let mut forbid_coercions = None;
     function
         .iter_fields()
         .for_each(|(_idx, field)| match field.name() {
@@ -637,6 +772,26 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
                     None => None,
                 }
             }
+            "forbid_coercions" => {
+                if let Some(values) = field
+                    .expr
+                    .as_ref()
+                    .and_then(|val| coerce_array(val, &coerce::string_with_span, ctx.diagnostics))
+                {
+                    let mut coercions = vec![];
+                    for (value, span) in values {
+                        match value.parse::<Coercion>() {
+                            Ok(c) if !coercions.contains(&c) => coercions.push(c),
+                            Ok(_) => {}
+                            Err(e) => ctx.push_error(DatamodelError::new_validation_error(
+                                &e.to_string(),
+                                span.clone(),
+                            )),
+                        }
+                    }
+                    forbid_coercions = Some(coercions);
+                }
+            }
             config => ctx.push_error(DatamodelError::new_validation_error(
                 &format!("Unknown field `{}` in function", config),
                 field.span().clone(),
@@ -651,6 +806,7 @@ fn visit_function<'db>(idx: ValExpId, function: &'db ast::ValueExprBlock, ctx: &
                     dependencies: (input_deps.clone(), output_deps),
                     prompt: Some(prompt.clone()),
                     client: Some(client),
+                    forbid_coercions,
                 },
             );
 
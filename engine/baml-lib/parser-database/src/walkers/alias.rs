@@ -18,6 +18,16 @@ impl<'db> TypeAliasWalker<'db> {
         self.db.ast[self.id].identifier.span()
     }
 
+    /// Type parameters of a generic alias, empty if the alias is not generic.
+    pub fn type_params(&self) -> &'db [Identifier] {
+        &self.db.ast[self.id].type_params
+    }
+
+    /// Whether `name` is one of the type parameters of the alias.
+    pub fn is_type_param(&self, name: &str) -> bool {
+        self.type_params().iter().any(|param| param.name() == name)
+    }
+
     /// Returns the field type that the alias points to.
     pub fn target(&self) -> &'db FieldType {
         &self.db.ast[self.id].value
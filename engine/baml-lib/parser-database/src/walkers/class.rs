@@ -24,6 +24,16 @@ impl<'db> ClassWalker<'db> {
         &self.db.ast[self.id]
     }
 
+    /// Type parameters of a generic class, empty if the class is not generic.
+    pub fn type_params(self) -> &'db [Identifier] {
+        &self.ast_type_block().type_params
+    }
+
+    /// Whether `name` is one of the type parameters of the class.
+    pub fn is_type_param(self, name: &str) -> bool {
+        self.type_params().iter().any(|param| param.name() == name)
+    }
+
     /// Iterate all the scalar fields in a given class in the order they were defined.
     pub fn static_fields(self) -> impl ExactSizeIterator<Item = FieldWalker<'db>> {
         self.ast_type_block()
@@ -33,6 +43,29 @@ impl<'db> ClassWalker<'db> {
             .into_iter()
     }
 
+    /// The classes this class extends, in the order they are listed.
+    pub fn bases(self) -> impl ExactSizeIterator<Item = ClassWalker<'db>> {
+        self.db.types.class_bases[&self.id]
+            .iter()
+            .map(move |base| self.walk(*base))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
+    /// Iterate all the fields of the class, including the ones inherited from
+    /// the classes it extends, which come first.
+    ///
+    /// Inherited fields are walked from the class that defines them.
+    pub fn fields(self) -> impl ExactSizeIterator<Item = FieldWalker<'db>> {
+        self.db
+            .types
+            .class_fields(&self.db.ast, self.id)
+            .into_iter()
+            .map(move |(class_id, field_id)| self.walk((class_id, field_id, false)))
+            .collect::<Vec<_>>()
+            .into_iter()
+    }
+
     /// Iterate all the scalar fields in a given class in the order they were defined.
     pub fn dependencies(self) -> &'db HashSet<String> {
         &self.db.types.class_dependencies[&self.id]
@@ -70,7 +103,7 @@ impl<'db> ClassWalker<'db> {
     pub fn add_to_types(self, types: &mut internal_baml_jinja_types::PredefinedTypes) {
         types.add_class(
             self.name(),
-            self.static_fields()
+            self.fields()
                 .filter_map(|f| {
                     f.r#type()
                         .as_ref()
@@ -14,6 +14,10 @@ pub struct Assignment {
     /// expression.
     pub identifier: Identifier,
 
+    /// Type parameters of a generic type alias, the `T` in
+    /// `type Page<T> = Paginated<T>`.
+    pub type_params: Vec<Identifier>,
+
     /// Right side of the assignment.
     ///
     /// Since for now it's only used for type aliases then it's just a type.
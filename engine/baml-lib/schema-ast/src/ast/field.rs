@@ -111,7 +111,14 @@ impl FieldArity {
 
 #[derive(Debug, Clone)]
 pub enum FieldType {
-    Symbol(FieldArity, Identifier, Option<Vec<Attribute>>),
+    // The third field holds the type arguments of a generic class or type
+    // alias, like `Resume` in `Paginated<Resume>`.
+    Symbol(
+        FieldArity,
+        Identifier,
+        Vec<FieldType>,
+        Option<Vec<Attribute>>,
+    ),
     Primitive(FieldArity, TypeValue, Span, Option<Vec<Attribute>>),
     Literal(FieldArity, LiteralValue, Span, Option<Vec<Attribute>>),
     // The second field is the number of dims for the list
@@ -146,7 +153,7 @@ impl FieldType {
         match self {
             FieldType::Primitive(.., span, _) => span,
             FieldType::Literal(.., span, _) => span,
-            FieldType::Symbol(.., idn, _) => idn.span(),
+            FieldType::Symbol(_, idn, ..) => idn.span(),
             FieldType::Union(.., span, _) => span,
             FieldType::Tuple(.., span, _) => span,
             FieldType::Map(.., span, _) => span,
@@ -186,11 +193,70 @@ impl FieldType {
         }
     }
 
+    /// Replaces the type parameters of a generic class or type alias with the
+    /// given type arguments. The arity and attributes of the parameter are
+    /// kept, so `T?` becomes `Resume?` when `T` is `Resume`.
+    pub fn substitute_type_params(&self, params: &[Identifier], args: &[FieldType]) -> Self {
+        let substitute = |f: &FieldType| f.substitute_type_params(params, args);
+
+        match self {
+            FieldType::Symbol(arity, idn, type_args, attrs) => {
+                match params.iter().position(|param| param.name() == idn.name()) {
+                    Some(i) if i < args.len() => {
+                        let mut arg = if arity.is_optional() {
+                            args[i].to_nullable()
+                        } else {
+                            args[i].to_owned()
+                        };
+                        if let Some(attrs) = attrs.as_ref().filter(|attrs| !attrs.is_empty()) {
+                            arg.extend_attributes(attrs.to_owned());
+                        }
+                        arg
+                    }
+                    _ => FieldType::Symbol(
+                        *arity,
+                        idn.to_owned(),
+                        type_args.iter().map(substitute).collect(),
+                        attrs.to_owned(),
+                    ),
+                }
+            }
+            FieldType::Primitive(..) | FieldType::Literal(..) => self.to_owned(),
+            FieldType::List(arity, item, dims, span, attrs) => FieldType::List(
+                *arity,
+                Box::new(substitute(item)),
+                *dims,
+                span.to_owned(),
+                attrs.to_owned(),
+            ),
+            FieldType::Tuple(arity, items, span, attrs) => FieldType::Tuple(
+                *arity,
+                items.iter().map(substitute).collect(),
+                span.to_owned(),
+                attrs.to_owned(),
+            ),
+            FieldType::Union(arity, items, span, attrs) => FieldType::Union(
+                *arity,
+                items.iter().map(substitute).collect(),
+                span.to_owned(),
+                attrs.to_owned(),
+            ),
+            FieldType::Map(arity, kv, span, attrs) => FieldType::Map(
+                *arity,
+                Box::new((substitute(&kv.0), substitute(&kv.1))),
+                span.to_owned(),
+                attrs.to_owned(),
+            ),
+        }
+    }
+
     // All the identifiers used in this type.
     pub fn flat_idns(&self) -> Vec<&Identifier> {
         match self {
-            FieldType::Symbol(_, idn, ..) => {
-                vec![&idn]
+            FieldType::Symbol(_, idn, args, _) => {
+                let mut idns = vec![idn];
+                idns.extend(args.iter().flat_map(|arg| arg.flat_idns()));
+                idns
             }
 
             FieldType::Union(_, f, _, _) => f.iter().flat_map(|t| t.flat_idns()).collect(),
@@ -279,9 +345,17 @@ impl FieldType {
             }
         }
         match (self, other) {
-            (Symbol(arity1, ident1, attrs1), Symbol(arity2, ident2, attrs2)) => {
+            (Symbol(arity1, ident1, args1, attrs1), Symbol(arity2, ident2, args2, attrs2)) => {
                 assert_eq!(arity1, arity2);
                 ident1.assert_eq_up_to_span(ident2);
+                assert_eq!(
+                    args1.len(),
+                    args2.len(),
+                    "Type argument lengths are different"
+                );
+                for (a1, a2) in args1.iter().zip(args2) {
+                    a1.assert_eq_up_to_span(a2);
+                }
                 attrs_eq(attrs1, attrs2);
             }
             (Symbol(..), _) => {
@@ -355,13 +429,13 @@ impl FieldType {
 impl std::fmt::Display for FieldType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            FieldType::Symbol(arity, idn, ..) => {
-                write!(
-                    f,
-                    "{:#?}{}",
-                    idn,
-                    if arity.is_optional() { "?" } else { "" }
-                )
+            FieldType::Symbol(arity, idn, args, _) => {
+                write!(f, "{:#?}", idn)?;
+                if !args.is_empty() {
+                    let args = args.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+                    write!(f, "<{}>", args.join(", "))?;
+                }
+                write!(f, "{}", if arity.is_optional() { "?" } else { "" })
             }
             FieldType::Union(arity, ft, ..) => {
                 let ft = ft.iter().map(|t| t.to_string()).collect::<Vec<_>>();
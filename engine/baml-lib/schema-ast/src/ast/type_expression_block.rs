@@ -57,6 +57,22 @@ pub struct TypeExpressionBlock {
 
     pub fields: Vec<Field<FieldType>>, // needs to support field as well
 
+    /// The type parameters of a generic class.
+    ///
+    /// ```ignore
+    /// class Paginated<T> { ... }
+    ///                 ^
+    /// ```
+    pub type_params: Vec<Identifier>,
+
+    /// The classes this class extends.
+    ///
+    /// ```ignore
+    /// class Foo extends Bar, Baz { ... }
+    ///                   ^^^  ^^^
+    /// ```
+    pub extends: Vec<Identifier>,
+
     /// The attributes of this enum.
     ///
     /// ```ignore
@@ -172,15 +172,43 @@ impl Formatter {
     ) -> Result<RcDoc<'a, ()>> {
         let class_or_enum = next_pair!(pairs, Rule::identifier)?;
         let ident = next_pair!(pairs, Rule::identifier)?;
+        let type_params = next_pair!(pairs, Rule::type_params, optional);
         next_pair!(pairs, Rule::named_argument_list, optional);
+        let extends = next_pair!(pairs, Rule::extends_list, optional);
         next_pair!(pairs, Rule::BLOCK_OPEN)?;
         let contents = next_pair!(pairs, Rule::type_expression_contents)?;
         next_pair!(pairs, Rule::BLOCK_CLOSE)?;
 
+        let type_params = match type_params {
+            Some(type_params) => RcDoc::text("<")
+                .append(RcDoc::intersperse(
+                    type_params
+                        .into_inner()
+                        .filter(|pair| pair.as_rule() == Rule::identifier)
+                        .map(pair_to_doc_text),
+                    RcDoc::text(", "),
+                ))
+                .append(RcDoc::text(">")),
+            None => RcDoc::nil(),
+        };
+
+        let extends = match extends {
+            Some(extends) => RcDoc::text(" extends ").append(RcDoc::intersperse(
+                extends
+                    .into_inner()
+                    .filter(|pair| pair.as_rule() == Rule::identifier)
+                    .map(pair_to_doc_text),
+                RcDoc::text(", "),
+            )),
+            None => RcDoc::nil(),
+        };
+
         Ok(RcDoc::nil()
             .append(pair_to_doc_text(class_or_enum))
             .append(RcDoc::space())
             .append(pair_to_doc_text(ident))
+            .append(type_params)
+            .append(extends)
             .append(RcDoc::space())
             .append(RcDoc::text("{"))
             .append(
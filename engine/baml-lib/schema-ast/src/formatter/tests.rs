@@ -220,3 +220,53 @@ fn newlines_with_only_spaces_are_stripped() -> anyhow::Result<()> {
 
     assert_format_eq(&actual, &expected)
 }
+
+#[test]
+fn class_extends() -> anyhow::Result<()> {
+    let actual = r#"
+          class Foo   extends Bar,Baz {
+              field1   string
+          }
+        "#
+    .unindent()
+    .trim_end()
+    .to_string();
+
+    let expected = r#"
+          class Foo extends Bar, Baz {
+            field1 string
+          }
+        "#
+    .unindent()
+    .trim_end()
+    .to_string();
+
+    assert_format_eq(&actual, &expected)?;
+    assert_format_eq(&expected, &expected)
+}
+
+#[test]
+fn class_type_params() -> anyhow::Result<()> {
+    let actual = r#"
+          class Paginated< T ,U>   extends Base {
+              items   T[]
+              pages   Paginated<T, U>[]
+          }
+        "#
+    .unindent()
+    .trim_end()
+    .to_string();
+
+    let expected = r#"
+          class Paginated<T, U> extends Base {
+            items T[]
+            pages Paginated<T, U>[]
+          }
+        "#
+    .unindent()
+    .trim_end()
+    .to_string();
+
+    assert_format_eq(&actual, &expected)?;
+    assert_format_eq(&expected, &expected)
+}
@@ -8,7 +8,10 @@ use super::{
 use crate::{
     assert_correct_parser,
     ast::*,
-    parser::{parse_field::parse_field_type_with_attr, parse_types::parse_field_type},
+    parser::{
+        parse_field::parse_field_type_with_attr,
+        parse_types::{parse_field_type, parse_type_params},
+    },
 };
 
 use internal_baml_diagnostics::{DatamodelError, Diagnostics};
@@ -26,6 +29,7 @@ pub(crate) fn parse_assignment(pair: Pair<'_>, diagnostics: &mut Diagnostics) ->
 
     let mut identifier: Option<Identifier> = None;
     let mut field_type: Option<FieldType> = None;
+    let mut type_params = Vec::new();
 
     for current in pair.into_inner() {
         match current.as_rule() {
@@ -47,6 +51,8 @@ pub(crate) fn parse_assignment(pair: Pair<'_>, diagnostics: &mut Diagnostics) ->
                 }
             }
 
+            Rule::type_params => type_params = parse_type_params(current, diagnostics),
+
             Rule::assignment => {} // Ok, equal sign.
 
             // TODO: We probably only need field_type_with_attr since that's how
@@ -64,6 +70,7 @@ pub(crate) fn parse_assignment(pair: Pair<'_>, diagnostics: &mut Diagnostics) ->
     match (identifier, field_type) {
         (Some(identifier), Some(field_type)) => Assignment {
             identifier,
+            type_params,
             value: field_type,
             span: diagnostics.span(span),
         },
@@ -149,4 +156,32 @@ mod tests {
             panic!("Expected int | string union, got: {:?}", elements);
         };
     }
+
+    #[test]
+    fn parse_generic_type_alias() {
+        let assignment = parse_type_alias("type Page<T> = Paginated<T, string>");
+
+        assert_eq!(assignment.identifier.to_string(), "Page");
+        assert_eq!(
+            assignment
+                .type_params
+                .iter()
+                .map(|param| param.name())
+                .collect::<Vec<_>>(),
+            ["T"]
+        );
+
+        let FieldType::Symbol(_, name, args, _) = &assignment.value else {
+            panic!("Expected generic type, got: {:?}", assignment.value);
+        };
+        assert_eq!(name.to_string(), "Paginated");
+
+        let [FieldType::Symbol(_, param, params, _), FieldType::Primitive(_, TypeValue::String, _, _)] =
+            args.as_slice()
+        else {
+            panic!("Expected <T, string> type arguments, got: {:?}", args);
+        };
+        assert_eq!(param.to_string(), "T");
+        assert!(params.is_empty());
+    }
 }
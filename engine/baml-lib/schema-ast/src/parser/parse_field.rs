@@ -8,6 +8,7 @@ use super::{
     Rule,
 };
 use crate::ast::*;
+use baml_types::Validator;
 use internal_baml_diagnostics::{DatamodelError, Diagnostics};
 
 pub(crate) fn parse_value_expr(
@@ -66,9 +67,11 @@ pub(crate) fn parse_value_expr(
 fn reassociate_type_attributes(field_attributes: &mut Vec<Attribute>, field_type: &mut FieldType) {
     let mut all_attrs = field_type.attributes().to_owned();
     all_attrs.append(field_attributes);
-    let (attrs_for_type, attrs_for_field): (Vec<_>, Vec<_>) = all_attrs
-        .into_iter()
-        .partition(|attr| TYPE_ATTRIBUTE_NAMES.contains(&attr.name()));
+    let (attrs_for_type, attrs_for_field): (Vec<_>, Vec<_>) =
+        all_attrs.into_iter().partition(|attr| {
+            TYPE_ATTRIBUTE_NAMES.contains(&attr.name())
+                || Validator::ATTRIBUTE_NAMES.contains(&attr.name())
+        });
     field_type.set_attributes(attrs_for_type.clone());
     *field_attributes = attrs_for_field;
 }
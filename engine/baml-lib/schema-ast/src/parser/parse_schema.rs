@@ -389,7 +389,7 @@ mod tests {
             panic!("Expected union type (string | One), got: {:?}", two.value);
         };
 
-        let [FieldType::Primitive(_, TypeValue::String, _, _), FieldType::Symbol(_, alias, _)] =
+        let [FieldType::Primitive(_, TypeValue::String, _, _), FieldType::Symbol(_, alias, _, _)] =
             elements.as_slice()
         else {
             panic!("Expected union type (string | One), got: {:?}", two.value);
@@ -4,6 +4,7 @@ use super::{
     parse_comments::*,
     parse_identifier::parse_identifier,
     parse_named_args_list::parse_named_argument_list,
+    parse_types::parse_type_params,
     Rule,
 };
 
@@ -23,6 +24,8 @@ pub(crate) fn parse_type_expression_block(
     let mut name: Option<Identifier> = None;
     let mut attributes: Vec<Attribute> = Vec::new();
     let mut fields: Vec<Field<FieldType>> = Vec::new();
+    let mut extends: Vec<Identifier> = Vec::new();
+    let mut type_params: Vec<Identifier> = Vec::new();
     let mut sub_type: Option<SubType> = None;
     let mut input = None;
 
@@ -65,6 +68,30 @@ pub(crate) fn parse_type_expression_block(
                 Ok(arg) => input = Some(arg),
                 Err(err) => diagnostics.push_error(err),
             },
+            Rule::type_params => {
+                if !matches!(sub_type, Some(SubType::Class)) {
+                    diagnostics.push_error(DatamodelError::new_validation_error(
+                        "Only classes and type aliases can have type parameters.",
+                        diagnostics.span(current.as_span()),
+                    ));
+                }
+                type_params = parse_type_params(current, diagnostics);
+            }
+            Rule::extends_list => {
+                if !matches!(sub_type, Some(SubType::Class)) {
+                    diagnostics.push_error(DatamodelError::new_validation_error(
+                        "Only classes can extend other classes.",
+                        diagnostics.span(current.as_span()),
+                    ));
+                }
+                for base in current.into_inner() {
+                    match base.as_rule() {
+                        Rule::EXTENDS_KEYWORD => {}
+                        Rule::identifier => extends.push(parse_identifier(base, diagnostics)),
+                        _ => parsing_catch_all(base, "extends_list"),
+                    }
+                }
+            }
             Rule::type_expression_contents => {
                 let mut pending_field_comment: Option<Pair<'_>> = None;
 
@@ -116,6 +143,8 @@ pub(crate) fn parse_type_expression_block(
         Some(name) => TypeExpressionBlock {
             name,
             fields,
+            type_params,
+            extends,
             input,
             attributes,
             documentation: doc_comment.and_then(parse_comment_block),
@@ -188,4 +217,26 @@ mod tests {
         assert_eq!(name.to_string(), "Test");
         assert!(fields[0].expr.is_some());
     }
+
+    #[test]
+    fn class_extends() {
+        let root_path = "test_file.baml";
+
+        let input = r#"class Foo extends Bar, Baz { a int }"#;
+        let source = SourceFile::new_static(root_path.into(), input);
+        let mut diagnostics = Diagnostics::new(root_path.into());
+        diagnostics.set_source(&source);
+        let parsed = BAMLParser::parse(Rule::type_expression_block, input)
+            .unwrap()
+            .next()
+            .unwrap();
+        let result = parse_type_expression_block(parsed, None, &mut diagnostics);
+        assert!(!diagnostics.has_errors());
+        assert_eq!(result.name.to_string(), "Foo");
+        assert_eq!(
+            result.extends.iter().map(|e| e.name()).collect::<Vec<_>>(),
+            ["Bar", "Baz"]
+        );
+        assert_eq!(result.fields.len(), 1);
+    }
 }
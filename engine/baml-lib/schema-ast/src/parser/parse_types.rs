@@ -161,6 +161,7 @@ fn parse_base_type(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<Fiel
                             identifier.name().to_string(),
                             diagnostics.span(current.as_span()),
                         ),
+                        Vec::new(),
                         None,
                     ),
                 };
@@ -168,6 +169,7 @@ fn parse_base_type(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<Fiel
             }
             Rule::array_notation => parse_array(current, diagnostics),
             Rule::map => parse_map(current, diagnostics),
+            Rule::generic_type => parse_generic_type(current, diagnostics),
             Rule::group => parse_group(current, diagnostics),
             Rule::tuple => parse_tuple(current, diagnostics),
             Rule::parenthesized_type => parse_parenthesized_type(current, diagnostics),
@@ -350,6 +352,51 @@ fn parse_map(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<FieldType>
     }
 }
 
+/// Parses a generic class or type alias applied to type arguments, like
+/// `Paginated<Resume>` or `Result<Resume, string>`.
+fn parse_generic_type(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<FieldType> {
+    assert_correct_parser!(pair, Rule::generic_type);
+
+    let mut name = None;
+    let mut args = Vec::new();
+
+    for current in pair.into_inner() {
+        match current.as_rule() {
+            Rule::identifier => {
+                let identifier = parse_identifier(current.clone(), diagnostics);
+                name = Some(Identifier::Local(
+                    identifier.name().to_string(),
+                    diagnostics.span(current.as_span()),
+                ));
+            }
+            Rule::field_type => {
+                if let Some(arg) = parse_field_type(current, diagnostics) {
+                    args.push(arg)
+                }
+            }
+            _ => unreachable_rule!(current, Rule::generic_type),
+        }
+    }
+
+    name.map(|name| FieldType::Symbol(FieldArity::Required, name, args, None))
+}
+
+/// Parses the type parameters of a generic class or type alias, the `<T, U>`
+/// in `class Foo<T, U>`.
+pub(crate) fn parse_type_params(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Vec<Identifier> {
+    assert_correct_parser!(pair, Rule::type_params);
+
+    pair.into_inner()
+        .filter_map(|current| match current.as_rule() {
+            Rule::identifier => Some(parse_identifier(current, diagnostics)),
+            _ => {
+                parsing_catch_all(current, "type_params");
+                None
+            }
+        })
+        .collect()
+}
+
 fn parse_group(pair: Pair<'_>, diagnostics: &mut Diagnostics) -> Option<FieldType> {
     assert_correct_parser!(pair, Rule::group);
     let mut attributes = Vec::new();
@@ -87,6 +87,7 @@ impl InitArgs {
                 GeneratorOutputType::PythonPydantic => "Python clients".to_string(),
                 GeneratorOutputType::Typescript => "TypeScript clients".to_string(),
                 GeneratorOutputType::RubySorbet => "Ruby clients".to_string(),
+                GeneratorOutputType::Go => "Go clients".to_string(),
                 GeneratorOutputType::OpenApi => match &self.openapi_client_type {
                     Some(s) => format!("{} clients via OpenAPI", s),
                     None => "REST clients".to_string(),
@@ -99,6 +100,7 @@ impl InitArgs {
                 GeneratorOutputType::PythonPydantic => "python",
                 GeneratorOutputType::Typescript => "typescript",
                 GeneratorOutputType::RubySorbet => "ruby",
+                GeneratorOutputType::Go => "go",
                 GeneratorOutputType::OpenApi => "openapi",
             }
         );
@@ -113,7 +115,9 @@ fn generate_main_baml_content(
     openapi_client_type: Option<&str>,
 ) -> String {
     let default_client_mode = match output_type {
-        GeneratorOutputType::OpenApi | GeneratorOutputType::RubySorbet => "".to_string(),
+        GeneratorOutputType::OpenApi | GeneratorOutputType::RubySorbet | GeneratorOutputType::Go => {
+            "".to_string()
+        }
         GeneratorOutputType::PythonPydantic | GeneratorOutputType::Typescript => format!(
             r#"
     // Valid values: "sync", "async"
@@ -183,7 +187,7 @@ fn generate_main_baml_content(
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "go", "rest/openapi"
     output_type "{output_type}"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -221,7 +225,7 @@ mod tests {
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "go", "rest/openapi"
     output_type "python/pydantic"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -250,7 +254,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "go", "rest/openapi"
     output_type "typescript"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -279,7 +283,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "go", "rest/openapi"
     output_type "ruby/sorbet"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -304,7 +308,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "go", "rest/openapi"
     output_type "rest/openapi"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -333,7 +337,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "go", "rest/openapi"
     output_type "rest/openapi"
 
     // Where the generated code will be saved (relative to baml_src/)
@@ -362,7 +366,7 @@ generator target {{
 // your choice. You can have multiple generators if you use multiple languages.
 // Just ensure that the output_dir is different for each generator.
 generator target {{
-    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "rest/openapi"
+    // Valid values: "python/pydantic", "typescript", "ruby/sorbet", "go", "rest/openapi"
     output_type "rest/openapi"
 
     // Where the generated code will be saved (relative to baml_src/)
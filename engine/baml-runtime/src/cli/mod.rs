@@ -1,7 +1,9 @@
 pub mod dev;
 pub mod generate;
 pub mod init;
+pub mod parse;
 pub mod serve;
+pub mod test;
 
 use internal_baml_core::configuration::GeneratorOutputType;
 
@@ -0,0 +1,79 @@
+use crate::BamlRuntime;
+use anyhow::{Context, Result};
+use baml_types::BamlValue;
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::PathBuf;
+
+#[derive(clap::Args, Debug)]
+pub struct ParseArgs {
+    #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
+    pub from: PathBuf,
+    #[arg(
+        long = "type",
+        short = 't',
+        help = "The type to parse into, like Resume or Resume[]"
+    )]
+    target: String,
+    #[arg(help = "File with the text to parse. Reads stdin if not given")]
+    input: Option<PathBuf>,
+    #[arg(
+        long,
+        help = "Allow incomplete values, like a response that is still streaming",
+        default_value_t = false
+    )]
+    partial: bool,
+    #[arg(
+        long,
+        help = "Print how the text had to be fixed up to stderr",
+        default_value_t = false
+    )]
+    flags: bool,
+    #[arg(
+        long,
+        help = "Print the parse report, with the score and every repair, as JSON to stderr",
+        default_value_t = false
+    )]
+    report: bool,
+}
+
+impl ParseArgs {
+    pub fn run(&self) -> Result<()> {
+        let env_vars: HashMap<String, String> = std::env::vars().collect();
+        let runtime = BamlRuntime::from_directory(&self.from, env_vars)
+            .context("Failed to build BAML runtime")?;
+
+        let text = match &self.input {
+            Some(path) => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?,
+            None => {
+                let mut text = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut text)
+                    .context("Failed to read stdin")?;
+                text
+            }
+        };
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("cli".to_string()), None);
+        let target = runtime.find_type(&self.target, &ctx, None)?;
+        let parsed = runtime.parse(&text, &target, self.partial, &ctx, None)?;
+
+        if self.flags {
+            for (path, flag) in parsed.flags() {
+                let path = if path.is_empty() { "<root>" } else { &path };
+                eprintln!("{path}: {flag}");
+            }
+        }
+        if self.report {
+            eprintln!("{}", serde_json::to_string_pretty(&parsed.parse_report())?);
+        }
+        let json = if self.partial {
+            serde_json::to_string_pretty(&parsed.serialize_partial())?
+        } else {
+            serde_json::to_string_pretty(&parsed.serialize_final())?
+        };
+        println!("{json}");
+        Ok(())
+    }
+}
@@ -99,6 +99,7 @@ impl BamlError {
                     | crate::internal::llm_client::ErrorCode::RateLimited
                     | crate::internal::llm_client::ErrorCode::ServerError
                     | crate::internal::llm_client::ErrorCode::ServiceUnavailable
+                    | crate::internal::llm_client::ErrorCode::Timeout
                     | crate::internal::llm_client::ErrorCode::UnsupportedResponse(_) => {
                         Self::ClientError {
                             message: format!("{:?}", err),
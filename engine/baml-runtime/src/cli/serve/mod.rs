@@ -162,6 +162,7 @@ async fn status_handler(
     basic_creds: Option<TypedHeader<Authorization<Basic>>>,
     baml_api_key: Option<TypedHeader<XBamlApiKey>>,
 ) -> Response {
+    let circuit_breakers = crate::internal::llm_client::circuit_breaker::status();
     match Server::enforce_auth(basic_creds.as_deref(), baml_api_key.as_deref()) {
         AuthEnforcementMode::EnforceAndFail(e) => (
             StatusCode::FORBIDDEN,
@@ -180,6 +181,7 @@ async fn status_handler(
                     "enforcement": "active",
                     "outcome": "pass"
                 },
+                "circuit_breakers": circuit_breakers,
             })),
         ),
         AuthEnforcementMode::NoEnforcement => (
@@ -188,6 +190,7 @@ async fn status_handler(
                 "authz": {
                     "enforcement": "none",
                 },
+                "circuit_breakers": circuit_breakers,
             })),
         ),
     }
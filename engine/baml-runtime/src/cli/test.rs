@@ -0,0 +1,488 @@
+use crate::{
+    internal::llm_client::LLMResponse, BamlRuntime, FunctionResult, InternalRuntimeInterface,
+    TestFailReason, TestResponse, TestStatus,
+};
+use anyhow::{Context, Result};
+use baml_types::BamlValue;
+use colored::Colorize;
+use futures::StreamExt;
+use indexmap::IndexMap;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+#[derive(clap::Args, Debug)]
+pub struct TestArgs {
+    #[arg(long, help = "path/to/baml_src", default_value = "./baml_src")]
+    pub from: PathBuf,
+    #[arg(
+        long,
+        short = 'i',
+        help = "Only run tests matching FUNCTION or FUNCTION::TEST (supports * wildcards). Can be repeated."
+    )]
+    include: Vec<String>,
+    #[arg(
+        long,
+        short = 'x',
+        help = "Skip tests matching FUNCTION or FUNCTION::TEST (supports * wildcards). Can be repeated."
+    )]
+    exclude: Vec<String>,
+    #[arg(
+        long,
+        help = "Maximum number of tests to run at the same time",
+        default_value = "10"
+    )]
+    parallel: usize,
+    #[arg(
+        long,
+        help = "List the selected tests without running them",
+        default_value_t = false
+    )]
+    list: bool,
+    #[arg(
+        long,
+        help = "Replay LLM calls from this cassette file instead of calling the providers"
+    )]
+    cassette: Option<PathBuf>,
+    #[arg(
+        long,
+        requires = "cassette",
+        help = "Make real LLM calls and record them into the cassette file",
+        default_value_t = false
+    )]
+    record: bool,
+    #[arg(long, help = "Write a JUnit XML report to this path")]
+    junit_xml: Option<PathBuf>,
+    #[arg(long, help = "Write a JSON report to this path")]
+    json: Option<PathBuf>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum Outcome {
+    Passed,
+    /// The test ran but did not produce an acceptable result.
+    Failed,
+    /// The test could not be run at all (bad args, missing client, ...).
+    Error,
+    /// All asserts passed, but some checks need a human to look at them.
+    NeedsHumanEval,
+}
+
+#[derive(Debug, Serialize)]
+struct TestReport {
+    function: String,
+    test: String,
+    outcome: Outcome,
+    #[serde(serialize_with = "serialize_secs")]
+    duration: Duration,
+    /// What the LLM calls of the test cost in US dollars, if they were priced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cost: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    raw_output: Option<String>,
+}
+
+fn serialize_secs<S: serde::Serializer>(d: &Duration, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_f64(d.as_secs_f64())
+}
+
+impl TestArgs {
+    pub fn run(&self) -> Result<()> {
+        let mut env_vars: HashMap<String, String> = std::env::vars().collect();
+        if let Some(cassette) = &self.cassette {
+            env_vars.insert("BAML_CASSETTE".into(), cassette.display().to_string());
+            env_vars.insert(
+                "BAML_CASSETTE_MODE".into(),
+                if self.record { "record" } else { "replay" }.into(),
+            );
+        }
+        let runtime = BamlRuntime::from_directory(&self.from, env_vars)
+            .context("Failed to build BAML runtime")?;
+
+        let selected = self.select_tests(&runtime);
+        if selected.is_empty() {
+            anyhow::bail!("No tests matched the given filters");
+        }
+
+        if self.list {
+            for (function, test) in &selected {
+                println!("{function}::{test}");
+            }
+            return Ok(());
+        }
+
+        log::info!(
+            "Running {} test{} (parallel: {})",
+            selected.len(),
+            if selected.len() == 1 { "" } else { "s" },
+            self.parallel.max(1)
+        );
+
+        let started = Instant::now();
+        let reports = runtime
+            .async_runtime
+            .block_on(self.run_tests(&runtime, selected));
+        let elapsed = started.elapsed();
+        let costs = reports.iter().filter_map(|r| r.cost).collect::<Vec<_>>();
+        let cost = (!costs.is_empty()).then(|| costs.iter().sum::<f64>());
+
+        if let Some(path) = &self.junit_xml {
+            std::fs::write(path, junit_xml(&reports, elapsed))
+                .with_context(|| format!("Failed to write JUnit report to {}", path.display()))?;
+        }
+        if let Some(path) = &self.json {
+            let json = serde_json::to_string_pretty(&serde_json::json!({
+                "duration": elapsed.as_secs_f64(),
+                "cost": cost,
+                "tests": reports,
+            }))?;
+            std::fs::write(path, json)
+                .with_context(|| format!("Failed to write JSON report to {}", path.display()))?;
+        }
+
+        let count = |outcome| reports.iter().filter(|r| r.outcome == outcome).count();
+        let (passed, failed, errored, human_eval) = (
+            count(Outcome::Passed),
+            count(Outcome::Failed),
+            count(Outcome::Error),
+            count(Outcome::NeedsHumanEval),
+        );
+        println!(
+            "\n{} passed, {} failed, {} errored, {} need human evaluation ({:.2}s{})",
+            passed,
+            failed,
+            errored,
+            human_eval,
+            elapsed.as_secs_f64(),
+            cost_suffix(cost)
+        );
+
+        if failed + errored > 0 {
+            anyhow::bail!(
+                "{} of {} tests did not pass",
+                failed + errored,
+                reports.len()
+            );
+        }
+        Ok(())
+    }
+
+    /// All `(function, test)` pairs that pass the include/exclude filters, in
+    /// source order.
+    fn select_tests(&self, runtime: &BamlRuntime) -> Vec<(String, String)> {
+        runtime
+            .inner
+            .ir()
+            .walk_tests()
+            .map(|t| {
+                (
+                    t.function().name().to_string(),
+                    t.test_case().name.to_string(),
+                )
+            })
+            .filter(|(function, test)| self.is_selected(function, test))
+            .collect()
+    }
+
+    fn is_selected(&self, function: &str, test: &str) -> bool {
+        let matches =
+            |patterns: &[String]| patterns.iter().any(|p| matches_filter(p, function, test));
+        (self.include.is_empty() || matches(&self.include)) && !matches(&self.exclude)
+    }
+
+    async fn run_tests(
+        &self,
+        runtime: &BamlRuntime,
+        selected: Vec<(String, String)>,
+    ) -> Vec<TestReport> {
+        let mut reports = futures::stream::iter(selected.into_iter().enumerate())
+            .map(|(idx, (function, test))| async move {
+                let ctx = runtime.create_ctx_manager(BamlValue::String("cli".to_string()), None);
+                let started = Instant::now();
+                let (response, _) = runtime
+                    .run_test(&function, &test, &ctx, None::<fn(FunctionResult)>)
+                    .await;
+                (
+                    idx,
+                    TestReport::new(function, test, response, started.elapsed()),
+                )
+            })
+            .buffer_unordered(self.parallel.max(1))
+            .inspect(|(_, report)| report.print())
+            .collect::<Vec<_>>()
+            .await;
+
+        reports.sort_by_key(|(idx, _)| *idx);
+        reports.into_iter().map(|(_, report)| report).collect()
+    }
+}
+
+impl TestReport {
+    fn new(
+        function: String,
+        test: String,
+        response: Result<TestResponse>,
+        duration: Duration,
+    ) -> Self {
+        let cost = response
+            .as_ref()
+            .ok()
+            .and_then(|response| response.function_response.cost());
+        let (outcome, message, raw_output) = match &response {
+            Err(e) => (Outcome::Error, Some(format!("{e:#}")), None),
+            Ok(response) => {
+                let raw_output = response
+                    .function_response
+                    .llm_response()
+                    .content()
+                    .ok()
+                    .map(str::to_string);
+                let (outcome, message) = match response.status() {
+                    TestStatus::Pass => (Outcome::Passed, None),
+                    TestStatus::NeedsHumanEval(checks) => (
+                        Outcome::NeedsHumanEval,
+                        Some(format!(
+                            "Checks need human evaluation: {}",
+                            checks.join(", ")
+                        )),
+                    ),
+                    TestStatus::Fail(reason) => (Outcome::Failed, Some(failure_message(reason))),
+                };
+                (outcome, message, raw_output)
+            }
+        };
+
+        TestReport {
+            function,
+            test,
+            outcome,
+            duration,
+            cost,
+            message,
+            raw_output,
+        }
+    }
+
+    fn print(&self) {
+        let label = match self.outcome {
+            Outcome::Passed => "PASS ".green(),
+            Outcome::Failed => "FAIL ".red(),
+            Outcome::Error => "ERROR".red(),
+            Outcome::NeedsHumanEval => "CHECK".yellow(),
+        };
+        println!(
+            "{} {}::{} ({:.2}s{})",
+            label,
+            self.function,
+            self.test,
+            self.duration.as_secs_f64(),
+            cost_suffix(self.cost)
+        );
+        if let Some(message) = &self.message {
+            for line in message.lines() {
+                println!("      {}", line.dimmed());
+            }
+        }
+    }
+}
+
+fn cost_suffix(cost: Option<f64>) -> String {
+    cost.map(|cost| format!(", ${cost:.6}")).unwrap_or_default()
+}
+
+fn failure_message(reason: TestFailReason) -> String {
+    match reason {
+        TestFailReason::TestUnspecified(e) => format!("{e:#}"),
+        TestFailReason::TestLLMFailure(LLMResponse::LLMFailure(e)) => {
+            format!("LLM call failed: {} {}", e.code, e.message)
+        }
+        TestFailReason::TestLLMFailure(response) => response.to_string(),
+        TestFailReason::TestParseFailure(e) => format!("Failed to parse LLM response: {e:#}"),
+        TestFailReason::TestFinishReasonFailed(e) => format!("{e:#}"),
+        TestFailReason::TestConstraintsFailure {
+            checks,
+            failed_assert,
+        } => {
+            let mut message = vec![];
+            if let Some(name) = failed_assert {
+                message.push(format!("Assert failed: {name}"));
+            }
+            let failed_checks = checks
+                .iter()
+                .filter(|(_, passed)| !passed)
+                .map(|(name, _)| name.as_str())
+                .collect::<Vec<_>>();
+            if !failed_checks.is_empty() {
+                message.push(format!("Checks failed: {}", failed_checks.join(", ")));
+            }
+            message.join("\n")
+        }
+    }
+}
+
+/// `pattern` is either `FUNCTION` (all tests of a function) or
+/// `FUNCTION::TEST`. Both halves may use `*` as a wildcard.
+fn matches_filter(pattern: &str, function: &str, test: &str) -> bool {
+    let (function_pattern, test_pattern) = pattern.split_once("::").unwrap_or((pattern, "*"));
+    wildcard_match(function_pattern, function) && wildcard_match(test_pattern, test)
+}
+
+fn wildcard_match(pattern: &str, text: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == text,
+        Some((prefix, rest)) => {
+            let Some(text) = text.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=text.len())
+                .filter(|i| text.is_char_boundary(*i))
+                .any(|i| wildcard_match(rest, &text[i..]))
+        }
+    }
+}
+
+fn junit_xml(reports: &[TestReport], elapsed: Duration) -> String {
+    let mut by_function: IndexMap<&str, Vec<&TestReport>> = IndexMap::new();
+    for report in reports {
+        by_function
+            .entry(report.function.as_str())
+            .or_default()
+            .push(report);
+    }
+
+    let count =
+        |reports: &[&TestReport], outcome| reports.iter().filter(|r| r.outcome == outcome).count();
+
+    let all = reports.iter().collect::<Vec<_>>();
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    xml.push_str(&format!(
+        "<testsuites name=\"baml\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+        all.len(),
+        count(&all, Outcome::Failed),
+        count(&all, Outcome::Error),
+        elapsed.as_secs_f64()
+    ));
+    for (function, reports) in by_function {
+        let time: Duration = reports.iter().map(|r| r.duration).sum();
+        xml.push_str(&format!(
+            "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"{}\" time=\"{:.3}\">\n",
+            xml_escape(function),
+            reports.len(),
+            count(&reports, Outcome::Failed),
+            count(&reports, Outcome::Error),
+            time.as_secs_f64()
+        ));
+        for report in reports {
+            xml.push_str(&format!(
+                "    <testcase name=\"{}\" classname=\"{}\" time=\"{:.3}\">\n",
+                xml_escape(&report.test),
+                xml_escape(function),
+                report.duration.as_secs_f64()
+            ));
+            let message = report.message.as_deref().unwrap_or_default();
+            match report.outcome {
+                Outcome::Passed => {}
+                Outcome::Failed => xml.push_str(&format!(
+                    "      <failure message=\"{}\">{}</failure>\n",
+                    xml_escape(message.lines().next().unwrap_or_default()),
+                    xml_escape(message)
+                )),
+                Outcome::Error => xml.push_str(&format!(
+                    "      <error message=\"{}\">{}</error>\n",
+                    xml_escape(message.lines().next().unwrap_or_default()),
+                    xml_escape(message)
+                )),
+                Outcome::NeedsHumanEval => xml.push_str(&format!(
+                    "      <skipped message=\"{}\"/>\n",
+                    xml_escape(message)
+                )),
+            }
+            if let Some(raw_output) = &report.raw_output {
+                xml.push_str(&format!(
+                    "      <system-out>{}</system-out>\n",
+                    xml_escape(raw_output)
+                ));
+            }
+            xml.push_str("    </testcase>\n");
+        }
+        xml.push_str("  </testsuite>\n");
+    }
+    xml.push_str("</testsuites>\n");
+    xml
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            // Control characters other than whitespace are not allowed in XML 1.0.
+            c if c.is_control() && !matches!(c, '\n' | '\r' | '\t') => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters() {
+        assert!(matches_filter("ExtractResume", "ExtractResume", "basic"));
+        assert!(!matches_filter("ExtractResume", "ExtractResumeV2", "basic"));
+        assert!(matches_filter("Extract*", "ExtractResumeV2", "basic"));
+        assert!(matches_filter("*::basic", "ExtractResume", "basic"));
+        assert!(!matches_filter("*::basic", "ExtractResume", "basic_2"));
+        assert!(matches_filter("Extract*::*_2", "ExtractResume", "basic_2"));
+    }
+
+    #[test]
+    fn test_junit_xml() {
+        let reports = vec![
+            TestReport {
+                function: "Classify".to_string(),
+                test: "spam".to_string(),
+                outcome: Outcome::Passed,
+                duration: Duration::from_millis(1500),
+                cost: Some(0.0012),
+                message: None,
+                raw_output: Some("<spam>".to_string()),
+            },
+            TestReport {
+                function: "Classify".to_string(),
+                test: "ham".to_string(),
+                outcome: Outcome::Failed,
+                duration: Duration::from_millis(500),
+                cost: None,
+                message: Some("Assert failed: is_ham".to_string()),
+                raw_output: None,
+            },
+        ];
+
+        assert_eq!(
+            junit_xml(&reports, Duration::from_secs(2)),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<testsuites name="baml" tests="2" failures="1" errors="0" time="2.000">
+  <testsuite name="Classify" tests="2" failures="1" errors="0" time="2.000">
+    <testcase name="spam" classname="Classify" time="1.500">
+      <system-out>&lt;spam&gt;</system-out>
+    </testcase>
+    <testcase name="ham" classname="Classify" time="0.500">
+      <failure message="Assert failed: is_ham">Assert failed: is_ham</failure>
+    </testcase>
+  </testsuite>
+</testsuites>
+"#
+        );
+    }
+}
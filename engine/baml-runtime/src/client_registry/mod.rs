@@ -1,6 +1,6 @@
 // This is designed to build any type of client, not just primitives
 use anyhow::{Context, Result};
-pub use internal_llm_client::ClientProvider;
+pub use internal_llm_client::{ClientProvider, Pricing};
 use internal_llm_client::{ClientSpec, PropertyHandler, UnresolvedClientProperty};
 use std::{collections::HashMap, str::FromStr};
 use std::sync::Arc;
@@ -79,6 +79,8 @@ pub struct ClientRegistry {
     #[serde(deserialize_with = "deserialize_clients")]
     clients: HashMap<String, ClientProperty>,
     primary: Option<String>,
+    #[serde(default)]
+    model_pricing: HashMap<String, Pricing>,
 }
 
 impl Default for ClientRegistry {
@@ -92,6 +94,7 @@ impl ClientRegistry {
         Self {
             clients: Default::default(),
             primary: None,
+            model_pricing: Default::default(),
         }
     }
 
@@ -103,6 +106,16 @@ impl ClientRegistry {
         self.primary = Some(primary);
     }
 
+    /// Prices calls to `model`, like `gpt-4o`, made by clients without
+    /// pricing of their own.
+    pub fn set_model_pricing(&mut self, model: String, pricing: Pricing) {
+        self.model_pricing.insert(model, pricing);
+    }
+
+    pub fn model_pricing(&self) -> &HashMap<String, Pricing> {
+        &self.model_pricing
+    }
+
     pub fn to_clients(
         &self,
         ctx: &RuntimeContext,
@@ -0,0 +1,367 @@
+//! Process-wide circuit breakers for clients configured with
+//! `circuit_breaker { ... }`.
+//!
+//! A circuit opens after `failure_threshold` consecutive failures, each
+//! within `window_seconds` of the previous one. While open, fallback,
+//! round-robin and weighted strategies skip the client. Once
+//! `cooldown_seconds` have passed the circuit is half-open: a single call is
+//! let through as a probe, which closes the circuit on success and re-opens it
+//! on failure. Other calls keep skipping the client while the probe runs.
+
+use std::{
+    collections::HashMap,
+    sync::{Mutex, OnceLock},
+};
+
+use internal_llm_client::CircuitBreakerConfig;
+use serde::Serialize;
+use web_time::{Duration, Instant};
+
+use super::LLMResponse;
+
+#[derive(Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    last_failure: Option<Instant>,
+    opened_at: Option<Instant>,
+    cooldown: Duration,
+    /// Set while the probe of a half-open circuit is running.
+    probe_in_flight: bool,
+    probe_started: Option<Instant>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CircuitStatus {
+    pub client: String,
+    pub state: CircuitState,
+    pub consecutive_failures: u32,
+    /// Time left until a probe is let through, while open.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_in_ms: Option<u64>,
+}
+
+static BREAKERS: OnceLock<Mutex<HashMap<String, Breaker>>> = OnceLock::new();
+
+fn breakers() -> std::sync::MutexGuard<'static, HashMap<String, Breaker>> {
+    BREAKERS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+impl Breaker {
+    fn state(&self, now: Instant) -> CircuitState {
+        match self.opened_at {
+            None => CircuitState::Closed,
+            Some(t) if now.duration_since(t) < self.cooldown => CircuitState::Open,
+            Some(_) => CircuitState::HalfOpen,
+        }
+    }
+
+    /// Whether a probe is running. A probe that never reported back, because
+    /// its call was cancelled, is given up on after a cooldown.
+    fn probing(&self, now: Instant) -> bool {
+        self.probe_in_flight
+            && self
+                .probe_started
+                .is_some_and(|t| now.duration_since(t) < self.cooldown)
+    }
+
+    fn is_open(&self, now: Instant) -> bool {
+        match self.state(now) {
+            CircuitState::Closed => false,
+            CircuitState::Open => true,
+            CircuitState::HalfOpen => self.probing(now),
+        }
+    }
+
+    fn try_acquire(&mut self, now: Instant) -> bool {
+        match self.state(now) {
+            CircuitState::Closed => true,
+            CircuitState::Open => false,
+            CircuitState::HalfOpen => {
+                if self.probing(now) {
+                    return false;
+                }
+                self.probe_in_flight = true;
+                self.probe_started = Some(now);
+                true
+            }
+        }
+    }
+
+    fn record(
+        &mut self,
+        client: &str,
+        config: &CircuitBreakerConfig,
+        response: &LLMResponse,
+        now: Instant,
+    ) {
+        self.cooldown = Duration::from_secs(config.cooldown_seconds);
+        let was = self.state(now);
+
+        match response {
+            LLMResponse::Success(_) => {
+                *self = Breaker {
+                    cooldown: self.cooldown,
+                    ..Default::default()
+                };
+            }
+            LLMResponse::LLMFailure(_) => {
+                let window = Duration::from_secs(config.window_seconds);
+                self.consecutive_failures = match self.last_failure {
+                    Some(t) if now.duration_since(t) <= window => self.consecutive_failures + 1,
+                    _ => 1,
+                };
+                self.last_failure = Some(now);
+
+                let reopen = was == CircuitState::HalfOpen;
+                let trip = self.opened_at.is_none()
+                    && self.consecutive_failures >= config.failure_threshold;
+                if reopen || trip {
+                    self.opened_at = Some(now);
+                }
+            }
+            // Not the client's fault.
+            LLMResponse::UserFailure(_) | LLMResponse::InternalFailure(_) => {}
+        }
+        self.probe_in_flight = false;
+        self.probe_started = None;
+
+        let state = self.state(now);
+        if state != was {
+            trace_state_change(client, state, self.consecutive_failures, config);
+        }
+    }
+}
+
+/// Reports a circuit opening or closing as a `baml_events` tracing event, like
+/// the events of function calls.
+fn trace_state_change(
+    client: &str,
+    state: CircuitState,
+    consecutive_failures: u32,
+    config: &CircuitBreakerConfig,
+) {
+    match state {
+        CircuitState::Open => ::tracing::warn!(
+            target: "baml_events",
+            client,
+            consecutive_failures,
+            cooldown_seconds = config.cooldown_seconds,
+            "Circuit breaker for client {client} opened, skipping it for {}s",
+            config.cooldown_seconds
+        ),
+        CircuitState::Closed => ::tracing::info!(
+            target: "baml_events",
+            client,
+            "Circuit breaker for client {client} closed"
+        ),
+        // Only reached by time passing, not by a call.
+        CircuitState::HalfOpen => {}
+    }
+}
+
+/// Whether calls to `client` should currently be skipped: its circuit is
+/// open, or half-open with a probe running.
+pub fn is_open(client: &str) -> bool {
+    breakers()
+        .get(client)
+        .is_some_and(|b| b.is_open(Instant::now()))
+}
+
+/// Whether a call to `client` may go ahead now. Once the cooldown has passed,
+/// only the first caller gets through, as the probe; it must report its
+/// outcome with [`record`].
+pub fn try_acquire(client: &str) -> bool {
+    breakers()
+        .get_mut(client)
+        .is_none_or(|b| b.try_acquire(Instant::now()))
+}
+
+/// Feeds the outcome of a call to `client` into its circuit breaker.
+pub fn record(client: &str, config: &CircuitBreakerConfig, response: &LLMResponse) {
+    breakers().entry(client.to_string()).or_default().record(
+        client,
+        config,
+        response,
+        Instant::now(),
+    );
+}
+
+/// State of every client that has a circuit breaker and was called at least
+/// once, sorted by client name.
+pub fn status() -> Vec<CircuitStatus> {
+    let now = Instant::now();
+    let mut status = breakers()
+        .iter()
+        .map(|(client, b)| CircuitStatus {
+            client: client.clone(),
+            state: b.state(now),
+            consecutive_failures: b.consecutive_failures,
+            retry_in_ms: match b.state(now) {
+                CircuitState::Open => b
+                    .opened_at
+                    .map(|t| b.cooldown.saturating_sub(now.duration_since(t)).as_millis() as u64),
+                _ => None,
+            },
+        })
+        .collect::<Vec<_>>();
+    status.sort_by(|a, b| a.client.cmp(&b.client));
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn internal_failure() -> LLMResponse {
+        LLMResponse::InternalFailure("ignored".into())
+    }
+
+    fn http_failure() -> LLMResponse {
+        LLMResponse::LLMFailure(crate::internal::llm_client::LLMErrorResponse {
+            client: "Flaky".into(),
+            model: None,
+            prompt: internal_baml_jinja::RenderedPrompt::Completion(String::new()),
+            start_time: web_time::SystemTime::now(),
+            latency: Duration::ZERO,
+            request_options: Default::default(),
+            message: "boom".into(),
+            code: crate::internal::llm_client::ErrorCode::ServiceUnavailable,
+            retry_after: None,
+        })
+    }
+
+    fn success() -> LLMResponse {
+        LLMResponse::Success(crate::internal::llm_client::LLMCompleteResponse {
+            client: "Flaky".into(),
+            model: "gpt-4o".into(),
+            prompt: internal_baml_jinja::RenderedPrompt::Completion(String::new()),
+            request_options: Default::default(),
+            content: "ok".into(),
+            start_time: web_time::SystemTime::now(),
+            latency: Duration::ZERO,
+            metadata: crate::internal::llm_client::LLMCompleteResponseMetadata {
+                baml_is_complete: true,
+                finish_reason: None,
+                prompt_tokens: None,
+                output_tokens: None,
+                total_tokens: None,
+                cached: false,
+                cost: None,
+            },
+        })
+    }
+
+    /// A breaker that opened at `t0`, with a 30s cooldown.
+    fn open_breaker(t0: Instant) -> (Breaker, CircuitBreakerConfig) {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            window_seconds: 60,
+            cooldown_seconds: 30,
+        };
+        let mut breaker = Breaker::default();
+        breaker.record("Flaky", &config, &http_failure(), t0);
+        breaker.record("Flaky", &config, &http_failure(), t0);
+        assert_eq!(breaker.state(t0), CircuitState::Open);
+        (breaker, config)
+    }
+
+    #[test]
+    fn failures_outside_the_window_are_not_consecutive() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            window_seconds: 10,
+            cooldown_seconds: 30,
+        };
+        let t0 = Instant::now();
+        let mut breaker = Breaker::default();
+        breaker.record("Flaky", &config, &http_failure(), t0);
+        breaker.record(
+            "Flaky",
+            &config,
+            &http_failure(),
+            t0 + Duration::from_secs(11),
+        );
+        assert_eq!(breaker.consecutive_failures, 1);
+        assert_eq!(
+            breaker.state(t0 + Duration::from_secs(11)),
+            CircuitState::Closed
+        );
+    }
+
+    #[test]
+    fn half_open_lets_a_single_probe_through() {
+        let t0 = Instant::now();
+        let (mut breaker, config) = open_breaker(t0);
+        assert!(!breaker.try_acquire(t0 + Duration::from_secs(29)));
+
+        let t1 = t0 + Duration::from_secs(30);
+        assert_eq!(breaker.state(t1), CircuitState::HalfOpen);
+        assert!(!breaker.is_open(t1));
+        assert!(breaker.try_acquire(t1));
+        // Everyone else keeps skipping the client while the probe runs.
+        assert!(breaker.is_open(t1));
+        assert!(!breaker.try_acquire(t1));
+
+        breaker.record("Flaky", &config, &success(), t1);
+        assert_eq!(breaker.state(t1), CircuitState::Closed);
+        assert_eq!(breaker.consecutive_failures, 0);
+        assert!(breaker.try_acquire(t1));
+        assert!(breaker.try_acquire(t1));
+    }
+
+    #[test]
+    fn failed_probe_reopens() {
+        let t0 = Instant::now();
+        let (mut breaker, config) = open_breaker(t0);
+        let t1 = t0 + Duration::from_secs(30);
+        assert!(breaker.try_acquire(t1));
+
+        breaker.record("Flaky", &config, &http_failure(), t1);
+        assert_eq!(breaker.state(t1), CircuitState::Open);
+        assert!(!breaker.try_acquire(t1 + Duration::from_secs(29)));
+        // The next cooldown allows a new probe.
+        assert!(breaker.try_acquire(t1 + Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn abandoned_probe_is_replaced() {
+        let t0 = Instant::now();
+        let (mut breaker, _) = open_breaker(t0);
+        let t1 = t0 + Duration::from_secs(30);
+        assert!(breaker.try_acquire(t1));
+        assert!(!breaker.try_acquire(t1 + Duration::from_secs(29)));
+        // The probe never reported back.
+        assert!(breaker.try_acquire(t1 + Duration::from_secs(30)));
+    }
+
+    #[test]
+    fn opens_after_consecutive_failures() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 3,
+            ..Default::default()
+        };
+        let client = "circuit_breaker_tests::opens";
+
+        record(client, &config, &http_failure());
+        record(client, &config, &internal_failure());
+        record(client, &config, &http_failure());
+        assert!(!is_open(client));
+
+        record(client, &config, &http_failure());
+        assert!(is_open(client));
+        assert!(status()
+            .iter()
+            .any(|s| s.client == client && s.state == CircuitState::Open));
+    }
+}
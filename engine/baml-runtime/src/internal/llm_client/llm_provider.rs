@@ -8,13 +8,14 @@ use crate::{
 };
 
 use super::{
+    circuit_breaker,
     orchestrator::{
         ExecutionScope, IterOrchestrator, OrchestrationScope, OrchestrationState,
         OrchestratorNodeIterator,
     },
     primitive::LLMPrimitiveProvider,
     strategy::LLMStrategyProvider,
-    traits::WithRetryPolicy,
+    traits::{WithClientProperties, WithRetryPolicy},
 };
 
 pub enum LLMProvider {
@@ -31,6 +32,20 @@ impl std::fmt::Debug for LLMProvider {
     }
 }
 
+impl LLMProvider {
+    /// Whether this is a client whose circuit breaker is open. Strategies
+    /// never are, their own clients are checked when they are planned.
+    pub fn circuit_open(&self) -> bool {
+        match self {
+            LLMProvider::Primitive(provider) => {
+                provider.circuit_breaker_config().is_some()
+                    && circuit_breaker::is_open(provider.name())
+            }
+            LLMProvider::Strategy(_) => false,
+        }
+    }
+}
+
 impl WithRetryPolicy for LLMProvider {
     fn retry_policy_name(&self) -> Option<&str> {
         match self {
@@ -80,13 +95,17 @@ impl IterOrchestrator for Arc<LLMProvider> {
     ) -> Result<OrchestratorNodeIterator> {
         if let Some(retry_policy) = self.retry_policy_name() {
             let policy = client_lookup.get_retry_policy(retry_policy, ctx)?;
+            let retry_on = policy.retry_on();
             Ok(policy
                 .into_iter()
                 .enumerate()
                 .map(move |(idx, node)| {
-                    previous
-                        .clone()
-                        .extend(ExecutionScope::Retry(retry_policy.into(), idx, node))
+                    previous.clone().extend(ExecutionScope::Retry(
+                        retry_policy.into(),
+                        idx,
+                        node,
+                        retry_on.clone(),
+                    ))
                 })
                 .map(|scope| {
                     // repeat the same provider for each retry policy
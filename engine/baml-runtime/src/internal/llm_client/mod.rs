@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
 use colored::*;
+pub mod circuit_breaker;
 pub mod llm_provider;
 pub mod orchestrator;
 pub mod primitive;
@@ -14,7 +15,7 @@ use anyhow::{Context, Result};
 use baml_types::{BamlMap, BamlValueWithMeta, FieldType, JinjaExpression, ResponseCheck};
 use internal_baml_core::ir::{repr::IntermediateRepr, ClientWalker};
 use internal_baml_jinja::RenderedPrompt;
-use internal_llm_client::AllowedRoleMetadata;
+use internal_llm_client::{AllowedRoleMetadata, Coercion};
 pub use jsonish::ResponseBamlValue;
 use jsonish::{
     deserializer::{
@@ -70,6 +71,83 @@ pub fn parsed_value_to_response(
     Ok(ResponseBamlValue(response_value))
 }
 
+/// Rejects a parsed value that needed any of the `forbidden` coercions,
+/// configured with `forbid_coercions` on the function or its client.
+pub fn check_coercions(
+    value: ResponseBamlValue,
+    forbidden: &[Coercion],
+) -> Result<ResponseBamlValue> {
+    if forbidden.is_empty() {
+        return Ok(value);
+    }
+    let mut found = vec![];
+    for (path, flag) in value.flags() {
+        for coercion in flag_coercions(flag) {
+            let at = match path.as_str() {
+                "" => coercion.to_string(),
+                path => format!("{coercion} at {path}"),
+            };
+            if forbidden.contains(&coercion) && !found.contains(&at) {
+                found.push(at);
+            }
+        }
+    }
+    if found.is_empty() {
+        return Ok(value);
+    }
+    anyhow::bail!("Response needed forbidden coercions: {}", found.join(", "))
+}
+
+/// The coercions a parser flag stands for. Flags that only record how the
+/// value was picked, like union matches or checks, stand for none.
+fn flag_coercions(flag: &Flag) -> Vec<Coercion> {
+    let coercion = match flag {
+        Flag::ObjectFromMarkdown(_) => Coercion::Markdown,
+        Flag::ObjectFromFixedJson(fixes) if fixes.is_empty() => Coercion::FixedJson,
+        Flag::ObjectFromFixedJson(fixes) => {
+            return fixes
+                .iter()
+                .filter_map(|fix| match fix {
+                    jsonish::jsonish::Fixes::GreppedForJSON => Some(Coercion::JsonInText),
+                    jsonish::jsonish::Fixes::InferredArray => Some(Coercion::InferredArray),
+                    // XML is an answer format rather than a repair.
+                    jsonish::jsonish::Fixes::ParsedXml => None,
+                })
+                .collect()
+        }
+        Flag::ObjectToString(_) | Flag::JsonToString(_) => Coercion::ObjectToString,
+        Flag::ObjectToPrimitive(_) => Coercion::ObjectToPrimitive,
+        Flag::ExtraKey(..) => Coercion::ExtraKey,
+        Flag::ImpliedKey(_) => Coercion::ImpliedKey,
+        Flag::InferedObject(_) => Coercion::InferredObject,
+        Flag::SingleToArray => Coercion::SingleToArray,
+        Flag::SubstringMatch(_) | Flag::StrMatchOneFromMany(_) => Coercion::SubstringMatch,
+        Flag::StrippedNonAlphaNumeric(_) => Coercion::StrippedNonAlphanumeric,
+        Flag::StringToBool(_) => Coercion::StringToBool,
+        Flag::StringToFloat(_) => Coercion::StringToFloat,
+        Flag::FloatToInt(_) => Coercion::FloatToInt,
+        Flag::DefaultFromNoValue => Coercion::MissingValue,
+        Flag::DefaultButHadValue(_) | Flag::DefaultButHadUnparseableValue(_) => {
+            Coercion::DroppedValue
+        }
+        Flag::ArrayItemParseError(..)
+        | Flag::MapKeyParseError(..)
+        | Flag::MapValueParseError(..) => Coercion::SkippedItem,
+        Flag::ObjectToMap(_)
+        | Flag::StringToNull(_)
+        | Flag::StringToChar(_)
+        | Flag::FirstMatch(..)
+        | Flag::UnionMatch(..)
+        | Flag::OptionalDefaultFromNoValue
+        | Flag::DefaultFromAttribute
+        | Flag::NoFields(_)
+        | Flag::ConstraintResults(_)
+        | Flag::Incomplete
+        | Flag::Pending => return vec![],
+    };
+    vec![coercion]
+}
+
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum ResolveMediaUrls {
@@ -189,6 +267,10 @@ pub struct LLMErrorResponse {
     // Short error message
     pub message: String,
     pub code: ErrorCode,
+    /// How long the provider asked us to wait before trying again, from the
+    /// `Retry-After` or rate limit reset headers of the response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<web_time::Duration>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -198,6 +280,7 @@ pub enum ErrorCode {
     RateLimited,           // 429
     ServerError,           // 500
     ServiceUnavailable,    // 503
+    Timeout,               // 408, also used when a client timeout fires
 
     // We failed to parse the response
     UnsupportedResponse(u16),
@@ -214,6 +297,7 @@ impl std::fmt::Display for ErrorCode {
             ErrorCode::RateLimited => f.write_str("RateLimited (429)"),
             ErrorCode::ServerError => f.write_str("ServerError (500)"),
             ErrorCode::ServiceUnavailable => f.write_str("ServiceUnavailable (503)"),
+            ErrorCode::Timeout => f.write_str("Timeout (408)"),
             ErrorCode::UnsupportedResponse(code) => write!(f, "BadResponse {code}"),
             ErrorCode::Other(code) => write!(f, "Unspecified error code: {code}"),
         }
@@ -225,6 +309,7 @@ impl ErrorCode {
         match status.as_u16() {
             401 => ErrorCode::InvalidAuthentication,
             403 => ErrorCode::NotSupported,
+            408 => ErrorCode::Timeout,
             429 => ErrorCode::RateLimited,
             500 => ErrorCode::ServerError,
             503 => ErrorCode::ServiceUnavailable,
@@ -236,6 +321,7 @@ impl ErrorCode {
         match code {
             401 => ErrorCode::InvalidAuthentication,
             403 => ErrorCode::NotSupported,
+            408 => ErrorCode::Timeout,
             429 => ErrorCode::RateLimited,
             500 => ErrorCode::ServerError,
             503 => ErrorCode::ServiceUnavailable,
@@ -250,10 +336,16 @@ impl ErrorCode {
             ErrorCode::RateLimited => 429,
             ErrorCode::ServerError => 500,
             ErrorCode::ServiceUnavailable => 503,
+            ErrorCode::Timeout => 408,
             ErrorCode::UnsupportedResponse(code) => *code,
             ErrorCode::Other(code) => *code,
         }
     }
+
+    /// The HTTP status of the response, if the provider sent one.
+    pub fn http_status(&self) -> Option<u16> {
+        Some(self.to_u16()).filter(|code| (100..600).contains(code))
+    }
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -269,13 +361,30 @@ pub struct LLMCompleteResponse {
     pub metadata: LLMCompleteResponseMetadata,
 }
 
-#[derive(Clone, Debug, Serialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct LLMCompleteResponseMetadata {
     pub baml_is_complete: bool,
     pub finish_reason: Option<String>,
     pub prompt_tokens: Option<u64>,
     pub output_tokens: Option<u64>,
     pub total_tokens: Option<u64>,
+    /// The response was served from the client's response cache.
+    #[serde(default)]
+    pub cached: bool,
+    /// What the call cost in US dollars, if the client or its model has
+    /// pricing. Responses served from the cache cost nothing.
+    #[serde(default)]
+    pub cost: Option<f64>,
+}
+
+impl LLMCompleteResponseMetadata {
+    /// `. Cost: $0.001234` if the cost is known, for the summary line of a
+    /// response.
+    fn cost_suffix(&self) -> String {
+        self.cost
+            .map(|cost| format!(". Cost: ${cost:.6}"))
+            .unwrap_or_default()
+    }
 }
 
 // This is how the response gets logged if you print the result to the console.
@@ -286,10 +395,11 @@ impl std::fmt::Display for LLMCompleteResponse {
             f,
             "{}",
             format!(
-                "Client: {} ({}) - {}ms. StopReason: {}. Tokens(in/out): {}/{}",
+                "Client: {} ({}) - {}ms{}. StopReason: {}. Tokens(in/out): {}/{}{}",
                 self.client,
                 self.model,
                 self.latency.as_millis(),
+                if self.metadata.cached { " (cached)" } else { "" },
                 self.metadata.finish_reason.as_deref().unwrap_or("unknown"),
                 self.metadata
                     .prompt_tokens
@@ -299,6 +409,7 @@ impl std::fmt::Display for LLMCompleteResponse {
                     .output_tokens
                     .map(|t| t.to_string())
                     .unwrap_or_else(|| "unknown".to_string()),
+                self.metadata.cost_suffix(),
             )
             .yellow()
         )?;
@@ -316,10 +427,11 @@ impl crate::tracing::Visualize for LLMCompleteResponse {
             format!(
                 "{}",
                 format!(
-                    "Client: {} ({}) - {}ms. StopReason: {}. Tokens(in/out): {}/{}",
+                    "Client: {} ({}) - {}ms{}. StopReason: {}. Tokens(in/out): {}/{}{}",
                     self.client,
                     self.model,
                     self.latency.as_millis(),
+                    if self.metadata.cached { " (cached)" } else { "" },
                     self.metadata.finish_reason.as_deref().unwrap_or("unknown"),
                     self.metadata
                         .prompt_tokens
@@ -329,6 +441,7 @@ impl crate::tracing::Visualize for LLMCompleteResponse {
                         .output_tokens
                         .map(|t| t.to_string())
                         .unwrap_or_else(|| "unknown".to_string()),
+                    self.metadata.cost_suffix(),
                 )
                 .yellow()
             ),
@@ -438,6 +551,43 @@ mod tests {
         assert!(response.is_ok());
     }
 
+    #[test]
+    fn rejects_forbidden_coercions() {
+        let ir = mk_ir();
+        let val = BamlValueWithFlags::Class(
+            "Foo".to_string(),
+            DeserializerConditions {
+                flags: vec![Flag::ObjectFromMarkdown(0)],
+            },
+            vec![
+                (
+                    "i".to_string(),
+                    BamlValueWithFlags::Int(ValueWithFlags {
+                        value: 12,
+                        flags: DeserializerConditions {
+                            flags: vec![Flag::FloatToInt(12.5)],
+                        },
+                    }),
+                ),
+                ("s".to_string(), mk_string("H")),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let response = parsed_value_to_response(&ir, val, &FieldType::class("Foo"), false)
+            .expect("value is valid");
+
+        let allowed = check_coercions(response.clone(), &[Coercion::SubstringMatch]);
+        assert!(allowed.is_ok());
+
+        let err = check_coercions(response, &[Coercion::FloatToInt, Coercion::Markdown])
+            .expect_err("float_to_int and markdown are forbidden");
+        assert_eq!(
+            err.to_string(),
+            "Response needed forbidden coercions: markdown, float_to_int at i"
+        );
+    }
+
     fn mk_null() -> BamlValueWithFlags {
         BamlValueWithFlags::Null(DeserializerConditions::default())
     }
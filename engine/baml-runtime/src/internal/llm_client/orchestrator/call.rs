@@ -7,14 +7,14 @@ use web_time::Duration;
 use crate::{
     internal::{
         llm_client::{
-            parsed_value_to_response, traits::{WithClientProperties, WithPrompt, WithSingleCallable}, LLMErrorResponse, LLMResponse
+            check_coercions, parsed_value_to_response, traits::{WithClientProperties, WithPrompt, WithSingleCallable}, LLMErrorResponse, LLMResponse
         },
         prompt_renderer::PromptRenderer,
     },
     RuntimeContext,
 };
 
-use super::{OrchestrationScope, OrchestratorNodeIterator};
+use super::{circuit_skippable, OrchestrationScope, OrchestratorNodeIterator};
 
 pub async fn orchestrate(
     iter: OrchestratorNodeIterator,
@@ -34,7 +34,14 @@ pub async fn orchestrate(
     let mut results = Vec::new();
     let mut total_sleep_duration = std::time::Duration::from_secs(0);
 
-    for node in iter {
+    let function_forbids = prompt.forbidden_coercions();
+    let skippable = circuit_skippable(&iter);
+    // Clients whose remaining retries were given up on, by scope name.
+    let mut retries_given_up = Vec::new();
+    for (node, skippable) in iter.into_iter().zip(skippable) {
+        if retries_given_up.contains(&node.scope.name()) {
+            continue;
+        }
         let prompt = match node.render_prompt(ir, prompt, ctx, params).await {
             Ok(p) => p,
             Err(e) => {
@@ -46,7 +53,19 @@ pub async fn orchestrate(
                 continue;
             }
         };
-        let response = node.single_call(ctx, &prompt).await;
+        if let Some(response) = node.context_window_response(ctx, &prompt) {
+            retries_given_up.push(node.scope.name());
+            results.push((node.scope, response, None));
+            continue;
+        }
+        if skippable && !node.circuit_admits() {
+            let response = node.circuit_open_response(prompt);
+            results.push((node.scope, response, None));
+            continue;
+        }
+
+        let response = node.with_cost(ctx, node.single_call(ctx, &prompt).await);
+        node.record_outcome(&response);
         let parsed_response = match &response {
             LLMResponse::Success(s) => {
                 if !node
@@ -60,7 +79,8 @@ pub async fn orchestrate(
                         finish_reason: s.metadata.finish_reason.clone(),
                     })))
                 } else {
-                    Some(parse_fn(&s.content))
+                    let forbidden = function_forbids.unwrap_or(node.forbidden_coercions());
+                    Some(parse_fn(&s.content).and_then(|v| check_coercions(v, forbidden)))
                 }
             },
             LLMResponse::LLMFailure(LLMErrorResponse { code, client, message, .. }) => {
@@ -79,20 +99,23 @@ pub async fn orchestrate(
             _ => None,
         };
 
-        let sleep_duration = node.error_sleep_duration().cloned();
+        let sleep_duration = node.error_sleep_duration(&response);
+        let retry_allowed = node.retry_allowed(&response);
+        if !retry_allowed {
+            retries_given_up.push(node.scope.name());
+        }
+        let should_continue = node.should_continue(&response, parsed_response.as_ref());
         results.push((
             node.scope,
             response,
             parsed_response,
         ));
 
-        // Currently, we break out of the loop if an LLM responded, even if we couldn't parse the result.
-        if results
-            .last()
-            .map_or(false, |(_, r, _)| matches!(r, LLMResponse::Success(_)))
-        {
+        // Once an LLM responded we stop, even if we couldn't parse the result, unless
+        // a retry policy or fallback strategy opted into retrying on that failure.
+        if !should_continue {
             break;
-        } else if let Some(duration) = sleep_duration {
+        } else if let Some(duration) = sleep_duration.filter(|_| retry_allowed) {
             total_sleep_duration += duration;
             async_std::task::sleep(duration).await;
         }
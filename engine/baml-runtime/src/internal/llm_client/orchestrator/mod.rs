@@ -10,9 +10,9 @@ use crate::{
 };
 
 use super::traits::{WithClientProperties, WithRenderRawCurl};
-use super::LLMCompleteResponse;
+use super::{circuit_breaker, ErrorCode, LLMCompleteResponse, LLMErrorResponse, ResponseBamlValue};
 use super::{
-    strategy::roundrobin::RoundRobinStrategy,
+    strategy::{roundrobin::RoundRobinStrategy, weighted::WeightedStrategy},
     traits::{StreamResponse, WithPrompt, WithSingleCallable, WithStreamable},
     LLMResponse,
 };
@@ -26,8 +26,12 @@ use baml_types::BamlValue;
 use internal_baml_core::ir::repr::IntermediateRepr;
 use internal_baml_jinja::RenderedChatMessage;
 use internal_baml_jinja::RenderedPrompt;
+use internal_llm_client::RetryOn;
 use serde::Serialize;
 use std::{collections::HashMap, sync::Arc};
+/// The longest a `Retry-After` from a provider can extend a retry delay.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+
 pub struct OrchestratorNode {
     pub scope: OrchestrationScope,
     pub provider: Arc<LLMPrimitiveProvider>,
@@ -37,15 +41,19 @@ impl std::fmt::Display for ExecutionScope {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ExecutionScope::Direct(s) => write!(f, "{}", s),
-            ExecutionScope::Retry(policy, count, delay) => {
-                write!(f, "Retry({}, {}, {}ms)", policy, count, delay.as_millis())
+            ExecutionScope::Retry(policy, count, delay, _) => {
+                let delay = delay.unwrap_or_default().as_millis();
+                write!(f, "Retry({}, {}, {}ms)", policy, count, delay)
             }
             ExecutionScope::RoundRobin(strategy, index) => {
                 write!(f, "RoundRobin({}, {})", strategy.name, index)
             }
-            ExecutionScope::Fallback(strategy, index) => {
+            ExecutionScope::Fallback(strategy, index, _) => {
                 write!(f, "Fallback({}, {})", strategy, index)
             }
+            ExecutionScope::Weighted(strategy, index) => {
+                write!(f, "Weighted({}, {})", strategy.name, index)
+            }
         }
     }
 }
@@ -75,13 +83,187 @@ impl OrchestratorNode {
         }
     }
 
-    pub fn error_sleep_duration(&self) -> Option<&Duration> {
-        // in reverse find the first retry scope, and return the delay
-        self.scope.scope.iter().rev().find_map(|scope| match scope {
-            ExecutionScope::Retry(_, _, delay) if !delay.is_zero() => Some(delay),
-            _ => None,
+    /// How long to wait before the next node after this one produced
+    /// `response`. A `Retry-After` sent by the provider extends the delay of
+    /// the retry policy, up to `MAX_RETRY_AFTER`.
+    pub fn error_sleep_duration(&self, response: &LLMResponse) -> Option<Duration> {
+        // in reverse find the first retry scope with another attempt left,
+        // and return its delay
+        let delay = self
+            .scope
+            .scope
+            .iter()
+            .rev()
+            .find_map(|scope| match scope {
+                ExecutionScope::Retry(_, _, delay, _) => *delay,
+                _ => None,
+            })?;
+        let delay = match response {
+            LLMResponse::LLMFailure(LLMErrorResponse {
+                retry_after: Some(after),
+                ..
+            }) => delay.max((*after).min(MAX_RETRY_AFTER)),
+            _ => delay,
+        };
+        (!delay.is_zero()).then_some(delay)
+    }
+
+    /// The failures this node should be retried on, combining every retry policy
+    /// and fallback strategy it is nested in.
+    pub fn retry_on(&self) -> RetryOn {
+        self.scope
+            .scope
+            .iter()
+            .filter_map(|scope| match scope {
+                ExecutionScope::Retry(_, _, _, retry_on) => Some(retry_on.clone()),
+                ExecutionScope::Fallback(_, _, retry_on) => Some(retry_on.clone()),
+                _ => None,
+            })
+            .reduce(RetryOn::union)
+            .unwrap_or_default()
+    }
+
+    /// Whether the retry policies this node is nested in allow retrying the
+    /// failure in `response`. When they don't, the remaining retries of the
+    /// same client are skipped, but a fallback strategy still moves on.
+    pub fn retry_allowed(&self, response: &LLMResponse) -> bool {
+        let LLMResponse::LLMFailure(e) = response else {
+            return true;
+        };
+        self.scope
+            .scope
+            .iter()
+            .filter_map(|scope| match scope {
+                ExecutionScope::Retry(_, _, _, retry_on) => Some(retry_on.clone()),
+                _ => None,
+            })
+            .reduce(RetryOn::union)
+            .is_none_or(|retry_on| retry_on.http_status(e.code.http_status()))
+    }
+
+    /// Lets the client's circuit breaker and the weighted strategies this node
+    /// was picked by learn from the outcome of its call.
+    pub fn record_outcome(&self, response: &LLMResponse) {
+        if let Some(config) = self.circuit_breaker_config() {
+            circuit_breaker::record(self.provider.name(), config, response);
+        }
+        for scope in &self.scope.scope {
+            if let ExecutionScope::Weighted(strategy, index) = scope {
+                strategy.record(*index, response);
+            }
+        }
+    }
+
+    /// Whether the circuit breaker of this node's client is open.
+    pub fn circuit_open(&self) -> bool {
+        self.circuit_breaker_config().is_some() && circuit_breaker::is_open(self.provider.name())
+    }
+
+    /// Whether the circuit breaker of this node's client lets a call through
+    /// now. A half-open circuit lets a single probe through, whose outcome
+    /// must be passed to [`Self::record_outcome`].
+    pub fn circuit_admits(&self) -> bool {
+        self.circuit_breaker_config().is_none()
+            || circuit_breaker::try_acquire(self.provider.name())
+    }
+
+    /// The response recorded in place of a call skipped because the circuit
+    /// breaker of the client is open.
+    pub fn circuit_open_response(&self, prompt: RenderedPrompt) -> LLMResponse {
+        LLMResponse::LLMFailure(LLMErrorResponse {
+            client: self.provider.name().to_string(),
+            model: None,
+            prompt,
+            request_options: self.provider.request_options().clone(),
+            start_time: web_time::SystemTime::now(),
+            latency: Duration::ZERO,
+            message: format!(
+                "Skipped client {}: its circuit breaker is open",
+                self.provider.name()
+            ),
+            code: ErrorCode::ServiceUnavailable,
+            retry_after: None,
         })
     }
+
+    /// The response recorded in place of a call skipped because `prompt` is
+    /// larger than the `max_context_tokens` of the client, if it is. The
+    /// provider would reject it anyway, so retrying it is pointless, but a
+    /// fallback may have a larger context window.
+    pub fn context_window_response(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+    ) -> Option<LLMResponse> {
+        let max_tokens = self.max_context_tokens()?;
+        let count = self.provider.count_prompt_tokens(ctx, prompt);
+        if count.tokens as u64 <= max_tokens {
+            return None;
+        }
+        Some(LLMResponse::LLMFailure(LLMErrorResponse {
+            client: self.provider.name().to_string(),
+            model: self.provider.model().map(str::to_string),
+            prompt: prompt.clone(),
+            request_options: self.provider.request_options().clone(),
+            start_time: web_time::SystemTime::now(),
+            latency: Duration::ZERO,
+            message: format!(
+                "Skipped client {}: the prompt has {count}, more than its max_context_tokens of {max_tokens}",
+                self.provider.name()
+            ),
+            code: ErrorCode::Other(400),
+            retry_after: None,
+        }))
+    }
+
+    /// `response` with the cost of the call filled in, from the pricing of
+    /// the client, or else of its model in the client registry.
+    pub fn with_cost(&self, ctx: &RuntimeContext, mut response: LLMResponse) -> LLMResponse {
+        let LLMResponse::Success(s) = &mut response else {
+            return response;
+        };
+        let pricing = self.pricing().or_else(|| {
+            [self.provider.model(), Some(s.model.as_str())]
+                .into_iter()
+                .flatten()
+                .find_map(|model| ctx.model_pricing.get(model))
+        });
+        s.metadata.cost = pricing.and_then(|pricing| {
+            if s.metadata.cached {
+                Some(0.0)
+            } else {
+                pricing.cost(s.metadata.prompt_tokens, s.metadata.output_tokens)
+            }
+        });
+        response
+    }
+
+    /// Whether orchestration should move on to the next node after this one
+    /// produced `response` and `parsed`.
+    pub fn should_continue(
+        &self,
+        response: &LLMResponse,
+        parsed: Option<&Result<ResponseBamlValue>>,
+    ) -> bool {
+        let retry_on = self.retry_on();
+        match response {
+            LLMResponse::Success(_) => match parsed {
+                Some(Err(e)) => {
+                    let is_assert_failure = e
+                        .downcast_ref::<jsonish::CoercionError>()
+                        .is_some_and(|e| e.is_assert_failure());
+                    if is_assert_failure {
+                        retry_on.assert_failed
+                    } else {
+                        retry_on.parse_error
+                    }
+                }
+                _ => false,
+            },
+            LLMResponse::LLMFailure(e) => retry_on.http_status(e.code.http_status()),
+            LLMResponse::UserFailure(_) | LLMResponse::InternalFailure(_) => true,
+        }
+    }
 }
 
 #[derive(Debug, Default, Clone)]
@@ -139,16 +321,31 @@ impl OrchestrationScope {
 #[derive(Clone, Debug)]
 pub enum ExecutionScope {
     Direct(String),
-    // PolicyName, RetryCount, RetryDelayMs
-    Retry(String, usize, Duration),
+    // PolicyName, RetryCount, RetryDelay (None for the last attempt), RetryOn
+    Retry(String, usize, Option<Duration>, RetryOn),
     // StrategyName, ClientIndex
     RoundRobin(Arc<RoundRobinStrategy>, usize),
-    // StrategyName, ClientIndex
-    Fallback(String, usize),
+    // StrategyName, ClientIndex, RetryOn
+    Fallback(String, usize, RetryOn),
+    // Strategy, ClientIndex
+    Weighted(Arc<WeightedStrategy>, usize),
 }
 
 pub type OrchestratorNodeIterator = Vec<OrchestratorNode>;
 
+/// For each node of a plan, whether it can be skipped when its circuit
+/// breaker doesn't let the call through, because a later node whose circuit
+/// is not open can still be tried.
+pub(super) fn circuit_skippable(nodes: &[OrchestratorNode]) -> Vec<bool> {
+    let open = nodes
+        .iter()
+        .map(OrchestratorNode::circuit_open)
+        .collect::<Vec<_>>();
+    (0..open.len())
+        .map(|idx| open[idx + 1..].contains(&false))
+        .collect()
+}
+
 #[derive(Default)]
 pub struct OrchestrationState {
     // Number of times a client was used so far
@@ -240,4 +437,32 @@ impl WithClientProperties for OrchestratorNode {
     fn allowed_roles(&self) -> Vec<String> {
         self.provider.allowed_roles()
     }
+
+    fn cache_config(&self) -> Option<&internal_llm_client::CacheConfig> {
+        self.provider.cache_config()
+    }
+
+    fn circuit_breaker_config(&self) -> Option<&internal_llm_client::CircuitBreakerConfig> {
+        self.provider.circuit_breaker_config()
+    }
+
+    fn rate_limit_config(&self) -> Option<&internal_llm_client::RateLimitConfig> {
+        self.provider.rate_limit_config()
+    }
+
+    fn timeout_config(&self) -> Option<&internal_llm_client::TimeoutConfig> {
+        self.provider.timeout_config()
+    }
+
+    fn forbidden_coercions(&self) -> &[internal_llm_client::Coercion] {
+        self.provider.forbidden_coercions()
+    }
+
+    fn max_context_tokens(&self) -> Option<u64> {
+        self.provider.max_context_tokens()
+    }
+
+    fn pricing(&self) -> Option<&internal_llm_client::Pricing> {
+        self.provider.pricing()
+    }
 }
@@ -8,7 +8,7 @@ use web_time::Duration;
 use crate::{
     internal::{
         llm_client::{
-            parsed_value_to_response,
+            check_coercions, parsed_value_to_response,
             traits::{WithClientProperties, WithPrompt, WithStreamable},
             LLMErrorResponse, LLMResponse, ResponseBamlValue,
         },
@@ -17,7 +17,7 @@ use crate::{
     FunctionResult, RuntimeContext,
 };
 
-use super::{OrchestrationScope, OrchestratorNodeIterator};
+use super::{circuit_skippable, OrchestrationScope, OrchestratorNodeIterator};
 
 pub async fn orchestrate_stream<F>(
     iter: OrchestratorNodeIterator,
@@ -25,7 +25,7 @@ pub async fn orchestrate_stream<F>(
     ctx: &RuntimeContext,
     prompt: &PromptRenderer,
     params: &BamlValue,
-    partial_parse_fn: impl Fn(&str) -> Result<ResponseBamlValue>,
+    partial_parse_fn: impl Fn(&mut jsonish::IncrementalParser, &str) -> Result<ResponseBamlValue>,
     parse_fn: impl Fn(&str) -> Result<ResponseBamlValue>,
     on_event: Option<F>,
 ) -> (
@@ -43,7 +43,14 @@ where
     let mut total_sleep_duration = std::time::Duration::from_secs(0);
 
     //advanced curl viewing, use render_raw_curl on each node. TODO
-    for node in iter {
+    let function_forbids = prompt.forbidden_coercions();
+    let skippable = circuit_skippable(&iter);
+    // Clients whose remaining retries were given up on, by scope name.
+    let mut retries_given_up = Vec::new();
+    for (node, skippable) in iter.into_iter().zip(skippable) {
+        if retries_given_up.contains(&node.scope.name()) {
+            continue;
+        }
         let prompt = match node.render_prompt(ir, prompt, ctx, params).await {
             Ok(p) => p,
             Err(e) => {
@@ -56,14 +63,28 @@ where
             }
         };
 
+        if let Some(response) = node.context_window_response(ctx, &prompt) {
+            retries_given_up.push(node.scope.name());
+            results.push((node.scope, response, None));
+            continue;
+        }
+        if skippable && !node.circuit_admits() {
+            let response = node.circuit_open_response(prompt);
+            results.push((node.scope, response, None));
+            continue;
+        }
+
         let (system_start, instant_start) = (web_time::SystemTime::now(), web_time::Instant::now());
         let stream_res = node.stream(ctx, &prompt).await;
+        // Each chunk carries the whole response so far, which the parser
+        // picks up from where the previous chunk left off.
+        let mut parser = jsonish::IncrementalParser::default();
         let final_response = match stream_res {
             Ok(response) => response
                 .map(|stream_part| {
                     if let Some(on_event) = on_event.as_ref() {
                         if let LLMResponse::Success(s) = &stream_part {
-                            let response_value = partial_parse_fn(&s.content);
+                            let response_value = partial_parse_fn(&mut parser, &s.content);
                             let response_value_without_flags = match response_value {
                                 Ok(baml_value) => Ok(ResponseBamlValue(
                                     baml_value.0.map_meta_owned(|m| (vec![], m.1, m.2)),
@@ -91,10 +112,13 @@ where
                         request_options: node.provider.request_options().clone(),
                         message: "Stream ended without response".to_string(),
                         code: crate::internal::llm_client::ErrorCode::from_u16(2),
+                        retry_after: None,
                     })
                 }),
             Err(response) => response,
         };
+        let final_response = node.with_cost(ctx, final_response);
+        node.record_outcome(&final_response);
 
         let response_value = match &final_response {
             LLMResponse::Success(s) => {
@@ -111,7 +135,8 @@ where
                         }
                     )))
                 } else {
-                    Some(parse_fn(&s.content))
+                    let forbidden = function_forbids.unwrap_or(node.forbidden_coercions());
+                    Some(parse_fn(&s.content).and_then(|v| check_coercions(v, forbidden)))
                 }
             }
             LLMResponse::LLMFailure(LLMErrorResponse { code, client, message, .. }) => {
@@ -127,16 +152,19 @@ where
             }
             _ => None,
         };
-        let sleep_duration = node.error_sleep_duration().cloned();
+        let sleep_duration = node.error_sleep_duration(&final_response);
+        let retry_allowed = node.retry_allowed(&final_response);
+        if !retry_allowed {
+            retries_given_up.push(node.scope.name());
+        }
+        let should_continue = node.should_continue(&final_response, response_value.as_ref());
         results.push((node.scope, final_response, response_value));
 
-        // Currently, we break out of the loop if an LLM responded, even if we couldn't parse the result.
-        if results
-            .last()
-            .map_or(false, |(_, r, _)| matches!(r, LLMResponse::Success(_)))
-        {
+        // Once an LLM responded we stop, even if we couldn't parse the result, unless
+        // a retry policy or fallback strategy opted into retrying on that failure.
+        if !should_continue {
             break;
-        } else if let Some(duration) = sleep_duration {
+        } else if let Some(duration) = sleep_duration.filter(|_| retry_allowed) {
             total_sleep_duration += duration;
             async_std::task::sleep(duration).await;
         }
@@ -14,16 +14,18 @@ use internal_baml_jinja::{
     ChatMessagePart, RenderContext_Client, RenderedChatMessage, RenderedPrompt,
 };
 use internal_llm_client::{
-    anthropic::ResolvedAnthropic, AllowedRoleMetadata, ClientProvider, ResolvedClientProperty,
-    UnresolvedClientProperty,
+    anthropic::ResolvedAnthropic, AllowedRoleMetadata, ClientProvider, OutputMode,
+    ResolvedClientProperty, UnresolvedClientProperty,
 };
 
 use crate::{
     client_registry::ClientProperty,
     internal::llm_client::{
         primitive::{
-            anthropic::types::{AnthropicMessageResponse, StopReason},
+            anthropic::types::{AnthropicMessageResponse, ContentType, StopReason},
+            output_tools,
             request::{make_parsed_request, make_request, RequestBuilder},
+            tool_calls_to_content,
         },
         traits::{
             SseResponseTrait, StreamResponse, WithChat, WithClient, WithNoCompletion,
@@ -32,7 +34,7 @@ use crate::{
         ErrorCode, LLMCompleteResponse, LLMCompleteResponseMetadata, LLMErrorResponse, LLMResponse,
         ModelFeatures,
     },
-    request::create_client,
+    request::create_llm_client,
 };
 use serde_json::json;
 
@@ -96,6 +98,29 @@ impl WithClientProperties for AnthropicClient {
     fn allowed_roles(&self) -> Vec<String> {
         self.properties.allowed_roles()
     }
+    fn cache_config(&self) -> Option<&internal_llm_client::CacheConfig> {
+        self.properties.cache.as_ref()
+    }
+    fn circuit_breaker_config(&self) -> Option<&internal_llm_client::CircuitBreakerConfig> {
+        self.properties.circuit_breaker.as_ref()
+    }
+    fn rate_limit_config(&self) -> Option<&internal_llm_client::RateLimitConfig> {
+        self.properties.rate_limit.as_ref()
+    }
+    fn timeout_config(&self) -> Option<&internal_llm_client::TimeoutConfig> {
+        self.properties.timeouts.as_ref()
+    }
+    fn forbidden_coercions(&self) -> &[internal_llm_client::Coercion] {
+        &self.properties.forbid_coercions
+    }
+
+    fn max_context_tokens(&self) -> Option<u64> {
+        self.properties.max_context_tokens
+    }
+
+    fn pricing(&self) -> Option<&internal_llm_client::Pricing> {
+        self.properties.pricing.as_ref()
+    }
 }
 
 impl WithClient for AnthropicClient {
@@ -122,6 +147,8 @@ impl SseResponseTrait for AnthropicClient {
         let prompt = prompt.to_vec();
         let client_name = self.context.name.clone();
         let params = self.properties.properties.clone();
+        // Arguments of the `tool_use` blocks, keyed by content block index.
+        let mut tool_inputs: Vec<(u32, String)> = vec![];
 
         Ok(Box::pin(
             resp.bytes_stream()
@@ -144,6 +171,8 @@ impl SseResponseTrait for AnthropicClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cached: false,
+                            cost: None,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -169,6 +198,7 @@ impl SseResponseTrait for AnthropicClient {
                                         latency: instant_start.elapsed(),
                                         message: format!("Failed to parse event: {:#?}", e),
                                         code: ErrorCode::UnsupportedResponse(2),
+                                        retry_after: None,
                                     },
                                 )));
                             }
@@ -180,7 +210,9 @@ impl SseResponseTrait for AnthropicClient {
                                 let inner = &mut inner.metadata;
                                 inner.baml_is_complete = matches!(
                                     body.stop_reason,
-                                    Some(StopReason::StopSequence) | Some(StopReason::EndTurn)
+                                    Some(StopReason::StopSequence)
+                                        | Some(StopReason::EndTurn)
+                                        | Some(StopReason::ToolUse)
                                 );
                                 inner.finish_reason =
                                     body.stop_reason.as_ref().map(ToString::to_string);
@@ -190,9 +222,28 @@ impl SseResponseTrait for AnthropicClient {
                                     Some(body.usage.input_tokens + body.usage.output_tokens);
                             }
                             MessageChunk::ContentBlockDelta(event) => {
-                                inner.content += &event.delta.text;
+                                match event.delta.partial_json {
+                                    Some(partial_json) => {
+                                        if let Some((_, input)) =
+                                            tool_inputs.iter_mut().find(|(i, _)| *i == event.index)
+                                        {
+                                            *input += &partial_json;
+                                        }
+                                        inner.content = tool_calls_to_content(
+                                            tool_inputs.iter().map(|(_, input)| input.as_str()),
+                                        );
+                                    }
+                                    None if tool_inputs.is_empty() => {
+                                        inner.content += &event.delta.text;
+                                    }
+                                    None => (),
+                                }
+                            }
+                            MessageChunk::ContentBlockStart(event) => {
+                                if event.content_block._type == ContentType::ToolUse {
+                                    tool_inputs.push((event.index, String::new()));
+                                }
                             }
-                            MessageChunk::ContentBlockStart(_) => (),
                             MessageChunk::ContentBlockStop(_) => (),
                             MessageChunk::Ping => (),
                             MessageChunk::MessageDelta(body) => {
@@ -200,7 +251,9 @@ impl SseResponseTrait for AnthropicClient {
 
                                 inner.baml_is_complete = matches!(
                                     body.delta.stop_reason,
-                                    Some(StopReason::StopSequence) | Some(StopReason::EndTurn)
+                                    Some(StopReason::StopSequence)
+                                        | Some(StopReason::EndTurn)
+                                        | Some(StopReason::ToolUse)
                                 );
                                 inner.finish_reason = body
                                     .delta
@@ -230,6 +283,7 @@ impl SseResponseTrait for AnthropicClient {
                                         latency: instant_start.elapsed(),
                                         message: err.message,
                                         code: ErrorCode::Other(2),
+                                        retry_after: None,
                                     },
                                 )));
                             }
@@ -247,11 +301,11 @@ impl SseResponseTrait for AnthropicClient {
 impl WithStreamChat for AnthropicClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &[RenderedChatMessage],
     ) -> StreamResponse {
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
@@ -279,7 +333,7 @@ impl AnthropicClient {
                 allowed_metadata: properties.allowed_metadata.clone(),
             },
             retry_policy: client.retry_policy.clone(),
-            client: create_client()?,
+            client: create_llm_client(properties.timeouts.as_ref())?,
             properties,
         })
     }
@@ -306,7 +360,7 @@ impl AnthropicClient {
                 .retry_policy_id
                 .as_ref()
                 .map(|s| s.to_string()),
-            client: create_client()?,
+            client: create_llm_client(properties.timeouts.as_ref())?,
             properties,
         })
     }
@@ -320,6 +374,7 @@ impl RequestBuilder for AnthropicClient {
 
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &[RenderedChatMessage]>,
         allow_proxy: bool,
         stream: bool,
@@ -360,6 +415,23 @@ impl RequestBuilder for AnthropicClient {
             }
         }
 
+        if self.properties.output_mode == OutputMode::Tools {
+            let tools = output_tools(ctx)?;
+            // Force a tool call. With several candidate tools, let the model pick.
+            let tool_choice = match tools.as_slice() {
+                [tool] => json!({ "type": "tool", "name": tool.name }),
+                _ => json!({ "type": "any" }),
+            };
+            body_obj.insert(
+                "tools".into(),
+                tools
+                    .into_iter()
+                    .map(|tool| json!({ "name": tool.name, "input_schema": tool.parameters }))
+                    .collect(),
+            );
+            body_obj.entry("tool_choice").or_insert(tool_choice);
+        }
+
         if stream {
             body_obj.insert("stream".into(), true.into());
         }
@@ -373,19 +445,28 @@ impl RequestBuilder for AnthropicClient {
 }
 
 impl WithChat for AnthropicClient {
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
-        let (response, system_now, instant_now) = match make_parsed_request::<
-            AnthropicMessageResponse,
-        >(
-            self, either::Either::Right(prompt), false
-        )
-        .await
-        {
-            Ok(v) => v,
-            Err(e) => return e,
-        };
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+        let (response, system_now, instant_now) =
+            match make_parsed_request::<AnthropicMessageResponse>(
+                self,
+                ctx,
+                either::Either::Right(prompt),
+                false,
+            )
+            .await
+            {
+                Ok(v) => v,
+                Err(e) => return e,
+            };
 
-        if response.content.len() != 1 {
+        let tool_inputs = response
+            .content
+            .iter()
+            .filter_map(|c| c.input.as_ref().filter(|_| c.r#type == "tool_use"))
+            .map(|input| input.to_string())
+            .collect::<Vec<_>>();
+
+        if tool_inputs.is_empty() && response.content.len() != 1 {
             return LLMResponse::LLMFailure(LLMErrorResponse {
                 client: self.context.name.to_string(),
                 model: None,
@@ -398,13 +479,18 @@ impl WithChat for AnthropicClient {
                     response.content.len()
                 ),
                 code: ErrorCode::Other(200),
+                retry_after: None,
             });
         }
 
         LLMResponse::Success(LLMCompleteResponse {
             client: self.context.name.to_string(),
             prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.to_vec()),
-            content: response.content[0].text.clone(),
+            content: if tool_inputs.is_empty() {
+                response.content[0].text.clone()
+            } else {
+                tool_calls_to_content(tool_inputs.iter().map(String::as_str))
+            },
             start_time: system_now,
             latency: instant_now.elapsed(),
             request_options: self.properties.properties.clone(),
@@ -412,7 +498,9 @@ impl WithChat for AnthropicClient {
             metadata: LLMCompleteResponseMetadata {
                 baml_is_complete: matches!(
                     response.stop_reason,
-                    Some(StopReason::StopSequence) | Some(StopReason::EndTurn)
+                    Some(StopReason::StopSequence)
+                        | Some(StopReason::EndTurn)
+                        | Some(StopReason::ToolUse)
                 ),
                 finish_reason: response
                     .stop_reason
@@ -421,6 +509,8 @@ impl WithChat for AnthropicClient {
                 prompt_tokens: Some(response.usage.input_tokens),
                 output_tokens: Some(response.usage.output_tokens),
                 total_tokens: Some(response.usage.input_tokens + response.usage.output_tokens),
+                cached: false,
+                cost: None,
             },
         })
     }
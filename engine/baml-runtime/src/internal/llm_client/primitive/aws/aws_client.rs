@@ -8,7 +8,7 @@ use aws_sdk_bedrockruntime::{self as bedrock, operation::converse::ConverseOutpu
 use anyhow::{Context, Result};
 use aws_smithy_json::serialize::JsonObjectWriter;
 use aws_smithy_runtime_api::client::result::SdkError;
-use aws_smithy_types::Blob;
+use aws_smithy_types::{Blob, Document, Number};
 use baml_types::{BamlMap, BamlMediaContent};
 use baml_types::{BamlMedia, BamlMediaType};
 use futures::stream;
@@ -16,7 +16,8 @@ use internal_baml_core::ir::ClientWalker;
 use internal_baml_jinja::{ChatMessagePart, RenderContext_Client, RenderedChatMessage};
 use internal_llm_client::aws_bedrock::ResolvedAwsBedrock;
 use internal_llm_client::{
-    AllowedRoleMetadata, ClientProvider, ResolvedClientProperty, UnresolvedClientProperty,
+    AllowedRoleMetadata, ClientProvider, OutputMode, ResolvedClientProperty,
+    UnresolvedClientProperty,
 };
 use secrecy::ExposeSecret;
 use serde::Deserialize;
@@ -27,7 +28,7 @@ use web_time::SystemTime;
 use crate::client_registry::ClientProperty;
 use crate::internal::llm_client::traits::{ToProviderMessageExt, WithClientProperties};
 use crate::internal::llm_client::{
-    primitive::request::RequestBuilder,
+    primitive::{output_tools, request::RequestBuilder, tool_calls_to_content},
     traits::{
         StreamResponse, WithChat, WithClient, WithNoCompletion, WithRenderRawCurl, WithRetryPolicy,
         WithStreamChat,
@@ -131,6 +132,10 @@ impl AwsClient {
         DEFAULT_REQUEST_OPTIONS.get_or_init(Default::default)
     }
 
+    pub fn model(&self) -> &str {
+        &self.properties.model
+    }
+
     // TODO: this should be memoized on client construction, but because config loading is async,
     // we can't do this in AwsClient::new (which is called from LLMPRimitiveProvider::try_from)
     // Note: This function necessarily exposes secret keys when they are provided, so it should
@@ -216,17 +221,45 @@ impl AwsClient {
             loader = loader.region(Region::new(aws_region.clone()));
         }
 
+        if let Some(ms) = self
+            .properties
+            .timeouts
+            .as_ref()
+            .and_then(|t| t.connect_timeout_ms)
+        {
+            loader = loader.timeout_config(
+                aws_config::timeout::TimeoutConfig::builder()
+                    .connect_timeout(std::time::Duration::from_millis(ms))
+                    .build(),
+            );
+        }
+
         let config = loader.load().await;
         Ok(bedrock::Client::new(&config))
     }
 
-    async fn chat_anyhow<'r>(&self, response: &'r ConverseOutput) -> Result<&'r String> {
+    async fn chat_anyhow(&self, response: &ConverseOutput) -> Result<String> {
         let Some(bedrock::types::ConverseOutput::Message(ref message)) = response.output else {
             anyhow::bail!(
                 "Expected message output in response, but is type {}",
                 "unknown"
             );
         };
+        let tool_inputs = message
+            .content
+            .iter()
+            .filter_map(|block| match block {
+                bedrock::types::ContentBlock::ToolUse(tool_use) => {
+                    Some(document_to_json(tool_use.input()).to_string())
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        if !tool_inputs.is_empty() {
+            return Ok(tool_calls_to_content(
+                tool_inputs.iter().map(String::as_str),
+            ));
+        }
         let content = message
             .content
             .first()
@@ -245,7 +278,39 @@ impl AwsClient {
             );
         };
 
-        Ok(content)
+        Ok(content.clone())
+    }
+
+    /// Declares the tools of the function being called and forces the model
+    /// to call one of them, for `output_mode "tools"`.
+    fn tool_config(&self, ctx: &RuntimeContext) -> Result<bedrock::types::ToolConfiguration> {
+        let tools = output_tools(ctx)?;
+        // Force a tool call. With several candidate tools, let the model pick.
+        let tool_choice = match tools.as_slice() {
+            [tool] => bedrock::types::ToolChoice::Tool(
+                bedrock::types::SpecificToolChoice::builder()
+                    .name(&tool.name)
+                    .build()?,
+            ),
+            _ => bedrock::types::ToolChoice::Any(bedrock::types::AnyToolChoice::builder().build()),
+        };
+        let tools = tools
+            .into_iter()
+            .map(|tool| {
+                let spec = bedrock::types::ToolSpecification::builder()
+                    .name(tool.name)
+                    .input_schema(bedrock::types::ToolInputSchema::Json(json_to_document(
+                        tool.parameters,
+                    )))
+                    .build()?;
+                Ok(bedrock::types::Tool::ToolSpec(spec))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(bedrock::types::ToolConfiguration::builder()
+            .set_tools(Some(tools))
+            .tool_choice(tool_choice)
+            .build()?)
     }
 
     fn build_request(
@@ -283,8 +348,14 @@ impl AwsClient {
                 .build()
         });
 
+        let tool_config = match self.properties.output_mode {
+            OutputMode::Tools => Some(self.tool_config(ctx)?),
+            _ => None,
+        };
+
         bedrock::operation::converse::ConverseInput::builder()
             .set_inference_config(inference_config)
+            .set_tool_config(tool_config)
             .set_model_id(Some(self.properties.model.clone()))
             .set_system(system_message)
             .set_messages(Some(converse_messages))
@@ -357,6 +428,29 @@ impl WithClientProperties for AwsClient {
     fn allowed_roles(&self) -> Vec<String> {
         self.properties.allowed_roles()
     }
+    fn cache_config(&self) -> Option<&internal_llm_client::CacheConfig> {
+        self.properties.cache.as_ref()
+    }
+    fn circuit_breaker_config(&self) -> Option<&internal_llm_client::CircuitBreakerConfig> {
+        self.properties.circuit_breaker.as_ref()
+    }
+    fn rate_limit_config(&self) -> Option<&internal_llm_client::RateLimitConfig> {
+        self.properties.rate_limit.as_ref()
+    }
+    fn timeout_config(&self) -> Option<&internal_llm_client::TimeoutConfig> {
+        self.properties.timeouts.as_ref()
+    }
+    fn forbidden_coercions(&self) -> &[internal_llm_client::Coercion] {
+        &self.properties.forbid_coercions
+    }
+
+    fn max_context_tokens(&self) -> Option<u64> {
+        self.properties.max_context_tokens
+    }
+
+    fn pricing(&self) -> Option<&internal_llm_client::Pricing> {
+        self.properties.pricing.as_ref()
+    }
 }
 
 impl WithClient for AwsClient {
@@ -395,6 +489,7 @@ impl WithStreamChat for AwsClient {
                     latency: web_time::Duration::ZERO,
                     message: format!("{:#?}", e),
                     code: ErrorCode::Other(2),
+                    retry_after: None,
                 }));
             }
         };
@@ -411,6 +506,7 @@ impl WithStreamChat for AwsClient {
                     latency: web_time::Duration::ZERO,
                     message: format!("{:#?}", e),
                     code: ErrorCode::Other(2),
+                    retry_after: None,
                 }))
             }
         };
@@ -420,7 +516,8 @@ impl WithStreamChat for AwsClient {
             .set_model_id(request.model_id)
             .set_inference_config(request.inference_config)
             .set_system(request.system)
-            .set_messages(request.messages);
+            .set_messages(request.messages)
+            .set_tool_config(request.tool_config);
 
         let system_start = SystemTime::now();
         let instant_start = Instant::now();
@@ -462,6 +559,7 @@ impl WithStreamChat for AwsClient {
                         }
                         _ => ErrorCode::Other(2),
                     },
+                    retry_after: None,
                 }));
             }
         };
@@ -482,11 +580,14 @@ impl WithStreamChat for AwsClient {
                         prompt_tokens: None,
                         output_tokens: None,
                         total_tokens: None,
+                        cached: false,
+                        cost: None,
                     },
                 }),
                 response,
+                Vec::<String>::new(),
             ),
-            move |(initial_state, mut response)| {
+            move |(initial_state, mut response, mut tool_inputs)| {
                 async move {
                     let mut new_state = initial_state?;
                     match response.stream.recv().await {
@@ -496,17 +597,32 @@ impl WithStreamChat for AwsClient {
                                 bedrock::types::ConverseStreamOutput::ContentBlockDelta(
                                     content_block_delta,
                                 ) => {
-                                    if let Some(bedrock::types::ContentBlockDelta::Text(
-                                        ref delta,
-                                    )) = content_block_delta.delta
-                                    {
-                                        new_state.content += delta;
+                                    match content_block_delta.delta {
+                                        Some(bedrock::types::ContentBlockDelta::Text(
+                                            ref delta,
+                                        )) => {
+                                            new_state.content += delta;
+                                        }
+                                        Some(bedrock::types::ContentBlockDelta::ToolUse(
+                                            ref delta,
+                                        )) => {
+                                            if let Some(input) = tool_inputs.last_mut() {
+                                                *input += delta.input();
+                                            }
+                                            new_state.content = tool_calls_to_content(
+                                                tool_inputs.iter().map(String::as_str),
+                                            );
+                                        }
                                         // TODO- handle
+                                        _ => {}
                                     }
-                                    // TODO- handle
                                 }
-                                bedrock::types::ConverseStreamOutput::ContentBlockStart(_) => {
-                                    // TODO- handle
+                                bedrock::types::ConverseStreamOutput::ContentBlockStart(start) => {
+                                    if let Some(bedrock::types::ContentBlockStart::ToolUse(_)) =
+                                        start.start
+                                    {
+                                        tool_inputs.push(String::new());
+                                    }
                                 }
                                 bedrock::types::ConverseStreamOutput::ContentBlockStop(_) => {
                                     // TODO- handle
@@ -519,6 +635,7 @@ impl WithStreamChat for AwsClient {
                                         stop.stop_reason,
                                         bedrock::types::StopReason::StopSequence
                                             | bedrock::types::StopReason::EndTurn
+                                            | bedrock::types::StopReason::ToolUse
                                     );
                                     // TODO- handle
                                 }
@@ -539,7 +656,7 @@ impl WithStreamChat for AwsClient {
                             new_state.latency = instant_start.elapsed();
                             Some((
                                 LLMResponse::Success(new_state.clone()),
-                                (Some(new_state), response),
+                                (Some(new_state), response, tool_inputs),
                             ))
                         }
                         Ok(None) => None,
@@ -553,8 +670,9 @@ impl WithStreamChat for AwsClient {
                                 latency: instant_start.elapsed(),
                                 message: format!("Failed to parse event: {:#?}", e),
                                 code: ErrorCode::Other(2),
+                                retry_after: None,
                             }),
-                            (None, response),
+                            (None, response, tool_inputs),
                         )),
                     }
                 }
@@ -687,6 +805,7 @@ impl WithChat for AwsClient {
                     latency: web_time::Duration::ZERO,
                     message: format!("{:#?}", e),
                     code: ErrorCode::Other(2),
+                    retry_after: None,
                 })
             }
         };
@@ -703,6 +822,7 @@ impl WithChat for AwsClient {
                     latency: web_time::Duration::ZERO,
                     message: format!("{:#?}", e),
                     code: ErrorCode::Other(2),
+                    retry_after: None,
                 })
             }
         };
@@ -711,7 +831,8 @@ impl WithChat for AwsClient {
             .set_model_id(request.model_id)
             .set_inference_config(request.inference_config)
             .set_system(request.system)
-            .set_messages(request.messages);
+            .set_messages(request.messages)
+            .set_tool_config(request.tool_config);
 
         let system_start = SystemTime::now();
         let instant_start = Instant::now();
@@ -753,6 +874,7 @@ impl WithChat for AwsClient {
                         }
                         _ => ErrorCode::Other(2),
                     },
+                    retry_after: None,
                 });
             }
         };
@@ -761,7 +883,7 @@ impl WithChat for AwsClient {
             Ok(content) => LLMResponse::Success(LLMCompleteResponse {
                 client,
                 prompt,
-                content: content.clone(),
+                content,
                 start_time: system_start,
                 latency: instant_start.elapsed(),
                 request_options,
@@ -771,6 +893,7 @@ impl WithChat for AwsClient {
                         response.stop_reason,
                         bedrock::types::StopReason::StopSequence
                             | bedrock::types::StopReason::EndTurn
+                            | bedrock::types::StopReason::ToolUse
                     ),
                     finish_reason: Some(response.stop_reason().as_str().into()),
                     prompt_tokens: response
@@ -785,6 +908,8 @@ impl WithChat for AwsClient {
                         .usage
                         .as_ref()
                         .and_then(|i| i.total_tokens.try_into().ok()),
+                    cached: false,
+                    cost: None,
                 },
             }),
             Err(e) => LLMResponse::LLMFailure(LLMErrorResponse {
@@ -796,7 +921,49 @@ impl WithChat for AwsClient {
                 latency: instant_start.elapsed(),
                 message: format!("{:#?}", e),
                 code: ErrorCode::Other(200),
+                retry_after: None,
             }),
         }
     }
 }
+
+/// Converts JSON into the document type the SDK uses for free-form values,
+/// such as tool schemas.
+fn json_to_document(value: serde_json::Value) -> Document {
+    match value {
+        serde_json::Value::Null => Document::Null,
+        serde_json::Value::Bool(b) => Document::Bool(b),
+        serde_json::Value::Number(n) => Document::Number(match (n.as_u64(), n.as_i64()) {
+            (Some(u), _) => Number::PosInt(u),
+            (None, Some(i)) => Number::NegInt(i),
+            (None, None) => Number::Float(n.as_f64().unwrap_or_default()),
+        }),
+        serde_json::Value::String(s) => Document::String(s),
+        serde_json::Value::Array(items) => {
+            Document::Array(items.into_iter().map(json_to_document).collect())
+        }
+        serde_json::Value::Object(fields) => Document::Object(
+            fields
+                .into_iter()
+                .map(|(k, v)| (k, json_to_document(v)))
+                .collect(),
+        ),
+    }
+}
+
+/// Converts a document, such as the input of a tool call, back into JSON.
+fn document_to_json(document: &Document) -> serde_json::Value {
+    match document {
+        Document::Null => serde_json::Value::Null,
+        Document::Bool(b) => serde_json::Value::Bool(*b),
+        Document::Number(Number::PosInt(u)) => serde_json::Value::from(*u),
+        Document::Number(Number::NegInt(i)) => serde_json::Value::from(*i),
+        Document::Number(Number::Float(f)) => serde_json::Value::from(*f),
+        Document::String(s) => serde_json::Value::String(s.clone()),
+        Document::Array(items) => items.iter().map(document_to_json).collect(),
+        Document::Object(fields) => fields
+            .iter()
+            .map(|(k, v)| (k.clone(), document_to_json(v)))
+            .collect(),
+    }
+}
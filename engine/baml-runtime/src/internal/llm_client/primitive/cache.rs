@@ -0,0 +1,394 @@
+//! Response cache for primitive clients configured with a `cache` option.
+//!
+//! Responses are keyed on the provider, the rendered prompt, the request
+//! options (which include the model) and, when the provider builds raw HTTP
+//! requests, the request URL and body. Only successful responses are stored.
+//! Responses served from the cache have `metadata.cached` set.
+
+use std::{
+    collections::HashMap,
+    path::PathBuf,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use indexmap::IndexMap;
+use internal_baml_jinja::RenderedPrompt;
+use internal_llm_client::{CacheConfig, CacheStore};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    internal::llm_client::{
+        traits::{StreamResponse, WithClientProperties},
+        LLMCompleteResponse, LLMCompleteResponseMetadata, LLMResponse,
+    },
+    RuntimeContext,
+};
+
+use super::LLMPrimitiveProvider;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct CachedResponse {
+    model: String,
+    content: String,
+    metadata: LLMCompleteResponseMetadata,
+    /// Seconds since the unix epoch at which the response was stored.
+    stored_at: u64,
+}
+
+enum Store {
+    /// Least recently used entries are evicted first.
+    Memory {
+        max_entries: usize,
+        entries: Mutex<IndexMap<String, CachedResponse>>,
+    },
+    /// One `<key>.json` file per entry.
+    Disk { path: PathBuf },
+}
+
+pub(super) struct ResponseCache {
+    store: Store,
+    ttl_seconds: Option<u64>,
+}
+
+type Caches = Mutex<HashMap<(String, CacheConfig), Arc<ResponseCache>>>;
+
+/// Memory stores are per client, disk stores are shared by path.
+static CACHES: OnceLock<Caches> = OnceLock::new();
+
+impl ResponseCache {
+    /// The cache configured for `client`, if any.
+    pub(super) fn for_client(client: &LLMPrimitiveProvider) -> Result<Option<Arc<ResponseCache>>> {
+        let Some(config) = client.cache_config() else {
+            return Ok(None);
+        };
+        let owner = match &config.store {
+            CacheStore::Memory { .. } => client.name().to_string(),
+            CacheStore::Disk { .. } => String::new(),
+        };
+
+        let mut caches = CACHES
+            .get_or_init(Default::default)
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Response cache registry is poisoned"))?;
+        let cache = caches
+            .entry((owner, config.clone()))
+            .or_insert_with(|| Arc::new(ResponseCache::new(config)))
+            .clone();
+        Ok(Some(cache))
+    }
+
+    fn new(config: &CacheConfig) -> Self {
+        let store = match &config.store {
+            CacheStore::Memory { max_entries } => Store::Memory {
+                max_entries: *max_entries,
+                entries: Mutex::new(IndexMap::new()),
+            },
+            CacheStore::Disk { path } => Store::Disk {
+                path: PathBuf::from(path),
+            },
+        };
+        ResponseCache {
+            store,
+            ttl_seconds: config.ttl_seconds,
+        }
+    }
+
+    pub(super) async fn single_call(
+        &self,
+        client: &LLMPrimitiveProvider,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+    ) -> LLMResponse {
+        let key = cache_key(client, ctx, prompt).await;
+        if let Some(hit) = self.get(&key, now_secs()) {
+            return hit.to_response(client, prompt);
+        }
+
+        let response = client.call_uncached(ctx, prompt).await;
+        if let LLMResponse::Success(r) = &response {
+            self.put(&key, r, now_secs());
+        }
+        response
+    }
+
+    pub(super) async fn stream(
+        self: Arc<Self>,
+        client: &LLMPrimitiveProvider,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+    ) -> StreamResponse {
+        let key = cache_key(client, ctx, prompt).await;
+        if let Some(hit) = self.get(&key, now_secs()) {
+            let response = hit.to_response(client, prompt);
+            return Ok(Box::pin(futures::stream::once(async move { response })));
+        }
+
+        let stream = client.stream_uncached(ctx, prompt).await?;
+        // Only chunks that complete the response are copied, since every chunk
+        // carries the whole response so far.
+        let complete = Arc::new(Mutex::new(None));
+        let recorder = complete.clone();
+        let on_end = futures::stream::once(async move {
+            if let Some(r) = complete.lock().unwrap().take() {
+                self.put(&key, &r, now_secs());
+            }
+            None
+        });
+        Ok(Box::pin(
+            stream
+                .inspect(move |chunk| {
+                    let mut complete = recorder.lock().unwrap();
+                    match chunk {
+                        LLMResponse::Success(r)
+                            if r.metadata.baml_is_complete
+                                || r.metadata.finish_reason.is_some() =>
+                        {
+                            *complete = Some(r.clone());
+                        }
+                        LLMResponse::Success(_) => {}
+                        // A failure after the end still fails the stream.
+                        _ => *complete = None,
+                    }
+                })
+                .map(Some)
+                .chain(on_end)
+                .filter_map(futures::future::ready),
+        ))
+    }
+
+    /// The entry stored under `key`, unless it expired by `now`, in seconds
+    /// since the unix epoch.
+    fn get(&self, key: &str, now: u64) -> Option<CachedResponse> {
+        let entry = match &self.store {
+            Store::Memory { entries, .. } => {
+                let mut entries = entries.lock().ok()?;
+                let idx = entries.get_index_of(key)?;
+                // Move the entry to the back, it is now the most recently used.
+                let last = entries.len() - 1;
+                entries.move_index(idx, last);
+                entries.get_index(last).map(|(_, v)| v.clone())
+            }
+            Store::Disk { path } => {
+                let file = path.join(format!("{key}.json"));
+                let contents = std::fs::read_to_string(&file).ok()?;
+                match serde_json::from_str(&contents) {
+                    Ok(entry) => Some(entry),
+                    Err(e) => {
+                        log::warn!("Ignoring corrupt cache entry {}: {e}", file.display());
+                        None
+                    }
+                }
+            }
+        }?;
+
+        match self.ttl_seconds {
+            Some(ttl) if now.saturating_sub(entry.stored_at) >= ttl => {
+                self.remove(key);
+                None
+            }
+            _ => Some(entry),
+        }
+    }
+
+    fn put(&self, key: &str, response: &LLMCompleteResponse, now: u64) {
+        let entry = CachedResponse {
+            model: response.model.clone(),
+            content: response.content.clone(),
+            metadata: response.metadata.clone(),
+            stored_at: now,
+        };
+        match &self.store {
+            Store::Memory {
+                max_entries,
+                entries,
+            } => {
+                let Ok(mut entries) = entries.lock() else {
+                    return;
+                };
+                entries.shift_remove(key);
+                entries.insert(key.to_string(), entry);
+                while entries.len() > *max_entries {
+                    entries.shift_remove_index(0);
+                }
+            }
+            Store::Disk { path } => {
+                let write = || -> Result<()> {
+                    std::fs::create_dir_all(path)?;
+                    let file = path.join(format!("{key}.json"));
+                    std::fs::write(&file, serde_json::to_vec(&entry)?)
+                        .with_context(|| format!("Failed to write {}", file.display()))
+                };
+                if let Err(e) = write() {
+                    log::warn!("Failed to store cached response: {e:#}");
+                }
+            }
+        }
+    }
+
+    fn remove(&self, key: &str) {
+        match &self.store {
+            Store::Memory { entries, .. } => {
+                if let Ok(mut entries) = entries.lock() {
+                    entries.shift_remove(key);
+                }
+            }
+            Store::Disk { path } => {
+                let _ = std::fs::remove_file(path.join(format!("{key}.json")));
+            }
+        }
+    }
+}
+
+impl CachedResponse {
+    fn to_response(&self, client: &LLMPrimitiveProvider, prompt: &RenderedPrompt) -> LLMResponse {
+        LLMResponse::Success(LLMCompleteResponse {
+            client: client.name().to_string(),
+            model: self.model.clone(),
+            prompt: prompt.clone(),
+            request_options: client.request_options().clone(),
+            content: self.content.clone(),
+            start_time: web_time::SystemTime::now(),
+            latency: web_time::Duration::ZERO,
+            metadata: LLMCompleteResponseMetadata {
+                cached: true,
+                ..self.metadata.clone()
+            },
+        })
+    }
+}
+
+/// Hex encoded SHA-256 of everything that determines the response.
+async fn cache_key(
+    client: &LLMPrimitiveProvider,
+    ctx: &RuntimeContext,
+    prompt: &RenderedPrompt,
+) -> String {
+    let material = serde_json::json!({
+        "provider": client.to_string(),
+        "prompt": prompt,
+        "request_options": client.request_options(),
+        "url": client.request_url(ctx, prompt).await,
+        "request": client.request_body(ctx, prompt, false).await,
+    });
+    let digest = ring::digest::digest(&ring::digest::SHA256, material.to_string().as_bytes());
+    digest.as_ref().iter().map(|b| format!("{b:02x}")).collect()
+}
+
+fn now_secs() -> u64 {
+    web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn memory(max_entries: usize, ttl_seconds: Option<u64>) -> ResponseCache {
+        ResponseCache::new(&CacheConfig {
+            store: CacheStore::Memory { max_entries },
+            ttl_seconds,
+        })
+    }
+
+    fn response(content: &str) -> LLMCompleteResponse {
+        LLMCompleteResponse {
+            client: "GPT4".to_string(),
+            model: "gpt-4o".to_string(),
+            prompt: RenderedPrompt::Completion("Hello".to_string()),
+            request_options: Default::default(),
+            content: content.to_string(),
+            start_time: web_time::SystemTime::now(),
+            latency: web_time::Duration::from_millis(500),
+            metadata: LLMCompleteResponseMetadata {
+                baml_is_complete: true,
+                finish_reason: Some("stop".to_string()),
+                prompt_tokens: Some(10),
+                output_tokens: Some(5),
+                total_tokens: Some(15),
+                cached: false,
+                cost: None,
+            },
+        }
+    }
+
+    fn content(cache: &ResponseCache, key: &str, now: u64) -> Option<String> {
+        cache.get(key, now).map(|entry| entry.content)
+    }
+
+    #[test]
+    fn stored_responses_are_hits() {
+        let cache = memory(10, None);
+        cache.put("a", &response("first"), 100);
+
+        let hit = cache.get("a", 100).unwrap();
+        assert_eq!(hit.content, "first");
+        assert_eq!(hit.model, "gpt-4o");
+        assert_eq!(hit.metadata.output_tokens, Some(5));
+        assert_eq!(content(&cache, "b", 100), None);
+    }
+
+    #[test]
+    fn storing_a_key_again_replaces_it() {
+        let cache = memory(10, None);
+        cache.put("a", &response("first"), 100);
+        cache.put("a", &response("second"), 100);
+
+        assert_eq!(content(&cache, "a", 100).as_deref(), Some("second"));
+    }
+
+    #[test]
+    fn entries_expire_after_the_ttl() {
+        let cache = memory(10, Some(60));
+        cache.put("a", &response("first"), 100);
+
+        assert_eq!(content(&cache, "a", 159).as_deref(), Some("first"));
+        assert_eq!(content(&cache, "a", 160), None);
+        // Expired entries are dropped, not just skipped.
+        assert_eq!(content(&cache, "a", 100), None);
+    }
+
+    #[test]
+    fn entries_without_ttl_never_expire() {
+        let cache = memory(10, None);
+        cache.put("a", &response("first"), 100);
+
+        assert_eq!(content(&cache, "a", u64::MAX).as_deref(), Some("first"));
+    }
+
+    #[test]
+    fn least_recently_used_entries_are_evicted() {
+        let cache = memory(2, None);
+        cache.put("a", &response("a"), 100);
+        cache.put("b", &response("b"), 101);
+        // Reading "a" makes "b" the least recently used.
+        assert!(cache.get("a", 102).is_some());
+        cache.put("c", &response("c"), 103);
+
+        assert_eq!(content(&cache, "b", 104), None);
+        assert_eq!(content(&cache, "a", 104).as_deref(), Some("a"));
+        assert_eq!(content(&cache, "c", 104).as_deref(), Some("c"));
+    }
+
+    #[test]
+    fn disk_entries_survive_the_cache_and_expire() {
+        let dir = std::env::temp_dir().join(format!("baml-cache-test-{}", std::process::id()));
+        let config = CacheConfig {
+            store: CacheStore::Disk {
+                path: dir.display().to_string(),
+            },
+            ttl_seconds: Some(60),
+        };
+        ResponseCache::new(&config).put("a", &response("first"), 100);
+
+        // A new cache on the same directory, like the next run, reads it.
+        let cache = ResponseCache::new(&config);
+        assert_eq!(content(&cache, "a", 120).as_deref(), Some("first"));
+        assert_eq!(content(&cache, "a", 160), None);
+        assert!(!dir.join("a.json").exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
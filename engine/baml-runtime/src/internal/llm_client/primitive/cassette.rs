@@ -0,0 +1,499 @@
+//! Record/replay of primitive client calls.
+//!
+//! With `BAML_CASSETTE=<path>` set, every call made by a primitive client is
+//! matched against (`BAML_CASSETTE_MODE=replay`, the default) or appended to
+//! (`BAML_CASSETTE_MODE=record`) a cassette file. A cassette is a JSON-lines
+//! file with one [`Interaction`] per line.
+//!
+//! Interactions are matched on the client name, the rendered prompt and the
+//! request options. Identical calls are replayed in the order they were
+//! recorded; once exhausted, the last matching interaction is reused.
+
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use anyhow::{Context, Result};
+use futures::StreamExt;
+use internal_baml_jinja::RenderedPrompt;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    internal::llm_client::{
+        traits::StreamResponse, ErrorCode, LLMCompleteResponse, LLMCompleteResponseMetadata,
+        LLMErrorResponse, LLMResponse,
+    },
+    RuntimeContext,
+};
+
+use super::LLMPrimitiveProvider;
+
+const CASSETTE_ENV: &str = "BAML_CASSETTE";
+const CASSETTE_MODE_ENV: &str = "BAML_CASSETTE_MODE";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum CassetteMode {
+    /// Make real calls and append them to the cassette.
+    Record,
+    /// Serve calls from the cassette, never touching the network.
+    Replay,
+}
+
+impl std::str::FromStr for CassetteMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "record" => Ok(Self::Record),
+            "replay" => Ok(Self::Replay),
+            other => anyhow::bail!(
+                "Invalid {CASSETTE_MODE_ENV}: {other}. Allowed values are: record, replay"
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Interaction {
+    client: String,
+    provider: String,
+    prompt: serde_json::Value,
+    request_options: serde_json::Value,
+    /// Body of the HTTP request, with secrets redacted. Not available for
+    /// providers that go through an SDK.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    request: Option<serde_json::Value>,
+    /// The final response. For streamed calls, this is the last chunk.
+    response: RecordedResponse,
+    /// Every partial response of a streamed call, in order.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    chunks: Vec<RecordedResponse>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum RecordedResponse {
+    Success {
+        model: String,
+        content: String,
+        latency_ms: u64,
+        metadata: LLMCompleteResponseMetadata,
+    },
+    LlmFailure {
+        model: Option<String>,
+        message: String,
+        code: u16,
+        latency_ms: u64,
+    },
+    UserFailure {
+        message: String,
+    },
+    InternalFailure {
+        message: String,
+    },
+}
+
+impl From<&LLMResponse> for RecordedResponse {
+    fn from(response: &LLMResponse) -> Self {
+        match response {
+            LLMResponse::Success(r) => RecordedResponse::Success {
+                model: r.model.clone(),
+                content: r.content.clone(),
+                latency_ms: r.latency.as_millis() as u64,
+                metadata: r.metadata.clone(),
+            },
+            LLMResponse::LLMFailure(r) => RecordedResponse::LlmFailure {
+                model: r.model.clone(),
+                message: r.message.clone(),
+                code: r.code.to_u16(),
+                latency_ms: r.latency.as_millis() as u64,
+            },
+            LLMResponse::UserFailure(message) => RecordedResponse::UserFailure {
+                message: message.clone(),
+            },
+            LLMResponse::InternalFailure(message) => RecordedResponse::InternalFailure {
+                message: message.clone(),
+            },
+        }
+    }
+}
+
+impl RecordedResponse {
+    fn to_response(&self, client: &LLMPrimitiveProvider, prompt: &RenderedPrompt) -> LLMResponse {
+        match self {
+            RecordedResponse::Success {
+                model,
+                content,
+                latency_ms,
+                metadata,
+            } => LLMResponse::Success(LLMCompleteResponse {
+                client: client.name().to_string(),
+                model: model.clone(),
+                prompt: prompt.clone(),
+                request_options: client.request_options().clone(),
+                content: content.clone(),
+                start_time: web_time::SystemTime::now(),
+                latency: web_time::Duration::from_millis(*latency_ms),
+                metadata: metadata.clone(),
+            }),
+            RecordedResponse::LlmFailure {
+                model,
+                message,
+                code,
+                latency_ms,
+            } => LLMResponse::LLMFailure(LLMErrorResponse {
+                client: client.name().to_string(),
+                model: model.clone(),
+                prompt: prompt.clone(),
+                request_options: client.request_options().clone(),
+                start_time: web_time::SystemTime::now(),
+                latency: web_time::Duration::from_millis(*latency_ms),
+                message: message.clone(),
+                code: ErrorCode::from_u16(*code),
+                retry_after: None,
+            }),
+            RecordedResponse::UserFailure { message } => LLMResponse::UserFailure(message.clone()),
+            RecordedResponse::InternalFailure { message } => {
+                LLMResponse::InternalFailure(message.clone())
+            }
+        }
+    }
+}
+
+pub(super) struct Cassette {
+    path: PathBuf,
+    mode: CassetteMode,
+    /// Recorded interactions and whether each one was already replayed.
+    /// Only populated in replay mode.
+    interactions: Mutex<Vec<(Interaction, bool)>>,
+}
+
+type Cassettes = Mutex<HashMap<(PathBuf, CassetteMode), Arc<Cassette>>>;
+
+static CASSETTES: OnceLock<Cassettes> = OnceLock::new();
+
+impl Cassette {
+    /// The cassette selected by the environment of `ctx`, if any.
+    ///
+    /// Cassettes are shared across the process, so that parallel calls replay
+    /// interactions in order and recording only truncates the file once.
+    pub(super) fn from_ctx(ctx: &RuntimeContext) -> Result<Option<Arc<Cassette>>> {
+        let Some(path) = ctx.env_vars().get(CASSETTE_ENV).filter(|p| !p.is_empty()) else {
+            return Ok(None);
+        };
+        let mode = match ctx.env_vars().get(CASSETTE_MODE_ENV) {
+            Some(mode) if !mode.is_empty() => mode.parse()?,
+            _ => CassetteMode::Replay,
+        };
+        let path = PathBuf::from(path);
+
+        let mut cassettes = CASSETTES
+            .get_or_init(Default::default)
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Cassette registry is poisoned"))?;
+        if let Some(cassette) = cassettes.get(&(path.clone(), mode)) {
+            return Ok(Some(cassette.clone()));
+        }
+        let cassette = Arc::new(Cassette::open(&path, mode)?);
+        cassettes.insert((path, mode), cassette.clone());
+        Ok(Some(cassette))
+    }
+
+    fn open(path: &Path, mode: CassetteMode) -> Result<Self> {
+        let interactions = match mode {
+            CassetteMode::Record => {
+                if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+                    std::fs::create_dir_all(parent)?;
+                }
+                std::fs::File::create(path)
+                    .with_context(|| format!("Failed to create cassette {}", path.display()))?;
+                vec![]
+            }
+            CassetteMode::Replay => std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read cassette {}", path.display()))?
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.trim().is_empty())
+                .map(|(idx, line)| {
+                    serde_json::from_str(line)
+                        .map(|interaction| (interaction, false))
+                        .with_context(|| {
+                            format!("Invalid interaction at {}:{}", path.display(), idx + 1)
+                        })
+                })
+                .collect::<Result<_>>()?,
+        };
+
+        Ok(Cassette {
+            path: path.to_path_buf(),
+            mode,
+            interactions: Mutex::new(interactions),
+        })
+    }
+
+    pub(super) async fn single_call(
+        self: Arc<Self>,
+        client: &LLMPrimitiveProvider,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+    ) -> LLMResponse {
+        match self.mode {
+            CassetteMode::Replay => {
+                let request = client.request_body(ctx, prompt, false).await;
+                match self.replay(client, prompt, request) {
+                    Ok((response, _)) => response.to_response(client, prompt),
+                    Err(e) => LLMResponse::InternalFailure(format!("{e:#}")),
+                }
+            }
+            CassetteMode::Record => {
+                let response = client.call_provider(ctx, prompt).await;
+                let interaction = Interaction {
+                    request: client.request_body(ctx, prompt, false).await,
+                    response: (&response).into(),
+                    chunks: vec![],
+                    ..Interaction::new(client, prompt)
+                };
+                if let Err(e) = self.record(&interaction) {
+                    log::warn!("{e:#}");
+                }
+                response
+            }
+        }
+    }
+
+    pub(super) async fn stream(
+        self: Arc<Self>,
+        client: &LLMPrimitiveProvider,
+        ctx: &RuntimeContext,
+        prompt: &RenderedPrompt,
+    ) -> StreamResponse {
+        match self.mode {
+            CassetteMode::Replay => {
+                let request = client.request_body(ctx, prompt, true).await;
+                let (response, chunks) = self
+                    .replay(client, prompt, request)
+                    .map_err(|e| LLMResponse::InternalFailure(format!("{e:#}")))?;
+                let chunks = if chunks.is_empty() {
+                    vec![response]
+                } else {
+                    chunks
+                };
+                let chunks = chunks
+                    .iter()
+                    .map(|c| c.to_response(client, prompt))
+                    .collect::<Vec<_>>();
+                Ok(Box::pin(futures::stream::iter(chunks)))
+            }
+            CassetteMode::Record => {
+                let stream = match client.stream_provider(ctx, prompt).await {
+                    Ok(stream) => stream,
+                    Err(response) => {
+                        let interaction = Interaction {
+                            request: client.request_body(ctx, prompt, true).await,
+                            response: (&response).into(),
+                            ..Interaction::new(client, prompt)
+                        };
+                        if let Err(e) = self.record(&interaction) {
+                            log::warn!("{e:#}");
+                        }
+                        return Err(response);
+                    }
+                };
+
+                let mut interaction = Interaction {
+                    request: client.request_body(ctx, prompt, true).await,
+                    ..Interaction::new(client, prompt)
+                };
+                let chunks = Arc::new(Mutex::new(vec![]));
+                let recorder = chunks.clone();
+                let on_end = futures::stream::once(async move {
+                    let chunks = std::mem::take(&mut *chunks.lock().unwrap());
+                    if let Some(last) = chunks.last() {
+                        interaction.response = RecordedResponse::clone(last);
+                    }
+                    interaction.chunks = chunks;
+                    if let Err(e) = self.record(&interaction) {
+                        log::warn!("{e:#}");
+                    }
+                    None
+                });
+                Ok(Box::pin(
+                    stream
+                        .inspect(move |chunk| recorder.lock().unwrap().push(chunk.into()))
+                        .map(Some)
+                        .chain(on_end)
+                        .filter_map(futures::future::ready),
+                ))
+            }
+        }
+    }
+
+    /// Finds the next recorded interaction for this call.
+    fn replay(
+        &self,
+        client: &LLMPrimitiveProvider,
+        prompt: &RenderedPrompt,
+        request: Option<serde_json::Value>,
+    ) -> Result<(RecordedResponse, Vec<RecordedResponse>)> {
+        let key = Interaction {
+            request,
+            ..Interaction::new(client, prompt)
+        };
+        let mut interactions = self
+            .interactions
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Cassette is poisoned"))?;
+
+        let mut matching = interactions
+            .iter_mut()
+            .filter(|(i, _)| i.matches(&key))
+            .peekable();
+        if matching.peek().is_none() {
+            anyhow::bail!(
+                "No interaction recorded in cassette {} for client {} with this prompt. Re-record it with {}=record.",
+                self.path.display(),
+                client.name(),
+                CASSETTE_MODE_ENV
+            );
+        }
+
+        let mut last = None;
+        for (interaction, used) in matching {
+            if !*used {
+                *used = true;
+                return Ok((interaction.response.clone(), interaction.chunks.clone()));
+            }
+            last = Some(interaction);
+        }
+        let interaction = last.expect("at least one matching interaction");
+        Ok((interaction.response.clone(), interaction.chunks.clone()))
+    }
+
+    fn record(&self, interaction: &Interaction) -> Result<()> {
+        let line = serde_json::to_string(interaction)?;
+        // Hold the lock so that lines of parallel calls don't interleave.
+        let _guard = self
+            .interactions
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Cassette is poisoned"))?;
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open cassette {}", self.path.display()))?;
+        writeln!(file, "{line}")
+            .with_context(|| format!("Failed to write cassette {}", self.path.display()))
+    }
+}
+
+impl Interaction {
+    /// An interaction with no response yet, used as the matching key.
+    fn new(client: &LLMPrimitiveProvider, prompt: &RenderedPrompt) -> Self {
+        Interaction {
+            client: client.name().to_string(),
+            provider: client.to_string(),
+            prompt: serde_json::to_value(prompt).unwrap_or_default(),
+            request_options: serde_json::to_value(client.request_options()).unwrap_or_default(),
+            request: None,
+            response: RecordedResponse::InternalFailure {
+                message: "No response recorded".to_string(),
+            },
+            chunks: vec![],
+        }
+    }
+
+    /// The request bodies are only compared when both are known, since they
+    /// are missing for providers that go through an SDK.
+    fn matches(&self, other: &Interaction) -> bool {
+        self.client == other.client
+            && self.prompt == other.prompt
+            && self.request_options == other.request_options
+            && match (&self.request, &other.request) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn interaction(client: &str, prompt: &str, request_options: &str) -> Interaction {
+        serde_json::from_str(&format!(
+            r#"{{
+                "client": "{client}",
+                "provider": "openai",
+                "prompt": {prompt},
+                "request_options": {request_options},
+                "response": {{ "type": "user_failure", "message": "recorded" }}
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn matching_ignores_the_order_of_options() {
+        let recorded = interaction(
+            "GPT4",
+            r#""Hello""#,
+            r#"{"temperature": 0.5, "max_tokens": 100, "stop": ["\n"]}"#,
+        );
+        let call = interaction(
+            "GPT4",
+            r#""Hello""#,
+            r#"{"stop": ["\n"], "max_tokens": 100, "temperature": 0.5}"#,
+        );
+        assert!(recorded.matches(&call));
+    }
+
+    #[test]
+    fn matching_needs_the_same_client_prompt_and_options() {
+        let options = r#"{"temperature": 0.5}"#;
+        let recorded = interaction("GPT4", r#""Hello""#, options);
+        assert!(!recorded.matches(&interaction("Claude", r#""Hello""#, options)));
+        assert!(!recorded.matches(&interaction("GPT4", r#""Bye""#, options)));
+        assert!(!recorded.matches(&interaction("GPT4", r#""Hello""#, r#"{"temperature": 1}"#)));
+        assert!(!recorded.matches(&interaction("GPT4", r#""Hello""#, "{}")));
+    }
+
+    #[test]
+    fn matching_compares_request_bodies_when_both_are_known() {
+        let options = r#"{"temperature": 0.5}"#;
+        let with_body = |body: &str| Interaction {
+            request: Some(serde_json::from_str(body).unwrap()),
+            ..interaction("GPT4", r#""Hello""#, options)
+        };
+        let recorded = with_body(r#"{"tools": [{"name": "Person"}]}"#);
+        assert!(recorded.matches(&with_body(r#"{"tools": [{"name": "Person"}]}"#)));
+        assert!(!recorded.matches(&with_body(r#"{"tools": [{"name": "Pet"}]}"#)));
+        // Providers that go through an SDK have no request body.
+        assert!(recorded.matches(&interaction("GPT4", r#""Hello""#, options)));
+    }
+
+    #[test]
+    fn recorded_responses_round_trip() {
+        let response = RecordedResponse::LlmFailure {
+            model: Some("gpt-4o".to_string()),
+            message: "rate limited".to_string(),
+            code: 429,
+            latency_ms: 12,
+        };
+        let line = serde_json::to_string(&response).unwrap();
+        match serde_json::from_str(&line).unwrap() {
+            RecordedResponse::LlmFailure {
+                model,
+                message,
+                code,
+                latency_ms,
+            } => {
+                assert_eq!(model.as_deref(), Some("gpt-4o"));
+                assert_eq!(message, "rate limited");
+                assert_eq!((code, latency_ms), (429, 12));
+            }
+            other => panic!("unexpected {other:?}"),
+        }
+    }
+}
@@ -7,8 +7,10 @@ use crate::RuntimeContext;
 use crate::{
     internal::llm_client::{
         primitive::{
+            constrain_generation_config, gemini_tools,
             google::types::{FinishReason, GoogleResponse},
             request::{make_parsed_request, make_request, RequestBuilder},
+            tool_calls_to_content,
         },
         traits::{
             SseResponseTrait, StreamResponse, WithChat, WithClient, WithNoCompletion,
@@ -17,7 +19,7 @@ use crate::{
         ErrorCode, LLMCompleteResponse, LLMCompleteResponseMetadata, LLMErrorResponse, LLMResponse,
         ModelFeatures,
     },
-    request::create_client,
+    request::create_llm_client,
 };
 use anyhow::{Context, Result};
 use baml_types::{BamlMap, BamlMedia, BamlMediaContent};
@@ -28,7 +30,8 @@ use internal_baml_core::ir::ClientWalker;
 use internal_baml_jinja::{ChatMessagePart, RenderContext_Client, RenderedChatMessage};
 use internal_llm_client::google_ai::ResolvedGoogleAI;
 use internal_llm_client::{
-    AllowedRoleMetadata, ClientProvider, ResolvedClientProperty, UnresolvedClientProperty,
+    AllowedRoleMetadata, ClientProvider, OutputMode, ResolvedClientProperty,
+    UnresolvedClientProperty,
 };
 use secrecy::ExposeSecret;
 use serde_json::json;
@@ -85,6 +88,29 @@ impl WithClientProperties for GoogleAIClient {
     fn allowed_roles(&self) -> Vec<String> {
         self.properties.allowed_roles()
     }
+    fn cache_config(&self) -> Option<&internal_llm_client::CacheConfig> {
+        self.properties.cache.as_ref()
+    }
+    fn circuit_breaker_config(&self) -> Option<&internal_llm_client::CircuitBreakerConfig> {
+        self.properties.circuit_breaker.as_ref()
+    }
+    fn rate_limit_config(&self) -> Option<&internal_llm_client::RateLimitConfig> {
+        self.properties.rate_limit.as_ref()
+    }
+    fn timeout_config(&self) -> Option<&internal_llm_client::TimeoutConfig> {
+        self.properties.timeouts.as_ref()
+    }
+    fn forbidden_coercions(&self) -> &[internal_llm_client::Coercion] {
+        &self.properties.forbid_coercions
+    }
+
+    fn max_context_tokens(&self) -> Option<u64> {
+        self.properties.max_context_tokens
+    }
+
+    fn pricing(&self) -> Option<&internal_llm_client::Pricing> {
+        self.properties.pricing.as_ref()
+    }
 }
 
 impl WithClient for GoogleAIClient {
@@ -111,6 +137,7 @@ impl SseResponseTrait for GoogleAIClient {
         let client_name = self.context.name.clone();
         let model_id = self.properties.model.clone();
         let params = self.properties.properties.clone();
+        let mut tool_calls = Vec::<String>::new();
         Ok(Box::pin(
             resp.bytes_stream()
                 .eventsource()
@@ -136,6 +163,8 @@ impl SseResponseTrait for GoogleAIClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cached: false,
+                            cost: None,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -162,6 +191,7 @@ impl SseResponseTrait for GoogleAIClient {
                                         latency: instant_start.elapsed(),
                                         message: format!("Failed to parse event: {:#?}", e),
                                         code: ErrorCode::UnsupportedResponse(2),
+                                        retry_after: None,
                                     },
                                 )));
                             }
@@ -169,12 +199,16 @@ impl SseResponseTrait for GoogleAIClient {
 
                         if let Some(choice) = event.candidates.get(0) {
                             let part_index = content_part(&model_id);
-                            if let Some(content) = choice
-                                .content
-                                .as_ref()
-                                .and_then(|c| c.parts.get(part_index))
-                            {
-                                inner.content += &content.text;
+                            if let Some(content) = choice.content.as_ref() {
+                                // Function calls arrive whole, one per part.
+                                tool_calls.extend(content.function_call_arguments());
+                                if !tool_calls.is_empty() {
+                                    inner.content = tool_calls_to_content(
+                                        tool_calls.iter().map(String::as_str),
+                                    );
+                                } else if let Some(part) = content.parts.get(part_index) {
+                                    inner.content += &part.text;
+                                }
                             }
                             if let Some(FinishReason::Stop) = choice.finish_reason.as_ref() {
                                 inner.metadata.baml_is_complete = true;
@@ -193,12 +227,12 @@ impl SseResponseTrait for GoogleAIClient {
 impl WithStreamChat for GoogleAIClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &[RenderedChatMessage],
     ) -> StreamResponse {
         //incomplete, streaming response object is returned
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
@@ -207,6 +241,10 @@ impl WithStreamChat for GoogleAIClient {
 }
 
 impl GoogleAIClient {
+    pub fn model(&self) -> &str {
+        &self.properties.model
+    }
+
     pub fn new(client: &ClientWalker, ctx: &RuntimeContext) -> Result<Self> {
         let properties = resolve_properties(&client.elem().provider, &client.options(), ctx)?;
         Ok(Self {
@@ -229,7 +267,7 @@ impl GoogleAIClient {
                 .retry_policy_id
                 .as_ref()
                 .map(|s| s.to_string()),
-            client: create_client()?,
+            client: create_llm_client(properties.timeouts.as_ref())?,
             properties,
         })
     }
@@ -253,7 +291,7 @@ impl GoogleAIClient {
                 allowed_metadata: properties.allowed_metadata.clone(),
             },
             retry_policy: client.retry_policy.clone(),
-            client: create_client()?,
+            client: create_llm_client(properties.timeouts.as_ref())?,
             properties,
         })
     }
@@ -266,6 +304,7 @@ impl RequestBuilder for GoogleAIClient {
 
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &[RenderedChatMessage]>,
         allow_proxy: bool,
         stream: bool,
@@ -311,6 +350,19 @@ impl RequestBuilder for GoogleAIClient {
             }
         }
 
+        if self.properties.output_mode == OutputMode::JsonSchema {
+            let generation_config = body_obj
+                .entry("generationConfig")
+                .or_insert_with(|| json!({}));
+            constrain_generation_config(generation_config, ctx)?;
+        }
+
+        if self.properties.output_mode == OutputMode::Tools {
+            let (tools, tool_config) = gemini_tools(ctx)?;
+            body_obj.insert("tools".into(), tools);
+            body_obj.entry("toolConfig").or_insert(tool_config);
+        }
+
         Ok(req.json(&body))
     }
 
@@ -320,15 +372,19 @@ impl RequestBuilder for GoogleAIClient {
 }
 
 impl WithChat for GoogleAIClient {
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
         //non-streaming, complete response is returned
-        let (response, system_now, instant_now) =
-            match make_parsed_request::<GoogleResponse>(self, either::Either::Right(prompt), false)
-                .await
-            {
-                Ok(v) => v,
-                Err(e) => return e,
-            };
+        let (response, system_now, instant_now) = match make_parsed_request::<GoogleResponse>(
+            self,
+            ctx,
+            either::Either::Right(prompt),
+            false,
+        )
+        .await
+        {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
 
         if response.candidates.len() != 1 {
             return LLMResponse::LLMFailure(LLMErrorResponse {
@@ -343,6 +399,7 @@ impl WithChat for GoogleAIClient {
                     response.candidates.len()
                 ),
                 code: ErrorCode::Other(200),
+                retry_after: None,
             });
         }
 
@@ -356,6 +413,7 @@ impl WithChat for GoogleAIClient {
                 latency: instant_now.elapsed(),
                 message: "No content returned".to_string(),
                 code: ErrorCode::Other(200),
+                retry_after: None,
             });
         };
 
@@ -363,7 +421,10 @@ impl WithChat for GoogleAIClient {
         LLMResponse::Success(LLMCompleteResponse {
             client: self.context.name.to_string(),
             prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.to_vec()),
-            content: content.parts[part_index].text.clone(),
+            content: match content.function_call_arguments().as_slice() {
+                [] => content.parts[part_index].text.clone(),
+                calls => tool_calls_to_content(calls.iter().map(String::as_str)),
+            },
             start_time: system_now,
             latency: instant_now.elapsed(),
             request_options: self.properties.properties.clone(),
@@ -380,6 +441,8 @@ impl WithChat for GoogleAIClient {
                 prompt_tokens: response.usage_metadata.prompt_token_count,
                 output_tokens: response.usage_metadata.candidates_token_count,
                 total_tokens: response.usage_metadata.total_token_count,
+                cached: false,
+                cost: None,
             },
         })
     }
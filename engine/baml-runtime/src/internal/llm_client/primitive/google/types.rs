@@ -236,9 +236,24 @@ pub struct Content {
     pub parts: Vec<Part>,
 }
 
+impl Content {
+    /// Arguments of the function calls in this content, as JSON.
+    pub fn function_call_arguments(&self) -> Vec<String> {
+        self.parts
+            .iter()
+            .filter_map(|part| part.function_call.as_ref())
+            .map(|call| match &call.args {
+                Some(args) => args.to_string(),
+                None => "{}".to_string(),
+            })
+            .collect()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Part {
+    #[serde(default)]
     pub text: String,
     pub inline_data: Option<Blob>,
     pub file_data: Option<FileData>,
@@ -264,7 +279,7 @@ pub struct FileData {
 #[derive(Serialize, Deserialize, Debug)]
 pub struct FunctionCall {
     pub name: String,
-    pub args: Option<Vec<Struct>>,
+    pub args: Option<serde_json::Value>,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
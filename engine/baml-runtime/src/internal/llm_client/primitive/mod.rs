@@ -3,6 +3,7 @@ use std::sync::Arc;
 use anyhow::Result;
 use baml_types::{BamlMap, BamlValue};
 use internal_baml_core::ir::{repr::IntermediateRepr, ClientWalker};
+use internal_baml_jinja::ToolSchema;
 use internal_llm_client::{AllowedRoleMetadata, ClientProvider, OpenAIClientProviderVariant};
 
 use crate::{
@@ -29,9 +30,17 @@ use super::{
 
 mod anthropic;
 mod aws;
+#[cfg(not(target_arch = "wasm32"))]
+mod cache;
+#[cfg(not(target_arch = "wasm32"))]
+mod cassette;
 mod google;
 mod openai;
+#[cfg(not(target_arch = "wasm32"))]
+mod rate_limit;
 pub(super) mod request;
+mod timeout;
+pub mod tokenizer;
 mod vertex;
 
 // use crate::internal::llm_client::traits::ambassador_impl_WithRenderRawCurl;
@@ -103,6 +112,27 @@ impl WithClientProperties for LLMPrimitiveProvider {
     fn allowed_roles(&self) -> Vec<String> {
         match_llm_provider!(self, allowed_roles)
     }
+    fn cache_config(&self) -> Option<&internal_llm_client::CacheConfig> {
+        match_llm_provider!(self, cache_config)
+    }
+    fn circuit_breaker_config(&self) -> Option<&internal_llm_client::CircuitBreakerConfig> {
+        match_llm_provider!(self, circuit_breaker_config)
+    }
+    fn rate_limit_config(&self) -> Option<&internal_llm_client::RateLimitConfig> {
+        match_llm_provider!(self, rate_limit_config)
+    }
+    fn timeout_config(&self) -> Option<&internal_llm_client::TimeoutConfig> {
+        match_llm_provider!(self, timeout_config)
+    }
+    fn forbidden_coercions(&self) -> &[internal_llm_client::Coercion] {
+        match_llm_provider!(self, forbidden_coercions)
+    }
+    fn max_context_tokens(&self) -> Option<u64> {
+        match_llm_provider!(self, max_context_tokens)
+    }
+    fn pricing(&self) -> Option<&internal_llm_client::Pricing> {
+        match_llm_provider!(self, pricing)
+    }
 }
 
 impl TryFrom<(&ClientProperty, &RuntimeContext)> for LLMPrimitiveProvider {
@@ -226,7 +256,13 @@ impl WithSingleCallable for LLMPrimitiveProvider {
         ctx: &RuntimeContext,
         prompt: &internal_baml_jinja::RenderedPrompt,
     ) -> LLMResponse {
-        match_llm_provider!(self, single_call, async, ctx, prompt)
+        #[cfg(not(target_arch = "wasm32"))]
+        match cache::ResponseCache::for_client(self) {
+            Ok(Some(cache)) => return cache.single_call(self, ctx, prompt).await,
+            Ok(None) => {}
+            Err(e) => return LLMResponse::InternalFailure(format!("{e:#}")),
+        }
+        self.call_uncached(ctx, prompt).await
     }
 }
 
@@ -236,7 +272,13 @@ impl WithStreamable for LLMPrimitiveProvider {
         ctx: &RuntimeContext,
         prompt: &internal_baml_jinja::RenderedPrompt,
     ) -> super::traits::StreamResponse {
-        match_llm_provider!(self, stream, async, ctx, prompt)
+        #[cfg(not(target_arch = "wasm32"))]
+        match cache::ResponseCache::for_client(self) {
+            Ok(Some(cache)) => return cache.stream(self, ctx, prompt).await,
+            Ok(None) => {}
+            Err(e) => return Err(LLMResponse::InternalFailure(format!("{e:#}"))),
+        }
+        self.stream_uncached(ctx, prompt).await
     }
 }
 
@@ -255,6 +297,78 @@ impl IterOrchestrator for Arc<LLMPrimitiveProvider> {
     }
 }
 
+/// Tool definitions for the function being called, for clients configured
+/// with `output_mode "tools"`.
+fn output_tools(ctx: &RuntimeContext) -> Result<Vec<ToolSchema>> {
+    match &ctx.output_format {
+        Some(output_format) => output_format.tool_schemas(),
+        None => anyhow::bail!("output_mode \"tools\" can only be used when calling a function"),
+    }
+}
+
+/// JSON schema of the return type of the function being called, for clients
+/// configured with `output_mode "json_schema"`.
+fn output_json_schema(ctx: &RuntimeContext) -> Result<serde_json::Value> {
+    match &ctx.output_format {
+        Some(output_format) => output_format.target_json_schema(),
+        None => {
+            anyhow::bail!("output_mode \"json_schema\" can only be used when calling a function")
+        }
+    }
+}
+
+/// Asks a Gemini model for JSON matching [`output_json_schema`], in the
+/// `generationConfig` of a Google AI or Vertex request. Settings the user put
+/// in `generationConfig` are kept.
+fn constrain_generation_config(
+    generation_config: &mut serde_json::Value,
+    ctx: &RuntimeContext,
+) -> Result<()> {
+    let schema = output_json_schema(ctx)?;
+    let Some(config) = generation_config.as_object_mut() else {
+        anyhow::bail!("generationConfig must be an object");
+    };
+    config
+        .entry("responseMimeType")
+        .or_insert(serde_json::json!("application/json"));
+    config.entry("responseJsonSchema").or_insert(schema);
+    Ok(())
+}
+
+/// The `tools` and `toolConfig` of a Google AI or Vertex request that
+/// declares [`output_tools`] as functions and makes the model call one of
+/// them.
+fn gemini_tools(ctx: &RuntimeContext) -> Result<(serde_json::Value, serde_json::Value)> {
+    let tools = output_tools(ctx)?;
+    let names = tools
+        .iter()
+        .map(|tool| tool.name.clone())
+        .collect::<Vec<_>>();
+    let declarations = tools
+        .into_iter()
+        .map(|tool| {
+            serde_json::json!({ "name": tool.name, "parametersJsonSchema": tool.parameters })
+        })
+        .collect::<Vec<_>>();
+    Ok((
+        serde_json::json!([{ "functionDeclarations": declarations }]),
+        serde_json::json!({
+            "functionCallingConfig": { "mode": "ANY", "allowedFunctionNames": names }
+        }),
+    ))
+}
+
+/// Turns the arguments of the tool calls made by the model into the text
+/// handed to the output parser. A single call is parsed as-is, several calls
+/// are parsed as a list.
+fn tool_calls_to_content<'a>(arguments: impl IntoIterator<Item = &'a str>) -> String {
+    let arguments = arguments.into_iter().collect::<Vec<_>>();
+    match arguments.as_slice() {
+        [single] => single.to_string(),
+        many => format!("[{}]", many.join(",")),
+    }
+}
+
 impl std::fmt::Display for LLMPrimitiveProvider {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -275,4 +389,181 @@ impl LLMPrimitiveProvider {
     pub fn request_options(&self) -> &BamlMap<String, serde_json::Value> {
         match_llm_provider!(self, request_options)
     }
+
+    /// The model the client calls, if it is known before calling it.
+    pub fn model(&self) -> Option<&str> {
+        match self {
+            LLMPrimitiveProvider::Google(client) => Some(client.model()),
+            LLMPrimitiveProvider::Vertex(client) => Some(client.model()),
+            LLMPrimitiveProvider::Aws(client) => Some(client.model()),
+            LLMPrimitiveProvider::OpenAI(_) | LLMPrimitiveProvider::Anthropic(_) => self
+                .request_options()
+                .get("model")
+                .and_then(|model| model.as_str()),
+        }
+    }
+
+    /// The size of `prompt` for the model of this client.
+    pub fn count_prompt_tokens(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> tokenizer::TokenCount {
+        let provider = &match_llm_provider!(self, context).provider;
+        let tokenizer = tokenizer::tokenizer_for(ctx, provider, self.model());
+        tokenizer::count_prompt(tokenizer.as_ref(), prompt)
+    }
+
+    /// Calls the provider, or the cassette if one is configured, bypassing
+    /// the response cache.
+    async fn call_uncached(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> LLMResponse {
+        #[cfg(not(target_arch = "wasm32"))]
+        match cassette::Cassette::from_ctx(ctx) {
+            Ok(Some(cassette)) => return cassette.single_call(self, ctx, prompt).await,
+            Ok(None) => {}
+            Err(e) => return LLMResponse::InternalFailure(format!("{e:#}")),
+        }
+        self.call_provider(ctx, prompt).await
+    }
+
+    /// Streaming counterpart of [`Self::call_uncached`].
+    async fn stream_uncached(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> super::traits::StreamResponse {
+        #[cfg(not(target_arch = "wasm32"))]
+        match cassette::Cassette::from_ctx(ctx) {
+            Ok(Some(cassette)) => return cassette.stream(self, ctx, prompt).await,
+            Ok(None) => {}
+            Err(e) => return Err(LLMResponse::InternalFailure(format!("{e:#}"))),
+        }
+        self.stream_provider(ctx, prompt).await
+    }
+
+    /// Calls the provider directly, bypassing any cassette.
+    async fn call_provider(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> LLMResponse {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(limiter) = rate_limit::RateLimiter::for_client(self) {
+            return limiter.single_call(self, ctx, prompt).await;
+        }
+        self.call_unlimited(ctx, prompt).await
+    }
+
+    /// Streams from the provider directly, bypassing any cassette.
+    async fn stream_provider(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> super::traits::StreamResponse {
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(limiter) = rate_limit::RateLimiter::for_client(self) {
+            return limiter.stream(self, ctx, prompt).await;
+        }
+        self.stream_unlimited(ctx, prompt).await
+    }
+
+    /// Calls the provider without waiting on its rate limits.
+    async fn call_unlimited(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> LLMResponse {
+        // Boxed, as the layers above it nest the future of the request
+        // deeper than the compiler lays out by default.
+        let call = Box::pin(async { match_llm_provider!(self, single_call, async, ctx, prompt) });
+        match self.timeout_config() {
+            Some(config) => {
+                timeout::Deadlines::start(self, config, prompt)
+                    .single_call(call)
+                    .await
+            }
+            None => call.await,
+        }
+    }
+
+    /// Streaming counterpart of [`Self::call_unlimited`].
+    async fn stream_unlimited(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> super::traits::StreamResponse {
+        let stream = Box::pin(async { match_llm_provider!(self, stream, async, ctx, prompt) });
+        match self.timeout_config() {
+            Some(config) => {
+                timeout::Deadlines::start(self, config, prompt)
+                    .stream(stream)
+                    .await
+            }
+            None => stream.await,
+        }
+    }
+
+    /// Best-effort request body, with secrets redacted; `None` if the
+    /// provider does not build raw HTTP requests.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn request_body(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+        stream: bool,
+    ) -> Option<serde_json::Value> {
+        let stream = stream && self.supports_streaming();
+        let prompt = match prompt {
+            internal_baml_jinja::RenderedPrompt::Completion(p) => either::Left(p),
+            internal_baml_jinja::RenderedPrompt::Chat(c) => either::Right(c.as_slice()),
+        };
+        let body = match self {
+            LLMPrimitiveProvider::OpenAI(c) => request::request_body(c, ctx, prompt, stream).await,
+            LLMPrimitiveProvider::Anthropic(c) => {
+                request::request_body(c, ctx, prompt, stream).await
+            }
+            LLMPrimitiveProvider::Google(c) => request::request_body(c, ctx, prompt, stream).await,
+            LLMPrimitiveProvider::Vertex(c) => request::request_body(c, ctx, prompt, stream).await,
+            LLMPrimitiveProvider::Aws(_) => return None,
+        };
+        match body {
+            Ok(body) => Some(body),
+            Err(e) => {
+                log::debug!("Could not render request body: {e:#}");
+                None
+            }
+        }
+    }
+
+    /// Best-effort request URL, with secrets redacted; `None` if the
+    /// provider does not build raw HTTP requests.
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn request_url(
+        &self,
+        ctx: &RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> Option<String> {
+        let prompt = match prompt {
+            internal_baml_jinja::RenderedPrompt::Completion(p) => either::Left(p),
+            internal_baml_jinja::RenderedPrompt::Chat(c) => either::Right(c.as_slice()),
+        };
+        let url = match self {
+            LLMPrimitiveProvider::OpenAI(c) => request::request_url(c, ctx, prompt).await,
+            LLMPrimitiveProvider::Anthropic(c) => request::request_url(c, ctx, prompt).await,
+            LLMPrimitiveProvider::Google(c) => request::request_url(c, ctx, prompt).await,
+            LLMPrimitiveProvider::Vertex(c) => request::request_url(c, ctx, prompt).await,
+            LLMPrimitiveProvider::Aws(_) => return None,
+        };
+        match url {
+            Ok(url) => Some(url),
+            Err(e) => {
+                log::debug!("Could not render request URL: {e:#}");
+                None
+            }
+        }
+    }
 }
@@ -6,7 +6,7 @@ use baml_types::{BamlMap, BamlMedia, BamlMediaContent, BamlMediaType};
 use internal_baml_core::ir::ClientWalker;
 use internal_baml_jinja::{ChatMessagePart, RenderContext_Client, RenderedChatMessage};
 use internal_llm_client::openai::ResolvedOpenAI;
-use internal_llm_client::{AllowedRoleMetadata, FinishReasonFilter};
+use internal_llm_client::{AllowedRoleMetadata, FinishReasonFilter, OutputMode};
 use secrecy::ExposeSecret;
 use serde_json::json;
 
@@ -21,6 +21,9 @@ use crate::client_registry::ClientProperty;
 use crate::internal::llm_client::primitive::request::{
     make_parsed_request, make_request, RequestBuilder,
 };
+use crate::internal::llm_client::primitive::{
+    output_json_schema, output_tools, tool_calls_to_content,
+};
 use crate::internal::llm_client::traits::{
     SseResponseTrait, StreamResponse, ToProviderMessage, ToProviderMessageExt,
     WithClientProperties, WithStreamChat,
@@ -30,7 +33,7 @@ use crate::internal::llm_client::{
     LLMResponse, ModelFeatures,
 };
 
-use crate::request::create_client;
+use crate::request::create_llm_client;
 use crate::RuntimeContext;
 use eventsource_stream::Eventsource;
 use futures::StreamExt;
@@ -66,6 +69,34 @@ impl WithClientProperties for OpenAIClient {
         self.properties.allowed_roles()
     }
 
+    fn cache_config(&self) -> Option<&internal_llm_client::CacheConfig> {
+        self.properties.cache.as_ref()
+    }
+
+    fn circuit_breaker_config(&self) -> Option<&internal_llm_client::CircuitBreakerConfig> {
+        self.properties.circuit_breaker.as_ref()
+    }
+
+    fn rate_limit_config(&self) -> Option<&internal_llm_client::RateLimitConfig> {
+        self.properties.rate_limit.as_ref()
+    }
+
+    fn timeout_config(&self) -> Option<&internal_llm_client::TimeoutConfig> {
+        self.properties.timeouts.as_ref()
+    }
+
+    fn forbidden_coercions(&self) -> &[internal_llm_client::Coercion] {
+        &self.properties.forbid_coercions
+    }
+
+    fn max_context_tokens(&self) -> Option<u64> {
+        self.properties.max_context_tokens
+    }
+
+    fn pricing(&self) -> Option<&internal_llm_client::Pricing> {
+        self.properties.pricing.as_ref()
+    }
+
     fn default_role(&self) -> String {
         self.properties.default_role()
     }
@@ -158,10 +189,11 @@ impl WithNoCompletion for OpenAIClient {}
 // }
 
 impl WithChat for OpenAIClient {
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
         let (response, system_start, instant_start) =
             match make_parsed_request::<ChatCompletionResponse>(
                 self,
+                ctx,
                 either::Either::Right(prompt),
                 false,
             )
@@ -184,27 +216,37 @@ impl WithChat for OpenAIClient {
                     response.choices.len()
                 ),
                 code: ErrorCode::Other(200),
+                retry_after: None,
             });
         }
 
         let usage = response.usage.as_ref();
-
-        LLMResponse::Success(LLMCompleteResponse {
-            client: self.context.name.to_string(),
-            prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.to_vec()),
-            content: response.choices[0]
-                .message
+        let message = &response.choices[0].message;
+        let content = match message.tool_calls.as_deref() {
+            Some(tool_calls) if !tool_calls.is_empty() => {
+                tool_calls_to_content(tool_calls.iter().map(|c| c.function.arguments.as_str()))
+            }
+            _ => message
                 .content
                 .as_ref()
                 .map_or("", |s| s.as_str())
                 .to_string(),
+        };
+
+        LLMResponse::Success(LLMCompleteResponse {
+            client: self.context.name.to_string(),
+            prompt: internal_baml_jinja::RenderedPrompt::Chat(prompt.to_vec()),
+            content,
             start_time: system_start,
             latency: instant_start.elapsed(),
             model: response.model,
             request_options: self.properties.properties.clone(),
             metadata: LLMCompleteResponseMetadata {
                 baml_is_complete: match response.choices.get(0) {
-                    Some(c) => c.finish_reason.as_ref().is_some_and(|f| f == "stop"),
+                    Some(c) => c
+                        .finish_reason
+                        .as_ref()
+                        .is_some_and(|f| f == "stop" || f == "tool_calls"),
                     None => false,
                 },
                 finish_reason: match response.choices.get(0) {
@@ -214,6 +256,8 @@ impl WithChat for OpenAIClient {
                 prompt_tokens: usage.map(|u| u.prompt_tokens),
                 output_tokens: usage.map(|u| u.completion_tokens),
                 total_tokens: usage.map(|u| u.total_tokens),
+                cached: false,
+                cost: None,
             },
         })
     }
@@ -226,6 +270,7 @@ impl RequestBuilder for OpenAIClient {
 
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &[RenderedChatMessage]>,
         allow_proxy: bool,
         stream: bool,
@@ -274,6 +319,45 @@ impl RequestBuilder for OpenAIClient {
             }
         }
 
+        if self.properties.output_mode == OutputMode::Tools {
+            let tools = output_tools(ctx)?;
+            // Force a tool call. With several candidate tools, let the model pick.
+            let tool_choice = match tools.as_slice() {
+                [tool] => json!({ "type": "function", "function": { "name": tool.name } }),
+                _ => json!("required"),
+            };
+            body_obj.insert(
+                "tools".into(),
+                tools
+                    .into_iter()
+                    .map(|tool| {
+                        json!({
+                            "type": "function",
+                            "function": { "name": tool.name, "parameters": tool.parameters },
+                        })
+                    })
+                    .collect(),
+            );
+            body_obj.entry("tool_choice").or_insert(tool_choice);
+        }
+
+        if self.properties.output_mode == OutputMode::JsonSchema {
+            let schema = output_json_schema(ctx)?;
+            if schema["type"] != "object" {
+                anyhow::bail!(
+                    "output_mode \"json_schema\" requires the function to return a class or a map for this provider"
+                );
+            }
+            let json_schema = match strict_json_schema(schema.clone()) {
+                Some(strict) => json!({ "name": "response", "schema": strict, "strict": true }),
+                None => json!({ "name": "response", "schema": schema }),
+            };
+            body_obj.entry("response_format").or_insert(json!({
+                "type": "json_schema",
+                "json_schema": json_schema,
+            }));
+        }
+
         if stream {
             body_obj.insert("stream".into(), json!(true));
             if self.provider == "openai" {
@@ -305,6 +389,7 @@ impl SseResponseTrait for OpenAIClient {
         let prompt = prompt.to_vec();
         let client_name = self.context.name.clone();
         let params = self.properties.properties.clone();
+        let mut tool_arguments: Vec<String> = vec![];
         Ok(Box::pin(
             resp.bytes_stream()
                 .eventsource()
@@ -332,6 +417,8 @@ impl SseResponseTrait for OpenAIClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cached: false,
+                            cost: None,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -358,6 +445,7 @@ impl SseResponseTrait for OpenAIClient {
                                         latency: instant_start.elapsed(),
                                         message: format!("Failed to parse event: {:#?}", e),
                                         code: ErrorCode::UnsupportedResponse(2),
+                                        retry_after: None,
                                     },
                                 )));
                             }
@@ -366,10 +454,27 @@ impl SseResponseTrait for OpenAIClient {
                             if let Some(content) = choice.delta.content.as_ref() {
                                 inner.content += content.as_str();
                             }
+                            if let Some(tool_calls) = choice.delta.tool_calls.as_ref() {
+                                for call in tool_calls {
+                                    if tool_arguments.len() <= call.index {
+                                        tool_arguments.resize(call.index + 1, String::new());
+                                    }
+                                    if let Some(arguments) =
+                                        call.function.as_ref().and_then(|f| f.arguments.as_ref())
+                                    {
+                                        tool_arguments[call.index] += arguments;
+                                    }
+                                }
+                                inner.content = tool_calls_to_content(
+                                    tool_arguments.iter().map(String::as_str),
+                                );
+                            }
                             inner.model = event.model;
                             inner.metadata.finish_reason = choice.finish_reason.clone();
-                            inner.metadata.baml_is_complete =
-                                choice.finish_reason.as_ref().is_some_and(|s| s == "stop");
+                            inner.metadata.baml_is_complete = choice
+                                .finish_reason
+                                .as_ref()
+                                .is_some_and(|s| s == "stop" || s == "tool_calls");
                         }
                         inner.latency = instant_start.elapsed();
                         if let Some(usage) = event.usage.as_ref() {
@@ -388,11 +493,11 @@ impl SseResponseTrait for OpenAIClient {
 impl WithStreamChat for OpenAIClient {
     async fn stream_chat(
         &self,
-        _ctx: &RuntimeContext,
+        ctx: &RuntimeContext,
         prompt: &[RenderedChatMessage],
     ) -> StreamResponse {
         let (resp, system_start, instant_start) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
@@ -418,9 +523,9 @@ macro_rules! make_openai_client {
                 resolve_media_urls: ResolveMediaUrls::Never,
                 allowed_metadata: $properties.allowed_metadata.clone(),
             },
-            properties: $properties,
             retry_policy: $client.retry_policy.clone(),
-            client: create_client()?,
+            client: create_llm_client($properties.timeouts.as_ref())?,
+            properties: $properties,
         })
     };
     ($client:ident, $properties:ident, $provider:expr) => {
@@ -440,13 +545,13 @@ macro_rules! make_openai_client {
                 resolve_media_urls: ResolveMediaUrls::Never,
                 allowed_metadata: $properties.allowed_metadata.clone(),
             },
-            properties: $properties,
             retry_policy: $client
                 .elem()
                 .retry_policy_id
                 .as_ref()
                 .map(|s| s.to_string()),
-            client: create_client()?,
+            client: create_llm_client($properties.timeouts.as_ref())?,
+            properties: $properties,
         })
     };
 }
@@ -625,3 +730,80 @@ impl ToProviderMessageExt for OpenAIClient {
         Ok(res)
     }
 }
+
+/// Adapts `schema` to OpenAI's strict mode, which decodes only valid
+/// responses: every object has to list all of its properties as required
+/// (optional fields already accept null) and forbid any others. Returns
+/// `None` if the schema uses what strict mode can't express, like maps and
+/// tuples.
+fn strict_json_schema(mut schema: serde_json::Value) -> Option<serde_json::Value> {
+    use serde_json::Value;
+
+    fn visit(schema: &mut Value) -> bool {
+        let Some(obj) = schema.as_object_mut() else {
+            return true;
+        };
+        if obj.contains_key("prefixItems")
+            || obj
+                .get("additionalProperties")
+                .is_some_and(|v| v.as_bool() != Some(false))
+        {
+            return false;
+        }
+        if let Some(properties) = obj.get("properties").and_then(|p| p.as_object()) {
+            let required = properties.keys().cloned().collect::<Vec<_>>();
+            obj.insert("required".into(), json!(required));
+            obj.insert("additionalProperties".into(), json!(false));
+        }
+        obj.iter_mut()
+            .all(|(key, value)| match (key.as_str(), value) {
+                ("properties" | "$defs", Value::Object(children)) => {
+                    children.values_mut().all(visit)
+                }
+                ("items", item) => visit(item),
+                ("anyOf", Value::Array(options)) => options.iter_mut().all(visit),
+                _ => true,
+            })
+    }
+
+    visit(&mut schema).then_some(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_schema_requires_all_properties() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "name": { "type": "string" },
+                "tags": { "type": "array", "items": { "$ref": "#/$defs/Tag" } },
+                "note": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+            },
+            "required": ["name", "tags"],
+            "$defs": {
+                "Tag": { "type": "object", "properties": { "label": { "type": "string" } }, "required": [] },
+            },
+        });
+
+        let strict = strict_json_schema(schema).unwrap();
+        assert_eq!(strict["required"], json!(["name", "tags", "note"]));
+        assert_eq!(strict["additionalProperties"], json!(false));
+        assert_eq!(strict["$defs"]["Tag"]["required"], json!(["label"]));
+        assert_eq!(strict["$defs"]["Tag"]["additionalProperties"], json!(false));
+    }
+
+    #[test]
+    fn maps_are_not_strict() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "scores": { "type": "object", "additionalProperties": { "type": "integer" } },
+            },
+            "required": ["scores"],
+        });
+        assert!(strict_json_schema(schema).is_none());
+    }
+}
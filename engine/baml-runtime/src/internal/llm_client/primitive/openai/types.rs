@@ -65,7 +65,7 @@ pub struct ChatCompletionResponseMessage {
     pub content: Option<String>,
 
     /// The tool calls generated by the model, such as function calls.
-    // pub tool_calls: Option<Vec<ChatCompletionMessageToolCall>>,
+    pub tool_calls: Option<Vec<ChatCompletionMessageToolCall>>,
 
     /// The role of the author of this message.
     pub role: ChatCompletionMessageRole,
@@ -89,6 +89,8 @@ pub struct ChatCompletionMessageDelta {
     pub role: Option<ChatCompletionMessageRole>,
     /// The contents of the message
     pub content: Option<String>,
+    /// Fragments of the tool calls generated by the model.
+    pub tool_calls: Option<Vec<ChatCompletionMessageToolCallChunk>>,
     // The name of the user in a multi-user chat
     // #[serde(skip_serializing_if = "Option::is_none")]
     // pub name: Option<String>,
@@ -99,6 +101,37 @@ pub struct ChatCompletionMessageDelta {
     // pub function_call: Option<ChatCompletionFunctionCallDelta>,
 }
 
+/// A tool call generated by the model.
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct ChatCompletionMessageToolCall {
+    /// The ID of the tool call.
+    pub id: Option<String>,
+    /// The function that the model called.
+    pub function: FunctionCall,
+}
+
+#[derive(Debug, Deserialize, Clone, PartialEq)]
+pub struct FunctionCall {
+    /// The name of the function to call.
+    pub name: String,
+    /// The arguments to call the function with, as generated by the model in JSON format.
+    pub arguments: String,
+}
+
+/// Same as ChatCompletionMessageToolCall, but received during a response stream.
+#[derive(Deserialize, Clone, Debug)]
+pub struct ChatCompletionMessageToolCallChunk {
+    /// The position of the tool call this fragment belongs to.
+    pub index: usize,
+    pub function: Option<FunctionCallChunk>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct FunctionCallChunk {
+    pub name: Option<String>,
+    pub arguments: Option<String>,
+}
+
 #[derive(Debug, Deserialize, Clone, Default, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionMessageRole {
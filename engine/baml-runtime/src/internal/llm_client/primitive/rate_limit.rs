@@ -0,0 +1,219 @@
+//! Client-side limits for primitive clients configured with
+//! `max_concurrency`, `requests_per_minute` or `tokens_per_minute`.
+//!
+//! Limiters are shared by every call to the same client in the process, so
+//! concurrent calls from one runtime queue up here instead of being rejected
+//! by the provider. Request and token budgets use a sliding one minute
+//! window. Token usage is only known once a call finishes, so calls are held
+//! back once the tokens used in the last minute reach the budget.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use futures::StreamExt;
+use internal_llm_client::RateLimitConfig;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use web_time::{Duration, Instant};
+
+use crate::internal::llm_client::{
+    traits::{StreamResponse, WithClientProperties},
+    LLMResponse,
+};
+
+use super::LLMPrimitiveProvider;
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+pub(super) struct RateLimiter {
+    config: RateLimitConfig,
+    concurrency: Option<Arc<Semaphore>>,
+    /// Start of every call in the current window.
+    requests: Mutex<VecDeque<Instant>>,
+    /// End and token usage of every call in the current window.
+    tokens: Mutex<VecDeque<(Instant, u64)>>,
+}
+
+/// Held for the duration of a call.
+pub(super) struct Permit {
+    limiter: Arc<RateLimiter>,
+    _concurrency: Option<OwnedSemaphorePermit>,
+    tokens: Option<u64>,
+}
+
+type Limiters = Mutex<HashMap<(String, RateLimitConfig), Arc<RateLimiter>>>;
+
+static LIMITERS: OnceLock<Limiters> = OnceLock::new();
+
+impl RateLimiter {
+    /// The limiter configured for `client`, if any.
+    pub(super) fn for_client(client: &LLMPrimitiveProvider) -> Option<Arc<RateLimiter>> {
+        let config = client.rate_limit_config()?;
+        let mut limiters = LIMITERS
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|e| e.into_inner());
+        let limiter = limiters
+            .entry((client.name().to_string(), config.clone()))
+            .or_insert_with(|| Arc::new(RateLimiter::new(config)))
+            .clone();
+        Some(limiter)
+    }
+
+    fn new(config: &RateLimitConfig) -> Self {
+        RateLimiter {
+            config: config.clone(),
+            concurrency: config
+                .max_concurrency
+                .map(|n| Arc::new(Semaphore::new(n as usize))),
+            requests: Default::default(),
+            tokens: Default::default(),
+        }
+    }
+
+    /// Waits until a call fits within every limit.
+    pub(super) async fn acquire(self: &Arc<Self>) -> Permit {
+        let concurrency = match &self.concurrency {
+            // The semaphore is never closed.
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+        while let Some(wait) = self.try_start() {
+            async_std::task::sleep(wait).await;
+        }
+        Permit {
+            limiter: self.clone(),
+            _concurrency: concurrency,
+            tokens: None,
+        }
+    }
+
+    /// Records the start of a call, or returns how long to wait before
+    /// trying again.
+    fn try_start(&self) -> Option<Duration> {
+        let now = Instant::now();
+        let expired = |t: &Instant| now.duration_since(*t) >= WINDOW;
+        let retry_at = |t: Instant| Some((t + WINDOW).saturating_duration_since(now));
+
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        while tokens.front().is_some_and(|(t, _)| expired(t)) {
+            tokens.pop_front();
+        }
+        if let Some(budget) = self.config.tokens_per_minute {
+            if tokens.iter().map(|(_, n)| n).sum::<u64>() >= budget {
+                return retry_at(tokens.front()?.0);
+            }
+        }
+
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        while requests.front().is_some_and(expired) {
+            requests.pop_front();
+        }
+        if let Some(budget) = self.config.requests_per_minute {
+            if requests.len() >= budget as usize {
+                return retry_at(*requests.front()?);
+            }
+        }
+        requests.push_back(now);
+        None
+    }
+
+    pub(super) async fn single_call(
+        self: Arc<Self>,
+        client: &LLMPrimitiveProvider,
+        ctx: &crate::RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> LLMResponse {
+        let mut permit = self.acquire().await;
+        let response = client.call_unlimited(ctx, prompt).await;
+        permit.record(&response);
+        response
+    }
+
+    pub(super) async fn stream(
+        self: Arc<Self>,
+        client: &LLMPrimitiveProvider,
+        ctx: &crate::RuntimeContext,
+        prompt: &internal_baml_jinja::RenderedPrompt,
+    ) -> StreamResponse {
+        let mut permit = self.acquire().await;
+        let stream = match client.stream_unlimited(ctx, prompt).await {
+            Ok(stream) => stream,
+            Err(response) => {
+                permit.record(&response);
+                return Err(response);
+            }
+        };
+        // The permit is released once the stream is done or dropped.
+        Ok(Box::pin(stream.map(move |chunk| {
+            permit.record(&chunk);
+            chunk
+        })))
+    }
+}
+
+impl Permit {
+    fn record(&mut self, response: &LLMResponse) {
+        if let LLMResponse::Success(r) = response {
+            let metadata = &r.metadata;
+            let used = match (metadata.prompt_tokens, metadata.output_tokens) {
+                (None, None) => None,
+                (prompt, output) => Some(prompt.unwrap_or(0) + output.unwrap_or(0)),
+            };
+            self.tokens = metadata.total_tokens.or(used);
+        }
+    }
+}
+
+impl Drop for Permit {
+    fn drop(&mut self) {
+        if self.limiter.config.tokens_per_minute.is_none() {
+            return;
+        }
+        if let Some(tokens) = self.tokens {
+            self.limiter
+                .tokens
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push_back((Instant::now(), tokens));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requests_per_minute_waits_for_the_window() {
+        let limiter = RateLimiter::new(&RateLimitConfig {
+            requests_per_minute: Some(2),
+            ..Default::default()
+        });
+        assert_eq!(limiter.try_start(), None);
+        assert_eq!(limiter.try_start(), None);
+        let wait = limiter.try_start().expect("third call should wait");
+        assert!(wait > Duration::from_secs(59) && wait <= WINDOW, "{wait:?}");
+    }
+
+    #[test]
+    fn tokens_per_minute_counts_finished_calls() {
+        let limiter = Arc::new(RateLimiter::new(&RateLimitConfig {
+            tokens_per_minute: Some(100),
+            ..Default::default()
+        }));
+        drop(Permit {
+            limiter: limiter.clone(),
+            _concurrency: None,
+            tokens: Some(60),
+        });
+        assert_eq!(limiter.try_start(), None);
+        drop(Permit {
+            limiter: limiter.clone(),
+            _concurrency: None,
+            tokens: Some(40),
+        });
+        assert!(limiter.try_start().is_some());
+    }
+}
@@ -7,11 +7,13 @@ use reqwest::Response;
 use serde::de::DeserializeOwned;
 
 use crate::internal::llm_client::{traits::WithClient, ErrorCode, LLMErrorResponse, LLMResponse};
+use crate::RuntimeContext;
 
 pub trait RequestBuilder {
     #[allow(async_fn_in_trait)]
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &[RenderedChatMessage]>,
         allow_proxy: bool,
         stream: bool,
@@ -34,13 +36,14 @@ fn to_prompt(
 
 pub async fn make_request(
     client: &(impl WithClient + RequestBuilder),
+    ctx: &RuntimeContext,
     prompt: either::Either<&String, &[RenderedChatMessage]>,
     stream: bool,
 ) -> Result<(Response, web_time::SystemTime, web_time::Instant), LLMResponse> {
     let (system_now, instant_now) = (web_time::SystemTime::now(), web_time::Instant::now());
 
     let req = match client
-        .build_request(prompt, true, stream, true)
+        .build_request(ctx, prompt, true, stream, true)
         .await
         .context("Failed to build request")
     {
@@ -55,6 +58,7 @@ pub async fn make_request(
                 latency: instant_now.elapsed(),
                 message: format!("{:#?}", e),
                 code: ErrorCode::Other(2),
+                retry_after: None,
             }));
         }
     };
@@ -71,6 +75,7 @@ pub async fn make_request(
                 latency: instant_now.elapsed(),
                 message: format!("{:#?}", e),
                 code: ErrorCode::Other(2),
+                retry_after: None,
             }));
         }
     };
@@ -86,7 +91,12 @@ pub async fn make_request(
                 request_options: client.request_options().clone(),
                 latency: instant_now.elapsed(),
                 message: format!("{:?}", e),
-                code: ErrorCode::Other(2),
+                code: if e.is_timeout() {
+                    ErrorCode::Timeout
+                } else {
+                    ErrorCode::Other(2)
+                },
+                retry_after: None,
             }));
         }
     };
@@ -94,6 +104,7 @@ pub async fn make_request(
     let status = response.status();
     if !status.is_success() {
         let url = response.url().to_string();
+        let retry_after = retry_after(response.headers());
         let text = response.text().await.map_or_else(
             |_| "<no response>".to_string(),
             |text| {
@@ -113,18 +124,91 @@ pub async fn make_request(
             latency: instant_now.elapsed(),
             message: format!("Request failed: {}\n{}", url, text),
             code: ErrorCode::from_status(status),
+            retry_after,
         }));
     }
 
     Ok((response, system_now, instant_now))
 }
 
+/// How long the provider asked us to wait before trying again. `Retry-After`
+/// (and its millisecond variant) take precedence; otherwise the latest of the
+/// rate limit reset headers is used.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<web_time::Duration> {
+    let header = |name: &str| headers.get(name).and_then(|v| v.to_str().ok());
+
+    if let Some(ms) = header("retry-after-ms")
+        .and_then(|v| v.trim().parse::<f64>().ok())
+        .filter(|v| v.is_finite())
+    {
+        return Some(web_time::Duration::from_secs_f64(ms.max(0.0) / 1000.0));
+    }
+    if let Some(after) = header("retry-after").and_then(parse_reset) {
+        return Some(after);
+    }
+    [
+        "x-ratelimit-reset",
+        "x-ratelimit-reset-requests",
+        "x-ratelimit-reset-tokens",
+    ]
+    .into_iter()
+    .filter_map(|name| header(name).and_then(parse_reset))
+    .max()
+}
+
+/// Parses a reset header value, which providers send as seconds (`"20"`),
+/// a duration (`"1m30s"`, `"250ms"`), a unix timestamp, or a date.
+fn parse_reset(value: &str) -> Option<web_time::Duration> {
+    let value = value.trim();
+    let now = chrono::Utc::now();
+    let until = |at: chrono::DateTime<chrono::Utc>| Some((at - now).to_std().unwrap_or_default());
+
+    if let Some(secs) = value.parse::<f64>().ok().filter(|v| v.is_finite()) {
+        // Anything past the year 2001 is a timestamp rather than a delay.
+        if secs > 1e9 {
+            return chrono::DateTime::from_timestamp(secs as i64, 0).and_then(until);
+        }
+        return Some(web_time::Duration::from_secs_f64(secs.max(0.0)));
+    }
+    if let Ok(at) = chrono::DateTime::parse_from_rfc2822(value)
+        .or_else(|_| chrono::DateTime::parse_from_rfc3339(value))
+    {
+        return until(at.with_timezone(&chrono::Utc));
+    }
+
+    // Go style durations, e.g. `1h2m3.5s` or `120ms`.
+    let mut total = 0.0;
+    let mut rest = value;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .filter(|i| *i > 0)?;
+        let (number, tail) = rest.split_at(split);
+        let number = number.parse::<f64>().ok()?;
+        let unit_len = tail
+            .find(|c: char| c.is_ascii_digit())
+            .unwrap_or(tail.len());
+        let (unit, tail) = tail.split_at(unit_len);
+        total += number
+            * match unit {
+                "h" => 3600.0,
+                "m" => 60.0,
+                "s" => 1.0,
+                "ms" => 0.001,
+                _ => return None,
+            };
+        rest = tail;
+    }
+    (total > 0.0).then(|| web_time::Duration::from_secs_f64(total))
+}
+
 pub async fn make_parsed_request<T: DeserializeOwned>(
     client: &(impl WithClient + RequestBuilder),
+    ctx: &RuntimeContext,
     prompt: either::Either<&String, &[RenderedChatMessage]>,
     stream: bool,
 ) -> Result<(T, web_time::SystemTime, web_time::Instant), LLMResponse> {
-    let (response, system_now, instant_now) = make_request(client, prompt, stream).await?;
+    let (response, system_now, instant_now) = make_request(client, ctx, prompt, stream).await?;
     let j = match response.json::<serde_json::Value>().await {
         Ok(response) => response,
         Err(e) => {
@@ -137,6 +221,7 @@ pub async fn make_parsed_request<T: DeserializeOwned>(
                 latency: instant_now.elapsed(),
                 message: e.to_string(),
                 code: ErrorCode::Other(2),
+                retry_after: None,
             }))
         }
     };
@@ -156,6 +241,61 @@ pub async fn make_parsed_request<T: DeserializeOwned>(
             latency: instant_now.elapsed(),
             message: format!("{:?}", e),
             code: ErrorCode::Other(2),
+            retry_after: None,
         })),
     }
 }
+
+/// The JSON body `client` would send for `prompt`, with secrets redacted.
+pub async fn request_body(
+    client: &impl RequestBuilder,
+    ctx: &RuntimeContext,
+    prompt: either::Either<&String, &[RenderedChatMessage]>,
+    stream: bool,
+) -> Result<serde_json::Value> {
+    let request = client
+        .build_request(ctx, prompt, false, stream, false)
+        .await?
+        .build()?;
+    let body = request
+        .body()
+        .and_then(|b| b.as_bytes())
+        .context("Request has no body")?;
+    Ok(serde_json::from_slice(body)?)
+}
+
+/// The URL `client` sends `prompt` to, with secrets redacted.
+pub async fn request_url(
+    client: &impl RequestBuilder,
+    ctx: &RuntimeContext,
+    prompt: either::Either<&String, &[RenderedChatMessage]>,
+) -> Result<String> {
+    let request = client
+        .build_request(ctx, prompt, false, false, false)
+        .await?
+        .build()?;
+    Ok(request.url().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_reset_headers() {
+        let secs = |v: &str| parse_reset(v).map(|d| d.as_secs_f64());
+        assert_eq!(secs("20"), Some(20.0));
+        assert_eq!(secs("1m30s"), Some(90.0));
+        assert_eq!(secs("250ms"), Some(0.25));
+        assert_eq!(secs("6m0s"), Some(360.0));
+        assert_eq!(secs("Wed, 21 Oct 2015 07:28:00 GMT"), Some(0.0));
+        assert_eq!(secs("soon"), None);
+
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("x-ratelimit-reset-requests", "2s".parse().unwrap());
+        headers.insert("x-ratelimit-reset-tokens", "15s".parse().unwrap());
+        assert_eq!(retry_after(&headers).map(|d| d.as_secs()), Some(15));
+        headers.insert("retry-after", "3".parse().unwrap());
+        assert_eq!(retry_after(&headers).map(|d| d.as_secs()), Some(3));
+    }
+}
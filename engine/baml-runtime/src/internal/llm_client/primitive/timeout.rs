@@ -0,0 +1,199 @@
+//! Request timeouts for clients configured with `request_timeout_ms`,
+//! `time_to_first_token_timeout_ms` or `idle_timeout_ms`.
+//!
+//! `connect_timeout_ms` is applied by the HTTP client of each provider. The
+//! other timeouts are enforced here, around the provider call, so they hold
+//! for every provider. A non-streaming response counts as its own first
+//! token. Calls that run out of time fail with [`ErrorCode::Timeout`].
+
+use futures::StreamExt;
+use internal_baml_jinja::RenderedPrompt;
+use internal_llm_client::TimeoutConfig;
+use web_time::{Duration, Instant, SystemTime};
+
+use crate::internal::llm_client::{
+    traits::StreamResponse, ErrorCode, LLMErrorResponse, LLMResponse,
+};
+
+use super::LLMPrimitiveProvider;
+
+pub(super) struct Deadlines {
+    client: String,
+    prompt: RenderedPrompt,
+    request_options: baml_types::BamlMap<String, serde_json::Value>,
+    config: TimeoutConfig,
+    system_start: SystemTime,
+    instant_start: Instant,
+}
+
+/// The first deadline to be hit, with the option that set it.
+struct Deadline {
+    at: Instant,
+    option: &'static str,
+    ms: u64,
+}
+
+impl Deadlines {
+    pub(super) fn start(
+        client: &LLMPrimitiveProvider,
+        config: &TimeoutConfig,
+        prompt: &RenderedPrompt,
+    ) -> Self {
+        Deadlines {
+            client: client.name().to_string(),
+            prompt: prompt.clone(),
+            request_options: client.request_options().clone(),
+            config: config.clone(),
+            system_start: SystemTime::now(),
+            instant_start: Instant::now(),
+        }
+    }
+
+    /// `last_chunk` is when the previous stream chunk arrived, if any did.
+    fn next(&self, last_chunk: Option<Instant>) -> Option<Deadline> {
+        let after = |from: Instant, option, ms: Option<u64>| {
+            ms.map(|ms| Deadline {
+                at: from + Duration::from_millis(ms),
+                option,
+                ms,
+            })
+        };
+        let waiting = match last_chunk {
+            None => after(
+                self.instant_start,
+                "time_to_first_token_timeout_ms",
+                self.config.time_to_first_token_timeout_ms,
+            ),
+            Some(t) => after(t, "idle_timeout_ms", self.config.idle_timeout_ms),
+        };
+        let request = after(
+            self.instant_start,
+            "request_timeout_ms",
+            self.config.request_timeout_ms,
+        );
+        [waiting, request]
+            .into_iter()
+            .flatten()
+            .min_by_key(|d| d.at)
+    }
+
+    /// Runs `future` until the next deadline, or fails with a timeout.
+    async fn run<F: std::future::Future>(
+        &self,
+        last_chunk: Option<Instant>,
+        future: F,
+    ) -> Result<F::Output, LLMResponse> {
+        let Some(deadline) = self.next(last_chunk) else {
+            return Ok(future.await);
+        };
+        let left = deadline.at.saturating_duration_since(Instant::now());
+        async_std::future::timeout(left, future)
+            .await
+            .map_err(|_| self.timed_out(&deadline))
+    }
+
+    fn timed_out(&self, deadline: &Deadline) -> LLMResponse {
+        LLMResponse::LLMFailure(LLMErrorResponse {
+            client: self.client.clone(),
+            model: None,
+            prompt: self.prompt.clone(),
+            request_options: self.request_options.clone(),
+            start_time: self.system_start,
+            latency: self.instant_start.elapsed(),
+            message: format!(
+                "Request timed out: {} of {}ms exceeded",
+                deadline.option, deadline.ms
+            ),
+            code: ErrorCode::Timeout,
+            retry_after: None,
+        })
+    }
+
+    pub(super) async fn single_call(
+        self,
+        call: impl std::future::Future<Output = LLMResponse>,
+    ) -> LLMResponse {
+        self.run(None, call).await.unwrap_or_else(|e| e)
+    }
+
+    pub(super) async fn stream(
+        self,
+        stream: impl std::future::Future<Output = StreamResponse>,
+    ) -> StreamResponse {
+        let stream = self.run(None, stream).await??;
+        // The stream ends after the first chunk that timed out.
+        let chunks = futures::stream::unfold(Some((self, stream, None)), |state| async move {
+            let (deadlines, mut stream, last_chunk) = state?;
+            match deadlines.run(last_chunk, stream.next()).await {
+                Ok(Some(chunk)) => Some((chunk, Some((deadlines, stream, Some(Instant::now()))))),
+                Ok(None) => None,
+                Err(timed_out) => Some((timed_out, None)),
+            }
+        });
+        Ok(Box::pin(chunks))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deadlines(config: TimeoutConfig) -> Deadlines {
+        Deadlines {
+            client: "Slow".into(),
+            prompt: RenderedPrompt::Completion(String::new()),
+            request_options: Default::default(),
+            config,
+            system_start: SystemTime::now(),
+            instant_start: Instant::now(),
+        }
+    }
+
+    #[test]
+    fn picks_the_earliest_deadline() {
+        let d = deadlines(TimeoutConfig {
+            request_timeout_ms: Some(5_000),
+            time_to_first_token_timeout_ms: Some(1_000),
+            idle_timeout_ms: Some(10_000),
+            ..Default::default()
+        });
+        assert_eq!(
+            d.next(None).map(|d| d.option),
+            Some("time_to_first_token_timeout_ms")
+        );
+        assert_eq!(
+            d.next(Some(Instant::now())).map(|d| d.option),
+            Some("request_timeout_ms")
+        );
+        assert!(deadlines(Default::default()).next(None).is_none());
+    }
+
+    #[tokio::test]
+    async fn idle_stream_times_out() {
+        let d = deadlines(TimeoutConfig {
+            idle_timeout_ms: Some(10),
+            ..Default::default()
+        });
+        let hung = futures::stream::once(async { LLMResponse::InternalFailure("first".into()) })
+            .chain(futures::stream::pending());
+        let started: StreamResponse = Ok(Box::pin(hung));
+        let chunks = d
+            .stream(async move { started })
+            .await
+            .expect("stream should start")
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(chunks.len(), 2);
+        match &chunks[1] {
+            LLMResponse::LLMFailure(e) => {
+                assert!(matches!(e.code, ErrorCode::Timeout));
+                assert!(
+                    e.message.contains("idle_timeout_ms of 10ms"),
+                    "{}",
+                    e.message
+                );
+            }
+            other => panic!("expected a timeout, got {other:?}"),
+        }
+    }
+}
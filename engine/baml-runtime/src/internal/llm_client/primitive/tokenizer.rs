@@ -0,0 +1,325 @@
+//! Offline token counts for rendered prompts, so that a prompt too large for
+//! the context window of a client is caught before it is sent.
+//!
+//! A prompt is counted with the tokenizer for the provider and model of the
+//! client. Tokenizers registered with [`register_tokenizer`] come first. OpenAI
+//! models then use the byte pair encoding of their tiktoken file, like
+//! `o200k_base.tiktoken`, read from the directory in `BAML_TIKTOKEN_DIR`.
+//! Every other model, and OpenAI models whose file isn't there, are estimated
+//! at four characters per token.
+//!
+//! Counts are estimates either way: the tokens chat formatting adds are
+//! approximated, and media is not counted.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use anyhow::{Context, Result};
+use base64::{prelude::BASE64_STANDARD, Engine};
+use internal_baml_jinja::RenderedPrompt;
+use serde::Serialize;
+
+use crate::RuntimeContext;
+
+const TIKTOKEN_DIR_ENV: &str = "BAML_TIKTOKEN_DIR";
+
+/// Splits text into the pieces that are encoded separately. This is the
+/// pattern of `cl100k_base`, without the lookahead that keeps the last space
+/// before a word out of a run of whitespace, which `regex` doesn't support.
+const PIECE_PATTERN: &str = r"(?i:'s|'t|'re|'ve|'m|'ll|'d)|[^\r\n\p{L}\p{N}]?\p{L}+|\p{N}{1,3}| ?[^\s\p{L}\p{N}]+[\r\n]*|\s*[\r\n]+|\s+";
+
+/// Tokens a chat message takes on top of its role and content.
+const TOKENS_PER_MESSAGE: usize = 3;
+/// Tokens that start the reply of the model.
+const TOKENS_PER_REPLY: usize = 3;
+
+pub trait Tokenizer: Send + Sync {
+    /// The name of the encoding, like `cl100k_base`.
+    fn name(&self) -> &str;
+    fn count(&self, text: &str) -> usize;
+}
+
+/// The size of a rendered prompt.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct TokenCount {
+    pub tokens: usize,
+    /// The tokenizer the prompt was counted with, or `estimate` if it was
+    /// estimated from its length.
+    pub tokenizer: String,
+}
+
+impl std::fmt::Display for TokenCount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} tokens ({})", self.tokens, self.tokenizer)
+    }
+}
+
+/// Byte pair encoding with the ranks of a tiktoken file.
+pub struct BpeTokenizer {
+    name: String,
+    ranks: HashMap<Vec<u8>, u32>,
+    pieces: regex::Regex,
+}
+
+impl BpeTokenizer {
+    /// Reads the contents of a tiktoken file, which has a base64 encoded
+    /// token and its rank on every line.
+    pub fn from_tiktoken(name: impl Into<String>, contents: &str) -> Result<Self> {
+        let mut ranks = HashMap::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let (token, rank) = line
+                .split_once(' ')
+                .with_context(|| format!("Line {}: expected a token and a rank", i + 1))?;
+            let token = BASE64_STANDARD
+                .decode(token)
+                .with_context(|| format!("Line {}: invalid token", i + 1))?;
+            let rank = rank
+                .trim()
+                .parse()
+                .with_context(|| format!("Line {}: invalid rank", i + 1))?;
+            ranks.insert(token, rank);
+        }
+        Ok(Self {
+            name: name.into(),
+            ranks,
+            pieces: regex::Regex::new(PIECE_PATTERN)?,
+        })
+    }
+
+    pub fn load(name: impl Into<String>, path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::from_tiktoken(name, &contents)
+            .with_context(|| format!("Invalid tiktoken file {}", path.display()))
+    }
+
+    /// The number of tokens of one piece. Starting from single bytes, the
+    /// adjacent parts that make the token with the lowest rank are merged
+    /// until no two parts make a token.
+    fn count_piece(&self, piece: &[u8]) -> usize {
+        if piece.len() <= 1 || self.ranks.contains_key(piece) {
+            return 1;
+        }
+        // Where each part starts, and where the last one ends.
+        let mut bounds = (0..=piece.len()).collect::<Vec<_>>();
+        while let Some((_, i)) = (0..bounds.len() - 2)
+            .filter_map(|i| {
+                self.ranks
+                    .get(&piece[bounds[i]..bounds[i + 2]])
+                    .map(|rank| (*rank, i))
+            })
+            .min()
+        {
+            bounds.remove(i + 1);
+        }
+        bounds.len() - 1
+    }
+}
+
+impl Tokenizer for BpeTokenizer {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn count(&self, text: &str) -> usize {
+        self.pieces
+            .find_iter(text)
+            .map(|piece| self.count_piece(piece.as_str().as_bytes()))
+            .sum()
+    }
+}
+
+/// Four characters per token, which is about right for English text with
+/// most tokenizers.
+pub struct CharEstimate;
+
+impl Tokenizer for CharEstimate {
+    fn name(&self) -> &str {
+        "estimate"
+    }
+
+    fn count(&self, text: &str) -> usize {
+        text.chars().count().div_ceil(4)
+    }
+}
+
+#[derive(Default)]
+struct Registry {
+    /// Provider, model prefix and tokenizer, in the order they were
+    /// registered.
+    registered: Vec<(String, String, Arc<dyn Tokenizer>)>,
+    /// Encodings read from tiktoken files, by path. `None` if the file
+    /// couldn't be read, so that it is only tried once.
+    loaded: HashMap<PathBuf, Option<Arc<dyn Tokenizer>>>,
+}
+
+static REGISTRY: OnceLock<Mutex<Registry>> = OnceLock::new();
+
+fn registry() -> std::sync::MutexGuard<'static, Registry> {
+    REGISTRY
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+/// Counts the prompts of clients of `provider`, like `openai`, whose model
+/// starts with `model_prefix` with `tokenizer`. An empty prefix matches every
+/// model. The last matching registration wins.
+pub fn register_tokenizer(provider: &str, model_prefix: &str, tokenizer: Arc<dyn Tokenizer>) {
+    registry()
+        .registered
+        .push((provider.to_string(), model_prefix.to_string(), tokenizer));
+}
+
+/// Like [`register_tokenizer`], with the byte pair encoding of the tiktoken
+/// file at `path`, named after the file. This is what the language clients
+/// register tokenizers with.
+pub fn register_tiktoken_file(provider: &str, model_prefix: &str, path: &Path) -> Result<()> {
+    let name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "tiktoken".to_string());
+    let tokenizer = BpeTokenizer::load(name, path)?;
+    register_tokenizer(provider, model_prefix, Arc::new(tokenizer));
+    Ok(())
+}
+
+/// The tiktoken encoding of an OpenAI model.
+fn openai_encoding(model: &str) -> Option<&'static str> {
+    const O200K_BASE: &[&str] = &[
+        "gpt-4o",
+        "gpt-4.1",
+        "gpt-4.5",
+        "gpt-5",
+        "chatgpt-4o",
+        "o1",
+        "o3",
+        "o4",
+    ];
+    const CL100K_BASE: &[&str] = &["gpt-4", "gpt-3.5", "text-embedding-"];
+    if O200K_BASE.iter().any(|prefix| model.starts_with(prefix)) {
+        Some("o200k_base")
+    } else if CL100K_BASE.iter().any(|prefix| model.starts_with(prefix)) {
+        Some("cl100k_base")
+    } else {
+        None
+    }
+}
+
+/// The tokenizer for the prompts of a client of `provider` calling `model`.
+pub(crate) fn tokenizer_for(
+    ctx: &RuntimeContext,
+    provider: &str,
+    model: Option<&str>,
+) -> Arc<dyn Tokenizer> {
+    let mut registry = registry();
+    let registered = registry
+        .registered
+        .iter()
+        .rev()
+        .find(|(p, prefix, _)| {
+            p == provider && (prefix.is_empty() || model.is_some_and(|m| m.starts_with(prefix)))
+        })
+        .map(|(_, _, tokenizer)| tokenizer.clone());
+    if let Some(tokenizer) = registered {
+        return tokenizer;
+    }
+
+    let encoding = match provider {
+        "openai" | "azure-openai" | "openai-generic" => model.and_then(openai_encoding),
+        _ => None,
+    };
+    let dir = ctx
+        .env_vars()
+        .get(TIKTOKEN_DIR_ENV)
+        .filter(|dir| !dir.is_empty());
+    let (Some(encoding), Some(dir)) = (encoding, dir) else {
+        return Arc::new(CharEstimate);
+    };
+    let path = Path::new(dir).join(format!("{encoding}.tiktoken"));
+    let loaded = registry.loaded.entry(path.clone()).or_insert_with(|| {
+        match BpeTokenizer::load(encoding, &path) {
+            Ok(tokenizer) => Some(Arc::new(tokenizer) as Arc<dyn Tokenizer>),
+            Err(e) => {
+                log::warn!("Estimating token counts instead: {e:#}");
+                None
+            }
+        }
+    });
+    match loaded {
+        Some(tokenizer) => tokenizer.clone(),
+        None => Arc::new(CharEstimate),
+    }
+}
+
+/// The number of tokens of `prompt`.
+pub(crate) fn count_prompt(tokenizer: &dyn Tokenizer, prompt: &RenderedPrompt) -> TokenCount {
+    let tokens = match prompt {
+        RenderedPrompt::Completion(text) => tokenizer.count(text),
+        RenderedPrompt::Chat(messages) => {
+            messages
+                .iter()
+                .map(|message| {
+                    TOKENS_PER_MESSAGE
+                        + tokenizer.count(&message.role)
+                        + message
+                            .parts
+                            .iter()
+                            .filter_map(|part| part.as_text())
+                            .map(|text| tokenizer.count(text))
+                            .sum::<usize>()
+                })
+                .sum::<usize>()
+                + TOKENS_PER_REPLY
+        }
+    };
+    TokenCount {
+        tokens,
+        tokenizer: tokenizer.name().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tiktoken(tokens: &[&str]) -> String {
+        tokens
+            .iter()
+            .enumerate()
+            .map(|(rank, token)| format!("{} {rank}\n", BASE64_STANDARD.encode(token)))
+            .collect()
+    }
+
+    #[test]
+    fn merges_by_rank() {
+        let tokens = ["a", "b", "c", " ", "ab", "bc", "abc", " abc"];
+        let tokenizer = BpeTokenizer::from_tiktoken("test", &tiktoken(&tokens)).unwrap();
+        assert_eq!(tokenizer.count("abc"), 1);
+        assert_eq!(tokenizer.count(" abc abc"), 2);
+        // `ab` has a lower rank than `bc`, so the last `c` is left over.
+        assert_eq!(tokenizer.count("abcc"), 2);
+        assert_eq!(tokenizer.count("cab"), 2);
+    }
+
+    #[test]
+    fn rejects_malformed_files() {
+        assert!(BpeTokenizer::from_tiktoken("test", "YQ==\n").is_err());
+        assert!(BpeTokenizer::from_tiktoken("test", "YQ== one\n").is_err());
+    }
+
+    #[test]
+    fn estimates_other_models() {
+        assert_eq!(CharEstimate.count("12345678"), 2);
+        assert_eq!(CharEstimate.count("123456789"), 3);
+        assert_eq!(openai_encoding("gpt-4o-mini"), Some("o200k_base"));
+        assert_eq!(openai_encoding("gpt-4-turbo"), Some("cl100k_base"));
+        assert_eq!(openai_encoding("llama3"), None);
+    }
+}
@@ -9,7 +9,9 @@ use crate::RuntimeContext;
 use crate::{
     internal::llm_client::{
         primitive::{
+            constrain_generation_config, gemini_tools,
             request::{make_parsed_request, make_request, RequestBuilder},
+            tool_calls_to_content,
             vertex::types::{FinishReason, VertexResponse},
         },
         traits::{
@@ -19,7 +21,7 @@ use crate::{
         ErrorCode, LLMCompleteResponse, LLMCompleteResponseMetadata, LLMErrorResponse, LLMResponse,
         ModelFeatures,
     },
-    request::create_client,
+    request::create_llm_client,
 };
 use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
@@ -30,7 +32,8 @@ use internal_llm_client::vertex::{
     BaseUrlOrLocation, ResolvedGcpAuthStrategy, ResolvedVertex, ServiceAccount,
 };
 use internal_llm_client::{
-    AllowedRoleMetadata, ClientProvider, ResolvedClientProperty, UnresolvedClientProperty,
+    AllowedRoleMetadata, ClientProvider, OutputMode, ResolvedClientProperty,
+    UnresolvedClientProperty,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -94,6 +97,29 @@ impl WithClientProperties for VertexClient {
     fn allowed_roles(&self) -> Vec<String> {
         self.properties.allowed_roles()
     }
+    fn cache_config(&self) -> Option<&internal_llm_client::CacheConfig> {
+        self.properties.cache.as_ref()
+    }
+    fn circuit_breaker_config(&self) -> Option<&internal_llm_client::CircuitBreakerConfig> {
+        self.properties.circuit_breaker.as_ref()
+    }
+    fn rate_limit_config(&self) -> Option<&internal_llm_client::RateLimitConfig> {
+        self.properties.rate_limit.as_ref()
+    }
+    fn timeout_config(&self) -> Option<&internal_llm_client::TimeoutConfig> {
+        self.properties.timeouts.as_ref()
+    }
+    fn forbidden_coercions(&self) -> &[internal_llm_client::Coercion] {
+        &self.properties.forbid_coercions
+    }
+
+    fn max_context_tokens(&self) -> Option<u64> {
+        self.properties.max_context_tokens
+    }
+
+    fn pricing(&self) -> Option<&internal_llm_client::Pricing> {
+        self.properties.pricing.as_ref()
+    }
 }
 
 impl WithClient for VertexClient {
@@ -120,6 +146,7 @@ impl SseResponseTrait for VertexClient {
         let client_name = self.context.name.clone();
         let model_id = self.properties.model.clone();
         let params = self.properties.properties.clone();
+        let mut tool_calls = Vec::<String>::new();
         Ok(Box::pin(
             resp.bytes_stream()
                 .eventsource()
@@ -145,6 +172,8 @@ impl SseResponseTrait for VertexClient {
                             prompt_tokens: None,
                             output_tokens: None,
                             total_tokens: None,
+                            cached: false,
+                            cost: None,
                         },
                     }),
                     move |accumulated: &mut Result<LLMCompleteResponse>, event| {
@@ -171,17 +200,22 @@ impl SseResponseTrait for VertexClient {
                                         latency: instant_start.elapsed(),
                                         message: format!("Failed to parse event: {:#?}", e),
                                         code: ErrorCode::UnsupportedResponse(2),
+                                        retry_after: None,
                                     },
                                 )));
                             }
                         };
                         if let Some(choice) = event.candidates.first() {
-                            if let Some(content) = choice
-                                .content
-                                .as_ref()
-                                .and_then(|c| c.parts.first().map(|p| p.text.as_ref()))
-                            {
-                                inner.content += content;
+                            if let Some(content) = choice.content.as_ref() {
+                                // Function calls arrive whole, one per part.
+                                tool_calls.extend(content.function_call_arguments());
+                                if !tool_calls.is_empty() {
+                                    inner.content = tool_calls_to_content(
+                                        tool_calls.iter().map(String::as_str),
+                                    );
+                                } else if let Some(part) = content.parts.first() {
+                                    inner.content += &part.text;
+                                }
                             }
                             if let Some(FinishReason::Stop) = choice.finish_reason.as_ref() {
                                 inner.metadata.baml_is_complete = true;
@@ -206,7 +240,7 @@ impl WithStreamChat for VertexClient {
     ) -> StreamResponse {
         //incomplete, streaming response object is returned
         let (response, system_now, instant_now) =
-            match make_request(self, either::Either::Right(prompt), true).await {
+            match make_request(self, ctx, either::Either::Right(prompt), true).await {
                 Ok(v) => v,
                 Err(e) => return Err(e),
             };
@@ -215,6 +249,10 @@ impl WithStreamChat for VertexClient {
 }
 
 impl VertexClient {
+    pub fn model(&self) -> &str {
+        &self.properties.model
+    }
+
     pub fn new(client: &ClientWalker, ctx: &RuntimeContext) -> Result<Self> {
         let properties = resolve_properties(&client.elem().provider, client.options(), ctx)?;
         Ok(Self {
@@ -237,7 +275,7 @@ impl VertexClient {
                 .retry_policy_id
                 .as_ref()
                 .map(|s| s.to_string()),
-            client: create_client()?,
+            client: create_llm_client(properties.timeouts.as_ref())?,
             properties,
         })
     }
@@ -261,7 +299,7 @@ impl VertexClient {
                 allowed_metadata: properties.allowed_metadata.clone(),
             },
             retry_policy: client.retry_policy.clone(),
-            client: create_client()?,
+            client: create_llm_client(properties.timeouts.as_ref())?,
             properties,
         })
     }
@@ -274,6 +312,7 @@ impl RequestBuilder for VertexClient {
 
     async fn build_request(
         &self,
+        ctx: &RuntimeContext,
         prompt: either::Either<&String, &[RenderedChatMessage]>,
         allow_proxy: bool,
         stream: bool,
@@ -331,6 +370,21 @@ impl RequestBuilder for VertexClient {
             either::Either::Right(messages) => json_body.extend(self.chat_to_message(messages)?),
         }
 
+        if self.properties.output_mode == OutputMode::JsonSchema {
+            let generation_config = json_body
+                .entry("generationConfig".to_string())
+                .or_insert_with(|| json!({}));
+            constrain_generation_config(generation_config, ctx)?;
+        }
+
+        if self.properties.output_mode == OutputMode::Tools {
+            let (tools, tool_config) = gemini_tools(ctx)?;
+            json_body.insert("tools".to_string(), tools);
+            json_body
+                .entry("toolConfig".to_string())
+                .or_insert(tool_config);
+        }
+
         let req = req.json(&json_body);
 
         Ok(req)
@@ -342,15 +396,19 @@ impl RequestBuilder for VertexClient {
 }
 
 impl WithChat for VertexClient {
-    async fn chat(&self, _ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
+    async fn chat(&self, ctx: &RuntimeContext, prompt: &[RenderedChatMessage]) -> LLMResponse {
         //non-streaming, complete response is returned
-        let (response, system_now, instant_now) =
-            match make_parsed_request::<VertexResponse>(self, either::Either::Right(prompt), false)
-                .await
-            {
-                Ok(v) => v,
-                Err(e) => return e,
-            };
+        let (response, system_now, instant_now) = match make_parsed_request::<VertexResponse>(
+            self,
+            ctx,
+            either::Either::Right(prompt),
+            false,
+        )
+        .await
+        {
+            Ok(v) => v,
+            Err(e) => return e,
+        };
 
         if response.candidates.len() != 1 {
             return LLMResponse::LLMFailure(LLMErrorResponse {
@@ -365,13 +423,17 @@ impl WithChat for VertexClient {
                     response.candidates.len()
                 ),
                 code: ErrorCode::Other(200),
+                retry_after: None,
             });
         }
 
         let content = if let Some(content) = response.candidates.first().and_then(|c| {
             c.content
                 .as_ref()
-                .and_then(|c| c.parts.first().map(|p| p.text.clone()))
+                .and_then(|c| match c.function_call_arguments().as_slice() {
+                    [] => c.parts.first().map(|p| p.text.clone()),
+                    calls => Some(tool_calls_to_content(calls.iter().map(String::as_str))),
+                })
         }) {
             content
         } else {
@@ -384,6 +446,7 @@ impl WithChat for VertexClient {
                 latency: instant_now.elapsed(),
                 message: "No content".to_string(),
                 code: ErrorCode::Other(200),
+                retry_after: None,
             });
         };
 
@@ -414,6 +477,8 @@ impl WithChat for VertexClient {
                 prompt_tokens: usage_metadata.prompt_token_count,
                 output_tokens: usage_metadata.candidates_token_count,
                 total_tokens: usage_metadata.total_token_count,
+                cached: false,
+                cost: None,
             },
         })
     }
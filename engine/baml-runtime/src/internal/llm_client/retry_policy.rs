@@ -1,20 +1,40 @@
 use internal_baml_core::{
-    internal_baml_parser_database::RetryPolicyStrategy, ir::RetryPolicyWalker,
+    internal_baml_parser_database::{RetryJitter, RetryPolicyStrategy},
+    ir::RetryPolicyWalker,
 };
+use internal_llm_client::RetryOn;
 
 #[derive(Clone)]
 pub struct CallablePolicy {
     max_retries: u32,
     strategy: RetryPolicyStrategy,
+    retry_on: RetryOn,
     current: std::time::Duration,
     counter: u32,
 }
 
+impl CallablePolicy {
+    pub fn retry_on(&self) -> RetryOn {
+        self.retry_on.clone()
+    }
+}
+
+/// A random number of milliseconds in `low..=high`.
+fn random_between(low: u64, high: u64) -> u64 {
+    if cfg!(target_arch = "wasm32") || high <= low {
+        // For VSCode, we don't want random delays,
+        // as it can make rendering inconsistent
+        return high.max(low);
+    }
+    fastrand::u64(low..=high)
+}
+
 impl From<RetryPolicyWalker<'_>> for CallablePolicy {
     fn from(policy: RetryPolicyWalker<'_>) -> Self {
         CallablePolicy {
             max_retries: policy.max_retries(),
             strategy: *policy.strategy(),
+            retry_on: policy.retry_on(),
             current: match policy.strategy() {
                 RetryPolicyStrategy::ExponentialBackoff(strategy) => {
                     std::time::Duration::from_millis(strategy.delay_ms as u64)
@@ -28,34 +48,116 @@ impl From<RetryPolicyWalker<'_>> for CallablePolicy {
     }
 }
 
+/// Yields the delay before the next attempt, for every attempt. The last
+/// attempt yields `None`, as no attempt follows it.
 impl Iterator for CallablePolicy {
-    type Item = std::time::Duration;
+    type Item = Option<std::time::Duration>;
 
     fn next(&mut self) -> Option<Self::Item> {
         if self.counter > self.max_retries {
             return None;
         }
 
-        let delay = match &self.strategy {
+        let (delay, sleep) = match &self.strategy {
             RetryPolicyStrategy::ExponentialBackoff(strategy) => {
-                let delay = (strategy.multiplier * self.current.as_millis() as f32) as u32;
-                if delay > strategy.max_delay_ms {
-                    strategy.max_delay_ms
-                } else {
-                    delay
-                }
+                let previous = self.current.as_millis() as u64;
+                let max_delay = strategy.max_delay_ms as u64;
+                let delay = match strategy.jitter {
+                    RetryJitter::Decorrelated => {
+                        let base = strategy.delay_ms as u64;
+                        let upper = (strategy.multiplier as f64 * previous as f64) as u64;
+                        random_between(base, upper).min(max_delay)
+                    }
+                    RetryJitter::None | RetryJitter::Full => {
+                        ((strategy.multiplier * previous as f32) as u64).min(max_delay)
+                    }
+                };
+                (delay, jittered(delay, strategy.jitter))
+            }
+            RetryPolicyStrategy::ConstantDelay(strategy) => {
+                let delay = strategy.delay_ms as u64;
+                (delay, jittered(delay, strategy.jitter))
             }
-            RetryPolicyStrategy::ConstantDelay(strategy) => strategy.delay_ms,
         };
 
-        if self.counter == self.max_retries {
-            // Last element has no delay
-            self.current = std::time::Duration::from_millis(0);
+        // The next delay grows from this one, before full jitter is applied.
+        self.current = std::time::Duration::from_millis(delay);
+        self.counter += 1;
+
+        if self.counter > self.max_retries {
+            Some(None)
         } else {
-            self.current = std::time::Duration::from_millis(delay as u64);
+            Some(Some(std::time::Duration::from_millis(sleep)))
         }
-        self.counter += 1;
+    }
+}
+
+fn jittered(delay: u64, jitter: RetryJitter) -> u64 {
+    match jitter {
+        RetryJitter::Full => random_between(0, delay),
+        RetryJitter::None | RetryJitter::Decorrelated => delay,
+    }
+}
 
-        Some(self.current)
+#[cfg(test)]
+mod tests {
+    use internal_baml_core::internal_baml_parser_database::{
+        ContantDelayStrategy, ExponentialBackoffStrategy,
+    };
+
+    use super::*;
+
+    fn policy(jitter: RetryJitter) -> CallablePolicy {
+        CallablePolicy {
+            max_retries: 4,
+            strategy: RetryPolicyStrategy::ExponentialBackoff(ExponentialBackoffStrategy {
+                delay_ms: 100,
+                multiplier: 2.0,
+                max_delay_ms: 1000,
+                jitter,
+            }),
+            retry_on: RetryOn::default(),
+            current: std::time::Duration::from_millis(100),
+            counter: 0,
+        }
+    }
+
+    fn delays(policy: CallablePolicy) -> Vec<u128> {
+        policy.map(|d| d.unwrap_or_default().as_millis()).collect()
+    }
+
+    #[test]
+    fn jitter_stays_within_bounds() {
+        assert_eq!(delays(policy(RetryJitter::None)), [200, 400, 800, 1000, 0]);
+
+        for _ in 0..100 {
+            let full = delays(policy(RetryJitter::Full));
+            for (delay, max) in full.iter().zip([200, 400, 800, 1000, 0]) {
+                assert!(*delay <= max, "{full:?}");
+            }
+
+            let decorrelated = delays(policy(RetryJitter::Decorrelated));
+            assert_eq!(decorrelated.len(), 5);
+            assert!(
+                decorrelated[..4].iter().all(|d| (100..=1000).contains(d)),
+                "{decorrelated:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn only_the_last_attempt_has_no_delay() {
+        let policy = CallablePolicy {
+            max_retries: 2,
+            strategy: RetryPolicyStrategy::ConstantDelay(ContantDelayStrategy {
+                delay_ms: 0,
+                jitter: RetryJitter::Full,
+            }),
+            retry_on: RetryOn::default(),
+            current: std::time::Duration::ZERO,
+            counter: 0,
+        };
+        let zero = std::time::Duration::ZERO;
+        assert_eq!(policy.collect::<Vec<_>>(), [Some(zero), Some(zero), None]);
     }
 }
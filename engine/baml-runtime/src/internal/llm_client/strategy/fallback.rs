@@ -4,7 +4,7 @@ use anyhow::{Context, Result};
 
 use internal_baml_core::ir::ClientWalker;
 use internal_llm_client::{
-    ClientProvider, ClientSpec, ResolvedClientProperty, UnresolvedClientProperty,
+    ClientProvider, ClientSpec, ResolvedClientProperty, RetryOn, UnresolvedClientProperty,
 };
 
 use crate::{
@@ -21,13 +21,14 @@ pub struct FallbackStrategy {
     pub(super) retry_policy: Option<String>,
     // TODO: We can add conditions to each client
     client_specs: Vec<ClientSpec>,
+    retry_on: RetryOn,
 }
 
 fn resolve_strategy(
     provider: &ClientProvider,
     properties: &UnresolvedClientProperty<()>,
     ctx: &RuntimeContext,
-) -> Result<Vec<ClientSpec>> {
+) -> Result<(Vec<ClientSpec>, RetryOn)> {
     let properties = properties.resolve(provider, &ctx.eval_ctx(false))?;
     let ResolvedClientProperty::Fallback(props) = properties else {
         anyhow::bail!(
@@ -35,7 +36,7 @@ fn resolve_strategy(
             properties.name()
         );
     };
-    Ok(props.strategy)
+    Ok((props.strategy, props.retry_on))
 }
 
 impl TryFrom<(&ClientProperty, &RuntimeContext)> for FallbackStrategy {
@@ -44,11 +45,13 @@ impl TryFrom<(&ClientProperty, &RuntimeContext)> for FallbackStrategy {
     fn try_from(
         (client, ctx): (&ClientProperty, &RuntimeContext),
     ) -> std::result::Result<Self, Self::Error> {
-        let strategy = resolve_strategy(&client.provider, &client.unresolved_options()?, ctx)?;
+        let (strategy, retry_on) =
+            resolve_strategy(&client.provider, &client.unresolved_options()?, ctx)?;
         Ok(Self {
             name: client.name.clone(),
             retry_policy: client.retry_policy.clone(),
             client_specs: strategy,
+            retry_on,
         })
     }
 }
@@ -57,11 +60,13 @@ impl TryFrom<(&ClientWalker<'_>, &RuntimeContext)> for FallbackStrategy {
     type Error = anyhow::Error;
 
     fn try_from((client, ctx): (&ClientWalker, &RuntimeContext)) -> Result<Self> {
-        let strategy = resolve_strategy(&client.elem().provider, client.options(), ctx)?;
+        let (strategy, retry_on) =
+            resolve_strategy(&client.elem().provider, client.options(), ctx)?;
         Ok(Self {
             name: client.item.elem.name.clone(),
             retry_policy: client.retry_policy().as_ref().map(String::from),
             client_specs: strategy,
+            retry_on,
         })
     }
 }
@@ -84,7 +89,8 @@ impl IterOrchestrator for FallbackStrategy {
                         let client = client.clone();
                         Ok(client.iter_orchestrator(
                             state,
-                            ExecutionScope::Fallback(self.name.clone(), idx).into(),
+                            ExecutionScope::Fallback(self.name.clone(), idx, self.retry_on.clone())
+                                .into(),
                             ctx,
                             client_lookup,
                         ))
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use anyhow::Result;
 mod fallback;
 pub mod roundrobin;
+pub mod weighted;
 
 use internal_baml_core::ir::ClientWalker;
 use internal_llm_client::{ClientProvider, StrategyClientProvider};
@@ -11,7 +12,9 @@ use crate::{
     client_registry::ClientProperty, runtime_interface::InternalClientLookup, RuntimeContext,
 };
 
-use self::{fallback::FallbackStrategy, roundrobin::RoundRobinStrategy};
+use self::{
+    fallback::FallbackStrategy, roundrobin::RoundRobinStrategy, weighted::WeightedStrategy,
+};
 
 use super::{
     orchestrator::{
@@ -23,6 +26,7 @@ use super::{
 pub enum LLMStrategyProvider {
     RoundRobin(Arc<RoundRobinStrategy>),
     Fallback(FallbackStrategy),
+    Weighted(Arc<WeightedStrategy>),
 }
 
 impl std::fmt::Display for LLMStrategyProvider {
@@ -34,6 +38,9 @@ impl std::fmt::Display for LLMStrategyProvider {
             LLMStrategyProvider::Fallback(strategy) => {
                 write!(f, "Fallback({})", strategy.name)
             }
+            LLMStrategyProvider::Weighted(strategy) => {
+                write!(f, "Weighted({})", strategy.name)
+            }
         }
     }
 }
@@ -50,6 +57,9 @@ impl TryFrom<(&ClientWalker<'_>, &RuntimeContext)> for LLMStrategyProvider {
                 StrategyClientProvider::Fallback => {
                     FallbackStrategy::try_from((client, ctx)).map(LLMStrategyProvider::Fallback)
                 }
+                StrategyClientProvider::Weighted => WeightedStrategy::try_from((client, ctx))
+                    .map(Arc::new)
+                    .map(LLMStrategyProvider::Weighted),
             },
             _ => {
                 anyhow::bail!("Unsupported strategy provider: {}", client.elem().provider,)
@@ -70,9 +80,12 @@ impl TryFrom<(&ClientProperty, &RuntimeContext)> for LLMStrategyProvider {
                 StrategyClientProvider::Fallback => {
                     FallbackStrategy::try_from((client, ctx)).map(LLMStrategyProvider::Fallback)
                 }
+                StrategyClientProvider::Weighted => WeightedStrategy::try_from((client, ctx))
+                    .map(Arc::new)
+                    .map(LLMStrategyProvider::Weighted),
             },
             other => {
-                let options = ["round-robin", "fallback"];
+                let options = ["round-robin", "fallback", "weighted"];
                 anyhow::bail!(
                     "Unsupported strategy provider: {}. Available ones are: {}",
                     other,
@@ -88,6 +101,7 @@ impl WithRetryPolicy for LLMStrategyProvider {
         match self {
             LLMStrategyProvider::RoundRobin(strategy) => strategy.retry_policy.as_deref(),
             LLMStrategyProvider::Fallback(strategy) => strategy.retry_policy.as_deref(),
+            LLMStrategyProvider::Weighted(strategy) => strategy.retry_policy.as_deref(),
         }
     }
 }
@@ -107,6 +121,9 @@ impl IterOrchestrator for LLMStrategyProvider {
             LLMStrategyProvider::RoundRobin(r) => {
                 r.iter_orchestrator(state, previous, ctx, client_lookup)
             }
+            LLMStrategyProvider::Weighted(w) => {
+                w.iter_orchestrator(state, previous, ctx, client_lookup)
+            }
         }
     }
 }
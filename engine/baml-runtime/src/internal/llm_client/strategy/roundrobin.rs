@@ -15,7 +15,7 @@ use internal_llm_client::{
 use crate::{
     client_registry::ClientProperty,
     internal::llm_client::orchestrator::{
-        ExecutionScope, IterOrchestrator, OrchestrationScope, OrchestrationState,
+        ExecutionScope, IterOrchestrator, OrchestrationScope, OrchestrationState, OrchestratorNode,
         OrchestratorNodeIterator,
     },
     runtime_interface::InternalClientLookup,
@@ -125,14 +125,28 @@ impl IterOrchestrator for Arc<RoundRobinStrategy> {
         // Update the usage count
         *offset += 1;
 
-        let client_spec = &self.client_specs[next];
-        let client = client_lookup.get_llm_provider(client_spec, ctx).unwrap();
-        let client = client.clone();
-        client.iter_orchestrator(
-            state,
-            ExecutionScope::RoundRobin(self.clone(), next).into(),
-            ctx,
-            client_lookup,
-        )
+        let mut plan = |idx: usize| {
+            let client_spec = &self.client_specs[idx];
+            let client = client_lookup.get_llm_provider(client_spec, ctx).unwrap();
+            let client = client.clone();
+            client.iter_orchestrator(
+                state,
+                ExecutionScope::RoundRobin(self.clone(), idx).into(),
+                ctx,
+                client_lookup,
+            )
+        };
+
+        // Move on to the next client whose circuit breaker is not open, if any.
+        let nodes = plan(next)?;
+        if nodes.iter().all(OrchestratorNode::circuit_open) {
+            for step in 1..self.client_specs.len() {
+                let candidate = plan((next + step) % self.client_specs.len())?;
+                if !candidate.iter().all(OrchestratorNode::circuit_open) {
+                    return Ok(candidate);
+                }
+            }
+        }
+        Ok(nodes)
     }
 }
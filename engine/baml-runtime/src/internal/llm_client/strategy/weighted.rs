@@ -0,0 +1,318 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, OnceLock},
+};
+
+use anyhow::Result;
+
+use internal_baml_core::ir::ClientWalker;
+use internal_llm_client::{
+    ClientProvider, ClientSpec, ResolvedClientProperty, UnresolvedClientProperty,
+};
+
+use crate::{
+    client_registry::ClientProperty,
+    internal::llm_client::{
+        orchestrator::{
+            ExecutionScope, IterOrchestrator, OrchestrationScope, OrchestrationState,
+            OrchestratorNodeIterator,
+        },
+        LLMResponse,
+    },
+    runtime_interface::InternalClientLookup,
+    RuntimeContext,
+};
+
+/// How much a single call moves the observed latency and error rate.
+const SMOOTHING: f64 = 0.2;
+/// Lower bound on the share of its weight an unhealthy client keeps, so that
+/// it is still probed and can recover.
+const MIN_HEALTH: f64 = 0.05;
+
+#[derive(Debug, Default, Clone, Copy)]
+struct ClientStats {
+    /// Moving average of the latency of successful calls.
+    latency_ms: Option<f64>,
+    /// Moving average of the share of calls that failed.
+    error_rate: f64,
+}
+
+/// Stats of every client called through an adaptive strategy, keyed by client
+/// name. They are process-wide because strategies are rebuilt whenever a
+/// client registry is used, which would otherwise reset them on every call.
+static STATS: OnceLock<Mutex<HashMap<String, ClientStats>>> = OnceLock::new();
+
+fn stats() -> std::sync::MutexGuard<'static, HashMap<String, ClientStats>> {
+    STATS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+#[derive(Debug)]
+pub struct WeightedStrategy {
+    pub name: String,
+    pub(super) retry_policy: Option<String>,
+    client_specs: Vec<ClientSpec>,
+    weights: Vec<f64>,
+    adaptive: bool,
+}
+
+fn resolve_strategy(
+    provider: &ClientProvider,
+    properties: &UnresolvedClientProperty<()>,
+    ctx: &RuntimeContext,
+) -> Result<(Vec<ClientSpec>, Vec<f64>, bool)> {
+    let properties = properties.resolve(provider, &ctx.eval_ctx(false))?;
+    let ResolvedClientProperty::Weighted(props) = properties else {
+        anyhow::bail!(
+            "Invalid client property. Should have been a weighted property but got: {}",
+            properties.name()
+        );
+    };
+    Ok((props.strategy, props.weights, props.adaptive))
+}
+
+impl WeightedStrategy {
+    fn new(
+        name: String,
+        retry_policy: Option<String>,
+        (client_specs, weights, adaptive): (Vec<ClientSpec>, Vec<f64>, bool),
+    ) -> Result<Self> {
+        if client_specs.len() != weights.len() {
+            anyhow::bail!(
+                "weights must have one entry per client in strategy. Expected {}, got {}",
+                client_specs.len(),
+                weights.len()
+            );
+        }
+        Ok(Self {
+            name,
+            retry_policy,
+            client_specs,
+            weights,
+            adaptive,
+        })
+    }
+
+    /// The configured weights, scaled by the health of each client when the
+    /// strategy is adaptive. Clients that have not been called yet keep their
+    /// configured weight.
+    pub fn effective_weights(&self) -> Vec<f64> {
+        if !self.adaptive {
+            return self.weights.clone();
+        }
+        let stats = {
+            let all = stats();
+            self.client_specs
+                .iter()
+                .map(|spec| all.get(&spec.as_str()).copied().unwrap_or_default())
+                .collect::<Vec<_>>()
+        };
+        let fastest = stats
+            .iter()
+            .filter_map(|s| s.latency_ms)
+            .fold(f64::INFINITY, f64::min);
+
+        self.weights
+            .iter()
+            .zip(stats.iter())
+            .map(|(weight, s)| {
+                let speed = match s.latency_ms {
+                    Some(latency) if latency > 0.0 => fastest / latency,
+                    _ => 1.0,
+                };
+                weight * (speed * (1.0 - s.error_rate)).max(MIN_HEALTH)
+            })
+            .collect()
+    }
+
+    /// Picks the index of the next client, proportionally to its weight.
+    /// Clients flagged in `circuit_open` are left out, unless no other client
+    /// could be picked.
+    fn pick(&self, circuit_open: &[bool]) -> usize {
+        let mut weights = self.effective_weights();
+        let available = weights
+            .iter()
+            .zip(circuit_open)
+            .any(|(weight, open)| *weight > 0.0 && !open);
+        if available {
+            for (weight, open) in weights.iter_mut().zip(circuit_open) {
+                if *open {
+                    *weight = 0.0;
+                }
+            }
+        }
+        if cfg!(target_arch = "wasm32") {
+            // For VSCode, we don't want a random pick,
+            // as it can make rendering inconsistent
+            return weights.iter().enumerate().fold(
+                0,
+                |best, (idx, w)| if *w > weights[best] { idx } else { best },
+            );
+        }
+
+        let total: f64 = weights.iter().sum();
+        let mut target = fastrand::f64() * total;
+        for (idx, weight) in weights.iter().enumerate() {
+            if target < *weight {
+                return idx;
+            }
+            target -= weight;
+        }
+        // Rounding, or every weight is zero.
+        weights.iter().rposition(|w| *w > 0.0).unwrap_or(0)
+    }
+
+    /// Feeds the outcome of a call made through the client at `index` into
+    /// the adaptive weights.
+    pub fn record(&self, index: usize, response: &LLMResponse) {
+        if !self.adaptive {
+            return;
+        }
+        let Some(spec) = self.client_specs.get(index) else {
+            return;
+        };
+        let mut stats = stats();
+        let s = stats.entry(spec.as_str()).or_default();
+        match response {
+            LLMResponse::Success(r) => {
+                let latency = r.latency.as_secs_f64() * 1000.0;
+                s.latency_ms = Some(match s.latency_ms {
+                    Some(prev) => prev + SMOOTHING * (latency - prev),
+                    None => latency,
+                });
+                s.error_rate -= SMOOTHING * s.error_rate;
+            }
+            LLMResponse::LLMFailure(_) => {
+                s.error_rate += SMOOTHING * (1.0 - s.error_rate);
+            }
+            // Not the client's fault.
+            LLMResponse::UserFailure(_) | LLMResponse::InternalFailure(_) => {}
+        }
+    }
+}
+
+impl TryFrom<(&ClientProperty, &RuntimeContext)> for WeightedStrategy {
+    type Error = anyhow::Error;
+
+    fn try_from(
+        (client, ctx): (&ClientProperty, &RuntimeContext),
+    ) -> std::result::Result<Self, Self::Error> {
+        let resolved = resolve_strategy(&client.provider, &client.unresolved_options()?, ctx)?;
+        Self::new(client.name.clone(), client.retry_policy.clone(), resolved)
+    }
+}
+
+impl TryFrom<(&ClientWalker<'_>, &RuntimeContext)> for WeightedStrategy {
+    type Error = anyhow::Error;
+
+    fn try_from((client, ctx): (&ClientWalker, &RuntimeContext)) -> Result<Self> {
+        let resolved = resolve_strategy(&client.elem().provider, client.options(), ctx)?;
+        Self::new(
+            client.item.elem.name.clone(),
+            client.retry_policy().as_ref().map(String::from),
+            resolved,
+        )
+    }
+}
+
+impl IterOrchestrator for Arc<WeightedStrategy> {
+    fn iter_orchestrator<'a>(
+        &self,
+        state: &mut OrchestrationState,
+        _previous: OrchestrationScope,
+        ctx: &RuntimeContext,
+        client_lookup: &'a dyn InternalClientLookup<'a>,
+    ) -> Result<OrchestratorNodeIterator> {
+        let circuit_open = self
+            .client_specs
+            .iter()
+            .map(|spec| Ok(client_lookup.get_llm_provider(spec, ctx)?.circuit_open()))
+            .collect::<Result<Vec<_>>>()?;
+        let next = self.pick(&circuit_open);
+        let client_spec = &self.client_specs[next];
+        let client = client_lookup.get_llm_provider(client_spec, ctx)?;
+        client.iter_orchestrator(
+            state,
+            ExecutionScope::Weighted(self.clone(), next).into(),
+            ctx,
+            client_lookup,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Stats are shared by client name, so each test uses its own clients.
+    fn strategy(test: &str, weights: Vec<f64>, adaptive: bool) -> WeightedStrategy {
+        let specs = (0..weights.len())
+            .map(|i| ClientSpec::Named(format!("{test}Client{i}")))
+            .collect();
+        WeightedStrategy::new("Weighted".into(), None, (specs, weights, adaptive)).unwrap()
+    }
+
+    fn llm_failure(client: &str) -> LLMResponse {
+        LLMResponse::LLMFailure(crate::internal::llm_client::LLMErrorResponse {
+            client: client.into(),
+            model: None,
+            prompt: internal_baml_jinja::RenderedPrompt::Completion(String::new()),
+            start_time: web_time::SystemTime::now(),
+            latency: web_time::Duration::ZERO,
+            request_options: Default::default(),
+            message: "boom".into(),
+            code: crate::internal::llm_client::ErrorCode::ServerError,
+            retry_after: None,
+        })
+    }
+
+    #[test]
+    fn picks_follow_weights() {
+        let strategy = strategy("Follow", vec![9.0, 1.0, 0.0], false);
+        let mut counts = [0; 3];
+        for _ in 0..10_000 {
+            counts[strategy.pick(&[false; 3])] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        assert!(counts[0] > 8_500 && counts[0] < 9_500, "{counts:?}");
+    }
+
+    #[test]
+    fn picks_skip_open_circuits() {
+        let strategy = strategy("Skip", vec![9.0, 1.0, 0.0], false);
+        for _ in 0..1_000 {
+            assert_eq!(strategy.pick(&[true, false, false]), 1);
+        }
+        // With every pickable client open, the weights apply as usual.
+        let mut counts = [0; 3];
+        for _ in 0..1_000 {
+            counts[strategy.pick(&[true, true, false])] += 1;
+        }
+        assert_eq!(counts[2], 0);
+        assert!(counts[0] > counts[1], "{counts:?}");
+    }
+
+    #[test]
+    fn adaptive_weights_penalize_failures() {
+        let strategy = strategy("Penalize", vec![1.0, 1.0], true);
+        for _ in 0..10 {
+            strategy.record(0, &LLMResponse::InternalFailure("ignored".into()));
+            strategy.record(1, &llm_failure("PenalizeClient1"));
+        }
+        let weights = strategy.effective_weights();
+        assert_eq!(weights[0], 1.0);
+        assert!(weights[1] < 0.2, "{weights:?}");
+    }
+
+    #[test]
+    fn adaptive_weights_survive_rebuilding_the_strategy() {
+        for _ in 0..10 {
+            strategy("Rebuilt", vec![1.0, 1.0], true).record(1, &llm_failure("RebuiltClient1"));
+        }
+        let weights = strategy("Rebuilt", vec![1.0, 1.0], true).effective_weights();
+        assert_eq!(weights[0], 1.0);
+        assert!(weights[1] < 0.2, "{weights:?}");
+    }
+}
@@ -2,7 +2,10 @@ use std::{collections::HashMap, path::PathBuf, pin::Pin};
 
 use anyhow::{Context, Result};
 use aws_smithy_types::byte_stream::error::Error;
-use internal_llm_client::{AllowedRoleMetadata, FinishReasonFilter};
+use internal_llm_client::{
+    AllowedRoleMetadata, CacheConfig, CircuitBreakerConfig, Coercion, FinishReasonFilter, Pricing,
+    RateLimitConfig, TimeoutConfig,
+};
 use serde_json::{json, Map};
 
 mod chat;
@@ -39,6 +42,13 @@ pub trait WithClientProperties {
     fn finish_reason_filter(&self) -> &FinishReasonFilter;
     fn default_role(&self) -> String;
     fn allowed_roles(&self) -> Vec<String>;
+    fn cache_config(&self) -> Option<&CacheConfig>;
+    fn circuit_breaker_config(&self) -> Option<&CircuitBreakerConfig>;
+    fn rate_limit_config(&self) -> Option<&RateLimitConfig>;
+    fn timeout_config(&self) -> Option<&TimeoutConfig>;
+    fn forbidden_coercions(&self) -> &[Coercion];
+    fn max_context_tokens(&self) -> Option<u64>;
+    fn pricing(&self) -> Option<&Pricing>;
 }
 
 pub trait WithSingleCallable {
@@ -280,6 +290,7 @@ where
 
         let request_builder = self
             .build_request(
+                ctx,
                 either::Right(&chat_messages),
                 false,
                 render_settings.stream && self.supports_streaming(),
@@ -1,7 +1,7 @@
 mod render_output_format;
-use internal_llm_client::ClientSpec;
+use internal_llm_client::{ClientSpec, Coercion};
 use jsonish::{BamlValueWithFlags, ResponseBamlValue};
-use render_output_format::render_output_format;
+pub(crate) use render_output_format::render_output_format;
 
 use anyhow::Result;
 use baml_types::{BamlValue, FieldType};
@@ -23,6 +23,7 @@ pub struct PromptRenderer {
     client_spec: ClientSpec,
     output_defs: OutputFormatContent,
     output_type: FieldType,
+    forbid_coercions: Option<Vec<Coercion>>,
 }
 
 impl PromptRenderer {
@@ -44,6 +45,7 @@ impl PromptRenderer {
             },
             output_defs: render_output_format(ir, ctx, &func_v2.output)?,
             output_type: func_v2.output.clone(),
+            forbid_coercions: config.forbid_coercions.clone(),
         })
     }
 
@@ -51,6 +53,16 @@ impl PromptRenderer {
         &self.client_spec
     }
 
+    /// The coercions the function forbids, if it overrides those of the
+    /// client.
+    pub fn forbidden_coercions(&self) -> Option<&[Coercion]> {
+        self.forbid_coercions.as_deref()
+    }
+
+    pub fn output_format(&self) -> &OutputFormatContent {
+        &self.output_defs
+    }
+
     pub fn parse(
         &self,
         ir: &IntermediateRepr,
@@ -67,6 +79,17 @@ impl PromptRenderer {
         res
     }
 
+    /// Parses a partial response that extends the one `parser` last saw.
+    pub fn parse_partial(
+        &self,
+        ir: &IntermediateRepr,
+        parser: &mut jsonish::IncrementalParser,
+        raw_string: &str,
+    ) -> Result<ResponseBamlValue> {
+        let parsed = parser.parse(&self.output_defs, &self.output_type, raw_string)?;
+        parsed_value_to_response(ir, parsed, &self.output_type, true)
+    }
+
     pub fn render_prompt(
         &self,
         ir: &IntermediateRepr,
@@ -372,11 +372,17 @@ fn relevant_data_models<'a>(
                         }
                     }
 
+                    let defaults = match &walker {
+                        Ok(walker) => walker.default_values()?,
+                        Err(_) => IndexMap::new(),
+                    };
+
                     classes.push(Class {
                         name: Name::new_with_alias(cls.to_string(), alias.value()),
                         fields,
                         constraints,
                         streaming_behavior,
+                        defaults,
                     });
                 } else {
                     // TODO: @antonio This one was nasty! If aliases are not
@@ -60,6 +60,9 @@ pub(crate) use internal_baml_jinja::{ChatMessagePart, RenderedPrompt};
 #[cfg(not(feature = "internal"))]
 pub(crate) use runtime_interface::InternalRuntimeInterface;
 
+pub use internal::llm_client::primitive::tokenizer::{
+    register_tiktoken_file, register_tokenizer, BpeTokenizer, TokenCount, Tokenizer,
+};
 pub use internal_baml_core::internal_baml_diagnostics;
 pub use internal_baml_core::internal_baml_diagnostics::Diagnostics as DiagnosticsError;
 pub use internal_baml_core::ir::{scope_diagnostics, FieldType, IRHelper, TypeValue};
@@ -366,6 +369,75 @@ impl BamlRuntime {
         )
     }
 
+    /// Looks up a type by name: a class, enum or type alias, including ones
+    /// added by `tb`, or a primitive like `string`. `[]` and `?` suffixes
+    /// make lists and optionals, as in BAML.
+    pub fn find_type(
+        &self,
+        name: &str,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+    ) -> Result<FieldType> {
+        let rctx = ctx.create_ctx(tb, None)?;
+        self.find_type_impl(name.trim(), &rctx)
+    }
+
+    fn find_type_impl(&self, name: &str, ctx: &RuntimeContext) -> Result<FieldType> {
+        if let Some(item) = name.strip_suffix("[]") {
+            return Ok(self.find_type_impl(item.trim_end(), ctx)?.as_list());
+        }
+        if let Some(item) = name.strip_suffix('?') {
+            return Ok(self.find_type_impl(item.trim_end(), ctx)?.as_optional());
+        }
+
+        let ir = self.inner.ir();
+        let is_recursive_alias = |cycles: &[IndexMap<String, FieldType>]| {
+            cycles.iter().any(|cycle| cycle.contains_key(name))
+        };
+        Ok(match name {
+            "string" => FieldType::string(),
+            "int" => FieldType::int(),
+            "float" => FieldType::float(),
+            "bool" => FieldType::bool(),
+            "null" => FieldType::null(),
+            _ if ir.find_class(name).is_ok() || ctx.class_override.contains_key(name) => {
+                FieldType::class(name)
+            }
+            _ if ir.find_enum(name).is_ok() || ctx.enum_overrides.contains_key(name) => {
+                FieldType::r#enum(name)
+            }
+            _ if is_recursive_alias(ir.structural_recursive_alias_cycles())
+                || is_recursive_alias(&ctx.recursive_type_alias_overrides) =>
+            {
+                FieldType::RecursiveTypeAlias(name.to_string())
+            }
+            _ => match ctx.type_alias_overrides.get(name) {
+                Some(target) => target.clone(),
+                None => match ir.find_type_alias(name) {
+                    Ok(alias) => alias.elem().r#type.elem.clone(),
+                    Err(_) => anyhow::bail!("Type not found: {name}"),
+                },
+            },
+        })
+    }
+
+    /// Parses `text` into `target` the same way the response of a function
+    /// returning `target` is parsed, without calling an LLM.
+    pub fn parse(
+        &self,
+        text: &str,
+        target: &FieldType,
+        allow_partials: bool,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+    ) -> Result<jsonish::ResponseBamlValue> {
+        let rctx = ctx.create_ctx(tb, None)?;
+        let ir = self.inner.ir();
+        let output_format = internal::prompt_renderer::render_output_format(ir, &rctx, target)?;
+        let parsed = jsonish::from_str(&output_format, target, text, allow_partials)?;
+        internal::llm_client::parsed_value_to_response(ir, parsed, target, allow_partials)
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     fn generate_client(
         &self,
@@ -27,6 +27,19 @@ pub fn create_client() -> Result<reqwest::Client> {
     builder().build().context("Failed to create reqwest client")
 }
 
+/// A client for calls to a `client<llm>`, honoring its `connect_timeout_ms`.
+pub fn create_llm_client(
+    timeouts: Option<&internal_llm_client::TimeoutConfig>,
+) -> Result<reqwest::Client> {
+    let builder = builder();
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = match timeouts.and_then(|t| t.connect_timeout_ms) {
+        Some(ms) => builder.connect_timeout(Duration::from_millis(ms)),
+        None => builder,
+    };
+    builder.build().context("Failed to create reqwest client")
+}
+
 pub(crate) fn create_tracing_client() -> Result<reqwest::Client> {
     cfg_if::cfg_if! {
         if #[cfg(target_arch = "wasm32")] {
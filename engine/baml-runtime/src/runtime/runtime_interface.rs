@@ -14,7 +14,7 @@ use crate::{
             orchestrator::{
                 orchestrate_call, IterOrchestrator, OrchestrationScope, OrchestratorNode,
             },
-            primitive::LLMPrimitiveProvider,
+            primitive::{tokenizer::TokenCount, LLMPrimitiveProvider},
             retry_policy::CallablePolicy,
             traits::{WithPrompt, WithRenderRawCurl},
         },
@@ -144,7 +144,12 @@ impl InternalRuntimeInterface for InternalBamlRuntime {
         ctx: &RuntimeContext,
         params: &BamlMap<String, BamlValue>,
         node_index: Option<usize>,
-    ) -> Result<(RenderedPrompt, OrchestrationScope, AllowedRoleMetadata)> {
+    ) -> Result<(
+        RenderedPrompt,
+        OrchestrationScope,
+        AllowedRoleMetadata,
+        TokenCount,
+    )> {
         let func = self.get_function(function_name, ctx)?;
         let baml_args = self.ir().check_function_params(
             &func,
@@ -173,29 +178,36 @@ impl InternalRuntimeInterface for InternalBamlRuntime {
         }
 
         let node = selected.swap_remove(node_index);
-        return node
+        let prompt = node
             .provider
             .render_prompt(self.ir(), &renderer, ctx, &baml_args)
-            .await
-            .map(|prompt| (prompt, node.scope, node.provider.allowed_metadata().clone()));
+            .await?;
+        let tokens = node.provider.count_prompt_tokens(ctx, &prompt);
+        Ok((
+            prompt,
+            node.scope,
+            node.provider.allowed_metadata().clone(),
+            tokens,
+        ))
     }
 
     async fn render_raw_curl(
         &self,
         function_name: &str,
-        ctx: &RuntimeContext,
+        mut ctx: RuntimeContext,
         prompt: &[internal_baml_jinja::RenderedChatMessage],
         render_settings: RenderCurlSettings,
         node_index: Option<usize>,
     ) -> Result<String> {
-        let func = self.get_function(function_name, ctx)?;
+        let func = self.get_function(function_name, &ctx)?;
 
-        let renderer = PromptRenderer::from_function(&func, self.ir(), ctx)?;
+        let renderer = PromptRenderer::from_function(&func, self.ir(), &ctx)?;
+        ctx.output_format = Some(renderer.output_format().clone());
 
         let client_spec = renderer.client_spec();
-        let client = self.get_llm_provider(client_spec, ctx)?;
+        let client = self.get_llm_provider(client_spec, &ctx)?;
         let mut selected =
-            client.iter_orchestrator(&mut Default::default(), Default::default(), ctx, self)?;
+            client.iter_orchestrator(&mut Default::default(), Default::default(), &ctx, self)?;
 
         let node_index = node_index.unwrap_or(0);
 
@@ -210,7 +222,7 @@ impl InternalRuntimeInterface for InternalBamlRuntime {
 
         let node = selected.swap_remove(node_index);
         node.provider
-            .render_raw_curl(ctx, prompt, render_settings)
+            .render_raw_curl(&ctx, prompt, render_settings)
             .await
     }
 
@@ -385,7 +397,7 @@ impl RuntimeInterface for InternalBamlRuntime {
         &self,
         function_name: String,
         params: &BamlMap<String, BamlValue>,
-        ctx: RuntimeContext,
+        mut ctx: RuntimeContext,
     ) -> Result<crate::FunctionResult> {
         let func = match self.get_function(&function_name, &ctx) {
             Ok(func) => func,
@@ -430,6 +442,7 @@ impl RuntimeInterface for InternalBamlRuntime {
         // };
 
         let renderer = PromptRenderer::from_function(&func, self.ir(), &ctx)?;
+        ctx.output_format = Some(renderer.output_format().clone());
         let orchestrator = self.orchestration_graph(renderer.client_spec(), &ctx)?;
 
         // Now actually execute the code.
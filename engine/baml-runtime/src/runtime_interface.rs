@@ -8,6 +8,7 @@ use std::{collections::HashMap, sync::Arc};
 
 use crate::internal::llm_client::llm_provider::LLMProvider;
 use crate::internal::llm_client::orchestrator::{OrchestrationScope, OrchestratorNode};
+use crate::internal::llm_client::primitive::tokenizer::TokenCount;
 use crate::tracing::{BamlTracer, TracingSpan};
 use crate::type_builder::TypeBuilder;
 use crate::types::on_log_event::LogEventCallbackSync;
@@ -139,13 +140,18 @@ pub trait InternalRuntimeInterface {
         ctx: &RuntimeContext,
         params: &BamlMap<String, BamlValue>,
         node_index: Option<usize>,
-    ) -> Result<(RenderedPrompt, OrchestrationScope, AllowedRoleMetadata)>;
+    ) -> Result<(
+        RenderedPrompt,
+        OrchestrationScope,
+        AllowedRoleMetadata,
+        TokenCount,
+    )>;
 
     #[allow(async_fn_in_trait)]
     async fn render_raw_curl(
         &self,
         function_name: &str,
-        ctx: &RuntimeContext,
+        ctx: RuntimeContext,
         prompt: &[internal_baml_jinja::RenderedChatMessage],
         render_settings: RenderCurlSettings,
         node_index: Option<usize>,
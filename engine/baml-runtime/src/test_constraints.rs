@@ -335,6 +335,8 @@ mod tests {
                 prompt_tokens: None,
                 output_tokens: None,
                 total_tokens: None,
+                cached: false,
+                cost: None,
             },
         }
     }
@@ -344,6 +346,7 @@ mod tests {
             label: Some(label.to_string()),
             level: ConstraintLevel::Check,
             expression: JinjaExpression(expr.to_string()),
+            validator: None,
         }
     }
 
@@ -352,6 +355,7 @@ mod tests {
             label: Some(label.to_string()),
             level: ConstraintLevel::Assert,
             expression: JinjaExpression(expr.to_string()),
+            validator: None,
         }
     }
 
@@ -28,6 +28,8 @@ pub struct LogSchema {
     pub io: IO,
     pub error: Option<Error>,
     pub metadata: Option<MetadataType>,
+    // What the LLM calls of the span cost in US dollars, if they were priced.
+    pub cost: Option<f64>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -131,6 +133,10 @@ pub struct LLMOutputModelMetadata {
     pub output_tokens: Option<i64>,
     pub total_tokens: Option<i64>,
     pub finish_reason: Option<String>,
+    #[serde(default)]
+    pub cached: bool,
+    #[serde(default)]
+    pub cost: Option<f64>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -138,6 +144,9 @@ pub struct LLMOutputModel {
     pub raw_text: String,
     pub metadata: LLMOutputModelMetadata,
     pub r#override: Option<HashMap<String, Value>>,
+    /// How much `raw_text` had to be repaired to parse, if it did.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parse_report: Option<jsonish::ParseReport>,
 }
 
 #[derive(Serialize, Debug, Clone)]
@@ -164,6 +164,7 @@ struct BamlEventJson {
     model: String,
     latency_ms: u128,
     stop_reason: Option<String>,
+    cached: bool,
 
     // Content
     prompt: Option<String>,
@@ -173,10 +174,14 @@ struct BamlEventJson {
 
     // Token Usage
     tokens: Option<TokenUsage>,
+    // Cost of every try, in US dollars, if the clients have pricing.
+    cost: Option<f64>,
 
     // Response/Error Info
     parsed_response_type: Option<String>,
     parsed_response: Option<String>,
+    // JSON string
+    parse_report: Option<String>,
     error: Option<String>,
 }
 
@@ -332,6 +337,7 @@ impl BamlTracer {
         if span.span_id != span_id {
             anyhow::bail!("Span ID mismatch: {} != {}", span.span_id, span_id);
         }
+        self.add_cost(&event_chain, response);
 
         if let Ok(response) = &response {
             let name = event_chain.last().map(|s| s.name.as_str());
@@ -382,6 +388,7 @@ impl BamlTracer {
         if span.span_id != span_id {
             anyhow::bail!("Span ID mismatch: {} != {}", span.span_id, span_id);
         }
+        self.add_cost(&event_chain, response);
 
         let log_json = Self::is_json_logging_enabled();
 
@@ -402,6 +409,16 @@ impl BamlTracer {
         }
     }
 
+    /// Counts the cost of a finished function towards its name, the last
+    /// span of `event_chain`.
+    fn add_cost(&self, event_chain: &[SpanCtx], response: &Result<FunctionResult>) {
+        let Some(cost) = response.as_ref().ok().and_then(FunctionResult::cost) else {
+            return;
+        };
+        let name = event_chain.last().map_or("", |span| span.name.as_str());
+        self.trace_stats.add_cost(name, cost);
+    }
+
     fn is_json_logging_enabled() -> bool {
         matches!(
             std::env::var("BAML_LOG_JSON"),
@@ -450,12 +467,15 @@ impl BamlTracer {
                 model: "unknown".to_string(),
                 latency_ms: 0,
                 stop_reason: None,
+                cached: false,
                 prompt: None,
                 llm_reply: None,
                 request_options_json: None,
                 tokens: None,
+                cost: None,
                 parsed_response_type: None,
                 parsed_response: None,
+                parse_report: None,
                 error: Some(error.to_string()),
             };
             rust_tracing::event!(
@@ -488,6 +508,7 @@ impl BamlTracer {
                 model: resp.model.clone(),
                 latency_ms: resp.latency.as_millis(),
                 stop_reason: resp.metadata.finish_reason.clone(),
+                cached: resp.metadata.cached,
                 prompt: Some(resp.prompt.to_string()),
                 llm_reply: Some(resp.content.clone()),
                 request_options_json: Some(
@@ -498,6 +519,7 @@ impl BamlTracer {
                     completion_tokens: resp.metadata.output_tokens,
                     total_tokens: resp.metadata.total_tokens,
                 }),
+                cost: response.cost(),
                 parsed_response_type: response
                     .result_with_constraints()
                     .as_ref()
@@ -508,6 +530,9 @@ impl BamlTracer {
                     .as_ref()
                     .and_then(|r| r.as_ref().ok())
                     .map(|v| serde_json::to_string(&v.serialize_final()).unwrap_or_default()),
+                parse_report: response
+                    .parse_report()
+                    .map(|r| serde_json::to_string(&r).unwrap_or_default()),
                 error,
             },
             LLMResponse::LLMFailure(err) => BamlEventJson {
@@ -518,14 +543,17 @@ impl BamlTracer {
                 model: err.model.clone().unwrap_or_default(),
                 latency_ms: err.latency.as_millis(),
                 stop_reason: None,
+                cached: false,
                 prompt: Some(err.prompt.to_string()),
                 llm_reply: None,
                 request_options_json: Some(
                     serde_json::to_string(&err.request_options).unwrap_or_default(),
                 ),
                 tokens: None,
+                cost: response.cost(),
                 parsed_response_type: None,
                 parsed_response: None,
+                parse_report: None,
                 error,
             },
             LLMResponse::UserFailure(msg) | LLMResponse::InternalFailure(msg) => BamlEventJson {
@@ -536,12 +564,15 @@ impl BamlTracer {
                 model: "unknown".to_string(),
                 latency_ms: 0,
                 stop_reason: None,
+                cached: false,
                 prompt: None,
                 llm_reply: None,
                 request_options_json: None,
                 tokens: None,
+                cost: response.cost(),
                 parsed_response_type: None,
                 parsed_response: None,
+                parse_report: None,
                 error: Some(msg.clone()),
             },
         }
@@ -752,6 +783,7 @@ impl<T: ToLogSchema> ToLogSchema for Result<T> {
                     r#override: None,
                 }),
                 metadata: None,
+                cost: None,
             },
         }
     }
@@ -785,6 +817,7 @@ impl ToLogSchema for Option<BamlValue> {
             },
             error: None,
             metadata: None,
+            cost: None,
         }
     }
 }
@@ -837,6 +870,7 @@ impl ToLogSchema for FunctionResult {
             },
             error: error_from_result(self),
             metadata: Some(self.into()),
+            cost: self.cost(),
         }
     }
 }
@@ -847,7 +881,13 @@ impl From<&FunctionResult> for MetadataType {
             result
                 .event_chain()
                 .iter()
-                .map(|(_, r, _)| r.into())
+                .map(|(_, r, parsed)| {
+                    let mut event: LLMEventSchema = r.into();
+                    if let (Some(output), Some(Ok(value))) = (&mut event.output, parsed) {
+                        output.parse_report = Some(value.parse_report());
+                    }
+                    event
+                })
                 .collect::<Vec<_>>(),
         )
     }
@@ -901,6 +941,7 @@ impl From<&LLMResponse> for LLMEventSchema {
                         .map_or_else(Err, serde_json::from_value)
                         .unwrap_or_default(),
                     r#override: None,
+                    parse_report: None,
                 }),
                 error: None,
             },
@@ -272,7 +272,12 @@ impl ThreadedTracer {
                             None
                         }),
                 }),
+                parse_report: llm_output_model
+                    .and_then(|llm_event| llm_event.output.as_ref())
+                    .and_then(|output| output.parse_report.as_ref())
+                    .and_then(|report| serde_json::to_string(report).ok()),
                 start_time: event.context.start_time,
+                cost: event.cost,
             });
 
             if log_event_result.is_err() {
@@ -141,10 +141,13 @@ impl RuntimeContextManager {
         );
 
         ctx.client_overrides = match client_registry {
-            Some(cr) => Some(
-                cr.to_clients(&ctx)
-                    .with_context(|| "Failed to create clients from client_registry")?,
-            ),
+            Some(cr) => {
+                ctx.model_pricing = cr.model_pricing().clone();
+                Some(
+                    cr.to_clients(&ctx)
+                        .with_context(|| "Failed to create clients from client_registry")?,
+                )
+            }
             None => None,
         };
 
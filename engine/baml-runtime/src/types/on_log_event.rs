@@ -8,7 +8,11 @@ pub struct LogEvent {
     pub raw_output: Option<String>,
     // json structure or a string
     pub parsed_output: Option<String>,
+    // json structure of jsonish::ParseReport
+    pub parse_report: Option<String>,
     pub start_time: String,
+    // US dollars, if the clients have pricing
+    pub cost: Option<f64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
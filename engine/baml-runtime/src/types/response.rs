@@ -109,6 +109,26 @@ impl FunctionResult {
         }
     }
 
+    /// What the calls made for this result cost in US dollars, retries and
+    /// fallbacks included, or `None` if none of them was priced.
+    pub fn cost(&self) -> Option<f64> {
+        self.event_chain
+            .iter()
+            .filter_map(|(_, response, _)| match response {
+                LLMResponse::Success(s) => s.metadata.cost,
+                _ => None,
+            })
+            .reduce(|total, cost| total + cost)
+    }
+
+    /// How much the final response had to be repaired to parse, if it did.
+    pub fn parse_report(&self) -> Option<jsonish::ParseReport> {
+        match self.result_with_constraints() {
+            Some(Ok(value)) => Some(value.parse_report()),
+            _ => None,
+        }
+    }
+
     pub fn result_with_constraints_content(&self) -> Result<&ResponseBamlValue> {
         self.result_with_constraints()
             .as_ref()
@@ -2,6 +2,8 @@ use anyhow::Result;
 use baml_types::{BamlValue, EvaluationContext, UnresolvedValue};
 use indexmap::IndexMap;
 use internal_baml_core::ir::FieldType;
+use internal_baml_jinja::types::OutputFormatContent;
+use internal_llm_client::Pricing;
 use std::{collections::HashMap, sync::Arc};
 
 use crate::internal::llm_client::llm_provider::LLMProvider;
@@ -58,6 +60,13 @@ pub struct RuntimeContext {
     pub enum_overrides: IndexMap<String, RuntimeEnumOverride>,
     pub type_alias_overrides: IndexMap<String, FieldType>,
     pub recursive_type_alias_overrides: Vec<IndexMap<String, FieldType>>,
+    /// Output format of the function being called. Set once the function is
+    /// known, for clients that send the return type natively instead of in
+    /// the prompt.
+    pub output_format: Option<OutputFormatContent>,
+    /// Pricing of models, by model name, from the client registry. Used for
+    /// clients without pricing of their own.
+    pub model_pricing: HashMap<String, Pricing>,
 }
 
 impl RuntimeContext {
@@ -92,6 +101,8 @@ impl RuntimeContext {
             enum_overrides,
             type_alias_overrides,
             recursive_type_alias_overrides,
+            output_format: None,
+            model_pricing: HashMap::new(),
         }
     }
 
@@ -93,14 +93,18 @@ impl FunctionResultStream {
 
         let rctx = ctx.create_ctx(tb, cb);
         let res = match rctx {
-            Ok(rctx) => {
+            Ok(mut rctx) => {
+                rctx.output_format = Some(self.renderer.output_format().clone());
                 let (history, _) = orchestrate_stream(
                     local_orchestrator,
                     self.ir.as_ref(),
                     &rctx,
                     &self.renderer,
                     &baml_types::BamlValue::Map(local_params),
-                    |content| self.renderer.parse(self.ir.as_ref(), content, true),
+                    |parser, content| {
+                        self.renderer
+                            .parse_partial(self.ir.as_ref(), parser, content)
+                    },
                     |content| self.renderer.parse(self.ir.as_ref(), content, false),
                     on_event,
                 )
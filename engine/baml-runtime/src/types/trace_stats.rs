@@ -6,7 +6,10 @@
 /// start -> finalize (ctx.exit) -> submit -> send
 /// ```
 ///
-use std::sync::{Arc, Mutex};
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
 
 #[derive(Clone, Default)]
 pub struct InnerTraceStats {
@@ -20,6 +23,11 @@ pub struct InnerTraceStats {
     pub done: u32,
     // All errors are counted here.
     pub failed: u32,
+
+    // What the LLM calls of finished functions cost, in US dollars, in total
+    // and by function name.
+    pub cost: f64,
+    pub cost_by_function: HashMap<String, f64>,
 }
 
 #[derive(Clone, Default)]
@@ -69,6 +77,15 @@ impl TraceStats {
         let mut inner = self.inner.lock().unwrap();
         inner.failed += 1;
     }
+
+    pub(crate) fn add_cost(&self, function_name: &str, cost: f64) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.cost += cost;
+        *inner
+            .cost_by_function
+            .entry(function_name.to_string())
+            .or_default() += cost;
+    }
 }
 
 pub struct SpanGuard {
@@ -114,3 +131,37 @@ impl Drop for SpanGuard {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_adds_up_in_total_and_by_function() {
+        let stats = TraceStats::default();
+        stats.add_cost("Extract", 0.5);
+        stats.add_cost("Classify", 0.25);
+        stats.add_cost("Extract", 0.125);
+
+        let inner = stats.drain();
+        assert_eq!(inner.cost, 0.875);
+        assert_eq!(
+            inner.cost_by_function,
+            HashMap::from([
+                ("Extract".to_string(), 0.625),
+                ("Classify".to_string(), 0.25)
+            ])
+        );
+    }
+
+    #[test]
+    fn drain_resets_the_cost() {
+        let stats = TraceStats::default();
+        stats.add_cost("Extract", 0.5);
+        stats.drain();
+
+        let inner = stats.drain();
+        assert_eq!(inner.cost, 0.0);
+        assert!(inner.cost_by_function.is_empty());
+    }
+}
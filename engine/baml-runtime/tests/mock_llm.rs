@@ -0,0 +1,177 @@
+//! A local stand-in for an OpenAI compatible API, so that tests can run
+//! functions end to end without a network or an API key. Requests with
+//! `"stream": true` are answered with server-sent events.
+
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::post,
+    Json, Router,
+};
+use serde_json::{json, Value};
+
+/// What the server answers to a chat completion request.
+#[derive(Clone, Debug)]
+pub enum MockResponse {
+    /// A completion with this content.
+    Content(String),
+    /// An error with this HTTP status.
+    Status(u16),
+    /// A `429` asking to retry after this many milliseconds.
+    RateLimited(u64),
+    /// A stream with this content that ends before the model finished.
+    Unfinished(String),
+}
+
+#[derive(Default)]
+struct MockState {
+    /// Answers for the next requests, in order. The last one is repeated.
+    responses: VecDeque<MockResponse>,
+    /// Bodies of the requests received so far.
+    requests: Vec<Value>,
+}
+
+pub struct MockLlm {
+    pub base_url: String,
+    state: Arc<Mutex<MockState>>,
+}
+
+impl MockLlm {
+    /// Starts a server on a free local port, on its own thread.
+    pub fn start(responses: impl IntoIterator<Item = MockResponse>) -> Self {
+        let state = Arc::new(Mutex::new(MockState {
+            responses: responses.into_iter().collect(),
+            requests: vec![],
+        }));
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("bind a free port");
+        listener.set_nonblocking(true).unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let app = Router::new()
+            .route("/chat/completions", post(chat_completion))
+            .with_state(state.clone());
+        std::thread::spawn(move || {
+            let runtime = tokio::runtime::Builder::new_current_thread()
+                .enable_all()
+                .build()
+                .unwrap();
+            runtime.block_on(async move {
+                let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+                axum::serve(listener, app).await.unwrap();
+            });
+        });
+
+        MockLlm { base_url, state }
+    }
+
+    /// Bodies of the requests received so far.
+    pub fn requests(&self) -> Vec<Value> {
+        self.state.lock().unwrap().requests.clone()
+    }
+
+    /// A `client<llm>` block named `name` that calls this server.
+    pub fn client(&self, name: &str, extra: &str) -> String {
+        format!(
+            r#"
+            client<llm> {name} {{
+              provider openai-generic
+              {extra}
+              options {{
+                base_url "{}"
+                model "gpt-4o"
+              }}
+            }}
+            "#,
+            self.base_url
+        )
+    }
+}
+
+async fn chat_completion(
+    State(state): State<Arc<Mutex<MockState>>>,
+    Json(request): Json<Value>,
+) -> Response {
+    let stream = request["stream"] == json!(true);
+    let response = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(request);
+        match state.responses.len() {
+            0 => MockResponse::Status(500),
+            1 => state.responses[0].clone(),
+            _ => state.responses.pop_front().unwrap(),
+        }
+    };
+    match response {
+        MockResponse::Content(content) if stream => (
+            StatusCode::OK,
+            [("content-type", "text/event-stream")],
+            stream_events(&content, true),
+        )
+            .into_response(),
+        MockResponse::Unfinished(content) => (
+            StatusCode::OK,
+            [("content-type", "text/event-stream")],
+            stream_events(&content, false),
+        )
+            .into_response(),
+        MockResponse::Content(content) => (
+            StatusCode::OK,
+            Json(json!({
+                "id": "chatcmpl-mock",
+                "object": "chat.completion",
+                "created": 0,
+                "model": "gpt-4o",
+                "choices": [{
+                    "index": 0,
+                    "message": { "role": "assistant", "content": content },
+                    "finish_reason": "stop"
+                }],
+                "usage": { "prompt_tokens": 1000, "completion_tokens": 500, "total_tokens": 1500 }
+            })),
+        )
+            .into_response(),
+        MockResponse::Status(status) => (
+            StatusCode::from_u16(status).unwrap(),
+            Json(json!({ "error": { "message": "mock failure" } })),
+        )
+            .into_response(),
+        MockResponse::RateLimited(ms) => (
+            StatusCode::TOO_MANY_REQUESTS,
+            [("retry-after-ms", ms.to_string())],
+            Json(json!({ "error": { "message": "mock rate limit" } })),
+        )
+            .into_response(),
+    }
+}
+
+/// Server-sent events streaming `content` in one chunk, then finishing if
+/// `finish` is set.
+fn stream_events(content: &str, finish: bool) -> String {
+    let chunk = |delta: Value, finish_reason: Value| {
+        json!({
+            "id": "chatcmpl-mock",
+            "object": "chat.completion.chunk",
+            "created": 0,
+            "model": "gpt-4o",
+            "choices": [{ "index": 0, "delta": delta, "finish_reason": finish_reason }]
+        })
+    };
+    let mut events = vec![chunk(
+        json!({ "role": "assistant", "content": content }),
+        Value::Null,
+    )];
+    if finish {
+        events.push(chunk(json!({}), json!("stop")));
+    }
+    events
+        .iter()
+        .map(|event| format!("data: {event}\n\n"))
+        .chain(["data: [DONE]\n\n".to_string()])
+        .collect()
+}
@@ -0,0 +1,827 @@
+//! Runs functions against a mock OpenAI compatible server, to test how the
+//! runtime drives clients: retries, fallbacks, caching and the like.
+
+mod mock_llm;
+
+use std::collections::HashMap;
+
+use baml_runtime::{BamlRuntime, FunctionResult};
+use baml_types::{BamlMap, BamlValue};
+use mock_llm::{MockLlm, MockResponse};
+
+const PERSON: &str = r#"{"name": "Ada", "age": 36}"#;
+
+fn runtime(source: &str, env_vars: &[(&str, &str)]) -> BamlRuntime {
+    let files = HashMap::from([("main.baml", source)]);
+    BamlRuntime::from_file_content("baml_src", &files, env_vars.iter().copied().collect())
+        .expect("valid project")
+}
+
+/// Calls `function` with `{text: <text>}`.
+fn call(runtime: &BamlRuntime, function: &str, text: &str) -> anyhow::Result<FunctionResult> {
+    let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+    let params = BamlMap::from([("text".to_string(), BamlValue::String(text.to_string()))]);
+    let (result, _) = runtime.call_function_sync(function.to_string(), &params, &ctx, None, None);
+    result
+}
+
+/// A project with an `Extract` function that uses `client`.
+fn extract_project(clients: &str, client: &str) -> String {
+    format!(
+        r##"
+        {clients}
+
+        class Person {{
+          name string
+          age int
+        }}
+
+        function Extract(text: string) -> Person {{
+          client {client}
+          prompt #"
+            {{{{ text }}}}
+            {{{{ ctx.output_format }}}}
+          "#
+        }}
+        "##
+    )
+}
+
+mod retry_on {
+    use super::*;
+
+    fn retrying_project(llm: &MockLlm, retry_on: &str) -> String {
+        let policy = format!(
+            r#"
+            retry_policy Retry {{
+              max_retries 1
+              retry_on {retry_on}
+              strategy {{
+                type constant_delay
+                delay_ms 0
+              }}
+            }}
+            "#
+        );
+        extract_project(
+            &format!("{policy}{}", llm.client("Mock", "retry_policy Retry")),
+            "Mock",
+        )
+    }
+
+    #[test]
+    fn parse_errors_are_retried_when_enabled() -> anyhow::Result<()> {
+        let llm = MockLlm::start([
+            MockResponse::Content("I don't know".into()),
+            MockResponse::Content(PERSON.into()),
+        ]);
+        let runtime = runtime(&retrying_project(&llm, r#"["parse_error"]"#), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert!(result.result_with_constraints_content().is_ok(), "{result}");
+        assert_eq!(llm.requests().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn retry_after_is_honoured_without_a_delay() -> anyhow::Result<()> {
+        let llm = MockLlm::start([
+            MockResponse::RateLimited(300),
+            MockResponse::Content(PERSON.into()),
+        ]);
+        let policy = r#"
+            retry_policy Retry {
+              max_retries 1
+              strategy {
+                type constant_delay
+                delay_ms 0
+                jitter full
+              }
+            }
+        "#;
+        let clients = format!("{policy}{}", llm.client("Mock", "retry_policy Retry"));
+        let runtime = runtime(&extract_project(&clients, "Mock"), &[]);
+
+        let start = std::time::Instant::now();
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert!(result.result_with_constraints_content().is_ok(), "{result}");
+        assert_eq!(llm.requests().len(), 2);
+        assert!(start.elapsed() >= std::time::Duration::from_millis(300));
+        Ok(())
+    }
+
+    #[test]
+    fn parse_errors_are_not_retried_by_default() -> anyhow::Result<()> {
+        let llm = MockLlm::start([
+            MockResponse::Content("I don't know".into()),
+            MockResponse::Content(PERSON.into()),
+        ]);
+        let runtime = runtime(&retrying_project(&llm, r#"["http"]"#), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert!(result.result_with_constraints_content().is_err(), "{result}");
+        assert_eq!(llm.requests().len(), 1);
+        Ok(())
+    }
+}
+
+mod cost {
+    use baml_runtime::{
+        client_registry::ClientRegistry, runtime_interface::ExperimentalTracingInterface,
+    };
+    use internal_llm_client::Pricing;
+
+    use super::*;
+
+    // The mock server reports 1000 prompt and 500 completion tokens per call.
+    const CALL_COST: f64 = 1000.0 * 2.5 / 1e6 + 500.0 * 10.0 / 1e6;
+
+    /// A client of `llm` named `Mock`, priced like gpt-4o.
+    fn priced_client(llm: &MockLlm, extra: &str) -> String {
+        format!(
+            r#"
+            client<llm> Mock {{
+              provider openai-generic
+              {extra}
+              options {{
+                base_url "{}"
+                model "gpt-4o"
+                pricing {{
+                  input_per_million_tokens 2.5
+                  output_per_million_tokens 10
+                }}
+              }}
+            }}
+            "#,
+            llm.base_url
+        )
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn calls_are_charged_the_client_pricing() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let runtime = runtime(&extract_project(&priced_client(&llm, ""), "Mock"), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert_close(result.cost().expect("a priced call"), CALL_COST);
+
+        let stats = runtime.drain_stats();
+        assert_close(stats.cost, CALL_COST);
+        assert_close(stats.cost_by_function["Extract"], CALL_COST);
+        Ok(())
+    }
+
+    #[test]
+    fn retries_are_charged_too() -> anyhow::Result<()> {
+        let llm = MockLlm::start([
+            MockResponse::Content("I don't know".into()),
+            MockResponse::Content(PERSON.into()),
+        ]);
+        let policy = r#"
+            retry_policy Retry {
+              max_retries 1
+              retry_on ["parse_error"]
+              strategy {
+                type constant_delay
+                delay_ms 0
+              }
+            }
+        "#;
+        let client = priced_client(&llm, "retry_policy Retry");
+        let runtime = runtime(&extract_project(&format!("{policy}{client}"), "Mock"), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert_close(result.cost().expect("priced calls"), 2.0 * CALL_COST);
+        assert_close(
+            runtime.drain_stats().cost_by_function["Extract"],
+            2.0 * CALL_COST,
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn calls_are_charged_the_registry_pricing_of_their_model() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let runtime = runtime(&extract_project(&llm.client("Mock", ""), "Mock"), &[]);
+        let mut registry = ClientRegistry::new();
+        registry.set_model_pricing(
+            "gpt-4o".to_string(),
+            Pricing {
+                input_per_million_tokens: 2.5,
+                output_per_million_tokens: 10.0,
+            },
+        );
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = BamlMap::from([("text".to_string(), BamlValue::String("Ada".to_string()))]);
+        let (result, _) =
+            runtime.call_function_sync("Extract".to_string(), &params, &ctx, None, Some(&registry));
+        assert_close(result?.cost().expect("a priced model"), CALL_COST);
+        Ok(())
+    }
+
+    #[test]
+    fn unpriced_calls_have_no_cost() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let runtime = runtime(&extract_project(&llm.client("Mock", ""), "Mock"), &[]);
+        let mut registry = ClientRegistry::new();
+        registry.set_model_pricing("some-other-model".to_string(), Pricing::default());
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = BamlMap::from([("text".to_string(), BamlValue::String("Ada".to_string()))]);
+        let (result, _) =
+            runtime.call_function_sync("Extract".to_string(), &params, &ctx, None, Some(&registry));
+        assert_eq!(result?.cost(), None);
+
+        let stats = runtime.drain_stats();
+        assert_eq!(stats.cost, 0.0);
+        assert!(stats.cost_by_function.is_empty());
+        Ok(())
+    }
+}
+
+mod cassette {
+    use super::*;
+
+    /// A cassette of its own for `test`, since cassettes are shared by path
+    /// across the process.
+    fn cassette(test: &str) -> String {
+        let dir = std::env::temp_dir().join("baml-runtime-cassettes");
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{test}-{}.jsonl", std::process::id()));
+        path.display().to_string()
+    }
+
+    /// A project whose `Mock` client calls `llm` with `options`.
+    fn project(llm: &MockLlm, options: &str) -> String {
+        let client = format!(
+            r#"
+            client<llm> Mock {{
+              provider openai-generic
+              options {{
+                base_url "{}"
+                model "gpt-4o"
+                {options}
+              }}
+            }}
+            "#,
+            llm.base_url
+        );
+        extract_project(&client, "Mock")
+    }
+
+    fn record(path: &str, source: &str, text: &str) -> anyhow::Result<FunctionResult> {
+        let recorder = runtime(
+            source,
+            &[("BAML_CASSETTE", path), ("BAML_CASSETTE_MODE", "record")],
+        );
+        call(&recorder, "Extract", text)
+    }
+
+    #[test]
+    fn replays_what_was_recorded() -> anyhow::Result<()> {
+        let path = cassette("round_trip");
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let source = project(&llm, "");
+
+        let recorded = record(&path, &source, "Ada is 36")?;
+        assert_eq!(llm.requests().len(), 1);
+        assert_eq!(std::fs::read_to_string(&path)?.lines().count(), 1);
+
+        let player = runtime(&source, &[("BAML_CASSETTE", &path)]);
+        let replayed = call(&player, "Extract", "Ada is 36")?;
+        assert_eq!(llm.requests().len(), 1, "replaying must not call the model");
+        assert_eq!(replayed.content()?, recorded.content()?);
+        assert!(
+            replayed.result_with_constraints_content().is_ok(),
+            "{replayed}"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn replaying_a_call_that_was_not_recorded_fails() -> anyhow::Result<()> {
+        let path = cassette("miss");
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let source = project(&llm, "");
+        record(&path, &source, "Ada is 36")?;
+
+        let player = runtime(&source, &[("BAML_CASSETTE", &path)]);
+        let result = call(&player, "Extract", "Grace is 85")?;
+        assert_eq!(llm.requests().len(), 1, "replaying must not call the model");
+        let error = format!("{:#}", result.content().unwrap_err());
+        assert!(error.contains("No interaction recorded"), "{error}");
+        Ok(())
+    }
+
+    #[test]
+    fn replaying_ignores_the_order_of_options() -> anyhow::Result<()> {
+        let path = cassette("option_order");
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        record(
+            &path,
+            &project(&llm, "temperature 0.5\nmax_tokens 100"),
+            "Ada is 36",
+        )?;
+
+        let player = runtime(
+            &project(&llm, "max_tokens 100\ntemperature 0.5"),
+            &[("BAML_CASSETTE", &path)],
+        );
+        let result = call(&player, "Extract", "Ada is 36")?;
+        assert!(result.result_with_constraints_content().is_ok(), "{result}");
+        assert_eq!(llm.requests().len(), 1, "replaying must not call the model");
+        Ok(())
+    }
+
+    #[test]
+    fn replaying_tells_apart_calls_that_only_differ_in_their_schema() -> anyhow::Result<()> {
+        const PET: &str = r#"{"species": "cat"}"#;
+        let path = cassette("schema");
+        let llm = MockLlm::start([
+            MockResponse::Content(PERSON.into()),
+            MockResponse::Content(PET.into()),
+        ]);
+        // With `json_schema`, the prompts are identical and only the bodies
+        // differ.
+        let source = format!(
+            r##"
+            {}
+
+            class Person {{
+              name string
+              age int
+            }}
+
+            class Pet {{
+              species string
+            }}
+
+            function Extract(text: string) -> Person {{
+              client Mock
+              prompt #"{{{{ text }}}}"#
+            }}
+
+            function ExtractPet(text: string) -> Pet {{
+              client Mock
+              prompt #"{{{{ text }}}}"#
+            }}
+            "##,
+            llm.client("Mock", "").replace(
+                "model \"gpt-4o\"",
+                "model \"gpt-4o\"\n                output_mode \"json_schema\"",
+            )
+        );
+        let recorder = runtime(
+            &source,
+            &[("BAML_CASSETTE", &path), ("BAML_CASSETTE_MODE", "record")],
+        );
+        call(&recorder, "Extract", "Ada has a cat")?;
+        call(&recorder, "ExtractPet", "Ada has a cat")?;
+
+        let player = runtime(&source, &[("BAML_CASSETTE", &path)]);
+        let pet = call(&player, "ExtractPet", "Ada has a cat")?;
+        assert_eq!(pet.content()?, PET);
+        let person = call(&player, "Extract", "Ada has a cat")?;
+        assert_eq!(person.content()?, PERSON);
+        assert_eq!(llm.requests().len(), 2, "replaying must not call the model");
+        Ok(())
+    }
+}
+
+mod cache {
+    use super::*;
+
+    fn cached_project(llm: &MockLlm) -> String {
+        let client = format!(
+            r#"
+            client<llm> Mock {{
+              provider openai-generic
+              options {{
+                base_url "{}"
+                model "gpt-4o"
+                cache {{
+                  store "memory"
+                }}
+              }}
+            }}
+            "#,
+            llm.base_url
+        );
+        extract_project(&client, "Mock")
+    }
+
+    #[test]
+    fn identical_calls_are_served_from_the_cache() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let runtime = runtime(&cached_project(&llm), &[]);
+
+        let first = call(&runtime, "Extract", "Ada is 36")?;
+        let second = call(&runtime, "Extract", "Ada is 36")?;
+        assert_eq!(llm.requests().len(), 1);
+        assert_eq!(second.content()?, first.content()?);
+
+        call(&runtime, "Extract", "Grace is 85")?;
+        assert_eq!(llm.requests().len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn failures_are_not_cached() -> anyhow::Result<()> {
+        let llm = MockLlm::start([
+            MockResponse::Status(500),
+            MockResponse::Content(PERSON.into()),
+        ]);
+        let runtime = runtime(&cached_project(&llm), &[]);
+
+        assert!(call(&runtime, "Extract", "Ada is 36")?.content().is_err());
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert!(result.content().is_ok(), "{result}");
+        assert_eq!(llm.requests().len(), 2);
+        Ok(())
+    }
+
+    fn stream(runtime: &BamlRuntime, text: &str) -> anyhow::Result<FunctionResult> {
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = BamlMap::from([("text".to_string(), BamlValue::String(text.to_string()))]);
+        let mut stream =
+            runtime.stream_function("Extract".to_string(), &params, &ctx, None, None)?;
+        let (result, _) = stream.run_sync(None::<fn(FunctionResult)>, &ctx, None, None);
+        result
+    }
+
+    #[test]
+    fn finished_streams_are_cached() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let runtime = runtime(&cached_project(&llm), &[]);
+
+        let first = stream(&runtime, "Ada is 36")?;
+        let second = stream(&runtime, "Ada is 36")?;
+        assert_eq!(llm.requests().len(), 1);
+        assert_eq!(second.content()?, first.content()?);
+        Ok(())
+    }
+
+    #[test]
+    fn unfinished_streams_are_not_cached() -> anyhow::Result<()> {
+        let llm = MockLlm::start([
+            MockResponse::Unfinished(PERSON.into()),
+            MockResponse::Content(PERSON.into()),
+        ]);
+        let runtime = runtime(&cached_project(&llm), &[]);
+
+        stream(&runtime, "Ada is 36")?;
+        stream(&runtime, "Ada is 36")?;
+        assert_eq!(llm.requests().len(), 2);
+        Ok(())
+    }
+}
+
+mod forbid_coercions {
+    use super::*;
+
+    /// An age the parser has to round to an int.
+    const FLOAT_AGE: &str = r#"{"name": "Ada", "age": 36.5}"#;
+
+    /// A project whose client and `Extract` function each forbid the given
+    /// coercions, when they are set.
+    fn project(llm: &MockLlm, client: Option<&str>, function: Option<&str>) -> String {
+        let client = client
+            .map(|c| format!("forbid_coercions {c}"))
+            .unwrap_or_default();
+        let function = function
+            .map(|c| format!("forbid_coercions {c}"))
+            .unwrap_or_default();
+        format!(
+            r##"
+            client<llm> Mock {{
+              provider openai-generic
+              options {{
+                base_url "{}"
+                model "gpt-4o"
+                {client}
+              }}
+            }}
+
+            class Person {{
+              name string
+              age int
+            }}
+
+            function Extract(text: string) -> Person {{
+              client Mock
+              {function}
+              prompt #"
+                {{{{ text }}}}
+                {{{{ ctx.output_format }}}}
+              "#
+            }}
+            "##,
+            llm.base_url
+        )
+    }
+
+    #[test]
+    fn client_forbids_coercions() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(FLOAT_AGE.into())]);
+        let runtime = runtime(&project(&llm, Some(r#"["float_to_int"]"#), None), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        let err = result.result_with_constraints_content().unwrap_err();
+        assert!(err.to_string().contains("float_to_int at age"), "{err:#}");
+        Ok(())
+    }
+
+    #[test]
+    fn function_overrides_the_client() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(FLOAT_AGE.into())]);
+        let runtime = runtime(
+            &project(&llm, Some(r#"["float_to_int"]"#), Some(r#"["markdown"]"#)),
+            &[],
+        );
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert!(result.result_with_constraints_content().is_ok(), "{result}");
+        Ok(())
+    }
+
+    #[test]
+    fn function_forbids_coercions() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(FLOAT_AGE.into())]);
+        let runtime = runtime(&project(&llm, None, Some(r#"["float_to_int"]"#)), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        let err = result.result_with_constraints_content().unwrap_err();
+        assert!(err.to_string().contains("float_to_int at age"), "{err:#}");
+        Ok(())
+    }
+}
+
+mod max_context_tokens {
+    use baml_runtime::internal::llm_client::LLMResponse;
+
+    use super::*;
+
+    /// A client of `llm` whose context window holds `max_context_tokens`.
+    fn client(llm: &MockLlm, name: &str, model: &str, max_context_tokens: Option<u64>) -> String {
+        let max_context_tokens = max_context_tokens
+            .map(|n| format!("max_context_tokens {n}"))
+            .unwrap_or_default();
+        format!(
+            r#"
+            client<llm> {name} {{
+              provider openai-generic
+              retry_policy Retry
+              options {{
+                base_url "{}"
+                model "{model}"
+                {max_context_tokens}
+              }}
+            }}
+            "#,
+            llm.base_url
+        )
+    }
+
+    const RETRY: &str = r#"
+        retry_policy Retry {
+          max_retries 2
+          strategy {
+            type constant_delay
+            delay_ms 0
+          }
+        }
+    "#;
+
+    #[test]
+    fn prompts_over_the_limit_are_not_sent() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let clients = format!("{RETRY}{}", client(&llm, "Small", "gpt-4o", Some(5)));
+        let runtime = runtime(&extract_project(&clients, "Small"), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        let LLMResponse::LLMFailure(failure) = result.llm_response() else {
+            panic!("expected a failure: {result}");
+        };
+        assert!(
+            failure
+                .message
+                .contains("more than its max_context_tokens of 5"),
+            "{}",
+            failure.message
+        );
+        assert!(llm.requests().is_empty(), "the prompt must not be sent");
+        Ok(())
+    }
+
+    #[test]
+    fn prompts_within_the_limit_are_sent() -> anyhow::Result<()> {
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let clients = format!("{RETRY}{}", client(&llm, "Large", "gpt-4o", Some(10_000)));
+        let runtime = runtime(&extract_project(&clients, "Large"), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert!(result.result_with_constraints_content().is_ok(), "{result}");
+        assert_eq!(llm.requests().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn fallbacks_skip_clients_over_the_limit() -> anyhow::Result<()> {
+        let small = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let large = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let clients = format!(
+            r#"
+            {RETRY}
+            {}
+            {}
+            client<llm> Fallback {{
+              provider fallback
+              options {{
+                strategy [Small, Large]
+              }}
+            }}
+            "#,
+            client(&small, "Small", "gpt-4o", Some(5)),
+            client(&large, "Large", "gpt-4o", None),
+        );
+        let runtime = runtime(&extract_project(&clients, "Fallback"), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        assert!(result.result_with_constraints_content().is_ok(), "{result}");
+        assert!(small.requests().is_empty());
+        assert_eq!(large.requests().len(), 1);
+        Ok(())
+    }
+
+    #[test]
+    fn registered_tokenizers_count_the_prompt() -> anyhow::Result<()> {
+        // Without merges, every byte of the prompt is a token.
+        let path = std::env::temp_dir().join(format!("bytes-{}.tiktoken", std::process::id()));
+        std::fs::write(&path, "YQ== 0\n")?;
+        baml_runtime::register_tiktoken_file("openai-generic", "bytes-", &path)?;
+
+        let llm = MockLlm::start([MockResponse::Content(PERSON.into())]);
+        let clients = format!(
+            "{RETRY}{}",
+            client(&llm, "PerByte", "bytes-model", Some(50))
+        );
+        let runtime = runtime(&extract_project(&clients, "PerByte"), &[]);
+
+        let result = call(&runtime, "Extract", "Ada is 36")?;
+        let LLMResponse::LLMFailure(failure) = result.llm_response() else {
+            panic!("expected a failure: {result}");
+        };
+        let tokenizer = path.file_stem().unwrap().to_string_lossy();
+        assert!(
+            failure.message.contains(&format!("tokens ({tokenizer})")),
+            "{}",
+            failure.message
+        );
+        assert!(llm.requests().is_empty());
+        Ok(())
+    }
+}
+
+mod weighted {
+    use baml_runtime::client_registry::{ClientProperty, ClientRegistry};
+    use internal_llm_client::ClientProvider;
+
+    use super::*;
+
+    #[test]
+    fn registry_clients_adapt_across_calls() -> anyhow::Result<()> {
+        // Adaptive stats are shared by client name across the process.
+        let good = MockLlm::start(vec![MockResponse::Content(PERSON.into()); 100]);
+        let bad = MockLlm::start([]);
+        let clients = format!(
+            "{}{}",
+            good.client("AdaptiveGood", ""),
+            bad.client("AdaptiveBad", "")
+        );
+        let runtime = runtime(&extract_project(&clients, "AdaptiveGood"), &[]);
+
+        // The registry rebuilds its clients on every call.
+        let mut registry = ClientRegistry::new();
+        registry.add_client(ClientProperty::new(
+            "Adaptive".to_string(),
+            "weighted".parse::<ClientProvider>()?,
+            None,
+            BamlMap::from([
+                (
+                    "strategy".to_string(),
+                    BamlValue::List(vec![
+                        BamlValue::String("AdaptiveGood".to_string()),
+                        BamlValue::String("AdaptiveBad".to_string()),
+                    ]),
+                ),
+                (
+                    "weights".to_string(),
+                    BamlValue::List(vec![BamlValue::Int(1), BamlValue::Int(1)]),
+                ),
+                ("adaptive".to_string(), BamlValue::Bool(true)),
+            ]),
+        ));
+        registry.set_primary("Adaptive".to_string());
+
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let params = BamlMap::from([("text".to_string(), BamlValue::String("Ada".to_string()))]);
+        for _ in 0..100 {
+            let (result, _) = runtime.call_function_sync(
+                "Extract".to_string(),
+                &params,
+                &ctx,
+                None,
+                Some(&registry),
+            );
+            result?;
+        }
+
+        // Without adaptation, the failing client would get about half of the
+        // calls.
+        let failed = bad.requests().len();
+        assert!(failed < 30, "{failed} calls went to the failing client");
+        assert_eq!(good.requests().len() + failed, 100);
+        Ok(())
+    }
+}
+
+mod cli_test {
+    use baml_runtime::cli::test::TestArgs;
+    use clap::Parser;
+
+    use super::*;
+
+    #[derive(Parser)]
+    struct Cli {
+        #[command(flatten)]
+        test: TestArgs,
+    }
+
+    #[test]
+    fn failing_asserts_fail_the_command() -> anyhow::Result<()> {
+        let llm = MockLlm::start([
+            MockResponse::Content(PERSON.into()),
+            MockResponse::Content(PERSON.into()),
+        ]);
+        let tests = r#"
+            test IsAda {
+              functions [Extract]
+              args { text "Ada is 36" }
+              @@assert(age, {{ this.age == 36 }})
+            }
+
+            test IsGrace {
+              functions [Extract]
+              args { text "Ada is 36" }
+              @@assert(age, {{ this.age == 85 }})
+            }
+        "#;
+        let dir = std::env::temp_dir().join(format!("baml-cli-test-{}", std::process::id()));
+        let baml_src = dir.join("baml_src");
+        std::fs::create_dir_all(&baml_src)?;
+        std::fs::write(
+            baml_src.join("main.baml"),
+            extract_project(&llm.client("Mock", ""), "Mock") + tests,
+        )?;
+        let report = dir.join("report.json");
+
+        let cli = Cli::try_parse_from([
+            "test".as_ref(),
+            "--from".as_ref(),
+            baml_src.as_os_str(),
+            "--parallel".as_ref(),
+            "1".as_ref(),
+            "--json".as_ref(),
+            report.as_os_str(),
+        ])?;
+        let error = cli
+            .test
+            .run()
+            .expect_err("a failing test fails the command");
+        assert_eq!(error.to_string(), "1 of 2 tests did not pass");
+        assert_eq!(llm.requests().len(), 2);
+
+        let report: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(report)?)?;
+        let outcomes = report["tests"]
+            .as_array()
+            .expect("a list of tests")
+            .iter()
+            .map(|t| (t["test"].as_str(), t["outcome"].as_str()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            outcomes,
+            [
+                (Some("IsAda"), Some("passed")),
+                (Some("IsGrace"), Some("failed"))
+            ]
+        );
+        let message = report["tests"][1]["message"].as_str().unwrap_or_default();
+        assert!(message.contains("age"), "{message}");
+        Ok(())
+    }
+}
@@ -188,7 +188,7 @@ mod internal_tests {
                 .internal()
                 .render_prompt(function_name, &ctx, &params, Some(0));
 
-        let (prompt, scope, _) = runtime.async_runtime.block_on(render_prompt_future)?;
+        let (prompt, scope, _, _) = runtime.async_runtime.block_on(render_prompt_future)?;
 
         log::info!("Prompt: {:#?}", prompt);
 
@@ -266,7 +266,7 @@ mod internal_tests {
                 .internal()
                 .render_prompt(function_name, &ctx, &params, Some(0));
 
-        let (prompt, scope, _) = runtime.async_runtime.block_on(render_prompt_future)?;
+        let (prompt, scope, _, _) = runtime.async_runtime.block_on(render_prompt_future)?;
 
         // let prompt = render_prompt_future
         //     .await
@@ -351,7 +351,7 @@ test ImageReceiptTest {
             runtime
                 .internal()
                 .render_prompt(function_name, &ctx, &params, None);
-        let (prompt, scope, _) = runtime.async_runtime.block_on(render_prompt_future)?;
+        let (prompt, scope, _, _) = runtime.async_runtime.block_on(render_prompt_future)?;
 
         Ok(())
     }
@@ -432,7 +432,7 @@ test TestName {
             runtime
                 .internal()
                 .render_prompt(function_name, &ctx, &params, None);
-        let (prompt, scope, _) = runtime.async_runtime.block_on(render_prompt_future)?;
+        let (prompt, scope, _, _) = runtime.async_runtime.block_on(render_prompt_future)?;
 
         Ok(())
     }
@@ -500,7 +500,7 @@ test TestTree {
             runtime
                 .internal()
                 .render_prompt(function_name, &ctx, &params, None);
-        let (prompt, scope, _) = runtime.async_runtime.block_on(render_prompt_future)?;
+        let (prompt, scope, _, _) = runtime.async_runtime.block_on(render_prompt_future)?;
 
         Ok(())
     }
@@ -554,7 +554,7 @@ test RunFoo2Test {
             runtime
                 .internal()
                 .render_prompt(function_name, &ctx, &params, None);
-        let (prompt, scope, _) = runtime.async_runtime.block_on(render_prompt_future)?;
+        let (prompt, scope, _, _) = runtime.async_runtime.block_on(render_prompt_future)?;
 
         Ok(())
     }
@@ -602,7 +602,7 @@ test RecursiveAliasCycle {
             runtime
                 .internal()
                 .render_prompt(function_name, &ctx, &params, None);
-        let (prompt, scope, _) = runtime.async_runtime.block_on(render_prompt_future)?;
+        let (prompt, scope, _, _) = runtime.async_runtime.block_on(render_prompt_future)?;
 
         Ok(())
     }
@@ -953,4 +953,101 @@ test RecursiveAliasCycle {
             "##,
         })
     }
+
+    #[test]
+    fn test_parse_without_a_function() -> anyhow::Result<()> {
+        let files = HashMap::from([(
+            "main.baml",
+            r##"
+                class Education {
+                  school string
+                  year int
+                }
+
+                enum Level {
+                  Junior
+                  Senior
+                }
+            "##,
+        )]);
+        let runtime =
+            BamlRuntime::from_file_content("baml_src", &files, HashMap::<&str, &str>::new())?;
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+
+        let target = runtime.find_type("Education[]", &ctx, None)?;
+        let parsed = runtime.parse(
+            r#"Here they are: [{"school": "Berkeley", "year": "2020"}]"#,
+            &target,
+            false,
+            &ctx,
+            None,
+        )?;
+        let value: BamlValue = parsed.clone().into();
+        let BamlValue::List(items) = &value else {
+            panic!("expected a list, got {value:?}");
+        };
+        assert_eq!(items.len(), 1);
+        assert!(!parsed.flags().is_empty());
+
+        let level = runtime.find_type("Level", &ctx, None)?;
+        let parsed = runtime.parse("SENIOR", &level, false, &ctx, None)?;
+        assert_eq!(
+            BamlValue::from(parsed),
+            BamlValue::Enum("Level".into(), "Senior".into())
+        );
+
+        assert!(runtime.find_type("Missing", &ctx, None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_report() -> anyhow::Result<()> {
+        let files = HashMap::from([(
+            "main.baml",
+            r##"
+                class Education {
+                  school string
+                  year int
+                }
+            "##,
+        )]);
+        let runtime =
+            BamlRuntime::from_file_content("baml_src", &files, HashMap::<&str, &str>::new())?;
+        let ctx = runtime.create_ctx_manager(BamlValue::String("test".to_string()), None);
+        let target = runtime.find_type("Education", &ctx, None)?;
+
+        let clean = runtime.parse(
+            r#"{"school": "Berkeley", "year": 2020}"#,
+            &target,
+            false,
+            &ctx,
+            None,
+        )?;
+        let report = clean.parse_report();
+        assert!(report.clean, "{report:?}");
+        assert_eq!(report.score, clean.score());
+
+        let repaired = runtime.parse(
+            "```json\n{\"school\": \"Berkeley\", \"year\": 2020.6, \"gpa\": 3.9}\n```",
+            &target,
+            false,
+            &ctx,
+            None,
+        )?;
+        let report = repaired.parse_report();
+        assert!(!report.clean);
+        assert!(report.score > clean.score());
+        assert!(!report.fixes.is_empty(), "{report:?}");
+        let coercions = report
+            .coercions
+            .iter()
+            .map(|c| (c.path.as_str(), c.kind))
+            .collect::<Vec<_>>();
+        assert!(
+            coercions.contains(&("year", "float_to_int")),
+            "{coercions:?}"
+        );
+        assert!(coercions.contains(&("", "extra_key")), "{coercions:?}");
+        Ok(())
+    }
 }
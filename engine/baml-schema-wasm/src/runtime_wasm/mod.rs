@@ -1535,7 +1535,7 @@ impl WasmFunction {
             .render_prompt(&self.name, &ctx, &params, wasm_call_context.node_index)
             .await
             .as_ref()
-            .map(|(p, scope, allowed)| (p, scope, allowed).into())
+            .map(|(p, scope, allowed, tokens)| (p, scope, allowed, tokens).into())
             .map_err(|e| JsError::new(format!("{e:?}").as_str()))
     }
 
@@ -1590,7 +1590,7 @@ impl WasmFunction {
             .await;
 
         let final_prompt = match result {
-            Ok((prompt, _, _)) => match prompt {
+            Ok((prompt, _, _, _)) => match prompt {
                 RenderedPrompt::Chat(chat_messages) => chat_messages,
                 RenderedPrompt::Completion(_) => vec![], // or handle this case differently
             },
@@ -1601,7 +1601,7 @@ impl WasmFunction {
             .internal()
             .render_raw_curl(
                 &self.name,
-                &ctx,
+                ctx,
                 &final_prompt,
                 RenderCurlSettings {
                     stream,
@@ -1696,11 +1696,12 @@ impl ToJsValue for ExecutionScope {
                 set_property(&obj, "type", JsValue::from_str("Direct"));
                 set_property(&obj, "name", JsValue::from_str(name));
             }
-            ExecutionScope::Retry(name, count, delay) => {
+            ExecutionScope::Retry(name, count, delay, _) => {
                 set_property(&obj, "type", JsValue::from_str("Retry"));
                 set_property(&obj, "name", JsValue::from_str(name));
                 set_property(&obj, "count", JsValue::from_f64(*count as f64));
-                set_property(&obj, "delay", JsValue::from_f64(delay.as_millis() as f64));
+                let delay = delay.unwrap_or_default().as_millis();
+                set_property(&obj, "delay", JsValue::from_f64(delay as f64));
             }
             ExecutionScope::RoundRobin(strategy, index) => {
                 set_property(&obj, "type", JsValue::from_str("RoundRobin"));
@@ -1711,11 +1712,16 @@ impl ToJsValue for ExecutionScope {
                 );
                 set_property(&obj, "index", JsValue::from_f64(*index as f64));
             }
-            ExecutionScope::Fallback(name, index) => {
+            ExecutionScope::Fallback(name, index, _) => {
                 set_property(&obj, "type", JsValue::from_str("Fallback"));
                 set_property(&obj, "name", JsValue::from_str(name));
                 set_property(&obj, "index", JsValue::from_f64(*index as f64));
             }
+            ExecutionScope::Weighted(strategy, index) => {
+                set_property(&obj, "type", JsValue::from_str("Weighted"));
+                set_property(&obj, "name", JsValue::from_str(&strategy.name));
+                set_property(&obj, "index", JsValue::from_f64(*index as f64));
+            }
         }
         obj.into()
     }
@@ -2,7 +2,7 @@ use std::collections::HashMap;
 
 use baml_runtime::{
     internal::llm_client::orchestrator::{ExecutionScope, OrchestrationScope},
-    ChatMessagePart, RenderedPrompt,
+    ChatMessagePart, RenderedPrompt, TokenCount,
 };
 use internal_llm_client::AllowedRoleMetadata;
 use serde_json::json;
@@ -22,6 +22,11 @@ pub struct WasmPrompt {
     prompt: RenderedPrompt,
     pub client_name: String,
     allowed: AllowedRoleMetadata,
+    /// The size of the prompt for the model of the client, when it was
+    /// counted.
+    pub estimated_tokens: Option<usize>,
+    /// The tokenizer `estimated_tokens` was counted with.
+    pub tokenizer: Option<String>,
 }
 
 impl From<OrchestrationScope> for WasmScope {
@@ -42,6 +47,32 @@ impl From<(&RenderedPrompt, &OrchestrationScope, &AllowedRoleMetadata)> for Wasm
             prompt: prompt.clone(),
             client_name: client_name.name(),
             allowed: allowed.clone(),
+            estimated_tokens: None,
+            tokenizer: None,
+        }
+    }
+}
+
+impl
+    From<(
+        &RenderedPrompt,
+        &OrchestrationScope,
+        &AllowedRoleMetadata,
+        &TokenCount,
+    )> for WasmPrompt
+{
+    fn from(
+        (prompt, client_name, allowed, tokens): (
+            &RenderedPrompt,
+            &OrchestrationScope,
+            &AllowedRoleMetadata,
+            &TokenCount,
+        ),
+    ) -> Self {
+        WasmPrompt {
+            estimated_tokens: Some(tokens.tokens),
+            tokenizer: Some(tokens.tokenizer.clone()),
+            ..(prompt, client_name, allowed).into()
         }
     }
 }
@@ -28,6 +28,12 @@ pub(crate) enum Commands {
     #[command(about = "Starts a development server")]
     Dev(baml_runtime::cli::dev::DevArgs),
 
+    #[command(about = "Runs the test blocks in the baml_src directory")]
+    Test(baml_runtime::cli::test::TestArgs),
+
+    #[command(about = "Parses text, like an LLM response, into a BAML type")]
+    Parse(baml_runtime::cli::parse::ParseArgs),
+
     #[command(subcommand, about = "Authenticate with Boundary Cloud")]
     Auth(crate::auth::AuthCommands),
 
@@ -37,6 +43,9 @@ pub(crate) enum Commands {
     #[command(about = "Deploy a BAML project to Boundary Cloud")]
     Deploy(crate::deploy::DeployArgs),
 
+    #[command(about = "Starts a language server for editors, over stdin and stdout")]
+    Lsp(crate::lsp::LspArgs),
+
     #[command(about = "Format BAML source files", name = "fmt", hide = true)]
     Format(crate::format::FormatArgs),
 }
@@ -62,12 +71,21 @@ impl RuntimeCli {
                 args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
                 args.run(defaults)
             }
+            Commands::Test(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
+            Commands::Parse(args) => {
+                args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
+                args.run()
+            }
             Commands::Auth(args) => t.block_on(async { args.run_async().await }),
             Commands::Login(args) => t.block_on(async { args.run_async().await }),
             Commands::Deploy(args) => {
                 args.from = BamlRuntime::parse_baml_src_path(&args.from)?;
                 t.block_on(async { args.run_async().await })
             }
+            Commands::Lsp(args) => args.run(),
             Commands::Format(args) => {
                 // We deliberately don't apply parse_baml_src_path here
                 // see format.rs for more details
@@ -4,6 +4,7 @@ pub(crate) mod colordiff;
 pub(crate) mod commands;
 pub(crate) mod deploy;
 pub(crate) mod format;
+pub(crate) mod lsp;
 pub(crate) mod propelauth;
 pub(crate) mod tui;
 
@@ -0,0 +1,264 @@
+use std::str::FromStr;
+
+use internal_llm_client::ClientProvider;
+use lsp_types::{CompletionItem, CompletionItemKind, CompletionTextEdit, Range, TextEdit};
+use regex::Regex;
+
+use super::{
+    project::position,
+    symbols::{SymbolIndex, SymbolKind},
+};
+
+const FIELD_ATTRIBUTES: &[(&str, &str)] = &[
+    (
+        "alias",
+        "The name of the field in the prompt and the response.",
+    ),
+    ("description", "Describes the field in the prompt."),
+    (
+        "skip",
+        "Leaves the field out of the prompt and the response.",
+    ),
+    (
+        "check",
+        "A named check of the value, reported with the result.",
+    ),
+    (
+        "assert",
+        "A check of the value that fails parsing when it fails.",
+    ),
+    ("stream.done", "Streams the value only once it is complete."),
+    (
+        "stream.not_null",
+        "Streams the parent only once the value is started.",
+    ),
+    (
+        "stream.with_state",
+        "Streams the value with how far along it is.",
+    ),
+];
+
+const BLOCK_ATTRIBUTES: &[(&str, &str)] = &[
+    (
+        "alias",
+        "The name of the type in the prompt and the response.",
+    ),
+    ("description", "Describes the type in the prompt."),
+    (
+        "dynamic",
+        "Lets the type builder add fields or values at runtime.",
+    ),
+    (
+        "check",
+        "A named check of the value, reported with the result.",
+    ),
+    (
+        "assert",
+        "A check of the value that fails parsing when it fails.",
+    ),
+];
+
+const CLIENT_PROPERTIES: &[&str] = &["provider", "retry_policy", "options"];
+
+/// The completions at `offset` of `text`: attributes after `@` or `@@`,
+/// and the providers, options, retry policies and clients of clients and
+/// functions.
+pub(super) fn completions(text: &str, offset: usize, symbols: &SymbolIndex) -> Vec<CompletionItem> {
+    let line_start = text[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = &text[line_start..offset];
+
+    let attribute = Regex::new(r"(?:^|\s)(@@?)([\w.]*)$").unwrap();
+    if let Some(captures) = attribute.captures(line) {
+        let attributes = match &captures[1] {
+            "@@" => BLOCK_ATTRIBUTES,
+            _ => FIELD_ATTRIBUTES,
+        };
+        let typed = captures.get(2).unwrap().start();
+        return attributes
+            .iter()
+            .map(|(name, detail)| {
+                item(
+                    text,
+                    line_start + typed,
+                    offset,
+                    name,
+                    detail,
+                    CompletionItemKind::PROPERTY,
+                )
+            })
+            .collect();
+    }
+
+    let Some((keyword, block_start, block_end)) = enclosing_block(text, line_start) else {
+        return vec![];
+    };
+    let before = &text[block_start..offset];
+    let depth = before.matches('{').count() as i64 - before.matches('}').count() as i64;
+    let word_start = line_start + line.trim_end_matches(is_word).len();
+    let property = text[line_start..word_start].trim();
+
+    match (keyword, property, depth) {
+        ("client<llm>" | "client", "provider", 1) => ClientProvider::allowed_providers()
+            .iter()
+            .map(|provider| {
+                item(
+                    text,
+                    word_start,
+                    offset,
+                    provider,
+                    "",
+                    CompletionItemKind::VALUE,
+                )
+            })
+            .collect(),
+        ("client<llm>" | "client", "retry_policy", 1) => {
+            symbol_items(text, word_start, offset, symbols, SymbolKind::RetryPolicy)
+        }
+        ("client<llm>" | "client", "", 1) => CLIENT_PROPERTIES
+            .iter()
+            .map(|name| {
+                item(
+                    text,
+                    word_start,
+                    offset,
+                    name,
+                    "",
+                    CompletionItemKind::PROPERTY,
+                )
+            })
+            .collect(),
+        ("client<llm>" | "client", "", 2) if in_options(before) => {
+            let provider = Regex::new(r#"(?m)^\s*provider\s+"?([\w-]+)"#)
+                .unwrap()
+                .captures(&text[block_start..block_end])
+                .and_then(|captures| ClientProvider::from_str(&captures[1]).ok());
+            provider
+                .map(|provider| provider.known_options())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|name| {
+                    item(
+                        text,
+                        word_start,
+                        offset,
+                        name,
+                        "",
+                        CompletionItemKind::PROPERTY,
+                    )
+                })
+                .collect()
+        }
+        ("function", "client", 1) => {
+            symbol_items(text, word_start, offset, symbols, SymbolKind::Client)
+        }
+        _ => vec![],
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '"'
+}
+
+/// The keyword, start and end of the top-level declaration that the line
+/// at `line_start` is in, which ends where the next declaration starts.
+fn enclosing_block(text: &str, line_start: usize) -> Option<(&str, usize, usize)> {
+    let declaration = Regex::new(
+        r"(?m)^(client<llm>|client|class|enum|function|template_string|retry_policy|generator|test|type)\b",
+    )
+    .unwrap();
+    let start = declaration
+        .find_iter(text)
+        .take_while(|m| m.start() <= line_start)
+        .last()?;
+    let end = declaration
+        .find_iter(&text[start.end()..])
+        .next()
+        .map_or(text.len(), |m| start.end() + m.start());
+    Some((start.as_str(), start.start(), end))
+}
+
+/// Whether the innermost open `{` of `before` is the `options` of a client.
+fn in_options(before: &str) -> bool {
+    let mut open = vec![];
+    for (i, c) in before.char_indices() {
+        match c {
+            '{' => open.push(i),
+            '}' => {
+                open.pop();
+            }
+            _ => {}
+        }
+    }
+    open.last()
+        .is_some_and(|&i| before[..i].trim_end().ends_with("options"))
+}
+
+fn symbol_items(
+    text: &str,
+    start: usize,
+    end: usize,
+    symbols: &SymbolIndex,
+    kind: SymbolKind,
+) -> Vec<CompletionItem> {
+    symbols
+        .symbols()
+        .filter(|(_, symbol)| symbol.kind == kind)
+        .map(|(name, _)| item(text, start, end, name, "", CompletionItemKind::REFERENCE))
+        .collect()
+}
+
+/// A completion that replaces the text between `start` and `end`, which
+/// editors wouldn't all replace for words like `stream.done`.
+fn item(
+    text: &str,
+    start: usize,
+    end: usize,
+    label: &str,
+    detail: &str,
+    kind: CompletionItemKind,
+) -> CompletionItem {
+    CompletionItem {
+        label: label.to_string(),
+        kind: Some(kind),
+        detail: (!detail.is_empty()).then(|| detail.to_string()),
+        text_edit: Some(CompletionTextEdit::Edit(TextEdit::new(
+            Range::new(position(text, start), position(text, end)),
+            label.to_string(),
+        ))),
+        ..Default::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The labels of the completions where `|` is.
+    fn labels(source: &str) -> Vec<String> {
+        let offset = source.find('|').unwrap();
+        let text = source.replace('|', "");
+        completions(&text, offset, &SymbolIndex::default())
+            .into_iter()
+            .map(|item| item.label)
+            .collect()
+    }
+
+    #[test]
+    fn test_completions() {
+        assert!(labels("class A {\n  b string @stream.|\n}").contains(&"stream.done".into()));
+        assert!(labels("class A {\n  b string\n  @@|\n}").contains(&"dynamic".into()));
+        assert!(labels("client<llm> A {\n  provider |\n}").contains(&"anthropic".into()));
+        assert_eq!(
+            labels("client<llm> A {\n  |\n}"),
+            vec!["provider", "retry_policy", "options"]
+        );
+
+        let options = labels("client<llm> A {\n  provider openai\n  options {\n    |\n  }\n}");
+        assert!(options.contains(&"api_key".into()));
+        assert!(options.contains(&"max_context_tokens".into()));
+        assert!(!options.contains(&"region".into()));
+
+        assert!(labels("class A {\n  b |\n}").is_empty());
+        assert!(labels("function F() -> string {\n  prompt #\"a@|\"#\n}").is_empty());
+    }
+}
@@ -0,0 +1,619 @@
+mod completion;
+mod project;
+mod symbols;
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Context, Result};
+use clap::Args;
+use internal_baml_core::internal_baml_schema_ast::{format_schema, FormatOptions};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+    notification::{
+        DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, DidSaveTextDocument,
+        Notification as _, PublishDiagnostics,
+    },
+    request::{
+        Completion, Formatting, GotoDefinition, HoverRequest, References, Rename, Request as _,
+    },
+    CompletionOptions, CompletionParams, CompletionResponse, DidChangeTextDocumentParams,
+    DidCloseTextDocumentParams, DidOpenTextDocumentParams, DidSaveTextDocumentParams,
+    DocumentFormattingParams, GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents,
+    HoverParams, HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf,
+    PublishDiagnosticsParams, Range, ReferenceParams, RenameParams, ServerCapabilities,
+    TextDocumentPositionParams, TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit, Url,
+    WorkspaceEdit,
+};
+
+use project::{location, offset, position, project_root, Project};
+
+#[derive(Args, Debug)]
+pub struct LspArgs {}
+
+impl LspArgs {
+    pub fn run(&self) -> Result<()> {
+        let (connection, io_threads) = Connection::stdio();
+        let capabilities = ServerCapabilities {
+            text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+            definition_provider: Some(OneOf::Left(true)),
+            references_provider: Some(OneOf::Left(true)),
+            hover_provider: Some(HoverProviderCapability::Simple(true)),
+            rename_provider: Some(OneOf::Left(true)),
+            document_formatting_provider: Some(OneOf::Left(true)),
+            completion_provider: Some(CompletionOptions {
+                trigger_characters: Some(vec!["@".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        connection.initialize(serde_json::to_value(capabilities)?)?;
+        log::info!("BAML language server started");
+
+        Server {
+            connection,
+            documents: HashMap::new(),
+            projects: HashMap::new(),
+        }
+        .run()?;
+        io_threads.join()?;
+        Ok(())
+    }
+}
+
+/// Answers the requests of an editor about the baml_src directories of the
+/// files it opens.
+struct Server {
+    connection: Connection,
+    /// The text of the open documents, which may not be saved.
+    documents: HashMap<PathBuf, String>,
+    /// The projects by baml_src directory, validated on every change.
+    projects: HashMap<PathBuf, Project>,
+}
+
+impl Server {
+    fn run(mut self) -> Result<()> {
+        while let Ok(message) = self.connection.receiver.recv() {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        break;
+                    }
+                    let response = self.on_request(request);
+                    self.connection.sender.send(response.into())?;
+                }
+                Message::Notification(notification) => {
+                    // A bad notification must not take the server down with it.
+                    let method = notification.method.clone();
+                    if let Err(e) = self.on_notification(notification) {
+                        log::error!("Failed to handle {method}: {e:#}");
+                    }
+                }
+                Message::Response(_) => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn on_request(&mut self, request: Request) -> Response {
+        let id = request.id.clone();
+        let result = match request.method.as_str() {
+            GotoDefinition::METHOD => handle::<GotoDefinition>(self, request, Server::definition),
+            References::METHOD => handle::<References>(self, request, Server::references),
+            HoverRequest::METHOD => handle::<HoverRequest>(self, request, Server::hover),
+            Completion::METHOD => handle::<Completion>(self, request, Server::completion),
+            Rename::METHOD => handle::<Rename>(self, request, Server::rename),
+            Formatting::METHOD => handle::<Formatting>(self, request, Server::format),
+            method => {
+                return Response::new_err(
+                    id,
+                    ErrorCode::MethodNotFound as i32,
+                    format!("Unsupported request: {method}"),
+                )
+            }
+        };
+        match result {
+            Ok(result) => Response::new_ok(id, result),
+            Err(e) => Response::new_err(id, ErrorCode::RequestFailed as i32, format!("{e:#}")),
+        }
+    }
+
+    fn on_notification(&mut self, notification: Notification) -> Result<()> {
+        let path = match notification.method.as_str() {
+            DidOpenTextDocument::METHOD => {
+                let params: DidOpenTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let path = file_path(&params.text_document.uri)?;
+                self.documents
+                    .insert(path.clone(), params.text_document.text);
+                path
+            }
+            DidChangeTextDocument::METHOD => {
+                let params: DidChangeTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let path = file_path(&params.text_document.uri)?;
+                // Documents are synced in full, so the last change is the text.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.documents.insert(path.clone(), change.text);
+                }
+                path
+            }
+            DidSaveTextDocument::METHOD => {
+                let params: DidSaveTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                file_path(&params.text_document.uri)?
+            }
+            DidCloseTextDocument::METHOD => {
+                let params: DidCloseTextDocumentParams =
+                    serde_json::from_value(notification.params)?;
+                let path = file_path(&params.text_document.uri)?;
+                self.documents.remove(&path);
+                path
+            }
+            _ => return Ok(()),
+        };
+        self.refresh(&project_root(&path))
+    }
+
+    /// Validates the project again, and publishes its diagnostics.
+    fn refresh(&mut self, root: &Path) -> Result<()> {
+        let Some(project) = Project::load(root, &self.documents) else {
+            return Ok(());
+        };
+        for (path, diagnostics) in project.diagnostics() {
+            let Ok(uri) = Url::from_file_path(&path) else {
+                continue;
+            };
+            let params = PublishDiagnosticsParams::new(uri, diagnostics, None);
+            self.connection
+                .sender
+                .send(Notification::new(PublishDiagnostics::METHOD.to_string(), params).into())?;
+        }
+        self.projects.insert(root.to_path_buf(), project);
+        Ok(())
+    }
+
+    /// The project of `path`, loading it if no document of it is open.
+    fn project(&mut self, path: &Path) -> Result<&Project> {
+        let root = project_root(path);
+        if !self.projects.contains_key(&root) {
+            let project = Project::load(&root, &self.documents)
+                .with_context(|| format!("Failed to validate {}", root.display()))?;
+            self.projects.insert(root.clone(), project);
+        }
+        Ok(&self.projects[&root])
+    }
+
+    /// The project of a position, and the name of the symbol there.
+    fn symbol_at(
+        &mut self,
+        params: &TextDocumentPositionParams,
+    ) -> Result<Option<(&Project, String)>> {
+        let path = file_path(&params.text_document.uri)?;
+        let project = self.project(&path)?;
+        let Some(text) = project.text(&path) else {
+            return Ok(None);
+        };
+        let offset = offset(text, params.position);
+        Ok(project
+            .symbols
+            .name_at(&path, offset)
+            .map(str::to_string)
+            .map(|name| (project, name)))
+    }
+
+    fn definition(
+        &mut self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let Some((project, name)) = self.symbol_at(&params.text_document_position_params)? else {
+            return Ok(None);
+        };
+        Ok(project
+            .symbols
+            .get(&name)
+            .and_then(|symbol| location(&symbol.name))
+            .map(GotoDefinitionResponse::Scalar))
+    }
+
+    fn references(&mut self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let Some((project, name)) = self.symbol_at(&params.text_document_position)? else {
+            return Ok(None);
+        };
+        let declaration = project
+            .symbols
+            .get(&name)
+            .filter(|_| params.context.include_declaration)
+            .map(|symbol| &symbol.name);
+        Ok(Some(
+            declaration
+                .into_iter()
+                .chain(project.symbols.references(&name))
+                .filter_map(location)
+                .collect(),
+        ))
+    }
+
+    fn hover(&mut self, params: HoverParams) -> Result<Option<Hover>> {
+        let Some((project, name)) = self.symbol_at(&params.text_document_position_params)? else {
+            return Ok(None);
+        };
+        let Some(symbol) = project.symbols.get(&name) else {
+            return Ok(None);
+        };
+        let mut value = format!("```baml\n{}\n```", symbol.signature());
+        if let Some(documentation) = &symbol.documentation {
+            value.push_str("\n\n");
+            value.push_str(documentation);
+        }
+        Ok(Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value,
+            }),
+            range: None,
+        }))
+    }
+
+    fn completion(&mut self, params: CompletionParams) -> Result<Option<CompletionResponse>> {
+        let position = params.text_document_position;
+        let path = file_path(&position.text_document.uri)?;
+        let project = self.project(&path)?;
+        let Some(text) = project.text(&path) else {
+            return Ok(None);
+        };
+        let items =
+            completion::completions(text, offset(text, position.position), &project.symbols);
+        Ok(Some(CompletionResponse::Array(items)))
+    }
+
+    fn rename(&mut self, params: RenameParams) -> Result<Option<WorkspaceEdit>> {
+        let new_name = params.new_name;
+        let is_identifier = new_name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+            && new_name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_');
+        if !is_identifier {
+            anyhow::bail!("{new_name:?} is not a valid name");
+        }
+        let Some((project, name)) = self.symbol_at(&params.text_document_position)? else {
+            return Ok(None);
+        };
+        let Some(symbol) = project.symbols.get(&name) else {
+            return Ok(None);
+        };
+        let mut changes: HashMap<Url, Vec<TextEdit>> = HashMap::new();
+        for span in std::iter::once(&symbol.name).chain(project.symbols.references(&name)) {
+            if let Some(location) = location(span) {
+                changes
+                    .entry(location.uri)
+                    .or_default()
+                    .push(TextEdit::new(location.range, new_name.clone()));
+            }
+        }
+        Ok(Some(WorkspaceEdit::new(changes)))
+    }
+
+    fn format(&mut self, params: DocumentFormattingParams) -> Result<Option<Vec<TextEdit>>> {
+        let path = file_path(&params.text_document.uri)?;
+        let text = match self.documents.get(&path) {
+            Some(text) => text.clone(),
+            None => std::fs::read_to_string(&path)?,
+        };
+        let options = FormatOptions {
+            indent_width: params.options.tab_size as isize,
+            fail_on_unhandled_rule: false,
+        };
+        match format_schema(&text, options) {
+            Ok(formatted) if formatted != text => Ok(Some(vec![TextEdit::new(
+                Range::new(position(&text, 0), position(&text, text.len())),
+                formatted,
+            )])),
+            Ok(_) => Ok(None),
+            // Files with syntax errors are left as they are.
+            Err(e) => {
+                log::debug!("Failed to format {}: {e:#}", path.display());
+                Ok(None)
+            }
+        }
+    }
+}
+
+fn handle<R>(
+    server: &mut Server,
+    request: Request,
+    handler: fn(&mut Server, R::Params) -> Result<R::Result>,
+) -> Result<serde_json::Value>
+where
+    R: lsp_types::request::Request,
+{
+    let params = serde_json::from_value(request.params)?;
+    Ok(serde_json::to_value(handler(server, params)?)?)
+}
+
+fn file_path(uri: &Url) -> Result<PathBuf> {
+    uri.to_file_path()
+        .map_err(|_| anyhow::anyhow!("{uri} is not a file"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIN: &str = r##"
+/// A candidate's resume.
+class Resume {
+  name string
+}
+
+client<llm> Primary {
+  provider openai
+  options {
+    model "gpt-4o"
+  }
+}
+
+client<llm> Backup {
+  provider anthropic
+  options {
+    model "claude-3-5-sonnet-latest"
+  }
+}
+
+client<llm> Both {
+  provider fallback
+  options {
+    strategy [Primary, "Backup"]
+  }
+}
+
+function Extract(text: string) -> Resume {
+  client "Both"
+  prompt #"
+    {{ text }} {{ ctx.output_format }}
+  "#
+}
+
+test ExtractResume {
+  functions [Extract]
+  args {
+    text "Backup"
+  }
+}
+"##;
+
+    fn main_path() -> PathBuf {
+        PathBuf::from("/lsp-tests/baml_src/main.baml")
+    }
+
+    fn main_uri() -> Url {
+        Url::from_file_path(main_path()).unwrap()
+    }
+
+    /// A server with `text` open as main.baml, and the client end of its
+    /// connection.
+    fn server(text: &str) -> (Server, Connection) {
+        let (connection, client) = Connection::memory();
+        let mut server = Server {
+            connection,
+            documents: HashMap::new(),
+            projects: HashMap::new(),
+        };
+        server
+            .on_notification(notification::<DidOpenTextDocument>(
+                DidOpenTextDocumentParams {
+                    text_document: lsp_types::TextDocumentItem::new(
+                        main_uri(),
+                        "baml".to_string(),
+                        1,
+                        text.to_string(),
+                    ),
+                },
+            ))
+            .unwrap();
+        (server, client)
+    }
+
+    fn notification<N: lsp_types::notification::Notification>(params: N::Params) -> Notification {
+        Notification::new(N::METHOD.to_string(), params)
+    }
+
+    /// The position of the `n`th occurrence of `pattern` in main.baml.
+    fn at(pattern: &str, n: usize) -> TextDocumentPositionParams {
+        let offset = MAIN.match_indices(pattern).nth(n).unwrap().0;
+        TextDocumentPositionParams::new(
+            lsp_types::TextDocumentIdentifier::new(main_uri()),
+            position(MAIN, offset),
+        )
+    }
+
+    /// The range of the `n`th occurrence of `pattern` in main.baml.
+    fn range_of(pattern: &str, n: usize) -> Range {
+        let offset = MAIN.match_indices(pattern).nth(n).unwrap().0;
+        Range::new(
+            position(MAIN, offset),
+            position(MAIN, offset + pattern.len()),
+        )
+    }
+
+    fn references(server: &mut Server, position: TextDocumentPositionParams) -> Vec<Range> {
+        let params = ReferenceParams {
+            text_document_position: position,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: lsp_types::ReferenceContext {
+                include_declaration: true,
+            },
+        };
+        let mut ranges = server
+            .references(params)
+            .unwrap()
+            .unwrap()
+            .into_iter()
+            .map(|location| location.range)
+            .collect::<Vec<_>>();
+        ranges.sort_by_key(|range| range.start);
+        ranges
+    }
+
+    #[test]
+    fn test_definition() {
+        let (mut server, _client) = server(MAIN);
+        let definition = |server: &mut Server, position| {
+            let params = GotoDefinitionParams {
+                text_document_position_params: position,
+                work_done_progress_params: Default::default(),
+                partial_result_params: Default::default(),
+            };
+            match server.definition(params).unwrap() {
+                Some(GotoDefinitionResponse::Scalar(location)) => Some(location.range),
+                other => {
+                    assert!(other.is_none(), "{other:?}");
+                    None
+                }
+            }
+        };
+
+        // The return type, a client in quotes, and a client in a strategy.
+        assert_eq!(
+            definition(&mut server, at("Resume {", 1)),
+            Some(range_of("Resume", 0))
+        );
+        assert_eq!(
+            definition(&mut server, at("Both\"", 0)),
+            Some(range_of("Both", 0))
+        );
+        assert_eq!(
+            definition(&mut server, at("Backup\"]", 0)),
+            Some(range_of("Backup", 0))
+        );
+        // A string that happens to match the name of a client.
+        assert_eq!(definition(&mut server, at("Backup\"\n", 0)), None);
+    }
+
+    #[test]
+    fn test_references() {
+        let (mut server, _client) = server(MAIN);
+        assert_eq!(
+            references(&mut server, at("Primary", 0)),
+            vec![range_of("Primary", 0), range_of("Primary", 1)]
+        );
+        // Not the "Backup" argument of the test.
+        assert_eq!(
+            references(&mut server, at("Backup", 0)),
+            vec![range_of("Backup", 0), range_of("Backup", 1)]
+        );
+    }
+
+    #[test]
+    fn test_rename() {
+        let (mut server, _client) = server(MAIN);
+        let params = RenameParams {
+            text_document_position: at("Backup", 1),
+            new_name: "Secondary".to_string(),
+            work_done_progress_params: Default::default(),
+        };
+        let edit = server.rename(params).unwrap().unwrap();
+        let mut edits = edit.changes.unwrap().remove(&main_uri()).unwrap();
+        edits.sort_by_key(|edit| edit.range.start);
+        assert_eq!(
+            edits,
+            vec![
+                TextEdit::new(range_of("Backup", 0), "Secondary".to_string()),
+                TextEdit::new(range_of("Backup", 1), "Secondary".to_string()),
+            ]
+        );
+
+        let params = RenameParams {
+            text_document_position: at("Backup", 1),
+            new_name: "not a name".to_string(),
+            work_done_progress_params: Default::default(),
+        };
+        assert!(server.rename(params).is_err());
+    }
+
+    #[test]
+    fn test_hover() {
+        let (mut server, _client) = server(MAIN);
+        let params = HoverParams {
+            text_document_position_params: at("Resume {", 1),
+            work_done_progress_params: Default::default(),
+        };
+        let Some(Hover {
+            contents: HoverContents::Markup(markup),
+            ..
+        }) = server.hover(params).unwrap()
+        else {
+            panic!("expected a hover");
+        };
+        assert_eq!(
+            markup.value,
+            "```baml\nclass Resume {\n  name string\n}\n```\n\nA candidate's resume."
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let (mut server, client) = server(&MAIN.replace("name string", "name Name"));
+        let published = |client: &Connection| {
+            let Ok(Message::Notification(notification)) = client.receiver.try_recv() else {
+                panic!("expected diagnostics");
+            };
+            assert_eq!(notification.method, PublishDiagnostics::METHOD);
+            serde_json::from_value::<PublishDiagnosticsParams>(notification.params).unwrap()
+        };
+
+        let diagnostics = published(&client);
+        assert_eq!(diagnostics.uri, main_uri());
+        assert_eq!(diagnostics.diagnostics.len(), 1);
+        assert!(diagnostics.diagnostics[0].message.contains("Name"));
+
+        // Fixing the error clears it.
+        server
+            .on_notification(notification::<DidChangeTextDocument>(
+                DidChangeTextDocumentParams {
+                    text_document: lsp_types::VersionedTextDocumentIdentifier::new(main_uri(), 2),
+                    content_changes: vec![lsp_types::TextDocumentContentChangeEvent {
+                        range: None,
+                        range_length: None,
+                        text: MAIN.to_string(),
+                    }],
+                },
+            ))
+            .unwrap();
+        assert_eq!(published(&client).diagnostics, vec![]);
+    }
+
+    #[test]
+    fn test_bad_notifications_are_ignored() {
+        let (server, client) = server(MAIN);
+        client
+            .sender
+            .send(Notification::new(DidOpenTextDocument::METHOD.to_string(), "oops").into())
+            .unwrap();
+        client
+            .sender
+            .send(Request::new(1.into(), "shutdown".to_string(), ()).into())
+            .unwrap();
+        client
+            .sender
+            .send(Notification::new("exit".to_string(), ()).into())
+            .unwrap();
+
+        server.run().unwrap();
+        let shutdown = client
+            .receiver
+            .iter()
+            .find_map(|message| match message {
+                Message::Response(response) => Some(response),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(shutdown.id, 1.into());
+    }
+}
@@ -0,0 +1,183 @@
+use std::{
+    collections::HashMap,
+    ffi::OsStr,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use baml_runtime::baml_src_files;
+use internal_baml_core::{
+    internal_baml_diagnostics::{SourceFile, Span},
+    ValidatedSchema,
+};
+use lsp_types::{Diagnostic, DiagnosticSeverity, Location, Position, Range, Url};
+
+use super::symbols::SymbolIndex;
+
+/// The baml_src directory a file belongs to: its closest `baml_src`
+/// ancestor, or the directory it is in.
+pub(super) fn project_root(path: &Path) -> PathBuf {
+    path.ancestors()
+        .find(|dir| dir.file_name() == Some(OsStr::new("baml_src")))
+        .or_else(|| path.parent())
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+/// A baml_src directory, with the unsaved changes of the open documents.
+pub(super) struct Project {
+    files: HashMap<PathBuf, String>,
+    schema: ValidatedSchema,
+    pub(super) symbols: SymbolIndex,
+}
+
+impl Project {
+    /// Reads the .baml files of `root`, and validates them. Returns `None` if
+    /// the validation panics, so that the server can keep the last project.
+    pub(super) fn load(root: &Path, documents: &HashMap<PathBuf, String>) -> Option<Project> {
+        let mut files = HashMap::new();
+        match baml_src_files(&root.to_path_buf()) {
+            Ok(paths) => {
+                for path in paths {
+                    if path.extension() != Some(OsStr::new("baml")) {
+                        continue;
+                    }
+                    match std::fs::read_to_string(&path) {
+                        Ok(text) => {
+                            files.insert(path, text);
+                        }
+                        Err(e) => log::warn!("Failed to read {}: {e}", path.display()),
+                    }
+                }
+            }
+            Err(e) => log::warn!("Failed to list the files of {}: {e:#}", root.display()),
+        }
+        for (path, text) in documents {
+            if project_root(path) == root {
+                files.insert(path.clone(), text.clone());
+            }
+        }
+
+        let sources = files
+            .iter()
+            .map(|(path, text)| SourceFile::from((path, text)))
+            .collect();
+        let schema = match catch_unwind(AssertUnwindSafe(|| {
+            internal_baml_core::validate(root, sources)
+        })) {
+            Ok(schema) => schema,
+            Err(_) => {
+                log::error!("Validating {} panicked", root.display());
+                return None;
+            }
+        };
+        let symbols = SymbolIndex::new(&schema.db.ast);
+        Some(Project {
+            files,
+            schema,
+            symbols,
+        })
+    }
+
+    /// The text of a file of the project, as the editor has it.
+    pub(super) fn text(&self, path: &Path) -> Option<&str> {
+        self.files.get(path).map(String::as_str)
+    }
+
+    /// The errors and warnings of every file, including files without any,
+    /// so that fixed problems are cleared in the editor.
+    pub(super) fn diagnostics(&self) -> HashMap<PathBuf, Vec<Diagnostic>> {
+        let mut diagnostics: HashMap<_, _> = self
+            .files
+            .keys()
+            .map(|path| (path.clone(), vec![]))
+            .collect();
+        let errors = self
+            .schema
+            .diagnostics
+            .errors()
+            .iter()
+            .map(|e| (e.span(), e.message(), DiagnosticSeverity::ERROR));
+        let warnings = self
+            .schema
+            .diagnostics
+            .warnings()
+            .iter()
+            .map(|w| (w.span(), w.message(), DiagnosticSeverity::WARNING));
+        for (span, message, severity) in errors.chain(warnings) {
+            if let Some(file_diagnostics) = diagnostics.get_mut(span.file.path_buf()) {
+                file_diagnostics.push(Diagnostic {
+                    range: range(span),
+                    severity: Some(severity),
+                    source: Some("baml".to_string()),
+                    message: message.to_string(),
+                    ..Default::default()
+                });
+            }
+        }
+        diagnostics
+    }
+}
+
+/// The LSP position, in UTF-16 code units, of a byte offset of `text`.
+pub(super) fn position(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position::new(
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+/// The byte offset of an LSP position of `text`.
+pub(super) fn offset(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(i) => line_start += i + 1,
+            None => return text.len(),
+        }
+    }
+    let line = text[line_start..].split('\n').next().unwrap_or_default();
+    let mut units = 0;
+    for (i, c) in line.char_indices() {
+        if units >= position.character as usize {
+            return line_start + i;
+        }
+        units += c.len_utf16();
+    }
+    line_start + line.len()
+}
+
+pub(super) fn range(span: &Span) -> Range {
+    let text = span.file.as_str();
+    Range::new(position(text, span.start), position(text, span.end))
+}
+
+pub(super) fn location(span: &Span) -> Option<Location> {
+    let uri = Url::from_file_path(span.file.path_buf()).ok()?;
+    Some(Location::new(uri, range(span)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions() {
+        let text = "class A {\n  // é😀\n  b string\n}";
+        let b = text.find("b string").unwrap();
+        assert_eq!(position(text, b), Position::new(2, 2));
+        assert_eq!(offset(text, Position::new(2, 2)), b);
+
+        let end = text.find("😀").unwrap() + "😀".len();
+        assert_eq!(position(text, end), Position::new(1, 8));
+        assert_eq!(offset(text, Position::new(1, 8)), end);
+        // Past the end of a line is the end of the line.
+        assert_eq!(offset(text, Position::new(0, 99)), text.find('\n').unwrap());
+    }
+}
@@ -0,0 +1,341 @@
+use std::{collections::HashMap, path::Path};
+
+use internal_baml_core::{
+    ast::{
+        Expression, FieldType, Identifier, SchemaAst, Top, WithDocumentation, WithIdentifier,
+        WithName, WithSpan,
+    },
+    internal_baml_diagnostics::Span,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(super) enum SymbolKind {
+    Class,
+    Enum,
+    TypeAlias,
+    Function,
+    Client,
+    RetryPolicy,
+    TemplateString,
+}
+
+/// A declaration that other declarations refer to by name.
+pub(super) struct Symbol {
+    pub(super) kind: SymbolKind,
+    /// The name in the declaration.
+    pub(super) name: Span,
+    /// The whole declaration.
+    pub(super) declaration: Span,
+    pub(super) documentation: Option<String>,
+}
+
+impl Symbol {
+    /// The declaration to show on hover: the whole type of classes, enums
+    /// and type aliases, the options of clients and retry policies, and the
+    /// signature of functions and template strings.
+    pub(super) fn signature(&self) -> &str {
+        let source = &self.declaration.file.as_str()[self.declaration.start..self.declaration.end];
+        let end = match self.kind {
+            SymbolKind::Function => source.find('{'),
+            SymbolKind::TemplateString => source.find("#\""),
+            _ => None,
+        };
+        source[..end.unwrap_or(source.len())].trim_end()
+    }
+}
+
+/// The names declared in a project, and where they are used.
+#[derive(Default)]
+pub(super) struct SymbolIndex {
+    symbols: HashMap<String, Symbol>,
+    /// Every identifier that may refer to a symbol, like field types and the
+    /// clients of functions.
+    references: Vec<(String, Span)>,
+}
+
+impl SymbolIndex {
+    pub(super) fn new(ast: &SchemaAst) -> Self {
+        let mut index = SymbolIndex::default();
+        for top in &ast.tops {
+            let (kind, documentation) = match top {
+                Top::Class(block) => (SymbolKind::Class, block.documentation()),
+                Top::Enum(block) => (SymbolKind::Enum, block.documentation()),
+                Top::TypeAlias(_) => (SymbolKind::TypeAlias, None),
+                Top::Function(block) => (SymbolKind::Function, block.documentation()),
+                Top::Client(block) => (SymbolKind::Client, block.documentation()),
+                Top::RetryPolicy(block) => (SymbolKind::RetryPolicy, block.documentation()),
+                Top::TemplateString(template) => {
+                    (SymbolKind::TemplateString, template.documentation())
+                }
+                Top::Generator(_) | Top::TestCase(_) => {
+                    index.add_block_references(top);
+                    continue;
+                }
+            };
+            // With duplicate names, which are errors, the first declaration wins.
+            index
+                .symbols
+                .entry(top.name().to_string())
+                .or_insert_with(|| Symbol {
+                    kind,
+                    name: top.identifier().span().clone(),
+                    declaration: top.span().clone(),
+                    documentation: documentation.map(str::to_string),
+                });
+            index.add_block_references(top);
+        }
+        index
+    }
+
+    fn add_block_references(&mut self, top: &Top) {
+        match top {
+            Top::Class(block) | Top::Enum(block) => {
+                for base in &block.extends {
+                    self.add_identifier(base);
+                }
+                for field in &block.fields {
+                    if let Some(field_type) = &field.expr {
+                        self.add_type(field_type);
+                    }
+                }
+            }
+            Top::TypeAlias(assignment) => self.add_type(&assignment.value),
+            Top::Function(block)
+            | Top::Client(block)
+            | Top::RetryPolicy(block)
+            | Top::TestCase(block) => {
+                for (_, arg) in block.input().iter().flat_map(|input| &input.args) {
+                    self.add_type(&arg.field_type);
+                }
+                if let Some(output) = block.output() {
+                    self.add_type(&output.field_type);
+                }
+                for field in block.fields() {
+                    let Some(value) = &field.expr else {
+                        continue;
+                    };
+                    self.add_expression(value);
+                    match (top, field.name()) {
+                        (Top::Function(_), "client") => self.add_client_name(value),
+                        // The clients of fallback, round-robin and weighted clients.
+                        (Top::Client(_), "options") => {
+                            let strategy = value
+                                .as_map()
+                                .into_iter()
+                                .flat_map(|(entries, _)| entries)
+                                .find(|(key, _)| key_name(key) == Some("strategy"));
+                            if let Some((_, Expression::Array(clients, _))) = strategy {
+                                for client in clients {
+                                    self.add_client_name(client);
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Top::TemplateString(template) => {
+                for (_, arg) in template.input().iter().flat_map(|input| &input.args) {
+                    self.add_type(&arg.field_type);
+                }
+                self.add_expression(template.value());
+            }
+            Top::Generator(_) => {}
+        }
+    }
+
+    fn add_type(&mut self, field_type: &FieldType) {
+        for identifier in field_type.flat_idns() {
+            self.add_identifier(identifier);
+        }
+    }
+
+    fn add_identifier(&mut self, identifier: &Identifier) {
+        if let Identifier::Local(name, span) = identifier {
+            self.references.push((name.clone(), span.clone()));
+        }
+    }
+
+    fn add_expression(&mut self, expression: &Expression) {
+        match expression {
+            Expression::Identifier(identifier) => self.add_identifier(identifier),
+            Expression::Array(values, _) => {
+                for value in values {
+                    self.add_expression(value);
+                }
+            }
+            Expression::Map(entries, _) => {
+                for (_, value) in entries {
+                    self.add_expression(value);
+                }
+            }
+            Expression::RawStringValue(raw) => self.add_template_calls(raw.span()),
+            _ => {}
+        }
+    }
+
+    /// Clients can be named in quotes, like `client "MyClient"`. Other strings
+    /// are not references, even when they happen to match a name.
+    fn add_client_name(&mut self, expression: &Expression) {
+        if let Expression::StringValue(value, span) = expression {
+            if span.end - span.start == value.len() + 2 {
+                let inner = Span::new(span.file.clone(), span.start + 1, span.end - 1);
+                self.references.push((value.clone(), inner));
+            }
+        }
+    }
+
+    /// Prompts call template strings like `{{ PrintResume(resume) }}`.
+    fn add_template_calls(&mut self, prompt: &Span) {
+        let text = &prompt.file.as_str()[prompt.start..prompt.end];
+        let is_identifier = |c: char| c.is_ascii_alphanumeric() || c == '_';
+        let mut start = None;
+        for (i, c) in text.char_indices() {
+            match start {
+                None if is_identifier(c) && !text[..i].ends_with('.') => start = Some(i),
+                Some(s) if !is_identifier(c) => {
+                    if text[i..].trim_start().starts_with('(') {
+                        let span =
+                            Span::new(prompt.file.clone(), prompt.start + s, prompt.start + i);
+                        self.references.push((text[s..i].to_string(), span));
+                    }
+                    start = None;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    pub(super) fn get(&self, name: &str) -> Option<&Symbol> {
+        self.symbols.get(name)
+    }
+
+    pub(super) fn symbols(&self) -> impl Iterator<Item = (&str, &Symbol)> {
+        self.symbols
+            .iter()
+            .map(|(name, symbol)| (name.as_str(), symbol))
+    }
+
+    /// The name of the symbol at `offset` of `path`, in its declaration or
+    /// in a reference to it.
+    pub(super) fn name_at(&self, path: &Path, offset: usize) -> Option<&str> {
+        let at = |span: &Span| {
+            span.file.path_buf() == path && span.start <= offset && offset <= span.end
+        };
+        self.symbols
+            .iter()
+            .find(|(_, symbol)| at(&symbol.name))
+            .map(|(name, _)| name.as_str())
+            .or_else(|| {
+                self.references
+                    .iter()
+                    .find(|(name, span)| at(span) && self.symbols.contains_key(name))
+                    .map(|(name, _)| name.as_str())
+            })
+    }
+
+    /// Every use of the symbol `name`, not including its declaration.
+    pub(super) fn references<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Span> {
+        self.references
+            .iter()
+            .filter(move |(reference, _)| reference == name)
+            .map(|(_, span)| span)
+    }
+}
+
+/// The name of a key of a map, like `strategy` in `options { strategy [...] }`.
+fn key_name(key: &Expression) -> Option<&str> {
+    match key {
+        Expression::Identifier(identifier) => Some(identifier.name()),
+        Expression::StringValue(value, _) => Some(value),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use internal_baml_core::internal_baml_diagnostics::SourceFile;
+
+    use super::*;
+
+    const SOURCE: &str = r##"
+class Resume {
+  name string
+  category Category?
+}
+
+enum Category {
+  TECH
+}
+
+template_string PrintResume(resume: Resume) #"
+  {{ resume.name }}
+"#
+
+client<llm> MyClient {
+  provider openai
+  options {
+    model "gpt-4o"
+  }
+}
+
+function ExtractResume(text: string) -> Resume {
+  client MyClient
+  prompt #"
+    {{ PrintResume(Resume) }} {{ ctx.output_format }}
+  "#
+}
+
+class SeniorResume extends Resume {
+  years int
+}
+"##;
+
+    fn index() -> (SymbolIndex, PathBuf) {
+        let path = PathBuf::from("/baml_src/main.baml");
+        let schema = internal_baml_core::validate(
+            Path::new("/baml_src"),
+            vec![SourceFile::from((path.clone(), SOURCE))],
+        );
+        (SymbolIndex::new(&schema.db.ast), path)
+    }
+
+    fn offsets(index: &SymbolIndex, name: &str) -> Vec<usize> {
+        index.references(name).map(|span| span.start).collect()
+    }
+
+    #[test]
+    fn test_references() {
+        let (index, path) = index();
+        let nth = |pattern: &str, n: usize| SOURCE.match_indices(pattern).nth(n).unwrap().0;
+
+        assert_eq!(
+            offsets(&index, "Resume"),
+            vec![nth("Resume", 2), nth("Resume", 4), nth("Resume", 8)]
+        );
+        assert_eq!(offsets(&index, "Category"), vec![nth("Category", 0)]);
+        assert_eq!(offsets(&index, "MyClient"), vec![nth("MyClient", 1)]);
+        assert_eq!(offsets(&index, "PrintResume"), vec![nth("PrintResume", 1)]);
+
+        assert_eq!(
+            index.name_at(&path, nth("Category?", 0) + 3),
+            Some("Category")
+        );
+        assert_eq!(
+            index.name_at(&path, nth("enum Category", 0) + 6),
+            Some("Category")
+        );
+        assert_eq!(index.name_at(&path, nth("name string", 0)), None);
+
+        assert_eq!(
+            index.get("ExtractResume").unwrap().signature(),
+            "function ExtractResume(text: string) -> Resume"
+        );
+        assert_eq!(
+            index.get("Category").unwrap().signature(),
+            "enum Category {\n  TECH\n}"
+        );
+    }
+}
@@ -0,0 +1,106 @@
+use baml_types::{BamlMediaType, FieldType, TypeValue};
+use itertools::Itertools;
+
+use crate::field_type_attributes;
+
+use super::go_language_features::ToGo;
+
+impl ToGo for FieldType {
+    fn to_go(&self) -> String {
+        match self {
+            FieldType::Class(name) | FieldType::Enum(name) => name.clone(),
+            // Go can't express these, so they are left as JSON to decode.
+            FieldType::RecursiveTypeAlias(_) | FieldType::Tuple(_) => "json.RawMessage".to_string(),
+            FieldType::Literal(value) => value.literal_base_type().to_go(),
+            FieldType::List(inner) => format!("[]{}", inner.to_go()),
+            FieldType::Map(key, value) => format!("map[{}]{}", map_key(key), value.to_go()),
+            FieldType::Primitive(r#type) => String::from(match r#type {
+                TypeValue::Bool => "bool",
+                TypeValue::Float => "float64",
+                TypeValue::Int => "int64",
+                TypeValue::String => "string",
+                TypeValue::Null => "any",
+                TypeValue::Media(BamlMediaType::Image) => "Image",
+                TypeValue::Media(BamlMediaType::Audio) => "Audio",
+            }),
+            FieldType::Union(variants) => union_type(variants, |t| t.to_go()),
+            FieldType::Optional(inner) => nillable(inner.to_go()),
+            FieldType::WithMetadata { base, .. } => match field_type_attributes(self) {
+                Some(_) => format!("Checked[{}]", base.to_go()),
+                None => base.to_go(),
+            },
+        }
+    }
+}
+
+/// Enums are strings in Go, so they can be map keys. Any other key is a
+/// string.
+pub(super) fn map_key(key: &FieldType) -> String {
+    match key {
+        FieldType::Enum(name) => name.clone(),
+        _ => "string".to_string(),
+    }
+}
+
+/// A union whose variants are all the same Go type, like a union of string
+/// literals, is that type, and a union with `null` is nillable. Other unions
+/// are left as JSON to decode.
+pub(super) fn union_type(variants: &[FieldType], to_go: impl Fn(&FieldType) -> String) -> String {
+    let is_null = |t: &&FieldType| matches!(t, FieldType::Primitive(TypeValue::Null));
+    let types = variants
+        .iter()
+        .filter(|t| !is_null(t))
+        .map(to_go)
+        .unique()
+        .collect::<Vec<_>>();
+    let go_type = match types.as_slice() {
+        [only] => only.clone(),
+        _ => "json.RawMessage".to_string(),
+    };
+    if variants.iter().any(|t| is_null(&t)) {
+        nillable(go_type)
+    } else {
+        go_type
+    }
+}
+
+/// `go_type`, or a pointer to it if it can't be nil.
+pub(super) fn nillable(go_type: String) -> String {
+    let can_be_nil = go_type.starts_with(['*', '['])
+        || go_type.starts_with("map[")
+        || go_type == "json.RawMessage"
+        || go_type == "any";
+    if can_be_nil {
+        go_type
+    } else {
+        format!("*{go_type}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use baml_types::LiteralValue;
+
+    use super::*;
+
+    #[test]
+    fn test_go_types() {
+        assert_eq!(FieldType::string().as_list().to_go(), "[]string");
+        assert_eq!(FieldType::class("Resume").as_optional().to_go(), "*Resume");
+        assert_eq!(
+            FieldType::map(FieldType::r#enum("Category"), FieldType::int()).to_go(),
+            "map[Category]int64"
+        );
+        let literals = FieldType::union(vec![
+            FieldType::Literal(LiteralValue::String("a".into())),
+            FieldType::Literal(LiteralValue::String("b".into())),
+        ]);
+        assert_eq!(literals.to_go(), "string");
+        let mixed = FieldType::union(vec![
+            FieldType::string(),
+            FieldType::int(),
+            FieldType::null(),
+        ]);
+        assert_eq!(mixed.to_go(), "json.RawMessage");
+    }
+}
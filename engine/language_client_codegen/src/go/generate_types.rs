@@ -0,0 +1,208 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use internal_baml_core::ir::{
+    repr::{Docstring, Field, IntermediateRepr, Node},
+    ClassWalker, EnumWalker, FieldType, IRHelper,
+};
+
+use crate::field_type_attributes;
+
+use super::{
+    field_type::{map_key, nillable, union_type},
+    go_language_features::{exported_name, ToGo},
+};
+
+#[derive(askama::Template)]
+#[template(path = "types.go.j2", escape = "none")]
+pub(crate) struct GoTypes<'ir> {
+    enums: Vec<GoEnum<'ir>>,
+    classes: Vec<GoStruct<'ir>>,
+    uses_json: bool,
+}
+
+struct GoEnum<'ir> {
+    name: &'ir str,
+    values: Vec<&'ir str>,
+    docstring: Option<String>,
+}
+
+struct GoStruct<'ir> {
+    name: &'ir str,
+    // the Go name, JSON name, type and docstring of the field
+    fields: Vec<(String, &'ir str, String, Option<String>)>,
+    docstring: Option<String>,
+}
+
+/// The Go struct corresponding to Partial<TypeDefinedInBaml>
+#[derive(askama::Template)]
+#[template(path = "partial_types.go.j2", escape = "none")]
+pub(crate) struct GoPartialTypes<'ir> {
+    partial_classes: Vec<GoStruct<'ir>>,
+    uses_json: bool,
+}
+
+fn uses_json(classes: &[GoStruct]) -> bool {
+    classes
+        .iter()
+        .flat_map(|c| &c.fields)
+        .any(|(_, _, r#type, _)| r#type.contains("json."))
+}
+
+impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir crate::GeneratorArgs)> for GoTypes<'ir> {
+    type Error = anyhow::Error;
+
+    fn try_from((ir, _): (&'ir IntermediateRepr, &'ir crate::GeneratorArgs)) -> Result<Self> {
+        let classes = ir
+            .walk_classes()
+            .map(|c| GoStruct::new(c, |f| f.elem.r#type.elem.to_type_ref()))
+            .collect::<Vec<_>>();
+        Ok(GoTypes {
+            enums: ir.walk_enums().map(|e| e.into()).collect(),
+            uses_json: uses_json(&classes),
+            classes,
+        })
+    }
+}
+
+impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir crate::GeneratorArgs)> for GoPartialTypes<'ir> {
+    type Error = anyhow::Error;
+
+    fn try_from((ir, _): (&'ir IntermediateRepr, &'ir crate::GeneratorArgs)) -> Result<Self> {
+        let partial_classes = ir
+            .walk_classes()
+            .map(|c| {
+                GoStruct::new(c, |f| {
+                    let needed = f.attributes.get("stream.not_null").is_some();
+                    f.elem.r#type.elem.to_partial_type_ref(ir, needed)
+                })
+            })
+            .collect::<Vec<_>>();
+        Ok(GoPartialTypes {
+            uses_json: uses_json(&partial_classes),
+            partial_classes,
+        })
+    }
+}
+
+impl<'ir> From<EnumWalker<'ir>> for GoEnum<'ir> {
+    fn from(e: EnumWalker<'ir>) -> GoEnum<'ir> {
+        GoEnum {
+            name: e.name(),
+            values: e
+                .item
+                .elem
+                .values
+                .iter()
+                .map(|v| v.0.elem.0.as_str())
+                .collect(),
+            docstring: e
+                .item
+                .elem
+                .docstring
+                .as_ref()
+                .map(|d| render_docstring(d, false)),
+        }
+    }
+}
+
+impl<'ir> GoStruct<'ir> {
+    fn new(c: ClassWalker<'ir>, field_type: impl Fn(&Node<Field>) -> String) -> Self {
+        // Names like `first_name` and `firstName` are both `FirstName` in Go.
+        let mut taken = HashSet::new();
+        GoStruct {
+            name: c.name(),
+            fields: c
+                .item
+                .elem
+                .static_fields
+                .iter()
+                .map(|f| {
+                    let mut name = exported_name(&f.elem.name);
+                    while !taken.insert(name.clone()) {
+                        name.push('_');
+                    }
+                    (
+                        name,
+                        f.elem.name.as_str(),
+                        field_type(f),
+                        f.elem.docstring.as_ref().map(|d| render_docstring(d, true)),
+                    )
+                })
+                .collect(),
+            docstring: c
+                .item
+                .elem
+                .docstring
+                .as_ref()
+                .map(|d| render_docstring(d, false)),
+        }
+    }
+}
+
+pub(super) trait ToTypeReferenceInTypeDefinition {
+    fn to_type_ref(&self) -> String;
+    fn to_partial_type_ref(&self, ir: &IntermediateRepr, needed: bool) -> String;
+}
+
+impl ToTypeReferenceInTypeDefinition for FieldType {
+    fn to_type_ref(&self) -> String {
+        self.to_go()
+    }
+
+    /// Render a type into a string for use in a partial-types context. Fields
+    /// that are `needed` are never null while streaming, so they don't need
+    /// to be nillable.
+    fn to_partial_type_ref(&self, ir: &IntermediateRepr, needed: bool) -> String {
+        let (field_type, metadata) = ir.distribute_metadata(self);
+        // Values that are done streaming are sent in full.
+        if metadata.1.done {
+            let go_type = self.to_type_ref();
+            return if needed { go_type } else { nillable(go_type) };
+        }
+        let base = match field_type {
+            FieldType::Class(name) => format!("Partial{name}"),
+            FieldType::Enum(name) => name.clone(),
+            FieldType::RecursiveTypeAlias(_) | FieldType::Tuple(_) => "json.RawMessage".to_string(),
+            FieldType::Literal(value) => value.literal_base_type().to_go(),
+            FieldType::List(inner) => format!("[]{}", inner.to_partial_type_ref(ir, false)),
+            FieldType::Map(key, value) => format!(
+                "map[{}]{}",
+                map_key(key),
+                value.to_partial_type_ref(ir, false)
+            ),
+            FieldType::Primitive(_) => field_type.to_go(),
+            FieldType::Union(variants) => union_type(variants, |t| t.to_partial_type_ref(ir, true)),
+            FieldType::Optional(inner) => inner.to_partial_type_ref(ir, false),
+            FieldType::WithMetadata { .. } => {
+                unreachable!("distribute_metadata makes this field unreachable.")
+            }
+        };
+        let with_checks = match field_type_attributes(self) {
+            Some(_) => format!("Checked[{base}]"),
+            None => base,
+        };
+        let go_type = if needed {
+            with_checks
+        } else {
+            nillable(with_checks)
+        };
+        if metadata.1.state {
+            format!("StreamState[{go_type}]")
+        } else {
+            go_type
+        }
+    }
+}
+
+/// Render the BAML documentation (a bare string with padding stripped)
+/// into Go comments.
+pub(super) fn render_docstring(d: &Docstring, indented: bool) -> String {
+    if indented {
+        let lines = d.0.as_str().replace("\n", "\n\t// ");
+        format!("// {lines}")
+    } else {
+        let lines = d.0.as_str().replace("\n", "\n// ");
+        format!("// {lines}")
+    }
+}
@@ -0,0 +1,110 @@
+use crate::dir_writer::LanguageFeatures;
+
+#[derive(Default)]
+pub(super) struct GoLanguageFeatures {}
+
+impl LanguageFeatures for GoLanguageFeatures {
+    // Go tooling recognizes generated files by the "Code generated ... DO NOT
+    // EDIT." line, which has to come before the package clause.
+    const CONTENT_PREFIX: &'static str = r#"
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Welcome to Baml! This client calls your BAML functions through the BAML
+//  server, so start it next to your baml_src directory:
+//
+//  $ npx @boundaryml/baml serve
+//
+///////////////////////////////////////////////////////////////////////////////
+
+// Code generated by BAML. DO NOT EDIT.
+// Edit the BAML files and re-generate this code instead.
+        "#;
+}
+
+pub(super) trait ToGo {
+    fn to_go(&self) -> String;
+}
+
+// Keywords, predeclared identifiers, and the parameters every generated
+// function already takes.
+const RESERVED_NAMES: &[&str] = &[
+    "break",
+    "case",
+    "chan",
+    "const",
+    "continue",
+    "default",
+    "defer",
+    "else",
+    "fallthrough",
+    "for",
+    "func",
+    "go",
+    "goto",
+    "if",
+    "import",
+    "interface",
+    "map",
+    "package",
+    "range",
+    "return",
+    "select",
+    "struct",
+    "switch",
+    "type",
+    "var",
+    "any",
+    "bool",
+    "error",
+    "float64",
+    "int64",
+    "len",
+    "nil",
+    "string",
+    "ctx",
+    "opts",
+];
+
+/// `first_name` as an exported Go identifier, like `FirstName`.
+pub(super) fn exported_name(name: &str) -> String {
+    let name = name
+        .split('_')
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            }
+        })
+        .collect::<String>();
+    if name.is_empty() {
+        "X".to_string()
+    } else {
+        name
+    }
+}
+
+/// A name that can be used as a parameter or variable.
+pub(super) fn local_name(name: &str) -> String {
+    if RESERVED_NAMES.contains(&name) {
+        format!("{name}_")
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_names() {
+        assert_eq!(exported_name("first_name"), "FirstName");
+        assert_eq!(exported_name("firstName"), "FirstName");
+        assert_eq!(exported_name("_id"), "Id");
+        assert_eq!(exported_name("_"), "X");
+        assert_eq!(local_name("type"), "type_");
+        assert_eq!(local_name("resume"), "resume");
+    }
+}
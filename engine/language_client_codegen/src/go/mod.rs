@@ -0,0 +1,219 @@
+mod field_type;
+mod generate_types;
+mod go_language_features;
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+
+use internal_baml_core::ir::repr::IntermediateRepr;
+
+use crate::dir_writer::FileCollector;
+
+use generate_types::ToTypeReferenceInTypeDefinition;
+use go_language_features::{exported_name, local_name, GoLanguageFeatures};
+
+#[derive(askama::Template)]
+#[template(path = "client.go.j2", escape = "none")]
+struct GoClient {
+    funcs: Vec<GoFunction>,
+    uses_json: bool,
+}
+struct GoFunction {
+    name: String,
+    baml_name: String,
+    partial_return_type: String,
+    return_type: String,
+    // the Go name, BAML name and type of the argument
+    args: Vec<(String, String, String)>,
+}
+
+/// The HTTP client for `baml-cli serve`, which is the same for every project.
+#[derive(askama::Template)]
+#[template(path = "runtime.go.j2", escape = "none")]
+struct GoRuntime {}
+
+pub(crate) fn generate(
+    ir: &IntermediateRepr,
+    generator: &crate::GeneratorArgs,
+) -> Result<IndexMap<PathBuf, String>> {
+    let mut collector = FileCollector::<GoLanguageFeatures>::new();
+
+    collector.add_template::<generate_types::GoTypes>("types.go", (ir, generator))?;
+    collector
+        .add_template::<generate_types::GoPartialTypes>("partial_types.go", (ir, generator))?;
+    collector.add_template::<GoClient>("client.go", (ir, generator))?;
+    collector.add_template::<GoRuntime>("runtime.go", (ir, generator))?;
+
+    collector.commit(&generator.output_dir())
+}
+
+impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir crate::GeneratorArgs)> for GoClient {
+    type Error = anyhow::Error;
+
+    fn try_from((ir, _): (&'ir IntermediateRepr, &'ir crate::GeneratorArgs)) -> Result<Self> {
+        let funcs = ir
+            .walk_functions()
+            .map(|f| {
+                let return_type = f.elem().output();
+                GoFunction {
+                    name: exported_name(f.name()),
+                    baml_name: f.name().to_string(),
+                    partial_return_type: return_type.to_partial_type_ref(ir, true),
+                    return_type: return_type.to_type_ref(),
+                    args: f
+                        .inputs()
+                        .iter()
+                        .map(|(name, r#type)| {
+                            (local_name(name), name.to_string(), r#type.to_type_ref())
+                        })
+                        .collect(),
+                }
+            })
+            .collect::<Vec<_>>();
+        let uses_json = funcs.iter().any(|f| {
+            f.return_type.contains("json.")
+                || f.partial_return_type.contains("json.")
+                || f.args.iter().any(|(_, _, r#type)| r#type.contains("json."))
+        });
+        Ok(GoClient { funcs, uses_json })
+    }
+}
+
+impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for GoRuntime {
+    type Error = anyhow::Error;
+
+    fn try_from(_: (&IntermediateRepr, &crate::GeneratorArgs)) -> Result<Self> {
+        Ok(GoRuntime {})
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use internal_baml_core::ir::repr::make_test_ir;
+
+    use super::*;
+
+    const SOURCE: &str = r##"
+enum Category {
+  Refund
+  Cancel
+}
+
+class Order {
+  id int
+  category Category
+  note string?
+  tags string[]
+  aliases string[]?
+  amount int | float
+  status "open" | "closed"
+  shipping_address Address?
+  first_name string
+  firstName string
+}
+
+class Address {
+  street string @stream.not_null
+  city string
+}
+
+function Classify(order_text: string, type: Category?) -> Order {
+  client "openai/gpt-4o"
+  prompt #"{{ order_text }}"#
+}
+    "##;
+
+    /// Generates `SOURCE` and returns the content of `file`.
+    fn generated(file: &str) -> String {
+        let ir = make_test_ir(SOURCE).unwrap();
+        let mut files = generate(&ir, &crate::test_generator_args()).unwrap();
+        files.swap_remove(&PathBuf::from(file)).unwrap()
+    }
+
+    /// The fields of the struct declared by `header`, one per line.
+    fn struct_fields(code: &str, header: &str) -> Vec<String> {
+        let start = code
+            .find(header)
+            .unwrap_or_else(|| panic!("{header} in\n{code}"));
+        let body = &code[start + header.len()..];
+        body[..body.find("\n}").unwrap()]
+            .lines()
+            .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|line| !line.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn enums_are_typed_strings() {
+        let code = generated("types.go");
+        assert!(code.contains("type Category string"), "{code}");
+        assert!(
+            code.contains(r#"Category_Refund Category = "Refund""#),
+            "{code}"
+        );
+        assert!(
+            code.contains(r#"Category_Cancel Category = "Cancel""#),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn classes_are_structs_with_json_tags() {
+        let code = generated("types.go");
+        assert!(code.contains("import \"encoding/json\""), "{code}");
+        assert_eq!(
+            struct_fields(&code, "type Order struct {"),
+            [
+                "Id int64 `json:\"id\"`",
+                "Category Category `json:\"category\"`",
+                "Note *string `json:\"note\"`",
+                "Tags []string `json:\"tags\"`",
+                "Aliases []string `json:\"aliases\"`",
+                "Amount json.RawMessage `json:\"amount\"`",
+                "Status string `json:\"status\"`",
+                "ShippingAddress *Address `json:\"shipping_address\"`",
+                "FirstName string `json:\"first_name\"`",
+                "FirstName_ string `json:\"firstName\"`",
+            ]
+        );
+    }
+
+    #[test]
+    fn partial_fields_are_nillable_until_not_null() {
+        let code = generated("partial_types.go");
+        assert_eq!(
+            struct_fields(&code, "type PartialAddress struct {"),
+            [
+                "Street string `json:\"street\"`",
+                "City *string `json:\"city\"`",
+            ]
+        );
+        let order = struct_fields(&code, "type PartialOrder struct {");
+        for field in [
+            "Id *int64 `json:\"id\"`",
+            "Category *Category `json:\"category\"`",
+            "Note *string `json:\"note\"`",
+            "Amount json.RawMessage `json:\"amount\"`",
+            "ShippingAddress *PartialAddress `json:\"shipping_address\"`",
+        ] {
+            assert!(order.iter().any(|f| f == field), "{field} in {order:#?}");
+        }
+    }
+
+    #[test]
+    fn functions_take_typed_arguments() {
+        let code = generated("client.go");
+        assert!(
+            code.contains("func (c *Client) Classify(ctx context.Context, order_text string, type_ *Category, opts ...CallOption) (Order, error) {"),
+            "{code}"
+        );
+        assert!(
+            code.contains("func (s *StreamClient) Classify(ctx context.Context, order_text string, type_ *Category, opts ...CallOption) (*Stream[PartialOrder, Order], error) {"),
+            "{code}"
+        );
+        assert!(code.contains(r#""type": type_,"#), "{code}");
+        assert!(!code.contains("encoding/json"), "{code}");
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::{Context, Result};
-use baml_types::{Constraint, ConstraintLevel, FieldType};
+use baml_types::{Constraint, ConstraintLevel, FieldType, Validator};
 use indexmap::IndexMap;
 use internal_baml_core::{
     configuration::{GeneratorDefaultClientMode, GeneratorOutputType},
@@ -12,6 +12,7 @@ use std::{
 use version_check::{check_version, GeneratorType, VersionCheckMode};
 
 mod dir_writer;
+mod go;
 pub mod openapi;
 mod python;
 mod ruby;
@@ -181,6 +182,7 @@ impl GenerateClient for GeneratorOutputType {
         }
 
         let files = match self {
+            GeneratorOutputType::Go => go::generate(ir, gen),
             GeneratorOutputType::OpenApi => openapi::generate(ir, gen),
             GeneratorOutputType::PythonPydantic => python::generate(ir, gen),
             GeneratorOutputType::RubySorbet => ruby::generate(ir, gen),
@@ -304,6 +306,26 @@ pub fn type_check_attributes(ir: &IntermediateRepr) -> HashSet<TypeCheckAttribut
         .collect()
 }
 
+/// The `@default` value of a class field.
+pub(crate) fn field_default(field: &internal_baml_core::ir::Field) -> Option<serde_json::Value> {
+    field
+        .attributes
+        .get("default")?
+        .resolve_serde(&baml_types::EvaluationContext::default())
+        .ok()
+}
+
+/// The built-in validators (`@regex`, `@min`, ...) on a type.
+pub(crate) fn type_validators(field_type: &FieldType) -> Vec<&Validator> {
+    match field_type {
+        FieldType::WithMetadata { constraints, .. } => constraints
+            .iter()
+            .filter_map(|c| c.validator.as_ref())
+            .collect(),
+        _ => vec![],
+    }
+}
+
 /// The set of Check names associated with a type.
 /// TODO: This should use `distribute_metadata` instead of pattern matching.
 fn field_type_attributes(field_type: &FieldType) -> Option<TypeCheckAttributes> {
@@ -335,6 +357,30 @@ fn field_type_attributes(field_type: &FieldType) -> Option<TypeCheckAttributes>
     }
 }
 
+/// Arguments that generate into a scratch directory of their own, so that
+/// tests generating code in parallel don't clobber each other's output.
+#[cfg(test)]
+pub(crate) fn test_generator_args() -> GeneratorArgs {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+    let baml_src = std::env::temp_dir().join(format!(
+        "baml_codegen_test_{}_{}",
+        std::process::id(),
+        COUNT.fetch_add(1, Ordering::Relaxed)
+    ));
+    GeneratorArgs::new(
+        "baml_client",
+        baml_src,
+        vec![],
+        "no_version".to_string(),
+        true,
+        GeneratorDefaultClientMode::Async,
+        Vec::new(),
+    )
+    .unwrap()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,7 +1,7 @@
 use std::path::PathBuf;
 
 use anyhow::{Context, Result};
-use baml_types::{BamlMediaType, FieldType, LiteralValue, TypeValue};
+use baml_types::{BamlMediaType, FieldType, LiteralValue, TypeValue, Validator};
 use indexmap::IndexMap;
 use internal_baml_core::ir::{
     repr::{Function, IntermediateRepr, Node, Walker},
@@ -12,7 +12,7 @@ use serde_json::json;
 
 use crate::{
     dir_writer::{FileCollector, LanguageFeatures, RemoveDirBehavior},
-    field_type_attributes, TypeCheckAttributes,
+    field_type_attributes, type_validators, TypeCheckAttributes,
 };
 
 #[derive(Default)]
@@ -399,6 +399,7 @@ impl<'ir> TryFrom<Walker<'ir, &'ir Node<Function>>> for OpenApiMethodDef<'ir> {
                     r#enum: None,
                     r#const: None,
                     nullable: true,
+                    validation: ValidationKeywords::default(),
                 },
                 type_spec: TypeSpec::Ref {
                     r#ref: "#/components/schemas/BamlOptions".into(),
@@ -422,6 +423,7 @@ impl<'ir> TryFrom<Walker<'ir, &'ir Node<Function>>> for OpenApiMethodDef<'ir> {
                     r#enum: None,
                     r#const: None,
                     nullable: false,
+                    validation: ValidationKeywords::default(),
                 },
                 type_spec: TypeSpec::Inline(TypeDef::Class {
                     properties,
@@ -467,6 +469,7 @@ impl<'ir> TryFrom<EnumWalker<'ir>> for TypeSpecWithMeta {
                 ),
                 r#const: None,
                 nullable: false,
+                validation: ValidationKeywords::default(),
             },
             type_spec: TypeSpec::Inline(TypeDef::String),
         })
@@ -483,6 +486,7 @@ impl<'ir> TryFrom<ClassWalker<'ir>> for TypeSpecWithMeta {
                 r#enum: None,
                 r#const: None,
                 nullable: false,
+                validation: ValidationKeywords::default(),
             },
             type_spec: TypeSpec::Inline(TypeDef::Class {
                 properties: c
@@ -534,6 +538,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     r#enum: None,
                     r#const: None,
                     nullable: false,
+                    validation: ValidationKeywords::default(),
                 },
                 type_spec: TypeSpec::Ref {
                     r#ref: format!("#/components/schemas/{}", name),
@@ -545,6 +550,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     r#enum: None,
                     r#const: None,
                     nullable: false,
+                    validation: ValidationKeywords::default(),
                 },
                 type_spec: TypeSpec::AnyValue { any_of: vec![] },
             },
@@ -554,6 +560,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     r#enum: None,
                     r#const: None,
                     nullable: false,
+                    validation: ValidationKeywords::default(),
                 },
                 type_spec: match v {
                     LiteralValue::Int(_) => TypeSpec::Inline(TypeDef::Int),
@@ -567,6 +574,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     r#enum: None,
                     r#const: None,
                     nullable: false,
+                    validation: ValidationKeywords::default(),
                 },
                 type_spec: TypeSpec::Inline(TypeDef::Array {
                     items: inner.to_type_spec(_ir)?.into(),
@@ -588,6 +596,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                         r#enum: None,
                         r#const: None,
                         nullable: false,
+                        validation: ValidationKeywords::default(),
                     },
                     type_spec: TypeSpec::Inline(TypeDef::Map {
                         additional_properties: Box::new(value.to_type_spec(_ir)?),
@@ -600,6 +609,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                     r#enum: None,
                     r#const: None,
                     nullable: false,
+                    validation: ValidationKeywords::default(),
                 },
                 type_spec: match inner {
                     TypeValue::Bool => TypeSpec::Inline(TypeDef::Bool),
@@ -638,6 +648,7 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                         r#enum: None,
                         r#const: None,
                         nullable: false,
+                        validation: ValidationKeywords::default(),
                     },
                     type_spec: TypeSpec::Union { one_of },
                 }
@@ -668,7 +679,17 @@ impl<'ir> ToTypeReferenceInTypeDefinition<'ir> for FieldType {
                         }),
                     }
                 }
-                None => base.to_type_spec(_ir)?,
+                None => {
+                    let mut type_spec = base.to_type_spec(_ir)?;
+                    let is_list = match base.as_ref() {
+                        FieldType::Optional(inner) => matches!(inner.as_ref(), FieldType::List(_)),
+                        base => matches!(base, FieldType::List(_)),
+                    };
+                    for validator in type_validators(self) {
+                        type_spec.meta.apply_validator(validator, is_list);
+                    }
+                    type_spec
+                }
             },
         })
     }
@@ -702,6 +723,65 @@ struct TypeMetadata {
     /// Nulls in OpenAPI are weird: https://swagger.io/docs/specification/data-models/data-types/
     #[serde(skip_serializing_if = "std::ops::Not::not")]
     nullable: bool,
+
+    /// Validation keywords from BAML validators such as `@regex` and `@min`.
+    #[serde(flatten)]
+    validation: ValidationKeywords,
+}
+
+#[derive(Clone, Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+struct ValidationKeywords {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    minimum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    maximum: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_length: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_items: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    max_items: Option<usize>,
+    /// A schema holds a single pattern, so every pattern after the first is
+    /// added as `allOf: [{pattern: ...}]`.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    all_of: Vec<PatternSchema>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+struct PatternSchema {
+    pattern: String,
+}
+
+impl TypeMetadata {
+    fn apply_validator(&mut self, validator: &Validator, is_list: bool) {
+        let validation = &mut self.validation;
+        match validator {
+            Validator::Regex(pattern) if validation.pattern.is_some() => {
+                validation.all_of.push(PatternSchema {
+                    pattern: pattern.clone(),
+                });
+            }
+            Validator::Regex(pattern) => validation.pattern = Some(pattern.clone()),
+            Validator::Format(format) => validation.format = Some(format.to_string()),
+            Validator::Min(min) => validation.minimum = Some(*min),
+            Validator::Max(max) => validation.maximum = Some(*max),
+            Validator::Length { min, max } if is_list => {
+                validation.min_items = Some(*min);
+                validation.max_items = Some(*max);
+            }
+            Validator::Length { min, max } => {
+                validation.min_length = Some(*min);
+                validation.max_length = Some(*max);
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -759,3 +839,60 @@ enum TypeDef {
     #[serde(rename = "boolean")]
     Bool,
 }
+
+#[cfg(test)]
+mod tests {
+    use internal_baml_core::ir::repr::make_test_ir;
+
+    use super::*;
+
+    /// The schema of the properties of `class` in the generated openapi.yaml.
+    fn properties(source: &str, class: &str) -> serde_yaml::Value {
+        let ir = make_test_ir(source).unwrap();
+        let files = generate(&ir, &crate::test_generator_args()).unwrap();
+        let schema: serde_yaml::Value =
+            serde_yaml::from_str(&files[&PathBuf::from("openapi.yaml")]).unwrap();
+        schema["components"]["schemas"][class]["properties"].clone()
+    }
+
+    #[test]
+    fn validators_become_validation_keywords() {
+        let properties = properties(
+            r#"
+class Signup {
+  email string @format("email")
+  handle string @regex("^[a-z]+$") @regex("[0-9]") @length(3, 12)
+  age int? @min(13) @max(120)
+  tags string[] @length(1, 5)
+}
+            "#,
+            "Signup",
+        );
+        let expected: serde_yaml::Value = serde_yaml::from_str(
+            r#"
+email:
+  format: email
+  type: string
+handle:
+  pattern: ^[a-z]+$
+  allOf:
+    - pattern: '[0-9]'
+  minLength: 3
+  maxLength: 12
+  type: string
+age:
+  minimum: 13.0
+  maximum: 120.0
+  type: integer
+tags:
+  minItems: 1
+  maxItems: 5
+  type: array
+  items:
+    type: string
+            "#,
+        )
+        .unwrap();
+        assert_eq!(properties, expected);
+    }
+}
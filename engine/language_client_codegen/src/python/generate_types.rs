@@ -1,9 +1,12 @@
 use anyhow::Result;
-use baml_types::LiteralValue;
+use baml_types::{LiteralValue, Validator};
 use itertools::Itertools;
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashSet};
 
-use crate::{field_type_attributes, type_check_attributes, TypeCheckAttributes};
+use crate::{
+    field_default, field_type_attributes, type_check_attributes, type_validators,
+    TypeCheckAttributes,
+};
 
 use super::python_language_features::ToPython;
 use internal_baml_core::ir::{
@@ -15,8 +18,10 @@ use internal_baml_core::ir::{
 #[template(path = "types.py.j2", escape = "none")]
 pub(crate) struct PythonTypes<'ir> {
     enums: Vec<PythonEnum<'ir>>,
+    type_params: Vec<&'ir str>,
     classes: Vec<PythonClass<'ir>>,
     structural_recursive_alias_cycles: Vec<PythonTypeAlias<'ir>>,
+    specializations: Vec<PythonTypeAlias<'ir>>,
 }
 
 #[derive(askama::Template)]
@@ -37,11 +42,25 @@ struct PythonClass<'ir> {
     name: Cow<'ir, str>,
     /// The docstring for the class, including comment delimiters.
     docstring: Option<String>,
+    /// The classes this class extends, and `Generic[T]` for generic classes.
+    bases: Vec<Cow<'ir, str>>,
     // the name, type and docstring of the field.
     fields: Vec<(Cow<'ir, str>, String, Option<String>)>,
+    /// How many of the fields are inherited. They come first.
+    inherited_fields: usize,
+    /// The own fields with several patterns, and a Python list of the
+    /// patterns that `pydantic.Field` can't hold.
+    pattern_validators: Vec<(Cow<'ir, str>, String)>,
     dynamic: bool,
 }
 
+impl PythonClass<'_> {
+    /// The fields the class defines itself.
+    fn own_fields(&self) -> &[(Cow<'_, str>, String, Option<String>)] {
+        &self.fields[self.inherited_fields..]
+    }
+}
+
 struct PythonTypeAlias<'ir> {
     name: Cow<'ir, str>,
     target: String,
@@ -50,7 +69,9 @@ struct PythonTypeAlias<'ir> {
 #[derive(askama::Template)]
 #[template(path = "partial_types.py.j2", escape = "none")]
 pub(crate) struct PythonStreamTypes<'ir> {
+    type_params: Vec<&'ir str>,
     partial_classes: Vec<PartialPythonClass<'ir>>,
+    specializations: Vec<PythonTypeAlias<'ir>>,
 }
 
 /// The Python class corresponding to Partial<TypeDefinedInBaml>
@@ -59,7 +80,9 @@ struct PartialPythonClass<'ir> {
     dynamic: bool,
     /// The docstring for the class, including comment delimiters.
     docstring: Option<String>,
-    // the name, type and docstring of the field.
+    /// The classes this class extends, and `Generic[T]` for generic classes.
+    bases: Vec<Cow<'ir, str>>,
+    // the name, type and docstring of the fields the class defines itself.
     fields: Vec<(&'ir str, String, Option<String>)>,
 }
 
@@ -71,11 +94,16 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for PythonT
     ) -> Result<PythonTypes<'ir>> {
         Ok(PythonTypes {
             enums: ir.walk_enums().map(PythonEnum::from).collect::<Vec<_>>(),
-            classes: ir.walk_classes().map(PythonClass::from).collect::<Vec<_>>(),
+            type_params: type_params(ir),
+            classes: classes_bases_first(ir)
+                .into_iter()
+                .map(PythonClass::from)
+                .collect::<Vec<_>>(),
             structural_recursive_alias_cycles: ir
                 .walk_alias_cycles()
                 .map(PythonTypeAlias::from)
                 .collect::<Vec<_>>(),
+            specializations: specializations(ir, |arg| arg.to_type_ref(ir, false)),
         })
     }
 }
@@ -114,6 +142,29 @@ impl<'ir> From<ClassWalker<'ir>> for PythonClass<'ir> {
     fn from(c: ClassWalker<'ir>) -> Self {
         PythonClass {
             name: Cow::Borrowed(c.name()),
+            bases: python_bases(&c),
+            inherited_fields: c
+                .item
+                .elem
+                .static_fields
+                .iter()
+                .filter(|f| f.elem.inherited_from.is_some())
+                .count(),
+            pattern_validators: c
+                .item
+                .elem
+                .static_fields
+                .iter()
+                .filter(|f| f.elem.inherited_from.is_none())
+                .filter_map(|f| {
+                    let patterns = type_patterns(&f.elem.r#type.elem);
+                    let extra = patterns.get(1..).filter(|extra| !extra.is_empty())?;
+                    Some((
+                        Cow::Borrowed(f.elem.name.as_str()),
+                        serde_json::Value::from(extra).to_string(),
+                    ))
+                })
+                .collect(),
             dynamic: c.item.attributes.get("dynamic_type").is_some(),
             fields: c
                 .item
@@ -121,11 +172,34 @@ impl<'ir> From<ClassWalker<'ir>> for PythonClass<'ir> {
                 .static_fields
                 .iter()
                 .map(|f| {
+                    let field_type = &f.elem.r#type.elem;
+                    let type_ref = field_type.to_type_ref(c.db, false);
+                    let default = match field_default(f) {
+                        Some(value) => Some(to_python_default(&value, field_type, c.db)),
+                        None if type_ref.starts_with("Optional[") => {
+                            Some(("None".to_string(), false))
+                        }
+                        None => None,
+                    };
+                    let validator_args = pydantic_field_args(field_type);
+                    let type_ref = match (default, validator_args.is_empty()) {
+                        (None, true) => type_ref,
+                        (Some((value, false)), true) => format!("{type_ref} = {value}"),
+                        (default, _) => {
+                            let args = default
+                                .map(|(value, is_instance)| match is_instance {
+                                    true => format!("default_factory=lambda: {value}"),
+                                    false => format!("default={value}"),
+                                })
+                                .into_iter()
+                                .chain(validator_args)
+                                .join(", ");
+                            format!("{type_ref} = pydantic.Field({args})")
+                        }
+                    };
                     (
                         Cow::Borrowed(f.elem.name.as_str()),
-                        add_default_value(
-                            &f.elem.r#type.elem.to_type_ref(c.db, false),
-                        ),
+                        type_ref,
                         f.elem.docstring.as_ref().map(render_docstring),
                     )
                 })
@@ -155,10 +229,12 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for PythonS
 
     fn try_from((ir, _): (&'ir IntermediateRepr, &'_ crate::GeneratorArgs)) -> Result<Self> {
         Ok(Self {
-            partial_classes: ir
-                .walk_classes()
+            type_params: type_params(ir),
+            partial_classes: classes_bases_first(ir)
+                .into_iter()
                 .map(PartialPythonClass::from)
                 .collect::<Vec<_>>(),
+            specializations: specializations(ir, |arg| arg.to_partial_type_ref(ir, true, true)),
         })
     }
 }
@@ -168,11 +244,13 @@ impl<'ir> From<ClassWalker<'ir>> for PartialPythonClass<'ir> {
         PartialPythonClass {
             name: c.name(),
             dynamic: c.item.attributes.get("dynamic_type").is_some(),
+            bases: python_bases(&c),
             fields: c
                 .item
                 .elem
                 .static_fields
                 .iter()
+                .filter(|f| f.elem.inherited_from.is_none())
                 .map(|f| {
                     // Fields with @stream.done should take their type from
                     let needed: bool = f.attributes.get("stream.not_null").is_some();
@@ -202,6 +280,142 @@ impl<'ir> From<ClassWalker<'ir>> for PartialPythonClass<'ir> {
     }
 }
 
+/// The classes of the IR, with every class after the classes it extends, since
+/// Python needs a base class to be defined before its subclasses. Otherwise
+/// the order of the IR is kept.
+fn classes_bases_first(ir: &IntermediateRepr) -> Vec<ClassWalker<'_>> {
+    fn visit<'ir>(
+        class: ClassWalker<'ir>,
+        ir: &'ir IntermediateRepr,
+        visited: &mut HashSet<&'ir str>,
+        ordered: &mut Vec<ClassWalker<'ir>>,
+    ) {
+        if !visited.insert(class.name()) {
+            return;
+        }
+        for base in &class.item.elem.bases {
+            if let Ok(base) = ir.find_class(base) {
+                visit(base, ir, visited, ordered);
+            }
+        }
+        ordered.push(class);
+    }
+
+    let mut visited = HashSet::new();
+    let mut ordered = Vec::new();
+    // Specialized generic classes are aliases of the generic class, see
+    // `specializations`.
+    let classes = ir
+        .walk_classes()
+        .filter(|class| ir.find_specialization(class.name()).is_none())
+        .chain(ir.walk_generic_classes());
+    for class in classes {
+        visit(class, ir, &mut visited, &mut ordered);
+    }
+    ordered
+}
+
+/// The type parameters of all the generic classes, which are declared with
+/// `TypeVar` before the classes.
+fn type_params(ir: &IntermediateRepr) -> Vec<&str> {
+    ir.walk_generic_classes()
+        .flat_map(|class| class.elem().type_params.iter().map(String::as_str))
+        .unique()
+        .collect()
+}
+
+/// The specialized generic classes as aliases of the generic class applied to
+/// its type arguments, `Paginated__Resume = Paginated["Resume"]`. Fields refer
+/// to the specialized classes by name, like any other class.
+fn specializations<'ir>(
+    ir: &'ir IntermediateRepr,
+    type_ref: impl Fn(&FieldType) -> String,
+) -> Vec<PythonTypeAlias<'ir>> {
+    ir.walk_classes()
+        .filter_map(|class| {
+            let specialization = ir.find_specialization(class.name())?;
+            Some(PythonTypeAlias {
+                name: Cow::Borrowed(class.name()),
+                target: format!(
+                    "{}[{}]",
+                    specialization.generic,
+                    specialization.args.iter().map(&type_ref).join(", ")
+                ),
+            })
+        })
+        .collect()
+}
+
+/// A generic class applied to the type parameters of the generic class it's
+/// used in, like `Tree[T]` in the fields of `class Tree<T>`. There's no alias
+/// for it since it isn't a concrete class.
+///
+/// The result is meant to be quoted as a forward reference, so the type
+/// arguments can't be quoted themselves.
+fn generic_application(ir: &IntermediateRepr, name: &str) -> Option<String> {
+    fn arg_ref(ir: &IntermediateRepr, arg: &FieldType) -> String {
+        match arg {
+            FieldType::Class(name) => {
+                generic_application(ir, name).unwrap_or_else(|| name.to_string())
+            }
+            FieldType::List(inner) => format!("List[{}]", arg_ref(ir, inner)),
+            FieldType::Optional(inner) => format!("Optional[{}]", arg_ref(ir, inner)),
+            FieldType::Map(key, value) => {
+                format!("Dict[{}, {}]", arg_ref(ir, key), arg_ref(ir, value))
+            }
+            FieldType::Union(items) => format!(
+                "Union[{}]",
+                items.iter().map(|item| arg_ref(ir, item)).join(", ")
+            ),
+            other => other.to_type_ref(ir, false).replace('"', "'"),
+        }
+    }
+
+    if ir.find_class(name).is_ok() {
+        return None;
+    }
+    let specialization = ir.find_specialization(name)?;
+    Some(format!(
+        "{}[{}]",
+        specialization.generic,
+        specialization
+            .args
+            .iter()
+            .map(|arg| arg_ref(ir, arg))
+            .join(", ")
+    ))
+}
+
+/// The bases of a class, leaving out the ones another base already extends:
+/// Python can't order the bases of `class C(A, B)` when `B` extends `A`.
+fn python_bases<'ir>(c: &ClassWalker<'ir>) -> Vec<Cow<'ir, str>> {
+    fn extends(ir: &IntermediateRepr, class: &str, ancestor: &str) -> bool {
+        ir.find_class(class).is_ok_and(|class| {
+            class
+                .item
+                .elem
+                .bases
+                .iter()
+                .any(|base| base == ancestor || extends(ir, base, ancestor))
+        })
+    }
+
+    let bases = &c.item.elem.bases;
+    let type_params = &c.item.elem.type_params;
+    let mut python_bases = bases
+        .iter()
+        .filter(|base| !bases.iter().any(|other| extends(c.db, other, base)))
+        .map(|base| Cow::Borrowed(base.as_str()))
+        .collect::<Vec<_>>();
+    if !type_params.is_empty() {
+        if python_bases.is_empty() {
+            python_bases.push(Cow::Borrowed("BaseModel"));
+        }
+        python_bases.push(Cow::Owned(format!("Generic[{}]", type_params.join(", "))));
+    }
+    python_bases
+}
+
 /// For a field whose type
 pub fn add_default_value(type_str: &String) -> String {
     if type_str.starts_with("Optional[") {
@@ -211,6 +425,126 @@ pub fn add_default_value(type_str: &String) -> String {
     }
 }
 
+/// The patterns of the `@regex` and `@format` validators of a field, in order.
+fn type_patterns(field_type: &FieldType) -> Vec<&str> {
+    type_validators(field_type)
+        .into_iter()
+        .filter_map(|validator| match validator {
+            Validator::Regex(pattern) => Some(pattern.as_str()),
+            Validator::Format(format) => Some(format.pattern()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Keyword arguments of `pydantic.Field` that enforce the validators of a
+/// field. Pydantic takes a single pattern, so the first `@regex` or `@format`
+/// goes here and the others become a field validator of the class.
+fn pydantic_field_args(field_type: &FieldType) -> Vec<String> {
+    let mut args = vec![];
+    let mut has_pattern = false;
+    for validator in type_validators(field_type) {
+        let pattern = match validator {
+            Validator::Regex(pattern) => Some(pattern.as_str()),
+            Validator::Format(format) => Some(format.pattern()),
+            Validator::Min(min) => {
+                args.push(format!("ge={min}"));
+                None
+            }
+            Validator::Max(max) => {
+                args.push(format!("le={max}"));
+                None
+            }
+            Validator::Length { min, max } => {
+                args.push(format!("min_length={min}"));
+                args.push(format!("max_length={max}"));
+                None
+            }
+        };
+        if let Some(pattern) = pattern.filter(|_| !has_pattern) {
+            args.push(format!("pattern={}", serde_json::Value::from(pattern)));
+            has_pattern = true;
+        }
+    }
+    args
+}
+
+/// The Python expression of the `@default` of a field, and whether it builds
+/// a class instance, which has to wait for the class to be declared.
+fn to_python_default(
+    value: &serde_json::Value,
+    field_type: &FieldType,
+    ir: &IntermediateRepr,
+) -> (String, bool) {
+    use serde_json::Value;
+
+    match (value, field_type) {
+        (_, FieldType::WithMetadata { base, .. }) => to_python_default(value, base, ir),
+        (Value::Null, _) => ("None".to_string(), false),
+        (_, FieldType::Optional(inner)) => to_python_default(value, inner, ir),
+        (Value::String(name), FieldType::Enum(enum_name))
+            if ir
+                .find_enum(enum_name)
+                .is_ok_and(|e| e.item.attributes.get("dynamic_type").is_none()) =>
+        {
+            (format!("{enum_name}.{name}"), false)
+        }
+        (Value::Object(fields), FieldType::Class(class_name)) => {
+            let Ok(class) = ir.find_class(class_name) else {
+                return to_python_default(value, &FieldType::string(), ir);
+            };
+            let args = fields
+                .iter()
+                .map(|(name, value)| {
+                    let field_type = class
+                        .find_field(name)
+                        .map(|f| f.elem().r#type.elem.clone())
+                        .unwrap_or_else(FieldType::string);
+                    format!("{name}={}", to_python_default(value, &field_type, ir).0)
+                })
+                .join(", ");
+            (format!("{class_name}({args})"), true)
+        }
+        (Value::Array(items), _) => {
+            let item_type = match field_type {
+                FieldType::List(inner) => inner.as_ref().clone(),
+                _ => FieldType::string(),
+            };
+            let items = items
+                .iter()
+                .map(|item| to_python_default(item, &item_type, ir))
+                .collect::<Vec<_>>();
+            (
+                format!("[{}]", items.iter().map(|(item, _)| item).join(", ")),
+                items.iter().any(|(_, is_instance)| *is_instance),
+            )
+        }
+        (Value::Object(entries), _) => {
+            let value_type = match field_type {
+                FieldType::Map(_, value_type) => value_type.as_ref().clone(),
+                _ => FieldType::string(),
+            };
+            let entries = entries
+                .iter()
+                .map(|(key, value)| (key, to_python_default(value, &value_type, ir)))
+                .collect::<Vec<_>>();
+            (
+                format!(
+                    "{{{}}}",
+                    entries
+                        .iter()
+                        .map(|(key, (value, _))| format!("{}: {value}", Value::from(key.as_str())))
+                        .join(", ")
+                ),
+                entries.iter().any(|(_, (_, is_instance))| *is_instance),
+            )
+        }
+        (Value::Bool(true), _) => ("True".to_string(), false),
+        (Value::Bool(false), _) => ("False".to_string(), false),
+        (Value::Number(_) | Value::String(_), _) => (value.to_string(), false),
+    }
+}
+
 pub fn type_name_for_checks(checks: &TypeCheckAttributes) -> String {
     let check_names = checks
         .0
@@ -261,7 +595,11 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
             }
             FieldType::RecursiveTypeAlias(name) => format!("\"{name}\""),
             FieldType::Literal(value) => to_python_literal(value),
-            FieldType::Class(name) => format!("\"{module_prefix}{name}\""),
+            FieldType::Class(name) if ir.is_type_param(name) => name.to_string(),
+            FieldType::Class(name) => match generic_application(ir, name) {
+                Some(application) => format!("\"{application}\""),
+                None => format!("\"{module_prefix}{name}\""),
+            },
             FieldType::List(inner) => format!("List[{}]", inner.to_type_ref(ir, use_module_prefix)),
             FieldType::Map(key, value) => {
                 format!("Dict[{}, {}]", key.to_type_ref(ir, use_module_prefix), value.to_type_ref(ir, use_module_prefix))
@@ -305,10 +643,17 @@ impl ToTypeReferenceInTypeDefinition for FieldType {
         let module_prefix = if is_partial_type { "" } else { "types." };
         let base_rep = match base_type {
             FieldType::Class(name) => {
-                if wrapped || needed {
+                let class_ref = if ir.is_type_param(name) {
+                    name.to_string()
+                } else if let Some(application) = generic_application(ir, name) {
+                    format!("\"{application}\"")
+                } else {
                     format!("\"{module_prefix}{name}\"")
+                };
+                if wrapped || needed {
+                    class_ref
                 } else {
-                    format!("Optional[\"{module_prefix}{name}\"]")
+                    format!("Optional[{class_ref}]")
                 }
             }
             FieldType::Enum(name) => {
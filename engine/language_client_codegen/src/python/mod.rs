@@ -352,4 +352,215 @@ class Foo {
         let partial_types = res.get(&PathBuf::from("partial_types.py")).unwrap();
         eprintln!("{}", partial_types);
     }
+
+    /// Generates `source` and returns the content of `file`.
+    fn generated(source: &str, file: &str) -> String {
+        let ir = make_test_ir(source).unwrap();
+        let mut files = generate(&ir, &crate::test_generator_args()).unwrap();
+        files.swap_remove(&PathBuf::from(file)).unwrap()
+    }
+
+    /// The definition of the class starting with `header`, up to the next class.
+    fn class_body<'a>(code: &'a str, header: &str) -> &'a str {
+        let start = code
+            .find(header)
+            .unwrap_or_else(|| panic!("{header} in\n{code}"));
+        let body = &code[start + header.len()..];
+        body.find("\nclass ").map_or(body, |end| &body[..end])
+    }
+
+    const INHERITANCE: &str = r#"
+class Employee extends Person, Named {
+  company string
+}
+
+class Person extends Named, Aged {
+  email string
+}
+
+class Named {
+  name string
+}
+
+class Aged {
+  age int
+}
+    "#;
+
+    #[test]
+    fn subclasses_come_after_their_bases() {
+        for file in ["types.py", "partial_types.py"] {
+            let code = generated(INHERITANCE, file);
+            let position = |header: &str| {
+                code.find(header)
+                    .unwrap_or_else(|| panic!("{header} in\n{code}"))
+            };
+            assert!(position("class Named(BaseModel):") < position("class Person(Named, Aged):"));
+            assert!(position("class Aged(BaseModel):") < position("class Person(Named, Aged):"));
+            assert!(position("class Person(Named, Aged):") < position("class Employee(Person):"));
+        }
+    }
+
+    #[test]
+    fn subclasses_only_declare_their_own_fields() {
+        let code = generated(INHERITANCE, "types.py");
+        let employee = class_body(&code, "class Employee(Person):");
+        assert!(employee.contains("company: str"), "{employee}");
+        for inherited in ["name:", "age:", "email:"] {
+            assert!(!employee.contains(inherited), "{employee}");
+        }
+        let person = class_body(&code, "class Person(Named, Aged):");
+        assert!(person.contains("email: str"), "{person}");
+        assert!(!person.contains("name:"), "{person}");
+    }
+
+    const VALIDATED: &str = r#"
+class Signup {
+  email string @format("email")
+  handle string @regex("^[a-z]+$") @length(3, 12)
+  age int? @min(13) @max(120)
+  tags string[] @length(1, 5)
+}
+    "#;
+
+    #[test]
+    fn extra_patterns_become_field_validators() {
+        let code = generated(
+            r#"
+class Signup {
+  handle string @regex("^[a-z]+$") @regex("[0-9]") @format("email")
+}
+            "#,
+            "types.py",
+        );
+        let signup = class_body(&code, "class Signup(BaseModel):");
+        let email_pattern = serde_json::Value::from(baml_types::Format::Email.pattern());
+        for line in [
+            r#"handle: str = pydantic.Field(pattern="^[a-z]+$")"#.to_string(),
+            r#"@pydantic.field_validator("handle")"#.to_string(),
+            format!(r#"for pattern in ["[0-9]",{email_pattern}]:"#),
+        ] {
+            assert!(signup.contains(&line), "{line} in\n{signup}");
+        }
+    }
+
+    #[test]
+    fn validators_become_pydantic_field_arguments() {
+        let code = generated(VALIDATED, "types.py");
+        let signup = class_body(&code, "class Signup(BaseModel):");
+        let email_pattern = serde_json::Value::from(baml_types::Format::Email.pattern());
+        for field in [
+            format!("email: str = pydantic.Field(pattern={email_pattern})"),
+            r#"handle: str = pydantic.Field(pattern="^[a-z]+$", min_length=3, max_length=12)"#
+                .to_string(),
+            "age: Optional[int] = pydantic.Field(default=None, ge=13, le=120)".to_string(),
+            "tags: List[str] = pydantic.Field(min_length=1, max_length=5)".to_string(),
+        ] {
+            assert!(signup.contains(&field), "{field} in\n{signup}");
+        }
+    }
+
+    const DEFAULTS: &str = r#"
+enum Currency {
+  USD
+  EUR
+}
+
+class Address {
+  city string
+  country string @default("US")
+}
+
+class Invoice {
+  currency Currency @default(USD)
+  quantity int @default(1)
+  note string? @default(null)
+  tags string[] @default(["billing"])
+  totals map<string, int> @default({ net 0 })
+  address Address @default({ city "Austin" })
+}
+    "#;
+
+    #[test]
+    fn defaults_become_field_defaults() {
+        let code = generated(DEFAULTS, "types.py");
+        let address = class_body(&code, "class Address(BaseModel):");
+        assert!(address.contains(r#"country: str = "US""#), "{address}");
+        let invoice = class_body(&code, "class Invoice(BaseModel):");
+        for field in [
+            r#"currency: "Currency" = Currency.USD"#,
+            "quantity: int = 1",
+            "note: Optional[str] = None",
+            r#"tags: List[str] = ["billing"]"#,
+            r#"totals: Dict[str, int] = {"net": 0}"#,
+            // Instances are built when the model is, so they aren't shared.
+            r#"address: "Address" = pydantic.Field(default_factory=lambda: Address(city="Austin"))"#,
+        ] {
+            assert!(invoice.contains(field), "{field} in\n{invoice}");
+        }
+    }
+
+    const GENERICS: &str = r##"
+class Paginated<T> {
+  items T[]
+  next_page int?
+}
+
+class Result<T, E> {
+  value T?
+  error E?
+}
+
+class Resume {
+  name string
+}
+
+class Feed {
+  resumes Paginated<Resume>
+  nested Paginated<Paginated<int>>
+  outcome Result<string, Resume>
+}
+
+function ExtractResumes(text: string) -> Paginated<Resume> {
+  client "openai/gpt-4o"
+  prompt #"{{ text }}"#
+}
+    "##;
+
+    #[test]
+    fn generic_classes_are_pydantic_generics() {
+        for file in ["types.py", "partial_types.py"] {
+            let code = generated(GENERICS, file);
+            let position = |line: &str| {
+                code.find(line)
+                    .unwrap_or_else(|| panic!("{line} in {file}:\n{code}"))
+            };
+            assert!(
+                position("T = TypeVar('T')") < position("class Paginated(BaseModel, Generic[T]):")
+            );
+            assert!(
+                position("E = TypeVar('E')") < position("class Result(BaseModel, Generic[T, E]):")
+            );
+            let paginated = class_body(&code, "class Paginated(BaseModel, Generic[T]):");
+            assert!(paginated.contains("items: List[T]"), "{paginated}");
+            // Specializations are aliases, declared once the generic class is.
+            for alias in [
+                r#"Paginated__Resume = Paginated["Resume"]"#,
+                r#"Paginated__Paginated__Int = Paginated["Paginated__Int"]"#,
+                r#"Result__String__Resume = Result[str, "Resume"]"#,
+            ] {
+                assert!(
+                    position("class Result(BaseModel") < position(alias),
+                    "{alias} in {file}"
+                );
+            }
+            assert!(!code.contains("class Paginated__"), "{code}");
+        }
+
+        let types = generated(GENERICS, "types.py");
+        let feed = class_body(&types, "class Feed(BaseModel):");
+        assert!(feed.contains(r#"resumes: "Paginated__Resume""#), "{feed}");
+        let client = generated(GENERICS, "sync_client.py");
+        assert!(client.contains(") -> types.Paginated__Resume:"), "{client}");
+    }
 }
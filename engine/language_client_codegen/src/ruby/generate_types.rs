@@ -5,7 +5,7 @@ use anyhow::Result;
 use baml_types::LiteralValue;
 use itertools::Itertools;
 
-use crate::{field_type_attributes, type_check_attributes, TypeCheckAttributes};
+use crate::{field_default, field_type_attributes, type_check_attributes, TypeCheckAttributes};
 
 use super::ruby_language_features::ToRuby;
 use internal_baml_core::ir::{
@@ -27,9 +27,18 @@ struct RubyEnum<'ir> {
     docstring: Option<String>,
 }
 
+/// The name, type, default and docstring of a field. The default is the
+/// option of the prop and the expression of the value.
+type RubyField<'ir> = (
+    Cow<'ir, str>,
+    String,
+    Option<(String, String)>,
+    Option<String>,
+);
+
 struct RubyStruct<'ir> {
     name: Cow<'ir, str>,
-    fields: Vec<(Cow<'ir, str>, String, Option<String>)>,
+    fields: Vec<RubyField<'ir>>,
     dynamic: bool,
     docstring: Option<String>,
 }
@@ -88,6 +97,9 @@ impl<'ir> From<EnumWalker<'ir>> for RubyEnum<'ir> {
     }
 }
 
+// Sorbet doesn't allow subclassing a T::Struct, so a class that extends others
+// is generated as a struct of its own with the inherited fields copied in,
+// which `static_fields` already has.
 impl<'ir> From<ClassWalker<'ir>> for RubyStruct<'ir> {
     fn from(c: ClassWalker<'ir>) -> RubyStruct<'ir> {
         RubyStruct {
@@ -99,9 +111,17 @@ impl<'ir> From<ClassWalker<'ir>> for RubyStruct<'ir> {
                 .static_fields
                 .iter()
                 .map(|f| {
+                    let default = field_default(f).map(|value| {
+                        match to_ruby_default(&value, &f.elem.r#type.elem, c.db) {
+                            // Instances are mutable, so each struct gets its own.
+                            (value, true) => (format!("factory: -> {{ {value} }}"), value),
+                            (value, false) => (format!("default: {value}"), value),
+                        }
+                    });
                     (
                         Cow::Borrowed(f.elem.name.as_str()),
                         f.elem.r#type.elem.to_type_ref(),
+                        default,
                         f.elem.docstring.as_ref().map(|d| render_docstring(d, true)),
                     )
                 })
@@ -273,6 +293,86 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'_ crate::GeneratorArgs)> for TypeReg
     }
 }
 
+/// The Ruby expression of the `@default` of a field, and whether it builds a
+/// struct.
+fn to_ruby_default(
+    value: &serde_json::Value,
+    field_type: &FieldType,
+    ir: &IntermediateRepr,
+) -> (String, bool) {
+    use serde_json::Value;
+
+    match (value, field_type) {
+        (_, FieldType::WithMetadata { base, .. }) => to_ruby_default(value, base, ir),
+        (Value::Null, _) => ("nil".to_string(), false),
+        (_, FieldType::Optional(inner)) => to_ruby_default(value, inner, ir),
+        (Value::String(name), FieldType::Enum(enum_name))
+            if ir
+                .find_enum(enum_name)
+                .is_ok_and(|e| e.item.attributes.get("dynamic_type").is_none()) =>
+        {
+            (format!("Baml::Types::{enum_name}::{name}"), false)
+        }
+        (Value::Object(fields), FieldType::Class(class_name)) => {
+            let Ok(class) = ir.find_class(class_name) else {
+                return to_ruby_default(value, &FieldType::string(), ir);
+            };
+            let props = fields
+                .iter()
+                .map(|(name, value)| {
+                    let field_type = class
+                        .find_field(name)
+                        .map(|f| f.elem().r#type.elem.clone())
+                        .unwrap_or_else(FieldType::string);
+                    format!("{name}: {}", to_ruby_default(value, &field_type, ir).0)
+                })
+                .join(", ");
+            (format!("Baml::Types::{class_name}.new({{ {props} }})"), true)
+        }
+        (Value::Array(items), _) => {
+            let item_type = match field_type {
+                FieldType::List(inner) => inner.as_ref().clone(),
+                _ => FieldType::string(),
+            };
+            let items = items
+                .iter()
+                .map(|item| to_ruby_default(item, &item_type, ir))
+                .collect::<Vec<_>>();
+            (
+                format!("[{}]", items.iter().map(|(item, _)| item).join(", ")),
+                items.iter().any(|(_, is_struct)| *is_struct),
+            )
+        }
+        (Value::Object(entries), _) => {
+            let value_type = match field_type {
+                FieldType::Map(_, value_type) => value_type.as_ref().clone(),
+                _ => FieldType::string(),
+            };
+            let entries = entries
+                .iter()
+                .map(|(key, value)| (key, to_ruby_default(value, &value_type, ir)))
+                .collect::<Vec<_>>();
+            (
+                format!(
+                    "{{ {} }}",
+                    entries
+                        .iter()
+                        .map(|(key, (value, _))| format!("{} => {value}", ruby_string(key)))
+                        .join(", ")
+                ),
+                entries.iter().any(|(_, (_, is_struct))| *is_struct),
+            )
+        }
+        (Value::String(s), _) => (ruby_string(s), false),
+        (Value::Bool(_) | Value::Number(_), _) => (value.to_string(), false),
+    }
+}
+
+/// A double-quoted Ruby string, which must not interpolate `#{...}`.
+fn ruby_string(s: &str) -> String {
+    serde_json::Value::from(s).to_string().replace('#', "\\#")
+}
+
 /// Render the BAML documentation (a bare string with padding stripped)
 /// into a Ruby docstring.
 fn render_docstring(d: &Docstring, indented: bool) -> String {
@@ -102,3 +102,155 @@ impl TryFrom<(&'_ IntermediateRepr, &'_ crate::GeneratorArgs)> for InlinedBaml {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use internal_baml_core::ir::repr::make_test_ir;
+
+    use super::*;
+
+    /// Generates `source` and returns the content of `file`.
+    fn generated(source: &str, file: &str) -> String {
+        let ir = make_test_ir(source).unwrap();
+        let mut files = generate(&ir, &crate::test_generator_args()).unwrap();
+        files.swap_remove(&PathBuf::from(file)).unwrap()
+    }
+
+    /// The definition of the struct starting with `header`, up to its `initialize`.
+    fn struct_body<'a>(code: &'a str, header: &str) -> &'a str {
+        let start = code
+            .find(header)
+            .unwrap_or_else(|| panic!("{header} in\n{code}"));
+        let body = &code[start + header.len()..];
+        &body[..body.find("def initialize").unwrap()]
+    }
+
+    #[test]
+    fn subclasses_copy_inherited_fields() {
+        let code = generated(
+            r#"
+class Employee extends Person {
+  company string
+}
+
+class Person {
+  name string
+  age int?
+}
+            "#,
+            "types.rb",
+        );
+        // A T::Struct can't be subclassed, so every struct has all its fields.
+        let employee = struct_body(&code, "class Employee < T::Struct");
+        assert!(!code.contains("class Employee < Person"), "{code}");
+        let fields = [
+            "const :name, String",
+            "const :age, T.nilable(Integer)",
+            "const :company, String",
+        ];
+        let positions = fields.map(|field| {
+            employee
+                .find(field)
+                .unwrap_or_else(|| panic!("{field} in\n{employee}"))
+        });
+        assert!(positions.windows(2).all(|w| w[0] < w[1]), "{employee}");
+    }
+
+    const DEFAULTS: &str = r#"
+enum Currency {
+  USD
+  EUR
+}
+
+class Address {
+  city string
+  country string @default("US")
+}
+
+class Invoice {
+  currency Currency @default(USD)
+  quantity int @default(1)
+  note string? @default(null)
+  tags string[] @default(["billing"])
+  totals map<string, int> @default({ net 0 })
+  address Address @default({ city "Austin" })
+}
+    "#;
+
+    #[test]
+    fn defaults_become_prop_defaults() {
+        let code = generated(DEFAULTS, "types.rb");
+        let invoice = struct_body(&code, "class Invoice < T::Struct\n");
+        for prop in [
+            "const :currency, T.any(Baml::Types::Currency, String), default: Baml::Types::Currency::USD",
+            "const :quantity, Integer, default: 1",
+            "const :note, T.nilable(String), default: nil",
+            r#"const :tags, T::Array[String], default: ["billing"]"#,
+            r#"const :totals, T::Hash[String, Integer], default: { "net" => 0 }"#,
+            r#"const :address, Baml::Types::Address, factory: -> { Baml::Types::Address.new({ city: "Austin" }) }"#,
+        ] {
+            assert!(invoice.contains(prop), "{prop} in\n{invoice}");
+        }
+        // `initialize` passes every prop on, so the defaults must be filled
+        // in there too.
+        let initialize = &code[code.find("class Invoice < T::Struct\n").unwrap()..];
+        for arg in [
+            "currency: props.fetch(:currency) { Baml::Types::Currency::USD },",
+            "quantity: props.fetch(:quantity) { 1 },",
+            r#"address: props.fetch(:address) { Baml::Types::Address.new({ city: "Austin" }) },"#,
+        ] {
+            assert!(initialize.contains(arg), "{arg} in\n{initialize}");
+        }
+        let address = &code[code.find("class Address < T::Struct\n").unwrap()..];
+        assert!(address.contains("city: props[:city],"), "{address}");
+    }
+
+    const GENERICS: &str = r##"
+class Paginated<T> {
+  items T[]
+  next_page int?
+}
+
+class Result<T, E> {
+  value T?
+  error E?
+}
+
+class Resume {
+  name string
+}
+
+class Feed {
+  resumes Paginated<Resume>
+  nested Paginated<Paginated<int>>
+  outcome Result<string, Resume>
+}
+
+function ExtractResumes(text: string) -> Paginated<Resume> {
+  client "openai/gpt-4o"
+  prompt #"{{ text }}"#
+}
+    "##;
+
+    #[test]
+    fn generic_classes_are_specialized() {
+        let code = generated(GENERICS, "types.rb");
+        // Sorbet structs have no type parameters, so there is a struct per
+        // specialization and none for the generic class.
+        assert!(!code.contains("class Paginated < T::Struct"), "{code}");
+        let page = struct_body(&code, "class Paginated__Resume < T::Struct\n");
+        assert!(
+            page.contains("const :items, T::Array[Baml::Types::Resume]"),
+            "{page}"
+        );
+        let result = struct_body(&code, "class Result__String__Resume < T::Struct\n");
+        assert!(
+            result.contains("const :value, T.nilable(String)"),
+            "{result}"
+        );
+        assert!(
+            result.contains("const :error, T.nilable(Baml::Types::Resume)"),
+            "{result}"
+        );
+    }
+}
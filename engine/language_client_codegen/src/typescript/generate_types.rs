@@ -3,12 +3,15 @@ use std::borrow::Cow;
 use anyhow::Result;
 use itertools::Itertools;
 
+use baml_types::Validator;
 use internal_baml_core::ir::{
     repr::{Docstring, IntermediateRepr, Walker},
     ClassWalker, EnumWalker, FieldType, IRHelper,
 };
 
-use crate::{type_check_attributes, GeneratorArgs, TypeCheckAttributes};
+use crate::{
+    field_default, type_check_attributes, type_validators, GeneratorArgs, TypeCheckAttributes,
+};
 
 use super::ToTypeReferenceInClientDefinition;
 
@@ -25,6 +28,14 @@ pub(crate) struct TypescriptTypes<'ir> {
     enums: Vec<TypescriptEnum<'ir>>,
     classes: Vec<TypescriptClass<'ir>>,
     structural_recursive_alias_cycles: Vec<TypescriptTypeAlias<'ir>>,
+    specializations: Vec<TypescriptTypeAlias<'ir>>,
+}
+
+impl TypescriptTypes<'_> {
+    /// Whether any class needs the helpers of its `validate` function.
+    fn has_checks(&self) -> bool {
+        self.classes.iter().any(|c| !c.checks.is_empty())
+    }
 }
 
 #[derive(askama::Template)]
@@ -42,11 +53,36 @@ struct TypescriptEnum<'ir> {
 
 pub struct TypescriptClass<'ir> {
     pub name: Cow<'ir, str>,
+    /// Type parameters of a generic class.
+    pub type_params: Vec<&'ir str>,
+    /// The classes this class extends.
+    pub bases: Vec<&'ir str>,
     pub fields: Vec<(Cow<'ir, str>, bool, String, Option<String>)>,
+    /// How many of the fields are inherited. They come first.
+    pub inherited_fields: usize,
+    /// The validators of the fields, as the field name, the description of
+    /// the validator as a string literal and a call of a helper of
+    /// types.ts.j2 that builds the check.
+    pub checks: Vec<(Cow<'ir, str>, String, String)>,
     pub dynamic: bool,
     pub docstring: Option<String>,
 }
 
+impl TypescriptClass<'_> {
+    /// The fields the class defines itself.
+    fn own_fields(&self) -> &[(Cow<'_, str>, bool, String, Option<String>)] {
+        &self.fields[self.inherited_fields..]
+    }
+
+    /// The class as a type, with `any` for its type parameters.
+    fn erased(&self) -> String {
+        match self.type_params.len() {
+            0 => self.name.to_string(),
+            n => format!("{}<{}>", self.name, vec!["any"; n].join(", ")),
+        }
+    }
+}
+
 struct TypescriptTypeAlias<'ir> {
     name: Cow<'ir, str>,
     target: String,
@@ -54,6 +90,11 @@ struct TypescriptTypeAlias<'ir> {
 
 pub struct PartialTypescriptClass<'ir> {
     name: Cow<'ir, str>,
+    /// Type parameters of a generic class.
+    type_params: Vec<&'ir str>,
+    /// The classes this class extends.
+    bases: Vec<&'ir str>,
+    /// The fields the class defines itself.
     fields: Vec<(Cow<'ir, str>, bool, String, Option<String>)>,
     dynamic: bool,
     docstring: Option<String>,
@@ -70,14 +111,21 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir GeneratorArgs)> for TypescriptTyp
                 .walk_enums()
                 .map(|e| Into::<TypescriptEnum>::into(&e))
                 .collect::<Vec<_>>(),
-            classes: ir
-                .walk_classes()
+            classes: interface_classes(ir)
                 .map(|e| Into::<TypescriptClass>::into(&e))
                 .collect::<Vec<_>>(),
             structural_recursive_alias_cycles: ir
                 .walk_alias_cycles()
                 .map(TypescriptTypeAlias::from)
                 .collect::<Vec<_>>(),
+            specializations: ir
+                .walk_classes()
+                .filter(|c| ir.find_specialization(c.name()).is_some())
+                .map(|c| TypescriptTypeAlias {
+                    name: Cow::Borrowed(c.name()),
+                    target: FieldType::Class(c.name().to_string()).to_type_ref(ir, false),
+                })
+                .collect::<Vec<_>>(),
         })
     }
 }
@@ -89,8 +137,7 @@ impl<'ir> TryFrom<(&'ir IntermediateRepr, &'ir GeneratorArgs)> for TypescriptStr
         (ir, _): (&'ir IntermediateRepr, &'ir GeneratorArgs),
     ) -> Result<TypescriptStreamTypes<'ir>> {
         Ok(TypescriptStreamTypes {
-            partial_classes: ir
-                .walk_classes()
+            partial_classes: interface_classes(ir)
                 .map(|e| Into::<PartialTypescriptClass>::into(e))
                 .collect::<Vec<_>>(),
         })
@@ -145,6 +192,32 @@ impl<'ir> From<&ClassWalker<'ir>> for TypescriptClass<'ir> {
     fn from(c: &ClassWalker<'ir>) -> TypescriptClass<'ir> {
         TypescriptClass {
             name: Cow::Borrowed(c.name()),
+            type_params: c.elem().type_params.iter().map(String::as_str).collect(),
+            bases: c.item.elem.bases.iter().map(String::as_str).collect(),
+            inherited_fields: c
+                .item
+                .elem
+                .static_fields
+                .iter()
+                .filter(|f| f.elem.inherited_from.is_some())
+                .count(),
+            checks: c
+                .item
+                .elem
+                .static_fields
+                .iter()
+                .flat_map(|f| {
+                    type_validators(&f.elem.r#type.elem)
+                        .into_iter()
+                        .map(|validator| {
+                            (
+                                Cow::Borrowed(f.elem.name.as_str()),
+                                serde_json::Value::from(validator.describe()).to_string(),
+                                validator_check(validator),
+                            )
+                        })
+                })
+                .collect(),
             dynamic: c.item.attributes.get("dynamic_type").is_some(),
             fields: c
                 .item
@@ -152,11 +225,29 @@ impl<'ir> From<&ClassWalker<'ir>> for TypescriptClass<'ir> {
                 .static_fields
                 .iter()
                 .map(|f| {
+                    // Interfaces can't hold values or validators, so defaults
+                    // and validators are documented with JSDoc tags.
+                    let tags = field_default(f)
+                        .map(|value| format!("@default {value}"))
+                        .into_iter()
+                        .chain(jsdoc_validator_tags(&f.elem.r#type.elem))
+                        .collect::<Vec<_>>();
+                    let docstring = (!tags.is_empty()).then(|| {
+                        Docstring(
+                            f.elem
+                                .docstring
+                                .iter()
+                                .map(|d| d.0.clone())
+                                .chain(tags)
+                                .join("\n"),
+                        )
+                    });
+                    let docstring = docstring.as_ref().or(f.elem.docstring.as_ref());
                     (
                         Cow::Borrowed(f.elem.name.as_str()),
                         f.elem.r#type.elem.is_optional(),
                         f.elem.r#type.elem.to_type_ref(c.db, false),
-                        f.elem.docstring.as_ref().map(|d| render_docstring(d, true)),
+                        docstring.map(|d| render_docstring(d, true)),
                     )
                 })
                 .collect(),
@@ -189,12 +280,15 @@ impl<'ir> From<ClassWalker<'ir>> for PartialTypescriptClass<'ir> {
     fn from(c: ClassWalker<'ir>) -> PartialTypescriptClass<'ir> {
         PartialTypescriptClass {
             name: Cow::Borrowed(c.name()),
+            type_params: c.elem().type_params.iter().map(String::as_str).collect(),
+            bases: c.item.elem.bases.iter().map(String::as_str).collect(),
             dynamic: c.item.attributes.get("dynamic_type").is_some(),
             fields: c
                 .item
                 .elem
                 .static_fields
                 .iter()
+                .filter(|f| f.elem.inherited_from.is_none())
                 .map(|f| {
                     let ir = c.db;
                     let needed: bool = f.attributes.get("stream.not_null").is_some();
@@ -224,6 +318,14 @@ impl<'ir> From<ClassWalker<'ir>> for PartialTypescriptClass<'ir> {
     }
 }
 
+/// The classes that get an interface: generic classes are generic interfaces,
+/// and their specializations are only referenced as `Paginated<Resume>`.
+fn interface_classes(ir: &IntermediateRepr) -> impl Iterator<Item = ClassWalker<'_>> {
+    ir.walk_classes()
+        .filter(|c| ir.find_specialization(c.name()).is_none())
+        .chain(ir.walk_generic_classes())
+}
+
 pub fn type_name_for_checks(checks: &TypeCheckAttributes) -> String {
     checks
         .0
@@ -245,3 +347,49 @@ pub fn render_docstring(d: &Docstring, indented: bool) -> String {
         format!("/**\n * {lines}\n */")
     }
 }
+
+/// The validators of a field as the JSDoc tags understood by JSON schema
+/// generators, e.g. `@minimum 0`.
+fn jsdoc_validator_tags(field_type: &FieldType) -> Vec<String> {
+    fn is_list(t: &FieldType) -> bool {
+        match t {
+            FieldType::List(_) => true,
+            FieldType::Optional(inner) | FieldType::WithMetadata { base: inner, .. } => {
+                is_list(inner)
+            }
+            _ => false,
+        }
+    }
+    type_validators(field_type)
+        .into_iter()
+        .flat_map(|validator| match validator {
+            // `*/` would close the doc comment.
+            Validator::Regex(pattern) => {
+                vec![format!("@pattern {}", pattern.replace("*/", "*\\/"))]
+            }
+            Validator::Format(format) => vec![format!("@format {format}")],
+            Validator::Min(min) => vec![format!("@minimum {min}")],
+            Validator::Max(max) => vec![format!("@maximum {max}")],
+            Validator::Length { min, max } if is_list(field_type) => {
+                vec![format!("@minItems {min}"), format!("@maxItems {max}")]
+            }
+            Validator::Length { min, max } => {
+                vec![format!("@minLength {min}"), format!("@maxLength {max}")]
+            }
+        })
+        .collect()
+}
+
+/// A call of the helper of types.ts.j2 that checks the validator at runtime.
+/// Patterns are compiled by `RegExp`, which understands the common subset of
+/// the Rust regex syntax.
+fn validator_check(validator: &Validator) -> String {
+    let quote = |s: &str| serde_json::Value::from(s).to_string();
+    match validator {
+        Validator::Regex(pattern) => format!("matches({})", quote(pattern)),
+        Validator::Format(format) => format!("matches({})", quote(format.pattern())),
+        Validator::Min(min) => format!("min({min})"),
+        Validator::Max(max) => format!("max({max})"),
+        Validator::Length { min, max } => format!("length({min}, {max})"),
+    }
+}
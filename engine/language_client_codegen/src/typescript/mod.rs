@@ -223,10 +223,26 @@ impl ToTypeReferenceInClientDefinition for FieldType {
         let module_prefix = if use_module_prefix { "types." } else { "partial_types." };
         let (base_rep, optional) = match base_type {
             FieldType::Class(name) => {
+                let class_ref = if ir.is_type_param(name) {
+                    name.to_owned()
+                } else if let Some(specialization) = ir.find_specialization(name) {
+                    let args = specialization
+                        .args
+                        .iter()
+                        .map(|arg| match is_partial_type {
+                            true => arg.to_partial_type_ref(ir, true).0,
+                            false => arg.to_type_ref(ir, true),
+                        })
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    format!("{module_prefix}{}<{args}>", specialization.generic)
+                } else {
+                    format!("{module_prefix}{name}")
+                };
                 if needed {
-                    (format!("{module_prefix}{name}"), false)
+                    (class_ref, false)
                 } else {
-                    (format!("({module_prefix}{name} | null)"), true)
+                    (format!("({class_ref} | null)"), true)
                 }
             }
             FieldType::RecursiveTypeAlias(name) => (name.to_owned(), !needed),
@@ -346,7 +362,22 @@ impl ToTypeReferenceInClientDefinition for FieldType {
                 }
             }
             FieldType::RecursiveTypeAlias(name) => name.to_owned(),
-            FieldType::Class(name) => format!("{module_prefix}{name}"),
+            FieldType::Class(name) if ir.is_type_param(name) => name.to_owned(),
+            // Specialized generic classes are referenced as the generic class
+            // applied to the type arguments.
+            FieldType::Class(name) => match ir.find_specialization(name) {
+                Some(specialization) => format!(
+                    "{module_prefix}{}<{}>",
+                    specialization.generic,
+                    specialization
+                        .args
+                        .iter()
+                        .map(|arg| arg.to_type_ref(ir, use_module_prefix))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                None => format!("{module_prefix}{name}"),
+            },
             FieldType::List(inner) => match inner.as_ref() {
                 FieldType::Union(_) | FieldType::Optional(_) => {
                     format!("({})[]", inner.to_type_ref(ir, use_module_prefix))
@@ -397,3 +428,199 @@ impl ToTypeReferenceInClientDefinition for FieldType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use internal_baml_core::ir::repr::make_test_ir;
+
+    use super::*;
+
+    /// Generates `source` and returns the content of `file`.
+    fn generated(source: &str, file: &str) -> String {
+        let ir = make_test_ir(source).unwrap();
+        let mut files = generate(&ir, &crate::test_generator_args()).unwrap();
+        files.swap_remove(&PathBuf::from(file)).unwrap()
+    }
+
+    /// The body of the interface declared by `header`.
+    fn interface_body<'a>(code: &'a str, header: &str) -> &'a str {
+        let start = code
+            .find(header)
+            .unwrap_or_else(|| panic!("{header} in\n{code}"));
+        let body = &code[start + header.len()..];
+        &body[..body.find("\n}").unwrap()]
+    }
+
+    const INHERITANCE: &str = r#"
+class Employee extends Person, Named {
+  company string
+}
+
+class Person extends Named, Aged {
+  email string
+}
+
+class Named {
+  name string
+}
+
+class Aged {
+  age int
+}
+    "#;
+
+    #[test]
+    fn interfaces_extend_their_bases() {
+        for file in ["types.ts", "partial_types.ts"] {
+            let code = generated(INHERITANCE, file);
+            let employee =
+                interface_body(&code, "export interface Employee extends Person, Named {");
+            assert!(employee.contains("company"), "{employee}");
+            for inherited in ["name", "age", "email"] {
+                assert!(!employee.contains(inherited), "{employee}");
+            }
+            let person = interface_body(&code, "export interface Person extends Named, Aged {");
+            assert!(person.contains("email"), "{person}");
+            assert!(!person.contains("name"), "{person}");
+            interface_body(&code, "export interface Named {");
+        }
+    }
+
+    const VALIDATED: &str = r#"
+class Signup {
+  email string @format("email")
+  handle string @regex("^[a-z]+$") @length(3, 12)
+  age int? @min(13) @max(120)
+  tags string[] @length(1, 5)
+}
+    "#;
+
+    #[test]
+    fn validators_are_documented_and_checked_at_runtime() {
+        let code = generated(VALIDATED, "types.ts");
+        let signup = interface_body(&code, "export interface Signup {");
+        for tag in [
+            "@format email",
+            "@pattern ^[a-z]+$",
+            "@minLength 3",
+            "@maximum 120",
+            "@minItems 1",
+        ] {
+            assert!(signup.contains(tag), "{tag} in\n{signup}");
+        }
+        for check in [
+            r#"["email", "format: email", matches("#,
+            r#"["handle", "pattern: ^[a-z]+$", matches("^[a-z]+$")],"#,
+            r#"["handle", "length: 3 to 12", length(3, 12)],"#,
+            r#"["age", "min: 13", min(13)],"#,
+            r#"["age", "max: 120", max(120)],"#,
+            r#"["tags", "length: 1 to 5", length(1, 5)],"#,
+        ] {
+            assert!(code.contains(check), "{check} in\n{code}");
+        }
+        assert!(
+            code.contains("export function validateSignup(value: Signup): FailedValidator[]"),
+            "{code}"
+        );
+    }
+
+    #[test]
+    fn runtime_checks_are_only_emitted_when_used() {
+        let code = generated("class Plain {\n  name string\n}", "types.ts");
+        assert!(!code.contains("FailedValidator"), "{code}");
+        assert!(!code.contains("validatePlain"), "{code}");
+    }
+
+    const DEFAULTS: &str = r#"
+enum Currency {
+  USD
+  EUR
+}
+
+class Address {
+  city string
+  country string @default("US")
+}
+
+class Invoice {
+  currency Currency @default(USD)
+  quantity int @default(1)
+  note string? @default(null)
+  tags string[] @default(["billing"])
+  totals map<string, int> @default({ net 0 })
+  address Address @default({ city "Austin" })
+}
+    "#;
+
+    #[test]
+    fn defaults_are_documented() {
+        let code = generated(DEFAULTS, "types.ts");
+        let invoice = interface_body(&code, "export interface Invoice {");
+        for (tag, field) in [
+            (r#"@default "USD""#, "currency: Currency"),
+            ("@default 1", "quantity: number"),
+            ("@default null", "note?: string | null"),
+            (r#"@default ["billing"]"#, "tags: string[]"),
+            (r#"@default {"city":"Austin"}"#, "address: Address"),
+        ] {
+            let doc = format!("   * {tag}\n   */\n  {field}");
+            assert!(invoice.contains(&doc), "{doc} in\n{invoice}");
+        }
+    }
+
+    const GENERICS: &str = r##"
+class Paginated<T> {
+  items T[]
+  next_page int?
+}
+
+class Result<T, E> {
+  value T?
+  error E?
+}
+
+class Resume {
+  name string
+}
+
+class Feed {
+  resumes Paginated<Resume>
+  nested Paginated<Paginated<int>>
+  outcome Result<string, Resume>
+}
+
+function ExtractResumes(text: string) -> Paginated<Resume> {
+  client "openai/gpt-4o"
+  prompt #"{{ text }}"#
+}
+    "##;
+
+    #[test]
+    fn generic_classes_are_generic_interfaces() {
+        let code = generated(GENERICS, "types.ts");
+        let paginated = interface_body(&code, "export interface Paginated<T> {");
+        assert!(paginated.contains("items: T[]"), "{paginated}");
+        interface_body(&code, "export interface Result<T, E> {");
+        let feed = interface_body(&code, "export interface Feed {");
+        for field in [
+            "resumes: Paginated<Resume>",
+            "nested: Paginated<Paginated<number>>",
+            "outcome: Result<string, Resume>",
+        ] {
+            assert!(feed.contains(field), "{field} in\n{feed}");
+        }
+        assert!(
+            code.contains("export type Paginated__Resume = Paginated<Resume>"),
+            "{code}"
+        );
+        assert!(!code.contains("export interface Paginated__"), "{code}");
+
+        let partial = generated(GENERICS, "partial_types.ts");
+        interface_body(&partial, "export interface Paginated<T> {");
+        let feed = interface_body(&partial, "export interface Feed {");
+        assert!(
+            feed.contains("resumes?: (partial_types.Paginated<partial_types.Resume> | null)"),
+            "{feed}"
+        );
+    }
+}
@@ -102,7 +102,9 @@ pub fn check_version(
             )
         } else {
             let update_instruction = match generator_language {
-                GeneratorOutputType::OpenApi => format!("use 'npx @boundaryml/baml@{gen_version}'"),
+                GeneratorOutputType::OpenApi | GeneratorOutputType::Go => {
+                    format!("use 'npx @boundaryml/baml@{gen_version}'")
+                }
                 GeneratorOutputType::PythonPydantic => {
                     format!("pip install --upgrade baml-py=={}", gen_version)
                 }
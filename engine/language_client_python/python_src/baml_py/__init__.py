@@ -11,9 +11,11 @@ from .baml_py import (
     BamlRuntime,
     FunctionResult,
     FunctionResultStream,
+    ParsedValue,
     BamlImagePy as Image,
     BamlAudioPy as Audio,
     invoke_runtime_cli,
+    register_tokenizer,
     ClientRegistry,
 )
 from .stream import BamlStream, BamlSyncStream
@@ -27,7 +29,9 @@ __all__ = [
     "BamlCtxManager",
     "FunctionResult",
     "FunctionResultStream",
+    "ParsedValue",
     "Image",
     "Audio",
     "invoke_runtime_cli",
+    "register_tokenizer",
 ]
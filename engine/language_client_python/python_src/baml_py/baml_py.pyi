@@ -1,4 +1,4 @@
-from typing import Any, Callable, Dict, Optional, Tuple
+from typing import Any, Callable, Dict, List, Optional, Tuple
 
 class FunctionResult:
     """The result of a BAML function call.
@@ -18,6 +18,13 @@ class FunctionResult:
     # Returns True if the function call was successful, False otherwise
     def is_ok(self) -> bool: ...
     def cast_to(self, enum_module: Any, class_module: Any) -> Any: ...
+    # How much the response had to be repaired to parse, as a dict with the
+    # keys score, fixes, coercions and unions. None if it didn't parse.
+    def parse_report(self) -> Optional[Dict[str, Any]]: ...
+
+    # What the calls made for this result cost in US dollars, retries and
+    # fallbacks included. None if none of them was priced.
+    def cost(self) -> Optional[float]: ...
 
     # This is a debug function that returns the internal representation of the response
     # This is not to be relied upon and is subject to change
@@ -27,6 +34,18 @@ class FunctionResult:
     #   print(val)
     def unstable_internal_repr(self) -> str: ...
 
+class ParsedValue:
+    """Text parsed into a BAML type with `BamlRuntime.parse`, without calling
+    a function."""
+
+    def __str__(self) -> str: ...
+    def cast_to(
+        self, enum_module: Any, class_module: Any, partial_class_module: Any
+    ) -> Any: ...
+    # How the text had to be fixed up to fit the type, as (path, description)
+    # pairs. The path of the top-level value is empty.
+    def flags(self) -> List[Tuple[str, str]]: ...
+
 class FunctionResultStream:
     """The result of a BAML function stream.
 
@@ -117,6 +136,17 @@ class BamlRuntime:
         cr: Optional[ClientRegistry],
     ) -> SyncFunctionResultStream: ...
     def create_context_manager(self) -> RuntimeContextManager: ...
+    def find_type(
+        self, name: str, ctx: RuntimeContextManager, tb: Optional[TypeBuilder]
+    ) -> FieldType: ...
+    def parse(
+        self,
+        text: str,
+        target: FieldType,
+        allow_partials: bool,
+        ctx: RuntimeContextManager,
+        tb: Optional[TypeBuilder],
+    ) -> ParsedValue: ...
     def flush(self) -> None: ...
     def drain_stats(self) -> TraceStats: ...
     def set_log_event_callback(
@@ -137,7 +167,11 @@ class BamlLogEvent:
     prompt: Optional[str]
     raw_output: Optional[str]
     parsed_output: Optional[str]
+    # JSON of the parse report, see FunctionResult.parse_report
+    parse_report: Optional[str]
     start_time: str
+    # US dollars, if the clients have pricing
+    cost: Optional[float]
 
     def __init__(
         self,
@@ -145,7 +179,9 @@ class BamlLogEvent:
         prompt: Optional[str],
         raw_output: Optional[str],
         parsed_output: Optional[str],
+        parse_report: Optional[str],
         start_time: str,
+        cost: Optional[float],
     ) -> None: ...
 
 class TraceStats:
@@ -161,6 +197,12 @@ class TraceStats:
     def sent(self) -> int: ...
     @property
     def done(self) -> int: ...
+    # What the LLM calls of finished functions cost in US dollars, in total
+    # and by function name.
+    @property
+    def cost(self) -> float: ...
+    @property
+    def cost_by_function(self) -> Dict[str, float]: ...
 
 class BamlSpan:
     @staticmethod
@@ -199,6 +241,14 @@ class ClientRegistry:
         retry_policy: Optional[str] = None,
     ) -> None: ...
     def set_primary(self, name: str) -> None: ...
+    # The price of a model, in US dollars per million tokens, for clients
+    # that don't set `pricing` themselves.
+    def set_model_pricing(
+        self,
+        model: str,
+        input_per_million_tokens: float,
+        output_per_million_tokens: float,
+    ) -> None: ...
 
 class FieldType:
     def list(self) -> FieldType: ...
@@ -225,6 +275,11 @@ class ClassPropertyBuilder:
 
 def invoke_runtime_cli() -> None: ...
 
+# Counts the prompts of clients of `provider` whose model starts with
+# `model_prefix` with the tiktoken file at `path`, for `max_context_tokens`.
+# An empty prefix matches every model.
+def register_tokenizer(provider: str, model_prefix: str, path: str) -> None: ...
+
 class BamlError(Exception):
     """Base class for all BAML-related errors."""
 
@@ -121,6 +121,7 @@ impl BamlError {
                     | baml_runtime::internal::llm_client::ErrorCode::RateLimited
                     | baml_runtime::internal::llm_client::ErrorCode::ServerError
                     | baml_runtime::internal::llm_client::ErrorCode::ServiceUnavailable
+                    | baml_runtime::internal::llm_client::ErrorCode::Timeout
                     | baml_runtime::internal::llm_client::ErrorCode::UnsupportedResponse(_) => {
                         raise_baml_client_http_error(failed.client.clone(), failed.message.clone(), failed.code.to_u16())
                     }
@@ -67,6 +67,18 @@ fn invoke_runtime_cli(py: Python) -> PyResult<()> {
     .map_err(errors::BamlError::from_anyhow)
 }
 
+/// Counts the prompts of clients of `provider` whose model starts with
+/// `model_prefix` with the byte pair encoding of the tiktoken file at `path`.
+#[pyfunction]
+fn register_tokenizer(
+    provider: &str,
+    model_prefix: &str,
+    path: std::path::PathBuf,
+) -> PyResult<()> {
+    baml_runtime::register_tiktoken_file(provider, model_prefix, &path)
+        .map_err(errors::BamlError::from_anyhow)
+}
+
 pub(crate) const MODULE_NAME: &str = "baml_py.baml_py";
 
 #[pymodule]
@@ -104,6 +116,7 @@ fn baml_py(m: Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<runtime::BamlRuntime>()?;
 
     m.add_class::<types::FunctionResult>()?;
+    m.add_class::<types::ParsedValue>()?;
     m.add_class::<types::FunctionResultStream>()?;
     m.add_class::<types::SyncFunctionResultStream>()?;
     m.add_class::<types::BamlImagePy>()?;
@@ -122,6 +135,7 @@ fn baml_py(m: Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<runtime::LogEventMetadata>()?;
 
     m.add_wrapped(wrap_pyfunction!(invoke_runtime_cli))?;
+    m.add_wrapped(wrap_pyfunction!(register_tokenizer))?;
 
     errors::errors(&m)?;
 
@@ -4,8 +4,9 @@ use crate::types::function_results::FunctionResult;
 use crate::types::trace_stats::TraceStats;
 
 use crate::types::function_result_stream::{FunctionResultStream, SyncFunctionResultStream};
+use crate::types::parsed_value::ParsedValue;
 use crate::types::runtime_ctx_manager::RuntimeContextManager;
-use crate::types::type_builder::TypeBuilder;
+use crate::types::type_builder::{FieldType, TypeBuilder};
 use crate::types::ClientRegistry;
 use baml_runtime::runtime_interface::ExperimentalTracingInterface;
 use baml_runtime::BamlRuntime as CoreBamlRuntime;
@@ -25,7 +26,11 @@ pub struct BamlLogEvent {
     pub raw_output: Option<String>,
     // json structure or a string
     pub parsed_output: Option<String>,
+    // json structure of the parse report
+    pub parse_report: Option<String>,
     pub start_time: String,
+    // US dollars, if the clients have pricing
+    pub cost: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
@@ -40,8 +45,8 @@ pub struct LogEventMetadata {
 impl BamlLogEvent {
     fn __repr__(&self) -> String {
         format!(
-            "BamlLogEvent {{\n    metadata: {:?},\n    prompt: {:?},\n    raw_output: {:?},\n    parsed_output: {:?},\n    start_time: {:?}\n}}",
-            self.metadata, self.prompt, self.raw_output, self.parsed_output, self.start_time
+            "BamlLogEvent {{\n    metadata: {:?},\n    prompt: {:?},\n    raw_output: {:?},\n    parsed_output: {:?},\n    parse_report: {:?},\n    start_time: {:?},\n    cost: {:?}\n}}",
+            self.metadata, self.prompt, self.raw_output, self.parsed_output, self.parse_report, self.start_time, self.cost
         )
     }
 
@@ -58,16 +63,23 @@ impl BamlLogEvent {
             .parsed_output
             .as_ref()
             .map_or("None".to_string(), |p| format!("\"{p}\""));
+        let parse_report = self
+            .parse_report
+            .as_ref()
+            .map_or("None".to_string(), |p| format!("\"{p}\""));
+        let cost = self.cost.map_or("None".to_string(), |c| c.to_string());
 
         format!(
-            "BamlLogEvent {{\n    metadata: {{\n        event_id: \"{}\",\n        parent_id: {},\n        root_event_id: \"{}\"\n    }},\n    prompt: {},\n    raw_output: {},\n    parsed_output: {},\n    start_time: \"{}\"\n}}",
+            "BamlLogEvent {{\n    metadata: {{\n        event_id: \"{}\",\n        parent_id: {},\n        root_event_id: \"{}\"\n    }},\n    prompt: {},\n    raw_output: {},\n    parsed_output: {},\n    parse_report: {},\n    start_time: \"{}\",\n    cost: {}\n}}",
             self.metadata.event_id,
             self.metadata.parent_id.as_ref().map_or("None".to_string(), |id| format!("\"{}\"", id)),
             self.metadata.root_event_id,
             prompt,
             raw_output,
             parsed_output,
-            self.start_time
+            parse_report,
+            self.start_time,
+            cost
         )
     }
 }
@@ -274,6 +286,41 @@ impl BamlRuntime {
         ))
     }
 
+    #[pyo3(signature = (name, ctx, tb))]
+    fn find_type(
+        &self,
+        name: &str,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+    ) -> PyResult<FieldType> {
+        self.inner
+            .find_type(name, &ctx.inner, tb.map(|tb| &tb.inner))
+            .map(FieldType::from)
+            .map_err(BamlError::from_anyhow)
+    }
+
+    #[pyo3(signature = (text, target, allow_partials, ctx, tb))]
+    fn parse(
+        &self,
+        text: &str,
+        target: &FieldType,
+        allow_partials: bool,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+    ) -> PyResult<ParsedValue> {
+        let target = target.inner.lock().unwrap().clone();
+        self.inner
+            .parse(
+                text,
+                &target,
+                allow_partials,
+                &ctx.inner,
+                tb.map(|tb| &tb.inner),
+            )
+            .map(|parsed| ParsedValue::new(parsed, allow_partials))
+            .map_err(BamlError::from_anyhow)
+    }
+
     #[pyo3()]
     fn flush(&self) -> PyResult<()> {
         self.inner.flush().map_err(BamlError::from_anyhow)
@@ -305,7 +352,9 @@ impl BamlRuntime {
                                 prompt: log_event.prompt.clone(),
                                 raw_output: log_event.raw_output.clone(),
                                 parsed_output: log_event.parsed_output.clone(),
+                                parse_report: log_event.parse_report.clone(),
                                 start_time: log_event.start_time.clone(),
+                                cost: log_event.cost,
                             },),
                         ) {
                             Ok(_) => Ok(()),
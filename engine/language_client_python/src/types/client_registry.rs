@@ -6,7 +6,7 @@ use pyo3::{IntoPyObjectExt, PyObject, Python};
 
 use crate::errors::BamlInvalidArgumentError;
 use crate::parse_py_type::parse_py_type;
-use client_registry::ClientProvider;
+use client_registry::{ClientProvider, Pricing};
 
 crate::lang_wrapper!(ClientRegistry, client_registry::ClientRegistry);
 
@@ -65,4 +65,19 @@ impl ClientRegistry {
     pub fn set_primary(&mut self, primary: String) {
         self.inner.set_primary(primary);
     }
+
+    pub fn set_model_pricing(
+        &mut self,
+        model: String,
+        input_per_million_tokens: f64,
+        output_per_million_tokens: f64,
+    ) {
+        self.inner.set_model_pricing(
+            model,
+            Pricing {
+                input_per_million_tokens,
+                output_per_million_tokens,
+            },
+        );
+    }
 }
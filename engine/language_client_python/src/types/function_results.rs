@@ -26,6 +26,21 @@ impl FunctionResult {
         serde_json::json!(self.inner.llm_response()).to_string()
     }
 
+    /// How much the response had to be repaired to parse, as a dict, or
+    /// None if it didn't parse.
+    fn parse_report(&self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        self.inner
+            .parse_report()
+            .map(|report| Ok(pythonize::pythonize(py, &report)?.into()))
+            .transpose()
+    }
+
+    /// What the calls made for this result cost in US dollars, or None if
+    /// none of them was priced.
+    fn cost(&self) -> Option<f64> {
+        self.inner.cost()
+    }
+
     // Cast the parsed value to a specific type
     // the module is the module that the type is defined in
     fn cast_to(
@@ -75,7 +90,7 @@ fn pythonize_checks<'a>(
     Ok(dict)
 }
 
-fn pythonize_strict(
+pub(crate) fn pythonize_strict(
     py: Python<'_>,
     parsed: ResponseBamlValue,
     enum_module: &Bound<'_, PyModule>,
@@ -7,6 +7,7 @@ pub(crate) mod function_result_stream;
 pub(crate) mod function_results;
 pub(crate) mod image;
 pub(super) mod media_repr;
+pub(crate) mod parsed_value;
 pub(crate) mod runtime_ctx_manager;
 pub(crate) mod span;
 pub(crate) mod trace_stats;
@@ -16,6 +17,7 @@ pub use audio::BamlAudioPy;
 pub use function_result_stream::{FunctionResultStream, SyncFunctionResultStream};
 pub use function_results::FunctionResult;
 pub use image::BamlImagePy;
+pub use parsed_value::ParsedValue;
 
 pub use runtime_ctx_manager::RuntimeContextManager;
 pub use span::BamlSpan;
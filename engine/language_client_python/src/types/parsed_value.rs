@@ -0,0 +1,51 @@
+use pyo3::prelude::{pymethods, PyResult};
+use pyo3::types::PyModule;
+use pyo3::{Bound, PyObject, Python};
+
+use super::function_results::pythonize_strict;
+
+crate::lang_wrapper!(ParsedValue, jsonish::ResponseBamlValue, no_from, allow_partials: bool);
+
+impl ParsedValue {
+    pub fn new(inner: jsonish::ResponseBamlValue, allow_partials: bool) -> Self {
+        Self {
+            inner,
+            allow_partials,
+        }
+    }
+}
+
+#[pymethods]
+impl ParsedValue {
+    fn __str__(&self) -> String {
+        serde_json::to_string_pretty(&self.inner.serialize_final()).unwrap_or_default()
+    }
+
+    // Cast the parsed value to a specific type, like FunctionResult.cast_to
+    fn cast_to(
+        &self,
+        py: Python<'_>,
+        enum_module: Bound<'_, PyModule>,
+        cls_module: Bound<'_, PyModule>,
+        partial_cls_module: Bound<'_, PyModule>,
+    ) -> PyResult<PyObject> {
+        pythonize_strict(
+            py,
+            self.inner.clone(),
+            &enum_module,
+            &cls_module,
+            &partial_cls_module,
+            self.allow_partials,
+        )
+    }
+
+    /// How the text had to be fixed up to fit the type, as (path, description)
+    /// pairs. The path of the top-level value is empty.
+    fn flags(&self) -> Vec<(String, String)> {
+        self.inner
+            .flags()
+            .into_iter()
+            .map(|(path, flag)| (path, flag.to_string()))
+            .collect()
+    }
+}
@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use pyo3::pymethods;
 
 crate::lang_wrapper!(TraceStats, baml_runtime::InnerTraceStats);
@@ -34,15 +36,26 @@ impl TraceStats {
         self.inner.done
     }
 
+    #[getter]
+    pub fn cost(&self) -> f64 {
+        self.inner.cost
+    }
+
+    #[getter]
+    pub fn cost_by_function(&self) -> HashMap<String, f64> {
+        self.inner.cost_by_function.clone()
+    }
+
     pub fn __repr__(&self) -> String {
         format!(
-            "TraceStats(failed={}, started={}, finalized={}, submitted={}, sent={}, done={})",
+            "TraceStats(failed={}, started={}, finalized={}, submitted={}, sent={}, done={}, cost={})",
             self.failed(),
             self.started(),
             self.finalized(),
             self.submitted(),
             self.sent(),
-            self.done()
+            self.done(),
+            self.cost()
         )
     }
 }
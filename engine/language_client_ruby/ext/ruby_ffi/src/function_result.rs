@@ -56,6 +56,18 @@ impl FunctionResult {
         res
     }
 
+    /// How much the response had to be repaired to parse, as a hash, or nil
+    /// if it didn't parse.
+    pub fn parse_report(&self) -> Result<Value> {
+        serde_magnus::serialize(&self.inner.parse_report())
+    }
+
+    /// What the calls made for this result cost in US dollars, or nil if
+    /// none of them was priced.
+    pub fn cost(&self) -> Option<f64> {
+        self.inner.cost()
+    }
+
     /// For usage in magnus::init
     ///
     /// TODO: use traits and macros to implement this
@@ -66,6 +78,8 @@ impl FunctionResult {
             "parsed_using_types",
             method!(FunctionResult::parsed_using_types, 3),
         )?;
+        cls.define_method("parse_report", method!(FunctionResult::parse_report, 0))?;
+        cls.define_method("cost", method!(FunctionResult::cost, 0))?;
 
         Ok(())
     }
@@ -8,10 +8,12 @@ use tracing_subscriber::EnvFilter;
 
 use function_result::FunctionResult;
 use function_result_stream::FunctionResultStream;
+use parsed_value::ParsedValue;
 use types::runtime_ctx_manager::RuntimeContextManager;
 
 mod function_result;
 mod function_result_stream;
+mod parsed_value;
 mod ruby_to_json;
 mod types;
 
@@ -183,6 +185,53 @@ impl BamlRuntimeFfi {
 
         retval
     }
+
+    pub fn find_type(
+        ruby: &Ruby,
+        rb_self: &BamlRuntimeFfi,
+        name: String,
+        ctx: &RuntimeContextManager,
+        type_registry: Option<&types::type_builder::TypeBuilder>,
+    ) -> Result<types::type_builder::FieldType> {
+        match rb_self
+            .inner
+            .find_type(&name, &ctx.inner, type_registry.map(|t| &t.inner))
+        {
+            Ok(field_type) => Ok(field_type.into()),
+            Err(e) => Err(Error::new(
+                ruby.exception_runtime_error(),
+                format!(
+                    "{:?}",
+                    e.context(format!("error while finding type {name}"))
+                ),
+            )),
+        }
+    }
+
+    pub fn parse(
+        ruby: &Ruby,
+        rb_self: &BamlRuntimeFfi,
+        text: String,
+        target: &types::type_builder::FieldType,
+        allow_partials: bool,
+        ctx: &RuntimeContextManager,
+        type_registry: Option<&types::type_builder::TypeBuilder>,
+    ) -> Result<ParsedValue> {
+        let target = target.inner.lock().unwrap().clone();
+        match rb_self.inner.parse(
+            &text,
+            &target,
+            allow_partials,
+            &ctx.inner,
+            type_registry.map(|t| &t.inner),
+        ) {
+            Ok(parsed) => Ok(ParsedValue::new(parsed, allow_partials)),
+            Err(e) => Err(Error::new(
+                ruby.exception_runtime_error(),
+                format!("{:?}", e.context("error while parsing")),
+            )),
+        }
+    }
 }
 
 fn invoke_runtime_cli(ruby: &Ruby, argv0: String, argv: Vec<String>) -> Result<()> {
@@ -203,6 +252,22 @@ fn invoke_runtime_cli(ruby: &Ruby, argv0: String, argv: Vec<String>) -> Result<(
     })
 }
 
+/// Counts the prompts of clients of `provider` whose model starts with
+/// `model_prefix` with the byte pair encoding of the tiktoken file at `path`.
+fn register_tokenizer(
+    ruby: &Ruby,
+    provider: String,
+    model_prefix: String,
+    path: PathBuf,
+) -> Result<()> {
+    baml_runtime::register_tiktoken_file(&provider, &model_prefix, &path).map_err(|e| {
+        Error::new(
+            ruby.exception_runtime_error(),
+            format!("{:?}", e.context("error while registering a tokenizer")),
+        )
+    })
+}
+
 #[magnus::init(name = "ruby_ffi")]
 fn init(ruby: &Ruby) -> Result<()> {
     let use_json = match std::env::var("BAML_LOG_JSON") {
@@ -238,6 +303,7 @@ fn init(ruby: &Ruby) -> Result<()> {
     let module = ruby.define_module("Baml")?.define_module("Ffi")?;
 
     module.define_module_function("invoke_runtime_cli", function!(invoke_runtime_cli, 2))?;
+    module.define_module_function("register_tokenizer", function!(register_tokenizer, 3))?;
 
     // must be kept in sync with the magnus::wrap annotation
     let runtime_class = module.define_class("BamlRuntime", class::object())?;
@@ -256,9 +322,12 @@ fn init(ruby: &Ruby) -> Result<()> {
         "stream_function",
         method!(BamlRuntimeFfi::stream_function, 5),
     )?;
+    runtime_class.define_method("find_type", method!(BamlRuntimeFfi::find_type, 3))?;
+    runtime_class.define_method("parse", method!(BamlRuntimeFfi::parse, 5))?;
 
     FunctionResult::define_in_ruby(&module)?;
     FunctionResultStream::define_in_ruby(&module)?;
+    ParsedValue::define_in_ruby(&module)?;
 
     RuntimeContextManager::define_in_ruby(&module)?;
 
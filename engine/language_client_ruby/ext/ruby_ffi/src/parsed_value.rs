@@ -0,0 +1,63 @@
+use magnus::{class, method, prelude::*, value::Value, RArray, RModule, Ruby};
+
+use crate::ruby_to_json;
+use crate::Result;
+
+#[magnus::wrap(class = "Baml::Ffi::ParsedValue", free_immediately, size)]
+pub struct ParsedValue {
+    inner: jsonish::ResponseBamlValue,
+    allow_partials: bool,
+}
+
+impl ParsedValue {
+    pub fn new(inner: jsonish::ResponseBamlValue, allow_partials: bool) -> Self {
+        Self {
+            inner,
+            allow_partials,
+        }
+    }
+
+    pub fn parsed_using_types(
+        ruby: &Ruby,
+        rb_self: &ParsedValue,
+        types: RModule,
+        partial_types: RModule,
+    ) -> Result<Value> {
+        ruby_to_json::RubyToJson::serialize_baml(
+            ruby,
+            types,
+            partial_types,
+            rb_self.allow_partials,
+            rb_self.inner.clone(),
+        )
+        .map_err(|e| {
+            magnus::Error::new(
+                ruby.exception_type_error(),
+                format!("failing inside parsed_using_types: {:?}", e),
+            )
+        })
+    }
+
+    /// How the text had to be fixed up to fit the type, as [path, description]
+    /// pairs. The path of the top-level value is empty.
+    pub fn flags(ruby: &Ruby, rb_self: &ParsedValue) -> Result<RArray> {
+        let flags = ruby.ary_new();
+        for (path, flag) in rb_self.inner.flags() {
+            flags.push((path, flag.to_string()))?;
+        }
+        Ok(flags)
+    }
+
+    /// For usage in magnus::init
+    pub fn define_in_ruby(module: &RModule) -> Result<()> {
+        let cls = module.define_class("ParsedValue", class::object())?;
+
+        cls.define_method(
+            "parsed_using_types",
+            method!(ParsedValue::parsed_using_types, 2),
+        )?;
+        cls.define_method("flags", method!(ParsedValue::flags, 0))?;
+
+        Ok(())
+    }
+}
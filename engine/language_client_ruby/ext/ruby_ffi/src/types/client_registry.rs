@@ -1,4 +1,4 @@
-use baml_runtime::client_registry;
+use baml_runtime::client_registry::{self, Pricing};
 use magnus::{
     class, function, method, scan_args::scan_args, Error, Module, Object, RHash, Ruby, Value,
 };
@@ -57,6 +57,21 @@ impl ClientRegistry {
         self.inner.borrow_mut().set_primary(primary);
     }
 
+    pub fn set_model_pricing(
+        &self,
+        model: String,
+        input_per_million_tokens: f64,
+        output_per_million_tokens: f64,
+    ) {
+        self.inner.borrow_mut().set_model_pricing(
+            model,
+            Pricing {
+                input_per_million_tokens,
+                output_per_million_tokens,
+            },
+        );
+    }
+
     pub fn define_in_ruby(module: &magnus::RModule) -> Result<()> {
         let cls = module.define_class("ClientRegistry", class::object())?;
 
@@ -66,6 +81,10 @@ impl ClientRegistry {
             method!(ClientRegistry::add_llm_client, -1),
         )?;
         cls.define_method("set_primary", method!(ClientRegistry::set_primary, 1))?;
+        cls.define_method(
+            "set_model_pricing",
+            method!(ClientRegistry::set_model_pricing, 3),
+        )?;
 
         Ok(())
     }
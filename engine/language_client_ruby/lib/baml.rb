@@ -21,6 +21,13 @@ module Baml
   # Reexport StreamState.
   StreamState = Baml::StreamState::StreamState
 
+  # Counts the prompts of clients of `provider` whose model starts with
+  # `model_prefix` with the tiktoken file at `path`, for max_context_tokens.
+  # An empty prefix matches every model.
+  def self.register_tokenizer(provider, model_prefix, path)
+    Baml::Ffi.register_tokenizer(provider, model_prefix, path)
+  end
+
   # Dynamically + idempotently define Baml::TypeConverter
   # NB: this does not respect raise_coercion_error = false
   def self.convert_to(type)
@@ -27,6 +27,8 @@ export declare class BamlRuntime {
   callFunctionSync(functionName: string, args: { [name: string]: any }, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, cb?: ClientRegistry | undefined | null): FunctionResult
   streamFunction(functionName: string, args: { [name: string]: any }, cb: ((err: any, param: FunctionResult) => void) | undefined, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, clientRegistry?: ClientRegistry | undefined | null): FunctionResultStream
   streamFunctionSync(functionName: string, args: { [name: string]: any }, cb: ((err: any, param: FunctionResult) => void) | undefined, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null, clientRegistry?: ClientRegistry | undefined | null): FunctionResultStream
+  findType(name: string, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null): FieldType
+  parse(text: string, target: FieldType, allowPartials: boolean, ctx: RuntimeContextManager, tb?: TypeBuilder | undefined | null): ParsedValue
   setLogEventCallback(func?: undefined | ((err: any, param: BamlLogEvent) => void)): void
   flush(): void
   drainStats(): TraceStats
@@ -52,6 +54,7 @@ export declare class ClientRegistry {
   constructor()
   addLlmClient(name: string, provider: string, options: { [key: string]: any }, retryPolicy?: string | undefined | null): void
   setPrimary(primary: string): void
+  setModelPricing(model: string, inputPerMillionTokens: number, outputPerMillionTokens: number): void
 }
 
 export declare class EnumBuilder {
@@ -74,6 +77,12 @@ export declare class FieldType {
 export declare class FunctionResult {
   isOk(): boolean
   parsed(allowPartials: boolean): any
+  parseReport(): ParseReport | null
+  /**
+   * What the calls made for this result cost in US dollars, or null if
+   * none of them was priced.
+   */
+  cost(): number | null
 }
 
 export declare class FunctionResultStream {
@@ -81,6 +90,11 @@ export declare class FunctionResultStream {
   done(rctx: RuntimeContextManager): Promise<FunctionResult>
 }
 
+export declare class ParsedValue {
+  value(): any
+  flags(): Array<ParseFlag>
+}
+
 export declare class RuntimeContextManager {
   upsertTags(tags: any): void
   deepClone(): RuntimeContextManager
@@ -94,6 +108,8 @@ export declare class TraceStats {
   get submitted(): number
   get sent(): number
   get done(): number
+  get cost(): number
+  get costByFunction(): Record<string, number>
   toJson(): string
 }
 
@@ -120,14 +136,55 @@ export interface BamlLogEvent {
   prompt?: string
   rawOutput?: string
   parsedOutput?: string
+  parseReport?: string
   startTime: string
+  cost?: number
 }
 
 export declare export declare function invoke_runtime_cli(params: Array<string>): void
 
+export declare export declare function registerTokenizer(provider: string, modelPrefix: string, path: string): void
+
 export interface LogEventMetadata {
   eventId: string
   parentId?: string
   rootEventId: string
 }
 
+/** How much a response had to be repaired to parse. */
+export interface ParseReport {
+  /** The score of the parser. Lower is better. */
+  score: number
+  /** Whether the response parsed without any fixes or coercions. */
+  clean: boolean
+  /** Repairs of the raw text, like taking the value out of a markdown block. */
+  fixes: Array<ParseReportEntry>
+  /** Conversions made to fit values to their type. */
+  coercions: Array<ParseReportEntry>
+  /** The branch picked for each union. */
+  unions: Array<UnionBranch>
+}
+
+/** A fix-up the parser had to make for the text to fit the type. */
+export interface ParseReportEntry {
+  /** Where in the value, like `items.0.name`. Empty for the top-level value. */
+  path: string
+  /** A stable name for the repair, like `float_to_int`. */
+  kind: string
+  description: string
+}
+
+export interface UnionBranch {
+  /** Where in the value, like `items.0.name`. Empty for the top-level value. */
+  path: string
+  branch: number
+  /** How many branches the value could be parsed as. */
+  candidates: number
+}
+
+export interface ParseFlag {
+  /** Where in the value, like `items.0.name`. Empty for the top-level value. */
+  path: string
+  description: string
+}
+
@@ -373,7 +373,9 @@ module.exports.EnumValueBuilder = nativeBinding.EnumValueBuilder
 module.exports.FieldType = nativeBinding.FieldType
 module.exports.FunctionResult = nativeBinding.FunctionResult
 module.exports.FunctionResultStream = nativeBinding.FunctionResultStream
+module.exports.ParsedValue = nativeBinding.ParsedValue
 module.exports.RuntimeContextManager = nativeBinding.RuntimeContextManager
 module.exports.TraceStats = nativeBinding.TraceStats
 module.exports.TypeBuilder = nativeBinding.TypeBuilder
 module.exports.invoke_runtime_cli = nativeBinding.invoke_runtime_cli
+module.exports.registerTokenizer = nativeBinding.registerTokenizer
@@ -59,6 +59,7 @@ pub fn from_anyhow_error(err: anyhow::Error) -> napi::Error {
                 | baml_runtime::internal::llm_client::ErrorCode::RateLimited
                 | baml_runtime::internal::llm_client::ErrorCode::ServerError
                 | baml_runtime::internal::llm_client::ErrorCode::ServiceUnavailable
+                | baml_runtime::internal::llm_client::ErrorCode::Timeout
                 | baml_runtime::internal::llm_client::ErrorCode::UnsupportedResponse(_) => {
                     throw_baml_client_http_error(failed.client.as_str(), failed.message.as_str(), &failed.code)
                 }
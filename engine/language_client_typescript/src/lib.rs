@@ -20,6 +20,18 @@ pub fn run_cli(env: Env, params: Vec<String>) -> napi::Result<JsUndefined> {
     env.get_undefined()
 }
 
+/// Counts the prompts of clients of `provider` whose model starts with
+/// `model_prefix` with the byte pair encoding of the tiktoken file at `path`.
+#[napi(js_name = "registerTokenizer")]
+pub fn register_tokenizer(
+    provider: String,
+    model_prefix: String,
+    path: String,
+) -> napi::Result<()> {
+    baml_runtime::register_tiktoken_file(&provider, &model_prefix, std::path::Path::new(&path))
+        .map_err(errors::from_anyhow_error)
+}
+
 #[napi::module_init]
 fn module_init() {
     // Check if JSON logging is enabled
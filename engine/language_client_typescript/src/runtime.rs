@@ -3,9 +3,10 @@ use crate::parse_ts_types;
 use crate::types::client_registry::ClientRegistry;
 use crate::types::function_result_stream::FunctionResultStream;
 use crate::types::function_results::FunctionResult;
+use crate::types::parsed_value::ParsedValue;
 use crate::types::runtime_ctx_manager::RuntimeContextManager;
 use crate::types::trace_stats::TraceStats;
-use crate::types::type_builder::TypeBuilder;
+use crate::types::type_builder::{FieldType, TypeBuilder};
 use baml_runtime::on_log_event::LogEvent;
 use baml_runtime::runtime_interface::ExperimentalTracingInterface;
 use baml_runtime::BamlRuntime as CoreRuntime;
@@ -43,7 +44,11 @@ pub struct BamlLogEvent {
     pub raw_output: Option<String>,
     // json structure or a string
     pub parsed_output: Option<String>,
+    // json structure of the parse report
+    pub parse_report: Option<String>,
     pub start_time: String,
+    // US dollars, if the clients have pricing
+    pub cost: Option<f64>,
 }
 
 #[napi]
@@ -251,6 +256,41 @@ impl BamlRuntime {
         Ok(FunctionResultStream::new(stream, cb, tb, client_registry))
     }
 
+    #[napi]
+    pub fn find_type(
+        &self,
+        name: String,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+    ) -> napi::Result<FieldType> {
+        self.inner
+            .find_type(&name, &ctx.inner, tb.map(|tb| &tb.inner))
+            .map(FieldType::from)
+            .map_err(from_anyhow_error)
+    }
+
+    #[napi]
+    pub fn parse(
+        &self,
+        text: String,
+        target: &FieldType,
+        allow_partials: bool,
+        ctx: &RuntimeContextManager,
+        tb: Option<&TypeBuilder>,
+    ) -> napi::Result<ParsedValue> {
+        let target = target.inner.lock().unwrap().clone();
+        self.inner
+            .parse(
+                &text,
+                &target,
+                allow_partials,
+                &ctx.inner,
+                tb.map(|tb| &tb.inner),
+            )
+            .map(|parsed| ParsedValue::new(parsed, allow_partials))
+            .map_err(from_anyhow_error)
+    }
+
     #[napi]
     pub fn set_log_event_callback(
         &mut self,
@@ -289,7 +329,9 @@ impl BamlRuntime {
                         prompt: event.prompt,
                         raw_output: event.raw_output,
                         parsed_output: event.parsed_output,
+                        parse_report: event.parse_report,
                         start_time: event.start_time,
+                        cost: event.cost,
                     };
 
                     let res = tsfn_clone.call(Ok(event), ThreadsafeFunctionCallMode::Blocking);
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use baml_runtime::client_registry;
-use client_registry::ClientProvider;
+use client_registry::{ClientProvider, Pricing};
 use napi::Env;
 use napi::JsObject;
 use napi_derive::napi;
@@ -65,4 +65,20 @@ impl ClientRegistry {
     pub fn set_primary(&mut self, primary: String) {
         self.inner.set_primary(primary);
     }
+
+    #[napi]
+    pub fn set_model_pricing(
+        &mut self,
+        model: String,
+        input_per_million_tokens: f64,
+        output_per_million_tokens: f64,
+    ) {
+        self.inner.set_model_pricing(
+            model,
+            Pricing {
+                input_per_million_tokens,
+                output_per_million_tokens,
+            },
+        );
+    }
 }
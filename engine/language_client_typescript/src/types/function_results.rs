@@ -30,4 +30,80 @@ impl FunctionResult {
         )?;
         Ok(response)
     }
+
+    #[napi]
+    pub fn parse_report(&self) -> Option<ParseReport> {
+        self.inner.parse_report().map(ParseReport::from)
+    }
+
+    /// What the calls made for this result cost in US dollars, or null if
+    /// none of them was priced.
+    #[napi]
+    pub fn cost(&self) -> Option<f64> {
+        self.inner.cost()
+    }
+}
+
+/// How much a response had to be repaired to parse.
+#[napi(object)]
+pub struct ParseReport {
+    /// The score of the parser. Lower is better.
+    pub score: i32,
+    /// Whether the response parsed without any fixes or coercions.
+    pub clean: bool,
+    /// Repairs of the raw text, like taking the value out of a markdown block.
+    pub fixes: Vec<ParseReportEntry>,
+    /// Conversions made to fit values to their type.
+    pub coercions: Vec<ParseReportEntry>,
+    /// The branch picked for each union.
+    pub unions: Vec<UnionBranch>,
+}
+
+/// A fix-up the parser had to make for the text to fit the type.
+#[napi(object)]
+pub struct ParseReportEntry {
+    /// Where in the value, like `items.0.name`. Empty for the top-level value.
+    pub path: String,
+    /// A stable name for the repair, like `float_to_int`.
+    pub kind: String,
+    pub description: String,
+}
+
+#[napi(object)]
+pub struct UnionBranch {
+    /// Where in the value, like `items.0.name`. Empty for the top-level value.
+    pub path: String,
+    pub branch: u32,
+    /// How many branches the value could be parsed as.
+    pub candidates: u32,
+}
+
+impl From<jsonish::ParseReport> for ParseReport {
+    fn from(report: jsonish::ParseReport) -> Self {
+        let entries = |entries: Vec<jsonish::ParseReportEntry>| {
+            entries
+                .into_iter()
+                .map(|e| ParseReportEntry {
+                    path: e.path,
+                    kind: e.kind.to_string(),
+                    description: e.description,
+                })
+                .collect()
+        };
+        ParseReport {
+            score: report.score,
+            clean: report.clean,
+            fixes: entries(report.fixes),
+            coercions: entries(report.coercions),
+            unions: report
+                .unions
+                .into_iter()
+                .map(|u| UnionBranch {
+                    path: u.path,
+                    branch: u.branch as u32,
+                    candidates: u.candidates as u32,
+                })
+                .collect(),
+        }
+    }
 }
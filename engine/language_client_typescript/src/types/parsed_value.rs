@@ -0,0 +1,45 @@
+use napi_derive::napi;
+
+crate::lang_wrapper!(ParsedValue, jsonish::ResponseBamlValue, no_from, allow_partials: bool);
+
+impl ParsedValue {
+    pub fn new(inner: jsonish::ResponseBamlValue, allow_partials: bool) -> Self {
+        Self {
+            inner,
+            allow_partials,
+        }
+    }
+}
+
+/// A fix-up the parser had to make for the text to fit the type.
+#[napi(object)]
+pub struct ParseFlag {
+    /// Where in the value, like `items.0.name`. Empty for the top-level value.
+    pub path: String,
+    pub description: String,
+}
+
+#[napi]
+impl ParsedValue {
+    #[napi]
+    pub fn value(&self) -> napi::Result<serde_json::Value> {
+        let value = serde_json::to_value(if self.allow_partials {
+            self.inner.serialize_partial()
+        } else {
+            self.inner.serialize_final()
+        })?;
+        Ok(value)
+    }
+
+    #[napi]
+    pub fn flags(&self) -> Vec<ParseFlag> {
+        self.inner
+            .flags()
+            .into_iter()
+            .map(|(path, flag)| ParseFlag {
+                path,
+                description: flag.to_string(),
+            })
+            .collect()
+    }
+}
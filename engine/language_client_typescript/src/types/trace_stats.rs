@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use napi_derive::napi;
 
 crate::lang_wrapper!(TraceStats, baml_runtime::InnerTraceStats);
@@ -34,6 +36,16 @@ impl TraceStats {
         self.inner.done
     }
 
+    #[napi(getter)]
+    pub fn get_cost(&self) -> f64 {
+        self.inner.cost
+    }
+
+    #[napi(getter)]
+    pub fn get_cost_by_function(&self) -> HashMap<String, f64> {
+        self.inner.cost_by_function.clone()
+    }
+
     #[napi]
     pub fn to_json(&self) -> String {
         serde_json::json!({
@@ -43,6 +55,8 @@ impl TraceStats {
             "submitted": self.inner.submitted,
             "sent": self.inner.sent,
             "done": self.inner.done,
+            "cost": self.inner.cost,
+            "cost_by_function": self.inner.cost_by_function,
         })
         .to_string()
     }
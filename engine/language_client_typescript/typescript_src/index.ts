@@ -2,10 +2,16 @@ export {
   BamlRuntime,
   FunctionResult,
   FunctionResultStream,
+  ParsedValue,
+  ParseFlag,
+  ParseReport,
+  ParseReportEntry,
+  UnionBranch,
   BamlImage as Image,
   ClientBuilder,
   BamlAudio as Audio,
   invoke_runtime_cli,
+  registerTokenizer,
   ClientRegistry,
   BamlLogEvent,
 } from "./native";
@@ -0,0 +1,67 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Welcome to Baml! This client calls your BAML functions through the BAML
+//  server, so start it next to your baml_src directory:
+//
+//  $ npx @boundaryml/baml serve
+//
+///////////////////////////////////////////////////////////////////////////////
+
+// Code generated by BAML. DO NOT EDIT.
+// Edit the BAML files and re-generate this code instead.
+
+package baml_client
+
+import (
+	"context"
+	"encoding/json"
+)
+
+// ClassifyOrder calls the BAML function ClassifyOrder.
+func (c *Client) ClassifyOrder(ctx context.Context, order_text string, category *Category, opts ...CallOption) (Order, error) {
+	return call[Order](ctx, c, "ClassifyOrder", map[string]any{
+		"order_text": order_text,
+		"category": category,
+	}, opts)
+}
+
+// ClassifyOrder calls the BAML function ClassifyOrder, and reads its
+// partial results as the model writes them.
+func (s *StreamClient) ClassifyOrder(ctx context.Context, order_text string, category *Category, opts ...CallOption) (*Stream[PartialOrder, Order], error) {
+	return stream[PartialOrder, Order](ctx, s.client, "ClassifyOrder", map[string]any{
+		"order_text": order_text,
+		"category": category,
+	}, opts)
+}
+
+// Describe calls the BAML function Describe.
+func (c *Client) Describe(ctx context.Context, image Image, type_ string, opts ...CallOption) (json.RawMessage, error) {
+	return call[json.RawMessage](ctx, c, "Describe", map[string]any{
+		"image": image,
+		"type": type_,
+	}, opts)
+}
+
+// Describe calls the BAML function Describe, and reads its
+// partial results as the model writes them.
+func (s *StreamClient) Describe(ctx context.Context, image Image, type_ string, opts ...CallOption) (*Stream[json.RawMessage, json.RawMessage], error) {
+	return stream[json.RawMessage, json.RawMessage](ctx, s.client, "Describe", map[string]any{
+		"image": image,
+		"type": type_,
+	}, opts)
+}
+
+// ExtractAddresses calls the BAML function ExtractAddresses.
+func (c *Client) ExtractAddresses(ctx context.Context, text string, opts ...CallOption) ([]Address, error) {
+	return call[[]Address](ctx, c, "ExtractAddresses", map[string]any{
+		"text": text,
+	}, opts)
+}
+
+// ExtractAddresses calls the BAML function ExtractAddresses, and reads its
+// partial results as the model writes them.
+func (s *StreamClient) ExtractAddresses(ctx context.Context, text string, opts ...CallOption) (*Stream[[]*PartialAddress, []Address], error) {
+	return stream[[]*PartialAddress, []Address](ctx, s.client, "ExtractAddresses", map[string]any{
+		"text": text,
+	}, opts)
+}
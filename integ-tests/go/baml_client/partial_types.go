@@ -0,0 +1,44 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Welcome to Baml! This client calls your BAML functions through the BAML
+//  server, so start it next to your baml_src directory:
+//
+//  $ npx @boundaryml/baml serve
+//
+///////////////////////////////////////////////////////////////////////////////
+
+// Code generated by BAML. DO NOT EDIT.
+// Edit the BAML files and re-generate this code instead.
+
+package baml_client
+
+import "encoding/json"
+
+/******************************************************************************
+*
+*  These types are used for streaming, for when an instance of a type
+*  is still being built up and any of its fields is not yet fully available.
+*
+******************************************************************************/
+
+type PartialAddress struct {
+	Street string `json:"street"`
+	City *string `json:"city"`
+	Zip *string `json:"zip"`
+}
+
+// An order, as described by a customer.
+type PartialOrder struct {
+	Id *int64 `json:"id"`
+	Category *Category `json:"category"`
+	Note *string `json:"note"`
+	Tags []*string `json:"tags"`
+	Amount json.RawMessage `json:"amount"`
+	Status *string `json:"status"`
+	ShippingAddress *PartialAddress `json:"shipping_address"`
+	Items map[string]*int64 `json:"items"`
+	Confidence *Checked[float64] `json:"confidence"`
+	Summary StreamState[*string] `json:"summary"`
+	FirstName *string `json:"first_name"`
+	FirstName_ *string `json:"firstName"`
+}
@@ -0,0 +1,356 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Welcome to Baml! This client calls your BAML functions through the BAML
+//  server, so start it next to your baml_src directory:
+//
+//  $ npx @boundaryml/baml serve
+//
+///////////////////////////////////////////////////////////////////////////////
+
+// Code generated by BAML. DO NOT EDIT.
+// Edit the BAML files and re-generate this code instead.
+
+package baml_client
+
+import (
+	"bufio"
+	"bytes"
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"io"
+	"net/http"
+	"strings"
+)
+
+// DefaultBaseURL is where `baml-cli serve` listens unless told otherwise.
+const DefaultBaseURL = "http://localhost:2024"
+
+// Client calls the BAML functions of baml_src through `baml-cli serve`.
+type Client struct {
+	// BaseURL is the address of the server, without a trailing slash.
+	BaseURL string
+	// APIKey is sent in the x-baml-api-key header, for servers started with
+	// BAML_PASSWORD set.
+	APIKey string
+	// HTTPClient makes the requests. http.DefaultClient is used if it is nil.
+	HTTPClient *http.Client
+	// Stream has a streaming version of every function.
+	Stream *StreamClient
+}
+
+// StreamClient calls functions and reads their partial results as the model
+// writes them.
+type StreamClient struct {
+	client *Client
+}
+
+// NewClient returns a client for the server at baseURL, like DefaultBaseURL.
+func NewClient(baseURL string) *Client {
+	c := &Client{BaseURL: strings.TrimRight(baseURL, "/")}
+	c.Stream = &StreamClient{client: c}
+	return c
+}
+
+// CallOption changes how a single function is called.
+type CallOption func(*callOptions)
+
+type callOptions struct {
+	ClientRegistry *ClientRegistry `json:"client_registry,omitempty"`
+}
+
+// WithClientRegistry calls the function with the clients of registry.
+func WithClientRegistry(registry *ClientRegistry) CallOption {
+	return func(o *callOptions) {
+		o.ClientRegistry = registry
+	}
+}
+
+// ClientRegistry adds clients for a call, and picks the one the function
+// uses.
+type ClientRegistry struct {
+	Clients []ClientProperty `json:"clients"`
+	Primary string           `json:"primary,omitempty"`
+}
+
+// ClientProperty is a client like the ones defined in .baml files.
+type ClientProperty struct {
+	Name        string         `json:"name"`
+	Provider    string         `json:"provider"`
+	RetryPolicy string         `json:"retry_policy,omitempty"`
+	Options     map[string]any `json:"options"`
+}
+
+func NewClientRegistry() *ClientRegistry {
+	return &ClientRegistry{Clients: []ClientProperty{}}
+}
+
+// AddLlmClient adds a client. A client with the same name as one in the
+// .baml files replaces it.
+func (r *ClientRegistry) AddLlmClient(name, provider string, options map[string]any) {
+	r.Clients = append(r.Clients, ClientProperty{Name: name, Provider: provider, Options: options})
+}
+
+// SetPrimary makes the function call the client named name.
+func (r *ClientRegistry) SetPrimary(name string) {
+	r.Primary = name
+}
+
+// Error is a failure the server reported, like a model that couldn't be
+// called or an output that couldn't be parsed.
+type Error struct {
+	// Kind is the kind of failure, like "client_error" or
+	// "validation_failure".
+	Kind    string `json:"error"`
+	Message string `json:"message"`
+	// Prompt and RawOutput are set when the output of the model couldn't be
+	// parsed.
+	Prompt    string `json:"prompt,omitempty"`
+	RawOutput string `json:"raw_output,omitempty"`
+	// StatusCode is the HTTP status of the provider for a
+	// "client_http_error", and of the server otherwise.
+	StatusCode int `json:"status_code,omitempty"`
+}
+
+func (e *Error) Error() string {
+	return fmt.Sprintf("%s: %s", e.Kind, e.Message)
+}
+
+// Image is an image input, from a URL or base64 encoded data.
+type Image struct {
+	URL       string `json:"url,omitempty"`
+	Base64    string `json:"base64,omitempty"`
+	MediaType string `json:"media_type,omitempty"`
+}
+
+func ImageFromURL(url string) Image {
+	return Image{URL: url}
+}
+
+func ImageFromBase64(mediaType, base64 string) Image {
+	return Image{Base64: base64, MediaType: mediaType}
+}
+
+// Audio is an audio input, from a URL or base64 encoded data.
+type Audio struct {
+	URL       string `json:"url,omitempty"`
+	Base64    string `json:"base64,omitempty"`
+	MediaType string `json:"media_type,omitempty"`
+}
+
+func AudioFromURL(url string) Audio {
+	return Audio{URL: url}
+}
+
+func AudioFromBase64(mediaType, base64 string) Audio {
+	return Audio{Base64: base64, MediaType: mediaType}
+}
+
+// Check is the outcome of a @check.
+type Check struct {
+	Name       string `json:"name"`
+	Expression string `json:"expression"`
+	// Status is "succeeded" or "failed".
+	Status string `json:"status"`
+}
+
+// Checked is a value with the outcome of its @checks, by name.
+type Checked[T any] struct {
+	Value  T                `json:"value"`
+	Checks map[string]Check `json:"checks"`
+}
+
+// StreamState is the value of a @stream.with_state field while streaming,
+// with how far along it is.
+type StreamState[T any] struct {
+	Value T `json:"value"`
+	// State is "Pending", "Incomplete" or "Complete".
+	State string `json:"state"`
+}
+
+func (c *Client) post(ctx context.Context, path string, args map[string]any, opts []CallOption) (*http.Response, error) {
+	var options callOptions
+	for _, opt := range opts {
+		opt(&options)
+	}
+	if options.ClientRegistry != nil {
+		args["__baml_options__"] = options
+	}
+	body, err := json.Marshal(args)
+	if err != nil {
+		return nil, err
+	}
+	req, err := http.NewRequestWithContext(ctx, http.MethodPost, c.BaseURL+path, bytes.NewReader(body))
+	if err != nil {
+		return nil, err
+	}
+	req.Header.Set("Content-Type", "application/json")
+	if c.APIKey != "" {
+		req.Header.Set("x-baml-api-key", c.APIKey)
+	}
+	httpClient := c.HTTPClient
+	if httpClient == nil {
+		httpClient = http.DefaultClient
+	}
+	resp, err := httpClient.Do(req)
+	if err != nil {
+		return nil, err
+	}
+	if resp.StatusCode != http.StatusOK {
+		defer resp.Body.Close()
+		return nil, readError(resp)
+	}
+	return resp, nil
+}
+
+func readError(resp *http.Response) error {
+	body, err := io.ReadAll(resp.Body)
+	if err != nil {
+		return err
+	}
+	e := &Error{}
+	if json.Unmarshal(body, e) != nil || e.Kind == "" {
+		e = &Error{Kind: "http_error", Message: strings.TrimSpace(string(body))}
+	}
+	if e.StatusCode == 0 {
+		e.StatusCode = resp.StatusCode
+	}
+	return e
+}
+
+func call[T any](ctx context.Context, c *Client, function string, args map[string]any, opts []CallOption) (T, error) {
+	var result T
+	resp, err := c.post(ctx, "/call/"+function, args, opts)
+	if err != nil {
+		return result, err
+	}
+	defer resp.Body.Close()
+	err = json.NewDecoder(resp.Body).Decode(&result)
+	return result, err
+}
+
+func stream[P, F any](ctx context.Context, c *Client, function string, args map[string]any, opts []CallOption) (*Stream[P, F], error) {
+	resp, err := c.post(ctx, "/stream/"+function, args, opts)
+	if err != nil {
+		return nil, err
+	}
+	return &Stream[P, F]{
+		ctx:      ctx,
+		client:   c,
+		function: function,
+		args:     args,
+		opts:     opts,
+		body:     resp.Body,
+		reader:   bufio.NewReader(resp.Body),
+	}, nil
+}
+
+// Stream is a function call whose partial results are read as the model
+// writes them:
+//
+//	for stream.Next() {
+//		fmt.Println(stream.Partial())
+//	}
+//	result, err := stream.Final()
+type Stream[P, F any] struct {
+	ctx      context.Context
+	client   *Client
+	function string
+	args     map[string]any
+	opts     []CallOption
+	body     io.ReadCloser
+	reader   *bufio.Reader
+	partial  P
+	final    F
+	err      error
+	done     bool
+	called   bool
+}
+
+// Next waits for the next partial result. It returns false once the stream
+// has ended.
+func (s *Stream[P, F]) Next() bool {
+	if s.done {
+		return false
+	}
+	data, err := s.readEvent()
+	if err == io.EOF {
+		s.finish(nil)
+		return false
+	}
+	if err != nil {
+		s.finish(err)
+		return false
+	}
+	var partial P
+	if err := json.Unmarshal(data, &partial); err != nil {
+		s.finish(err)
+		return false
+	}
+	s.partial = partial
+	return true
+}
+
+// Partial is the latest partial result.
+func (s *Stream[P, F]) Partial() P {
+	return s.partial
+}
+
+// Final waits for the stream to end, and returns the result of the call.
+//
+// The stream carries neither the final result nor why the call failed, so
+// Final calls the function again without streaming to get them. Skip it, and
+// Close the stream, if the partial results are enough.
+func (s *Stream[P, F]) Final() (F, error) {
+	for s.Next() {
+	}
+	if s.err == nil && !s.called {
+		s.called = true
+		s.final, s.err = call[F](s.ctx, s.client, s.function, s.args, s.opts)
+	}
+	return s.final, s.err
+}
+
+// Close stops reading the stream before it has ended.
+func (s *Stream[P, F]) Close() error {
+	if !s.done {
+		s.finish(errors.New("stream closed"))
+	}
+	return nil
+}
+
+func (s *Stream[P, F]) finish(err error) {
+	s.done = true
+	s.err = err
+	s.body.Close()
+}
+
+// readEvent reads the data of the next server-sent event, skipping comments
+// like the keep-alives of the server.
+func (s *Stream[P, F]) readEvent() ([]byte, error) {
+	var data []byte
+	hasData := false
+	for {
+		line, err := s.reader.ReadString('\n')
+		if err != nil {
+			if err == io.EOF && (hasData || line != "") {
+				err = io.ErrUnexpectedEOF
+			}
+			return nil, err
+		}
+		line = strings.TrimRight(line, "\r\n")
+		switch {
+		case line == "":
+			if hasData {
+				return data, nil
+			}
+		case strings.HasPrefix(line, "data:"):
+			if hasData {
+				data = append(data, '\n')
+			}
+			data = append(data, strings.TrimPrefix(strings.TrimPrefix(line, "data:"), " ")...)
+			hasData = true
+		}
+	}
+}
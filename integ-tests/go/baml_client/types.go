@@ -0,0 +1,46 @@
+///////////////////////////////////////////////////////////////////////////////
+//
+//  Welcome to Baml! This client calls your BAML functions through the BAML
+//  server, so start it next to your baml_src directory:
+//
+//  $ npx @boundaryml/baml serve
+//
+///////////////////////////////////////////////////////////////////////////////
+
+// Code generated by BAML. DO NOT EDIT.
+// Edit the BAML files and re-generate this code instead.
+
+package baml_client
+
+import "encoding/json"
+
+// What a customer wants done with an order.
+type Category string
+
+const (
+	Category_Refund Category = "Refund"
+	Category_Cancel Category = "Cancel"
+	Category_Other Category = "Other"
+)
+
+type Address struct {
+	Street string `json:"street"`
+	City string `json:"city"`
+	Zip *string `json:"zip"`
+}
+
+// An order, as described by a customer.
+type Order struct {
+	Id int64 `json:"id"`
+	Category Category `json:"category"`
+	Note *string `json:"note"`
+	Tags []string `json:"tags"`
+	Amount json.RawMessage `json:"amount"`
+	Status string `json:"status"`
+	ShippingAddress *Address `json:"shipping_address"`
+	Items map[string]int64 `json:"items"`
+	Confidence Checked[float64] `json:"confidence"`
+	Summary string `json:"summary"`
+	FirstName string `json:"first_name"`
+	FirstName_ string `json:"firstName"`
+}
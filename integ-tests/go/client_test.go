@@ -0,0 +1,202 @@
+package integtests
+
+import (
+	"context"
+	"encoding/json"
+	"errors"
+	"fmt"
+	"net/http"
+	"net/http/httptest"
+	"testing"
+
+	b "github.com/boundaryml/baml/integ-tests/go/baml_client"
+)
+
+const order = `{
+	"id": 7,
+	"category": "Refund",
+	"note": null,
+	"tags": ["late"],
+	"amount": 12.5,
+	"status": "open",
+	"shipping_address": {"street": "1 Main St", "city": "Springfield", "zip": null},
+	"items": {"socks": 2},
+	"confidence": {"value": 0.9, "checks": {"confident": {"name": "confident", "expression": "this > 0.5", "status": "succeeded"}}},
+	"summary": "A late refund",
+	"first_name": "Ada",
+	"firstName": "Lovelace"
+}`
+
+// serve starts a stand-in for `baml-cli serve` that answers every request
+// with handler.
+func serve(t *testing.T, handler http.HandlerFunc) *b.Client {
+	t.Helper()
+	server := httptest.NewServer(handler)
+	t.Cleanup(server.Close)
+	return b.NewClient(server.URL)
+}
+
+func TestClassesEnumsUnionsAndOptionalsDecode(t *testing.T) {
+	var o b.Order
+	if err := json.Unmarshal([]byte(order), &o); err != nil {
+		t.Fatal(err)
+	}
+	if o.Id != 7 || o.Category != b.Category_Refund || o.Status != "open" {
+		t.Errorf("unexpected order %+v", o)
+	}
+	if o.Note != nil {
+		t.Errorf("note is %q, want nil", *o.Note)
+	}
+	if o.ShippingAddress == nil || o.ShippingAddress.City != "Springfield" || o.ShippingAddress.Zip != nil {
+		t.Errorf("unexpected address %+v", o.ShippingAddress)
+	}
+	var amount float64
+	if err := json.Unmarshal(o.Amount, &amount); err != nil || amount != 12.5 {
+		t.Errorf("amount is %s", o.Amount)
+	}
+	if o.Items["socks"] != 2 || len(o.Tags) != 1 {
+		t.Errorf("unexpected items %v and tags %v", o.Items, o.Tags)
+	}
+	if o.Confidence.Value != 0.9 || o.Confidence.Checks["confident"].Status != "succeeded" {
+		t.Errorf("unexpected confidence %+v", o.Confidence)
+	}
+	if o.FirstName != "Ada" || o.FirstName_ != "Lovelace" {
+		t.Errorf("names are %q and %q", o.FirstName, o.FirstName_)
+	}
+}
+
+func TestCallSendsArgumentsAndDecodesTheResult(t *testing.T) {
+	var args map[string]any
+	client := serve(t, func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/call/ClassifyOrder" {
+			t.Errorf("called %s", r.URL.Path)
+		}
+		if err := json.NewDecoder(r.Body).Decode(&args); err != nil {
+			t.Error(err)
+		}
+		fmt.Fprint(w, order)
+	})
+
+	category := b.Category_Refund
+	o, err := client.ClassifyOrder(context.Background(), "refund my socks", &category)
+	if err != nil {
+		t.Fatal(err)
+	}
+	if o.Id != 7 {
+		t.Errorf("unexpected order %+v", o)
+	}
+	if args["order_text"] != "refund my socks" || args["category"] != "Refund" {
+		t.Errorf("unexpected arguments %v", args)
+	}
+
+	if _, err := client.ClassifyOrder(context.Background(), "refund my socks", nil); err != nil {
+		t.Fatal(err)
+	}
+	if category, ok := args["category"]; !ok || category != nil {
+		t.Errorf("category is %v, want null", category)
+	}
+}
+
+func TestCallReturnsTheErrorOfTheServer(t *testing.T) {
+	client := serve(t, func(w http.ResponseWriter, r *http.Request) {
+		w.WriteHeader(http.StatusInternalServerError)
+		fmt.Fprint(w, `{"error": "validation_failure", "message": "missing id", "prompt": "p", "raw_output": "r"}`)
+	})
+
+	_, err := client.ExtractAddresses(context.Background(), "nowhere")
+	var e *b.Error
+	if !errors.As(err, &e) {
+		t.Fatalf("error is %v, want a *baml_client.Error", err)
+	}
+	if e.Kind != "validation_failure" || e.RawOutput != "r" || e.StatusCode != http.StatusInternalServerError {
+		t.Errorf("unexpected error %+v", e)
+	}
+}
+
+func TestStreamReadsPartialsThenCallsForTheResult(t *testing.T) {
+	var paths []string
+	client := serve(t, func(w http.ResponseWriter, r *http.Request) {
+		paths = append(paths, r.URL.Path)
+		if r.URL.Path == "/call/ClassifyOrder" {
+			fmt.Fprint(w, order)
+			return
+		}
+		w.Header().Set("Content-Type", "text/event-stream")
+		fmt.Fprint(w, ": keep-alive\n\n")
+		fmt.Fprint(w, "data: {\"id\": 7, \"summary\": {\"value\": \"A la\", \"state\": \"Incomplete\"}}\n\n")
+		fmt.Fprint(w, "data: {\"id\": 7, \"shipping_address\": {\"street\": \"1 Main St\"}}\n\n")
+	})
+
+	stream, err := client.Stream.ClassifyOrder(context.Background(), "refund my socks", nil)
+	if err != nil {
+		t.Fatal(err)
+	}
+	var partials []b.PartialOrder
+	for stream.Next() {
+		partials = append(partials, stream.Partial())
+	}
+	o, err := stream.Final()
+	if err != nil {
+		t.Fatal(err)
+	}
+	if len(partials) != 2 {
+		t.Fatalf("got %d partials, want 2", len(partials))
+	}
+	if s := partials[0].Summary; s.State != "Incomplete" || s.Value == nil || *s.Value != "A la" {
+		t.Errorf("unexpected summary %+v", s)
+	}
+	if a := partials[1].ShippingAddress; a == nil || a.Street != "1 Main St" || a.City != nil {
+		t.Errorf("unexpected address %+v", a)
+	}
+	if o.Summary != "A late refund" {
+		t.Errorf("unexpected order %+v", o)
+	}
+	if len(paths) != 2 || paths[0] != "/stream/ClassifyOrder" || paths[1] != "/call/ClassifyOrder" {
+		t.Errorf("called %v", paths)
+	}
+}
+
+func TestStreamReturnsTheErrorOfTheCall(t *testing.T) {
+	client := serve(t, func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path == "/call/ExtractAddresses" {
+			w.WriteHeader(http.StatusInternalServerError)
+			fmt.Fprint(w, `{"error": "client_error", "message": "overloaded"}`)
+			return
+		}
+		fmt.Fprint(w, "data: [{\"street\": \"1 Ma\"}]\n\n")
+	})
+
+	stream, err := client.Stream.ExtractAddresses(context.Background(), "1 Main St")
+	if err != nil {
+		t.Fatal(err)
+	}
+	_, err = stream.Final()
+	var e *b.Error
+	if !errors.As(err, &e) || e.Kind != "client_error" {
+		t.Errorf("error is %v, want a client_error", err)
+	}
+	if _, again := stream.Final(); again != err {
+		t.Errorf("second Final returned %v, want %v", again, err)
+	}
+}
+
+func TestClosedStreamDoesNotCallForTheResult(t *testing.T) {
+	client := serve(t, func(w http.ResponseWriter, r *http.Request) {
+		if r.URL.Path != "/stream/ExtractAddresses" {
+			t.Errorf("called %s", r.URL.Path)
+		}
+		fmt.Fprint(w, "data: [{\"street\": \"1 Ma\"}]\n\n")
+	})
+
+	stream, err := client.Stream.ExtractAddresses(context.Background(), "1 Main St")
+	if err != nil {
+		t.Fatal(err)
+	}
+	if !stream.Next() {
+		t.Fatal("got no partial")
+	}
+	stream.Close()
+	if _, err := stream.Final(); err == nil {
+		t.Error("Final of a closed stream succeeded")
+	}
+}
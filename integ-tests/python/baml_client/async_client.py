@@ -464,6 +464,29 @@ class BamlAsyncClient:
       )
       return cast(types.Category, raw.cast_to(types, types, partial_types, False))
     
+    async def ClassifyWithCitations(
+        self,
+        text: str,
+        baml_options: BamlCallOptions = {},
+    ) -> List[types.WithCitations__Category]:
+      __tb__ = baml_options.get("tb", None)
+      if __tb__ is not None:
+        tb = __tb__._tb # type: ignore (we know how to use this private attribute)
+      else:
+        tb = None
+      __cr__ = baml_options.get("client_registry", None)
+
+      raw = await self.__runtime.call_function(
+        "ClassifyWithCitations",
+        {
+          "text": text,
+        },
+        self.__ctx_manager.get(),
+        tb,
+        __cr__,
+      )
+      return cast(List[types.WithCitations__Category], raw.cast_to(types, types, partial_types, False))
+    
     async def Completion(
         self,
         prefix: str,suffix: str,language: str,
@@ -901,6 +924,29 @@ class BamlAsyncClient:
       )
       return cast(types.Resume, raw.cast_to(types, types, partial_types, False))
     
+    async def ExtractResumePage(
+        self,
+        text: str,
+        baml_options: BamlCallOptions = {},
+    ) -> types.Paginated__Resume:
+      __tb__ = baml_options.get("tb", None)
+      if __tb__ is not None:
+        tb = __tb__._tb # type: ignore (we know how to use this private attribute)
+      else:
+        tb = None
+      __cr__ = baml_options.get("client_registry", None)
+
+      raw = await self.__runtime.call_function(
+        "ExtractResumePage",
+        {
+          "text": text,
+        },
+        self.__ctx_manager.get(),
+        tb,
+        __cr__,
+      )
+      return cast(types.Paginated__Resume, raw.cast_to(types, types, partial_types, False))
+    
     async def FnClassOptionalOutput(
         self,
         input: str,
@@ -4235,6 +4281,36 @@ class BamlStreamClient:
         self.__ctx_manager.get(),
       )
     
+    def ClassifyWithCitations(
+        self,
+        text: str,
+        baml_options: BamlCallOptions = {},
+    ) -> baml_py.BamlStream[List[partial_types.WithCitations__Category], List[types.WithCitations__Category]]:
+      __tb__ = baml_options.get("tb", None)
+      if __tb__ is not None:
+        tb = __tb__._tb # type: ignore (we know how to use this private attribute)
+      else:
+        tb = None
+      __cr__ = baml_options.get("client_registry", None)
+
+      raw = self.__runtime.stream_function(
+        "ClassifyWithCitations",
+        {
+          "text": text,
+        },
+        None,
+        self.__ctx_manager.get(),
+        tb,
+        __cr__,
+      )
+
+      return baml_py.BamlStream[List[partial_types.WithCitations__Category], List[types.WithCitations__Category]](
+        raw,
+        lambda x: cast(List[partial_types.WithCitations__Category], x.cast_to(types, types, partial_types, True)),
+        lambda x: cast(List[types.WithCitations__Category], x.cast_to(types, types, partial_types, False)),
+        self.__ctx_manager.get(),
+      )
+    
     def Completion(
         self,
         prefix: str,suffix: str,language: str,
@@ -4810,6 +4886,36 @@ class BamlStreamClient:
         self.__ctx_manager.get(),
       )
     
+    def ExtractResumePage(
+        self,
+        text: str,
+        baml_options: BamlCallOptions = {},
+    ) -> baml_py.BamlStream[partial_types.Paginated__Resume, types.Paginated__Resume]:
+      __tb__ = baml_options.get("tb", None)
+      if __tb__ is not None:
+        tb = __tb__._tb # type: ignore (we know how to use this private attribute)
+      else:
+        tb = None
+      __cr__ = baml_options.get("client_registry", None)
+
+      raw = self.__runtime.stream_function(
+        "ExtractResumePage",
+        {
+          "text": text,
+        },
+        None,
+        self.__ctx_manager.get(),
+        tb,
+        __cr__,
+      )
+
+      return baml_py.BamlStream[partial_types.Paginated__Resume, types.Paginated__Resume](
+        raw,
+        lambda x: cast(partial_types.Paginated__Resume, x.cast_to(types, types, partial_types, True)),
+        lambda x: cast(types.Paginated__Resume, x.cast_to(types, types, partial_types, False)),
+        self.__ctx_manager.get(),
+      )
+    
     def FnClassOptionalOutput(
         self,
         input: str,
@@ -32,6 +32,8 @@ T = TypeVar('T')
 class StreamState(BaseModel, Generic[T]):
     value: T
     state: Literal["Pending", "Incomplete", "Complete"]
+T = TypeVar('T')
+
 
 
 class AnotherObject(BaseModel):
@@ -429,3 +431,15 @@ class UnionTest_ReturnType(BaseModel):
 class WithReasoning(BaseModel):
     value: Optional[str] = None
     reasoning: Optional[str] = None
+
+class Paginated(BaseModel, Generic[T]):
+    items: List[T]
+    next_page: Optional[int] = None
+
+class WithCitations(BaseModel, Generic[T]):
+    answer: Optional[T] = None
+    citations: List[Optional[str]]
+
+Paginated__Resume = Paginated["Resume"]
+
+WithCitations__Category = WithCitations[types.Category]
@@ -14,6 +14,7 @@
 # pylint: disable=unused-import,line-too-long
 # fmt: off
 import baml_py
+import pydantic
 from enum import Enum
 from pydantic import BaseModel, ConfigDict
 from typing import Dict, Generic, List, Literal, Optional, TypeVar, Union, TypeAlias
@@ -154,6 +155,9 @@ class TestEnum(str, Enum):
     F = "F"
     G = "G"
 
+T = TypeVar('T')
+
+
 class AnotherObject(BaseModel):
     id: str
     thingy2: str
@@ -550,9 +554,13 @@ class WithReasoning(BaseModel):
     value: str
     reasoning: str
 
-JsonEntry: TypeAlias = Union["SimpleTag", "JsonTemplate"]
+class Paginated(BaseModel, Generic[T]):
+    items: List[T]
+    next_page: Optional[int] = None
 
-JsonTemplate: TypeAlias = Dict[str, "JsonEntry"]
+class WithCitations(BaseModel, Generic[T]):
+    answer: T
+    citations: List[str]
 
 RecursiveMapAlias: TypeAlias = Dict[str, "RecursiveMapAlias"]
 
@@ -569,3 +577,11 @@ JsonValue: TypeAlias = Union[int, str, bool, float, "JsonObject", "JsonArray"]
 JsonObject: TypeAlias = Dict[str, "JsonValue"]
 
 JsonArray: TypeAlias = List["JsonValue"]
+
+JsonEntry: TypeAlias = Union["SimpleTag", "JsonTemplate"]
+
+JsonTemplate: TypeAlias = Dict[str, "JsonEntry"]
+
+Paginated__Resume = Paginated["Resume"]
+
+WithCitations__Category = WithCitations["Category"]
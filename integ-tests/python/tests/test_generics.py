@@ -0,0 +1,58 @@
+"""Test the generated code of generic classes, see generics.baml."""
+
+import typing
+
+from ..baml_client import partial_types, types
+from ..baml_client.sync_client import b as sync_b
+
+
+def resume(name: str) -> types.Resume:
+    return types.Resume(
+        name=name,
+        email=f"{name.lower()}@example.com",
+        phone="555-0100",
+        experience=[],
+        education=[],
+        skills=["math"],
+    )
+
+
+def test_specializations_are_generic_classes():
+    assert issubclass(types.Paginated__Resume, types.Paginated)
+    assert issubclass(types.WithCitations__Category, types.WithCitations)
+    assert typing.get_type_hints(sync_b.ExtractResumePage)["return"] is types.Paginated__Resume
+    assert (
+        typing.get_type_hints(sync_b.ClassifyWithCitations)["return"]
+        == typing.List[types.WithCitations__Category]
+    )
+
+
+def test_specializations_validate_their_type_arguments():
+    # Results are built like this, from instances of the type arguments.
+    page = types.Paginated__Resume.model_validate(
+        {"items": [resume("Ada")], "next_page": 2}
+    )
+    assert page.items[0].name == "Ada"
+    assert page.next_page == 2
+
+    page = types.Paginated__Resume.model_validate(
+        {"items": [resume("Grace").model_dump()]}
+    )
+    assert isinstance(page.items[0], types.Resume)
+    assert page.next_page is None
+
+    answer = types.WithCitations__Category.model_validate(
+        {"answer": "Refund", "citations": ["money back"]}
+    )
+    assert answer.answer is types.Category.Refund
+
+
+def test_partial_specializations_allow_missing_fields():
+    page = partial_types.Paginated__Resume.model_validate(
+        {"items": [{"name": "Ad", "experience": [], "education": [], "skills": []}]}
+    )
+    assert isinstance(page.items[0], partial_types.Resume)
+    assert page.items[0].email is None
+
+    answer = partial_types.WithCitations__Category.model_validate({"citations": []})
+    assert answer.answer is None
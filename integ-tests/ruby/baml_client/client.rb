@@ -626,6 +626,38 @@ module Baml
       (raw.parsed_using_types(Baml::Types, Baml::PartialTypes, false))
     end
 
+    sig {
+      params(
+        varargs: T.untyped,
+        text: String,
+        baml_options: T::Hash[Symbol, T.any(Baml::TypeBuilder, Baml::ClientRegistry)]
+      ).returns(T::Array[Baml::Types::WithCitations__Category])
+    }
+    def ClassifyWithCitations(
+        *varargs,
+        text:,
+        baml_options: {}
+    )
+      if varargs.any?
+        
+        raise ArgumentError.new("ClassifyWithCitations may only be called with keyword arguments")
+      end
+      if (baml_options.keys - [:client_registry, :tb]).any?
+        raise ArgumentError.new("Received unknown keys in baml_options (valid keys: :client_registry, :tb): #{baml_options.keys - [:client_registry, :tb]}")
+      end
+
+      raw = @runtime.call_function(
+        "ClassifyWithCitations",
+        {
+          text: text,
+        },
+        @ctx_manager,
+        baml_options[:tb]&.instance_variable_get(:@registry),
+        baml_options[:client_registry],
+      )
+      (raw.parsed_using_types(Baml::Types, Baml::PartialTypes, false))
+    end
+
     sig {
       params(
         varargs: T.untyped,
@@ -1234,6 +1266,38 @@ module Baml
       (raw.parsed_using_types(Baml::Types, Baml::PartialTypes, false))
     end
 
+    sig {
+      params(
+        varargs: T.untyped,
+        text: String,
+        baml_options: T::Hash[Symbol, T.any(Baml::TypeBuilder, Baml::ClientRegistry)]
+      ).returns(Baml::Types::Paginated__Resume)
+    }
+    def ExtractResumePage(
+        *varargs,
+        text:,
+        baml_options: {}
+    )
+      if varargs.any?
+        
+        raise ArgumentError.new("ExtractResumePage may only be called with keyword arguments")
+      end
+      if (baml_options.keys - [:client_registry, :tb]).any?
+        raise ArgumentError.new("Received unknown keys in baml_options (valid keys: :client_registry, :tb): #{baml_options.keys - [:client_registry, :tb]}")
+      end
+
+      raw = @runtime.call_function(
+        "ExtractResumePage",
+        {
+          text: text,
+        },
+        @ctx_manager,
+        baml_options[:tb]&.instance_variable_get(:@registry),
+        baml_options[:client_registry],
+      )
+      (raw.parsed_using_types(Baml::Types, Baml::PartialTypes, false))
+    end
+
     sig {
       params(
         varargs: T.untyped,
@@ -5749,6 +5813,41 @@ module Baml
       )
     end
 
+    sig {
+      params(
+        varargs: T.untyped,
+        text: String,
+        baml_options: T::Hash[Symbol, T.any(Baml::TypeBuilder, Baml::ClientRegistry)]
+      ).returns(Baml::BamlStream[T::Array[Baml::Types::WithCitations__Category]])
+    }
+    def ClassifyWithCitations(
+        *varargs,
+        text:,
+        baml_options: {}
+    )
+      if varargs.any?
+        
+        raise ArgumentError.new("ClassifyWithCitations may only be called with keyword arguments")
+      end
+      if (baml_options.keys - [:client_registry, :tb]).any?
+        raise ArgumentError.new("Received unknown keys in baml_options (valid keys: :client_registry, :tb): #{baml_options.keys - [:client_registry, :tb]}")
+      end
+
+      raw = @runtime.stream_function(
+        "ClassifyWithCitations",
+        {
+          text: text,
+        },
+        @ctx_manager,
+        baml_options[:tb]&.instance_variable_get(:@registry),
+        baml_options[:client_registry],
+      )
+      Baml::BamlStream[T::Array[T.nilable(Baml::PartialTypes::WithCitations__Category)], T::Array[Baml::Types::WithCitations__Category]].new(
+        ffi_stream: raw,
+        ctx_manager: @ctx_manager
+      )
+    end
+
     sig {
       params(
         varargs: T.untyped,
@@ -6414,6 +6513,41 @@ module Baml
       )
     end
 
+    sig {
+      params(
+        varargs: T.untyped,
+        text: String,
+        baml_options: T::Hash[Symbol, T.any(Baml::TypeBuilder, Baml::ClientRegistry)]
+      ).returns(Baml::BamlStream[Baml::Types::Paginated__Resume])
+    }
+    def ExtractResumePage(
+        *varargs,
+        text:,
+        baml_options: {}
+    )
+      if varargs.any?
+        
+        raise ArgumentError.new("ExtractResumePage may only be called with keyword arguments")
+      end
+      if (baml_options.keys - [:client_registry, :tb]).any?
+        raise ArgumentError.new("Received unknown keys in baml_options (valid keys: :client_registry, :tb): #{baml_options.keys - [:client_registry, :tb]}")
+      end
+
+      raw = @runtime.stream_function(
+        "ExtractResumePage",
+        {
+          text: text,
+        },
+        @ctx_manager,
+        baml_options[:tb]&.instance_variable_get(:@registry),
+        baml_options[:client_registry],
+      )
+      Baml::BamlStream[T.nilable(Baml::PartialTypes::Paginated__Resume), Baml::Types::Paginated__Resume].new(
+        ffi_stream: raw,
+        ctx_manager: @ctx_manager
+      )
+    end
+
     sig {
       params(
         varargs: T.untyped,
@@ -209,6 +209,7 @@ module Baml
     class OrderInfo < T::Struct; end
     class OriginalA < T::Struct; end
     class OriginalB < T::Struct; end
+    class Paginated__Resume < T::Struct; end
     class Person < T::Struct; end
     class PhoneNumber < T::Struct; end
     class Quantity < T::Struct; end
@@ -233,6 +234,7 @@ module Baml
     class Tree < T::Struct; end
     class TwoStoriesOneTitle < T::Struct; end
     class UnionTest_ReturnType < T::Struct; end
+    class WithCitations__Category < T::Struct; end
     class WithReasoning < T::Struct; end
     class AnotherObject < T::Struct
       include Baml::Sorbet::Struct
@@ -1146,6 +1148,20 @@ module Baml
         @props = props
       end
     end
+    class Paginated__Resume < T::Struct
+      include Baml::Sorbet::Struct
+      const :items, T::Array[Baml::Types::Resume]
+      const :next_page, T.nilable(Integer)
+
+      def initialize(props)
+        super(
+          items: props[:items],
+          next_page: props[:next_page],
+        )
+
+        @props = props
+      end
+    end
     class Person < T::Struct
       include Baml::Sorbet::Struct
       const :name, T.nilable(String)
@@ -1526,6 +1542,20 @@ module Baml
         @props = props
       end
     end
+    class WithCitations__Category < T::Struct
+      include Baml::Sorbet::Struct
+      const :answer, T.any(Baml::Types::Category, String)
+      const :citations, T::Array[String]
+
+      def initialize(props)
+        super(
+          answer: props[:answer],
+          citations: props[:citations],
+        )
+
+        @props = props
+      end
+    end
     class WithReasoning < T::Struct
       include Baml::Sorbet::Struct
       const :value, String
@@ -23,3 +23,10 @@ bundle install
 rake generate
 rake test
 cd ..
+
+# Go Tests
+cd go
+cargo run --manifest-path ../../engine/Cargo.toml --bin baml-cli -- generate --from baml_src
+go vet ./...
+go test ./...
+cd ..
@@ -520,3 +520,13 @@ export interface WithReasoning {
     value?: (string | null)
     reasoning?: (string | null)
 }
+
+export interface Paginated<T> {
+    items?: (T | null)[]
+    next_page: ((number | null) | null)
+}
+
+export interface WithCitations<T> {
+    answer?: (T | null)
+    citations?: (string | null)[]
+}
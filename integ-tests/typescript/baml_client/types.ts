@@ -734,9 +734,17 @@ export interface WithReasoning {
   
 }
 
-type JsonEntry = SimpleTag | JsonTemplate
+export interface Paginated<T> {
+  items: T[]
+  next_page?: number | null
+  
+}
 
-type JsonTemplate = Record<string, JsonEntry>
+export interface WithCitations<T> {
+  answer: T
+  citations: string[]
+  
+}
 
 type RecursiveMapAlias = Record<string, RecursiveMapAlias>
 
@@ -753,3 +761,10 @@ type JsonValue = number | string | boolean | number | JsonObject | JsonArray
 type JsonObject = Record<string, JsonValue>
 
 type JsonArray = JsonValue[]
+
+type JsonEntry = SimpleTag | JsonTemplate
+
+type JsonTemplate = Record<string, JsonEntry>
+
+export type Paginated__Resume = Paginated<Resume>
+export type WithCitations__Category = WithCitations<Category>
\ No newline at end of file